@@ -7,6 +7,7 @@ mod common;
 mod prompt;
 mod prompt_handler;
 mod prompt_router;
+mod server_handler;
 mod task_handler;
 mod tool;
 mod tool_handler;
@@ -34,6 +35,26 @@ mod tool_router;
 ///     // handling tool request
 /// }
 /// ```
+///
+/// ## Mutable state
+///
+/// Tool methods take `&self`, never `&mut self`: the router may run several
+/// tool calls on the same server at once, so it only ever holds a shared
+/// reference. Keep mutable state behind interior mutability instead.
+///
+/// ```rust,ignore
+/// #[derive(Clone)]
+/// pub struct Counter {
+///     count: Arc<tokio::sync::Mutex<i32>>,
+/// }
+///
+/// #[tool(description = "Increment the counter")]
+/// async fn increment(&self) -> String {
+///     let mut count = self.count.lock().await;
+///     *count += 1;
+///     count.to_string()
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn tool(attr: TokenStream, input: TokenStream) -> TokenStream {
     tool::tool(attr.into(), input.into())
@@ -300,6 +321,53 @@ pub fn prompt_handler(attr: TokenStream, input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// # server_handler
+///
+/// This macro implements `ServerHandler` by delegating to routers stored in the
+/// server's fields: it combines [`tool_handler`](macro@tool_handler) and
+/// [`prompt_handler`](macro@prompt_handler), hands a `ResourceRouter` to the
+/// default resource methods, and generates a `get_info()` that enables the
+/// matching capabilities. Methods already written in the impl block,
+/// including `get_info()`, are kept, so you only implement what you want to customize.
+///
+/// ## Usage
+///
+/// | field          | type     | usage |
+/// | :-             | :-       | :-    |
+/// | `tools`        | `Expr`   | Route tools through a `ToolRouter`. A bare `tools` uses `self.tool_router`. |
+/// | `prompts`      | `Expr`   | Route prompts through a `PromptRouter`. A bare `prompts` uses `self.prompt_router`. |
+/// | `resources`    | `Expr`   | Route resources through a `ResourceRouter`. A bare `resources` uses `self.resource_router`. |
+/// | `name`         | `String` | Custom server name. Defaults to `CARGO_CRATE_NAME`. |
+/// | `version`      | `String` | Custom server version. Defaults to `CARGO_PKG_VERSION`. |
+/// | `instructions` | `String` | Optional human-readable instructions about using this server. |
+///
+/// At least one of `tools`, `prompts` and `resources` is required.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// #[derive(Clone)]
+/// struct MyServer {
+///     tool_router: ToolRouter<Self>,
+///     prompt_router: PromptRouter<Self>,
+///     resource_router: ResourceRouter<Self>,
+/// }
+///
+/// #[server_handler(tools, prompts, resources, instructions = "Helps with code review")]
+/// impl ServerHandler for MyServer {
+///     // override only what you need, e.g.
+///     async fn ping(&self, _context: RequestContext<RoleServer>) -> Result<(), ErrorData> {
+///         Ok(())
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn server_handler(attr: TokenStream, input: TokenStream) -> TokenStream {
+    server_handler::server_handler(attr.into(), input.into())
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
 /// # task_handler
 ///
 /// Generates basic task-handling methods (`enqueue_task` and `list_tasks`) for a server handler
@@ -29,44 +29,7 @@ pub fn prompt_handler(attr: TokenStream, input: TokenStream) -> syn::Result<Toke
         .router
         .unwrap_or_else(|| syn::parse2(quote! { Self::prompt_router() }).unwrap());
 
-    // Add get_prompt implementation
-    let get_prompt_impl: ImplItem = parse_quote! {
-        async fn get_prompt(
-            &self,
-            request: rmcp::model::GetPromptRequestParams,
-            context: rmcp::service::RequestContext<rmcp::RoleServer>,
-        ) -> Result<rmcp::model::GetPromptResult, rmcp::ErrorData> {
-            let prompt_context = rmcp::handler::server::prompt::PromptContext::new(
-                self,
-                request.name,
-                request.arguments,
-                context,
-            );
-            #router_expr.get_prompt(prompt_context).await
-        }
-    };
-
-    let meta = if let Some(meta) = attribute.meta {
-        quote! { Some(#meta) }
-    } else {
-        quote! { None }
-    };
-
-    // Add list_prompts implementation
-    let list_prompts_impl: ImplItem = parse_quote! {
-        async fn list_prompts(
-            &self,
-            _request: Option<rmcp::model::PaginatedRequestParams>,
-            _context: rmcp::service::RequestContext<rmcp::RoleServer>,
-        ) -> Result<rmcp::model::ListPromptsResult, rmcp::ErrorData> {
-            let prompts = #router_expr.list_all();
-            Ok(rmcp::model::ListPromptsResult {
-                prompts,
-                meta: #meta,
-                next_cursor: None,
-            })
-        }
-    };
+    let (get_prompt_impl, list_prompts_impl) = prompt_methods(&router_expr, attribute.meta);
 
     // Check if methods already exist and replace them if they do
     let mut has_get_prompt = false;
@@ -102,7 +65,7 @@ pub fn prompt_handler(attr: TokenStream, input: TokenStream) -> syn::Result<Toke
         // with both capabilities. Only generate here if tool_handler is NOT present.
         if !has_sibling_handler(&impl_block, "tool_handler") {
             let get_info_fn =
-                build_get_info(&impl_block, None, None, None, CallerCapability::Prompts)?;
+                build_get_info(&impl_block, None, None, None, &[CallerCapability::Prompts])?;
             impl_block.items.push(get_info_fn);
         }
     }
@@ -112,6 +75,50 @@ pub fn prompt_handler(attr: TokenStream, input: TokenStream) -> syn::Result<Toke
     })
 }
 
+/// Build `get_prompt` and `list_prompts` methods delegating to `router_expr`.
+pub(crate) fn prompt_methods(router_expr: &Expr, meta: Option<Expr>) -> (ImplItem, ImplItem) {
+    // Add get_prompt implementation
+    let get_prompt_impl: ImplItem = parse_quote! {
+        async fn get_prompt(
+            &self,
+            request: rmcp::model::GetPromptRequestParams,
+            context: rmcp::service::RequestContext<rmcp::RoleServer>,
+        ) -> Result<rmcp::model::GetPromptResult, rmcp::ErrorData> {
+            let prompt_context = rmcp::handler::server::prompt::PromptContext::new(
+                self,
+                request.name,
+                request.arguments,
+                context,
+            );
+            #router_expr.get_prompt(prompt_context).await
+        }
+    };
+
+    let meta = if let Some(meta) = meta {
+        quote! { Some(#meta) }
+    } else {
+        quote! { None }
+    };
+
+    // Add list_prompts implementation
+    let list_prompts_impl: ImplItem = parse_quote! {
+        async fn list_prompts(
+            &self,
+            _request: Option<rmcp::model::PaginatedRequestParams>,
+            _context: rmcp::service::RequestContext<rmcp::RoleServer>,
+        ) -> Result<rmcp::model::ListPromptsResult, rmcp::ErrorData> {
+            let prompts = #router_expr.list_all();
+            Ok(rmcp::model::ListPromptsResult {
+                prompts,
+                meta: #meta,
+                next_cursor: None,
+            })
+        }
+    };
+
+    (get_prompt_impl, list_prompts_impl)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
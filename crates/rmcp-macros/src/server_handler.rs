@@ -0,0 +1,154 @@
+use darling::{FromMeta, ast::NestedMeta, util::Override};
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use syn::{Expr, ImplItem, ItemImpl};
+
+use crate::{
+    common::has_method,
+    prompt_handler::prompt_methods,
+    tool_handler::{CallerCapability, add_tool_methods, build_get_info},
+};
+
+#[derive(FromMeta, Default)]
+#[darling(default)]
+pub struct ServerHandlerAttribute {
+    pub tools: Option<Override<Expr>>,
+    pub prompts: Option<Override<Expr>>,
+    pub resources: Option<Override<Expr>>,
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub instructions: Option<String>,
+}
+
+pub fn server_handler(attr: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
+    let attr_args = NestedMeta::parse_meta_list(attr)?;
+    let ServerHandlerAttribute {
+        tools,
+        prompts,
+        resources,
+        name,
+        version,
+        instructions,
+    } = ServerHandlerAttribute::from_list(&attr_args)?;
+    let mut item_impl = syn::parse2::<ItemImpl>(input)?;
+
+    if tools.is_none() && prompts.is_none() && resources.is_none() {
+        return Err(syn::Error::new_spanned(
+            &item_impl.self_ty,
+            "server_handler needs at least one router: add `tools`, `prompts` and/or `resources`",
+        ));
+    }
+
+    let mut capabilities = Vec::new();
+    if let Some(tools) = tools {
+        let router = tools.unwrap_or_else(|| syn::parse_quote! { self.tool_router });
+        add_tool_methods(&mut item_impl, &router, None)?;
+        capabilities.push(CallerCapability::Tools);
+    }
+    if let Some(prompts) = prompts {
+        let router = prompts.unwrap_or_else(|| syn::parse_quote! { self.prompt_router });
+        let (get_prompt_fn, list_prompts_fn) = prompt_methods(&router, None);
+        if !has_method("get_prompt", &item_impl) {
+            item_impl.items.push(get_prompt_fn);
+        }
+        if !has_method("list_prompts", &item_impl) {
+            item_impl.items.push(list_prompts_fn);
+        }
+        capabilities.push(CallerCapability::Prompts);
+    }
+    if let Some(resources) = resources {
+        let router = resources.unwrap_or_else(|| syn::parse_quote! { self.resource_router });
+        if !has_method("resource_router", &item_impl) {
+            let resource_router_fn = syn::parse2::<ImplItem>(quote! {
+                fn resource_router(
+                    &self,
+                ) -> Option<&rmcp::handler::server::router::resource::ResourceRouter<Self>> {
+                    Some(&#router)
+                }
+            })?;
+            item_impl.items.push(resource_router_fn);
+        }
+        capabilities.push(CallerCapability::Resources);
+    }
+
+    if !has_method("get_info", &item_impl) {
+        let get_info_fn = build_get_info(&item_impl, name, version, instructions, &capabilities)?;
+        item_impl.items.push(get_info_fn);
+    }
+
+    Ok(item_impl.into_token_stream())
+}
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+
+    use super::*;
+
+    #[test]
+    fn test_server_handler_delegates_to_router_fields() -> syn::Result<()> {
+        let attr = quote! { tools, prompts };
+        let input = quote! {
+            impl ServerHandler for MyServer {}
+        };
+
+        let result = server_handler(attr, input)?.to_string();
+
+        assert!(result.contains("async fn call_tool"));
+        assert!(result.contains("self . tool_router . call"));
+        assert!(result.contains("async fn get_prompt"));
+        assert!(result.contains("self . prompt_router . get_prompt"));
+        assert!(result.contains("enable_tools ()") && result.contains("enable_prompts ()"));
+        assert!(!result.contains("resource_router"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_server_handler_delegates_to_resource_router() -> syn::Result<()> {
+        let attr = quote! { resources };
+        let input = quote! {
+            impl ServerHandler for MyServer {}
+        };
+
+        let result = server_handler(attr, input)?.to_string();
+
+        assert!(result.contains("fn resource_router"));
+        assert!(result.contains("Some (& self . resource_router)"));
+        assert!(result.contains("enable_resources ()"));
+        assert!(!result.contains("call_tool"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_server_handler_keeps_user_methods() -> syn::Result<()> {
+        let attr = quote! { tools = self.admin_tools };
+        let input = quote! {
+            impl ServerHandler for MyServer {
+                fn get_info(&self) -> ServerInfo {
+                    custom_info()
+                }
+                fn get_tool(&self, name: &str) -> Option<Tool> {
+                    None
+                }
+            }
+        };
+
+        let result = server_handler(attr, input)?.to_string();
+
+        assert!(result.contains("self . admin_tools . call"));
+        assert_eq!(result.matches("fn get_info").count(), 1);
+        assert_eq!(result.matches("fn get_tool").count(), 1);
+        assert!(!result.contains("get_prompt"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_server_handler_requires_a_router() {
+        let input = quote! {
+            impl ServerHandler for MyServer {}
+        };
+
+        let error = server_handler(TokenStream::new(), input).unwrap_err();
+        assert!(error.to_string().contains("at least one router"));
+    }
+}
@@ -268,7 +268,7 @@ pub fn task_handler(attr: TokenStream, input: TokenStream) -> syn::Result<TokenS
             None,
             None,
             None,
-            crate::tool_handler::CallerCapability::Tasks,
+            &[crate::tool_handler::CallerCapability::Tasks],
         )?;
         item_impl.items.push(get_info_fn);
     }
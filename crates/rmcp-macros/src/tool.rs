@@ -175,6 +175,9 @@ impl ResolvedToolAttribute {
     }
 }
 
+/// Keys are checked at expansion time: unknown keys (including the camelCase
+/// wire names such as `readOnlyHint`) and values of the wrong type are compile
+/// errors rather than runtime failures.
 #[derive(FromMeta, Debug, Default)]
 #[darling(default)]
 pub struct ToolAnnotationsAttribute {
@@ -192,7 +195,6 @@ pub struct ToolAnnotationsAttribute {
     /// (This property is meaningful only when `readOnlyHint == false`)
     ///
     /// Default: true
-    /// A human-readable description of the tool's purpose.
     pub destructive_hint: Option<bool>,
 
     /// If true, calling the tool repeatedly with the same arguments
@@ -220,6 +222,18 @@ pub fn tool(attr: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
         ToolAttribute::from_list(&attr_args)?
     };
     let mut fn_item = syn::parse2::<ImplItemFn>(input.clone())?;
+    // tool handlers are called through a shared `&S`, so a `&mut self` tool can
+    // never be routed; reject it here instead of failing deep in the router
+    if let Some(receiver) = fn_item.sig.receiver() {
+        if receiver.reference.is_some() && receiver.mutability.is_some() {
+            return Err(syn::Error::new_spanned(
+                receiver,
+                "`#[tool]` methods cannot take `&mut self`: tools are called \
+                 concurrently through `&self`; keep mutable state behind interior \
+                 mutability (e.g. `Arc<tokio::sync::Mutex<_>>`) and take `&self`",
+            ));
+        }
+    }
     let fn_ident = &fn_item.sig.ident;
 
     let tool_attr_fn_ident = format_ident!("{}_tool_attr", fn_ident);
@@ -407,6 +421,41 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_mut_self_receiver_is_rejected() {
+        let attr = quote! {};
+        let input = quote! {
+            async fn increment(&mut self) -> String {
+                String::new()
+            }
+        };
+        let err = tool(attr, input).expect_err("`&mut self` tools must not compile");
+        assert!(err.to_string().contains("&mut self"), "{err}");
+    }
+
+    #[test]
+    fn test_annotations_unknown_key_is_rejected() {
+        let attr = quote! {
+            annotations(read_only_hnt = true)
+        };
+        let input = quote! {
+            fn annotated(&self) {}
+        };
+        let err = tool(attr, input).expect_err("typo'd annotation key must not compile");
+        assert!(err.to_string().contains("read_only_hnt"), "{err}");
+    }
+
+    #[test]
+    fn test_annotations_wrong_value_type_is_rejected() {
+        let attr = quote! {
+            annotations(read_only_hint = "yes")
+        };
+        let input = quote! {
+            fn annotated(&self) {}
+        };
+        assert!(tool(attr, input).is_err());
+    }
+
     #[test]
     fn test_doc_comment_description() -> syn::Result<()> {
         let attr = quote! {}; // No explicit description
@@ -41,7 +41,31 @@ pub fn tool_handler(attr: TokenStream, input: TokenStream) -> syn::Result<TokenS
     } = ToolHandlerAttribute::from_list(&attr_args)?;
     let mut item_impl = syn::parse2::<ItemImpl>(input)?;
 
-    if !has_method("call_tool", &item_impl) {
+    add_tool_methods(&mut item_impl, &router, meta)?;
+
+    // Auto-generate get_info() if not already provided
+    if !has_method("get_info", &item_impl) {
+        let get_info_fn = build_get_info(
+            &item_impl,
+            name,
+            version,
+            instructions,
+            &[CallerCapability::Tools],
+        )?;
+        item_impl.items.push(get_info_fn);
+    }
+
+    Ok(item_impl.into_token_stream())
+}
+
+/// Add `call_tool`, `list_tools` and `get_tool` methods delegating to `router`,
+/// keeping any the impl block already defines.
+pub(crate) fn add_tool_methods(
+    item_impl: &mut ItemImpl,
+    router: &Expr,
+    meta: Option<Expr>,
+) -> syn::Result<()> {
+    if !has_method("call_tool", item_impl) {
         let tool_call_fn = syn::parse2::<ImplItem>(quote! {
             async fn call_tool(
                 &self,
@@ -61,24 +85,23 @@ pub fn tool_handler(attr: TokenStream, input: TokenStream) -> syn::Result<TokenS
         quote! { None }
     };
 
-    if !has_method("list_tools", &item_impl) {
+    if !has_method("list_tools", item_impl) {
         let tool_list_fn = syn::parse2::<ImplItem>(quote! {
             async fn list_tools(
                 &self,
-                _request: Option<rmcp::model::PaginatedRequestParams>,
+                request: Option<rmcp::model::PaginatedRequestParams>,
                 _context: rmcp::service::RequestContext<rmcp::RoleServer>,
             ) -> Result<rmcp::model::ListToolsResult, rmcp::ErrorData> {
-                Ok(rmcp::model::ListToolsResult{
-                    tools: #router.list_all(),
-                    meta: #result_meta,
-                    next_cursor: None,
-                })
+                let cursor = request.and_then(|request| request.cursor);
+                let mut result = #router.list_page(cursor.as_deref());
+                result.meta = #result_meta;
+                Ok(result)
             }
         })?;
         item_impl.items.push(tool_list_fn);
     }
 
-    if !has_method("get_tool", &item_impl) {
+    if !has_method("get_tool", item_impl) {
         let get_tool_fn = syn::parse2::<ImplItem>(quote! {
             fn get_tool(&self, name: &str) -> Option<rmcp::model::Tool> {
                 #router.get(name).cloned()
@@ -86,20 +109,7 @@ pub fn tool_handler(attr: TokenStream, input: TokenStream) -> syn::Result<TokenS
         })?;
         item_impl.items.push(get_tool_fn);
     }
-
-    // Auto-generate get_info() if not already provided
-    if !has_method("get_info", &item_impl) {
-        let get_info_fn = build_get_info(
-            &item_impl,
-            name,
-            version,
-            instructions,
-            CallerCapability::Tools,
-        )?;
-        item_impl.items.push(get_info_fn);
-    }
-
-    Ok(item_impl.into_token_stream())
+    Ok(())
 }
 
 /// Which handler macro is generating `get_info()`.
@@ -107,12 +117,13 @@ pub fn tool_handler(attr: TokenStream, input: TokenStream) -> syn::Result<TokenS
 pub(crate) enum CallerCapability {
     Tools,
     Prompts,
+    Resources,
     Tasks,
 }
 
 /// Build a `get_info()` method that returns `ServerInfo` with the appropriate capabilities.
 ///
-/// The caller declares its own capability via `caller`. Sibling handler attributes
+/// The caller declares its own capabilities via `callers`. Sibling handler attributes
 /// (`prompt_handler`, `task_handler`, `tool_handler`) are detected automatically
 /// and their capabilities are included.
 pub(crate) fn build_get_info(
@@ -120,14 +131,15 @@ pub(crate) fn build_get_info(
     name: Option<String>,
     version: Option<String>,
     instructions: Option<String>,
-    caller: CallerCapability,
+    callers: &[CallerCapability],
 ) -> syn::Result<ImplItem> {
-    let has_tools =
-        caller == CallerCapability::Tools || has_sibling_handler(item_impl, "tool_handler");
-    let has_prompts =
-        caller == CallerCapability::Prompts || has_sibling_handler(item_impl, "prompt_handler");
-    let has_tasks =
-        caller == CallerCapability::Tasks || has_sibling_handler(item_impl, "task_handler");
+    let has_tools = callers.contains(&CallerCapability::Tools)
+        || has_sibling_handler(item_impl, "tool_handler");
+    let has_prompts = callers.contains(&CallerCapability::Prompts)
+        || has_sibling_handler(item_impl, "prompt_handler");
+    let has_resources = callers.contains(&CallerCapability::Resources);
+    let has_tasks = callers.contains(&CallerCapability::Tasks)
+        || has_sibling_handler(item_impl, "task_handler");
 
     let mut capability_calls = Vec::new();
     if has_tools {
@@ -136,6 +148,9 @@ pub(crate) fn build_get_info(
     if has_prompts {
         capability_calls.push(quote! { .enable_prompts() });
     }
+    if has_resources {
+        capability_calls.push(quote! { .enable_resources() });
+    }
     if has_tasks {
         capability_calls.push(quote! { .enable_tasks() });
     }
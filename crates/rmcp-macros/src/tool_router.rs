@@ -4,10 +4,14 @@
 //! `#[::rmcp::tool_handler]` so `tool_handler` expands in a later proc-macro pass—keeping all
 //! tool dispatch and `get_info` logic in `tool_handler.rs` without duplicating it here.
 
+use std::collections::HashMap;
+
 use darling::{FromMeta, ast::NestedMeta};
 use proc_macro2::TokenStream;
 use quote::{ToTokens, format_ident, quote};
-use syn::{Ident, ImplItem, ItemImpl, Visibility};
+use syn::{Ident, ImplItem, ItemImpl, Meta, Visibility};
+
+use crate::tool::ToolAttribute;
 
 #[derive(FromMeta)]
 #[darling(default)]
@@ -58,6 +62,7 @@ pub fn tool_router(attr: TokenStream, input: TokenStream) -> syn::Result<TokenSt
             }
         })
         .collect();
+    check_duplicate_tool_names(&item_impl)?;
     let mut routers = Vec::with_capacity(tool_attr_fns.len());
     for handler in tool_attr_fns {
         let tool_attr_fn_ident = format_ident!("{handler}_tool_attr");
@@ -95,6 +100,49 @@ pub fn tool_router(attr: TokenStream, input: TokenStream) -> syn::Result<TokenSt
     })
 }
 
+/// Reject two `#[tool]` methods in one impl block that resolve to the same tool name
+/// (explicit `name = "..."` or the fn name fallback), pointing at the later one.
+fn check_duplicate_tool_names(item_impl: &ItemImpl) -> syn::Result<()> {
+    let mut seen = HashMap::new();
+    for item in &item_impl.items {
+        let ImplItem::Fn(fn_item) = item else {
+            continue;
+        };
+        let Some(tool_attr) = fn_item.attrs.iter().find(|attr| {
+            attr.path()
+                .segments
+                .last()
+                .is_some_and(|seg| seg.ident == "tool")
+        }) else {
+            continue;
+        };
+        let explicit_name = match &tool_attr.meta {
+            Meta::List(list) => {
+                // malformed attributes are reported by `#[tool]` itself
+                let Ok(attr_args) = NestedMeta::parse_meta_list(list.tokens.clone()) else {
+                    continue;
+                };
+                let Ok(attribute) = ToolAttribute::from_list(&attr_args) else {
+                    continue;
+                };
+                attribute.name
+            }
+            _ => None,
+        };
+        let fn_ident = &fn_item.sig.ident;
+        let name = explicit_name.unwrap_or_else(|| fn_ident.to_string());
+        if let Some(first) = seen.insert(name.clone(), fn_ident.clone()) {
+            return Err(syn::Error::new_spanned(
+                &fn_item.sig,
+                format!(
+                    "duplicate tool name `{name}`: already used by `{first}` in this impl block"
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -137,4 +185,50 @@ mod test {
         assert!(server_handler);
         Ok(())
     }
+
+    #[test]
+    fn duplicate_explicit_tool_name_is_rejected() {
+        let input = quote! {
+            impl Server {
+                #[tool(name = "search")]
+                async fn search_web(&self) {}
+
+                #[tool(name = "search")]
+                async fn search_docs(&self) {}
+            }
+        };
+        let err = tool_router(quote! {}, input).expect_err("duplicate names must not compile");
+        let msg = err.to_string();
+        assert!(msg.contains("duplicate tool name `search`"), "{msg}");
+        assert!(msg.contains("search_web"), "{msg}");
+    }
+
+    #[test]
+    fn explicit_name_colliding_with_fn_name_is_rejected() {
+        let input = quote! {
+            impl Server {
+                #[tool]
+                async fn echo(&self) {}
+
+                #[tool(description = "another echo", name = "echo")]
+                async fn echo_v2(&self) {}
+            }
+        };
+        assert!(tool_router(quote! {}, input).is_err());
+    }
+
+    #[test]
+    fn distinct_tool_names_are_accepted() -> syn::Result<()> {
+        let input = quote! {
+            impl Server {
+                #[tool]
+                async fn echo(&self) {}
+
+                #[tool(name = "echo_v2")]
+                async fn echo_again(&self) {}
+            }
+        };
+        tool_router(quote! {}, input)?;
+        Ok(())
+    }
 }
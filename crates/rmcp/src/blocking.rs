@@ -0,0 +1,202 @@
+//! Synchronous client facade for code that does not run on an async runtime.
+//!
+//! [`BlockingClient`] drives an ordinary async client on a Tokio runtime and
+//! blocks the calling thread until each request completes. By default it owns a
+//! runtime with a single worker thread, which keeps the connection serviced
+//! (pings, notifications, server-initiated requests) between calls. Use
+//! [`BlockingClient::serve_with_handle`] to run on an existing runtime instead.
+//!
+//! # Calling from async code
+//!
+//! Do not use a `BlockingClient` from within an async context. Blocking calls
+//! panic when made on a thread that is driving a Tokio runtime, and dropping a
+//! client that owns its runtime there panics too. From async code, use the
+//! regular client returned by [`ServiceExt::serve`](crate::ServiceExt::serve);
+//! if you must mix the two, make blocking calls from
+//! [`tokio::task::spawn_blocking`] or a plain thread.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use rmcp::{blocking::BlockingClient, model::CallToolRequestParams, transport::TokioChildProcess};
+//!
+//! fn main() -> anyhow::Result<()> {
+//!     let client = BlockingClient::serve((), || {
+//!         TokioChildProcess::new(tokio::process::Command::new("my-mcp-server"))
+//!     })?;
+//!     for tool in client.list_all_tools_blocking()? {
+//!         println!("{}", tool.name);
+//!     }
+//!     let result = client.call_tool_blocking(CallToolRequestParams::new("ping"))?;
+//!     println!("{:?}", result.text());
+//!     client.close()?;
+//!     Ok(())
+//! }
+//! ```
+
+use std::future::Future;
+
+use tokio::runtime::{Handle, Runtime};
+
+use crate::{
+    RoleClient, ServiceError,
+    model::{
+        CallToolRequestParams, CallToolResult, GetPromptRequestParams, GetPromptResult, Prompt,
+        ReadResourceRequestParams, ReadResourceResult, Resource, Tool,
+    },
+    service::{ClientInitializeError, Peer, RunningService, Service, serve_client},
+    transport::IntoTransport,
+};
+
+/// The runtime a [`BlockingClient`] blocks on.
+#[derive(Debug)]
+enum BlockingRuntime {
+    Owned(Runtime),
+    Shared(Handle),
+}
+
+impl BlockingRuntime {
+    fn handle(&self) -> &Handle {
+        match self {
+            BlockingRuntime::Owned(runtime) => runtime.handle(),
+            BlockingRuntime::Shared(handle) => handle,
+        }
+    }
+}
+
+/// A client whose methods block the calling thread; see the [module docs](self).
+pub struct BlockingClient<S: Service<RoleClient> = ()> {
+    // declared before `runtime` so the service is dropped while the runtime is alive
+    client: Option<RunningService<RoleClient, S>>,
+    runtime: BlockingRuntime,
+}
+
+impl<S: Service<RoleClient>> std::fmt::Debug for BlockingClient<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlockingClient")
+            .field("runtime", &self.runtime)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: Service<RoleClient>> BlockingClient<S> {
+    /// Start a runtime with one worker thread and connect with the transport
+    /// created by `make_transport`.
+    ///
+    /// The transport is created inside the runtime, since most transports
+    /// (child processes, sockets) must be created from one.
+    #[allow(clippy::result_large_err)]
+    pub fn serve<F, T, E, A>(service: S, make_transport: F) -> Result<Self, ClientInitializeError>
+    where
+        F: FnOnce() -> T,
+        T: IntoTransport<RoleClient, E, A>,
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("rmcp-blocking")
+            .enable_all()
+            .build()
+            .map_err(|error| {
+                ClientInitializeError::ConnectionClosed(format!("failed to start runtime: {error}"))
+            })?;
+        Self::serve_on(BlockingRuntime::Owned(runtime), service, make_transport)
+    }
+
+    /// Connect on an existing runtime, e.g. one shared by several clients.
+    ///
+    /// The runtime must keep running (a multi-thread runtime, or a
+    /// current-thread runtime driven elsewhere) for the connection to be
+    /// serviced between calls.
+    #[allow(clippy::result_large_err)]
+    pub fn serve_with_handle<F, T, E, A>(
+        handle: Handle,
+        service: S,
+        make_transport: F,
+    ) -> Result<Self, ClientInitializeError>
+    where
+        F: FnOnce() -> T,
+        T: IntoTransport<RoleClient, E, A>,
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        Self::serve_on(BlockingRuntime::Shared(handle), service, make_transport)
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn serve_on<F, T, E, A>(
+        runtime: BlockingRuntime,
+        service: S,
+        make_transport: F,
+    ) -> Result<Self, ClientInitializeError>
+    where
+        F: FnOnce() -> T,
+        T: IntoTransport<RoleClient, E, A>,
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let client = runtime.handle().block_on(async move {
+            let transport = make_transport();
+            serve_client(service, transport).await
+        })?;
+        Ok(Self {
+            client: Some(client),
+            runtime,
+        })
+    }
+
+    /// Run any future on the client's runtime and block until it completes.
+    ///
+    /// Use this with [`peer`](Self::peer) for requests without a dedicated
+    /// blocking method.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.handle().block_on(future)
+    }
+
+    /// The async peer of the underlying connection.
+    pub fn peer(&self) -> &Peer<RoleClient> {
+        self.client
+            .as_ref()
+            .expect("client is only taken when closing")
+            .peer()
+    }
+
+    pub fn call_tool_blocking(
+        &self,
+        params: CallToolRequestParams,
+    ) -> Result<CallToolResult, ServiceError> {
+        self.block_on(self.peer().call_tool(params))
+    }
+
+    pub fn list_all_tools_blocking(&self) -> Result<Vec<Tool>, ServiceError> {
+        self.block_on(self.peer().list_all_tools())
+    }
+
+    pub fn list_all_prompts_blocking(&self) -> Result<Vec<Prompt>, ServiceError> {
+        self.block_on(self.peer().list_all_prompts())
+    }
+
+    pub fn get_prompt_blocking(
+        &self,
+        params: GetPromptRequestParams,
+    ) -> Result<GetPromptResult, ServiceError> {
+        self.block_on(self.peer().get_prompt(params))
+    }
+
+    pub fn list_all_resources_blocking(&self) -> Result<Vec<Resource>, ServiceError> {
+        self.block_on(self.peer().list_all_resources())
+    }
+
+    pub fn read_resource_blocking(
+        &self,
+        params: ReadResourceRequestParams,
+    ) -> Result<ReadResourceResult, ServiceError> {
+        self.block_on(self.peer().read_resource(params))
+    }
+
+    /// Close the connection and wait for it to shut down.
+    pub fn close(mut self) -> Result<(), tokio::task::JoinError> {
+        match self.client.take() {
+            Some(client) => self.block_on(client.cancel()).map(drop),
+            None => Ok(()),
+        }
+    }
+}
@@ -1,8 +1,15 @@
 // Sampling/Roots/Logging are SEP-2577-deprecated; internal references are expected.
 #![expect(deprecated)]
 pub mod progress;
+pub mod resource_cache;
+pub mod roots;
+pub mod tool_list;
 use std::sync::Arc;
 
+use self::{
+    resource_cache::ResourceCache,
+    tool_list::{ToolListCache, ToolListDiff},
+};
 use crate::{
     error::ErrorData as McpError,
     model::*,
@@ -55,12 +62,24 @@ impl<H: ClientHandler> Service<RoleClient> for H {
                 self.on_logging_message(notification.params, context).await
             }
             ServerNotification::ResourceUpdatedNotification(notification) => {
+                if let Some(cache) = self.resource_cache() {
+                    cache.invalidate(&notification.params.uri);
+                }
                 self.on_resource_updated(notification.params, context).await
             }
             ServerNotification::ResourceListChangedNotification(_notification_no_param) => {
                 self.on_resource_list_changed(context).await
             }
             ServerNotification::ToolListChangedNotification(_notification_no_param) => {
+                if let Some(cache) = self.tool_list_cache() {
+                    match cache.refresh(&context.peer).await {
+                        Ok(diff) if !diff.is_empty() => {
+                            self.on_tools_changed(diff, context.clone()).await
+                        }
+                        Ok(_) => {}
+                        Err(error) => tracing::warn!(%error, "failed to refresh tool list"),
+                    }
+                }
                 self.on_tool_list_changed(context).await
             }
             ServerNotification::PromptListChangedNotification(_notification_no_param) => {
@@ -94,16 +113,28 @@ pub trait ClientHandler: Sized + Send + Sync + 'static {
         std::future::ready(Ok(()))
     }
 
+    /// Handle a `sampling/createMessage` request from the server.
+    ///
+    /// The default implementation rejects the request with a
+    /// `METHOD_NOT_FOUND` (`-32601`) error explaining that the client does not
+    /// support sampling. Clients that advertise the `sampling` capability
+    /// should override this to forward the messages to an LLM.
     fn create_message(
         &self,
         params: CreateMessageRequestParams,
         context: RequestContext<RoleClient>,
     ) -> impl Future<Output = Result<CreateMessageResult, McpError>> + MaybeSendFuture + '_ {
-        std::future::ready(Err(
-            McpError::method_not_found::<CreateMessageRequestMethod>(),
-        ))
+        std::future::ready(Err(McpError::new(
+            ErrorCode::METHOD_NOT_FOUND,
+            "client does not support sampling (sampling/createMessage)",
+            Some(serde_json::json!({ "method": CreateMessageRequestMethod::VALUE })),
+        )))
     }
 
+    /// Handle a `roots/list` request from the server.
+    ///
+    /// The default implementation reports no roots. Clients that advertise the
+    /// `roots` capability should override this.
     fn list_roots(
         &self,
         context: RequestContext<RoleClient>,
@@ -117,6 +148,10 @@ pub trait ClientHandler: Sized + Send + Sync + 'static {
     /// during tool execution. Implementations should present the message to the user,
     /// collect their input according to the requested schema, and return the result.
     ///
+    /// The default implementation declines every request with
+    /// [`ElicitationAction::Decline`], so a client without elicitation support
+    /// still answers per spec instead of failing the server's tool call.
+    ///
     /// # Arguments
     /// * `request` - The elicitation request with message and schema
     /// * `context` - The request context
@@ -186,13 +221,8 @@ pub trait ClientHandler: Sized + Send + Sync + 'static {
         request: CustomRequest,
         context: RequestContext<RoleClient>,
     ) -> impl Future<Output = Result<CustomResult, McpError>> + MaybeSendFuture + '_ {
-        let CustomRequest { method, .. } = request;
         let _ = context;
-        std::future::ready(Err(McpError::new(
-            ErrorCode::METHOD_NOT_FOUND,
-            method,
-            None,
-        )))
+        std::future::ready(Err(McpError::method_not_found_for(request.method)))
     }
 
     fn on_cancelled(
@@ -235,6 +265,29 @@ pub trait ClientHandler: Sized + Send + Sync + 'static {
     ) -> impl Future<Output = ()> + MaybeSendFuture + '_ {
         std::future::ready(())
     }
+    /// Called with the delta after `notifications/tools/list_changed`.
+    ///
+    /// Only invoked when [`ClientHandler::tool_list_cache`] returns a cache.
+    fn on_tools_changed(
+        &self,
+        diff: ToolListDiff,
+        context: NotificationContext<RoleClient>,
+    ) -> impl Future<Output = ()> + MaybeSendFuture + '_ {
+        std::future::ready(())
+    }
+    /// The cache used to compute [`ClientHandler::on_tools_changed`] deltas.
+    ///
+    /// Returns `None` by default, in which case the tool list is not re-fetched.
+    fn tool_list_cache(&self) -> Option<&ToolListCache> {
+        None
+    }
+    /// The cache of `resources/read` results to invalidate when the server
+    /// sends `notifications/resources/updated`.
+    ///
+    /// Returns `None` by default.
+    fn resource_cache(&self) -> Option<&ResourceCache> {
+        None
+    }
     fn on_prompt_list_changed(
         &self,
         context: NotificationContext<RoleClient>,
@@ -369,6 +422,22 @@ macro_rules! impl_client_handler_for_wrapper {
                 (**self).on_tool_list_changed(context)
             }
 
+            fn on_tools_changed(
+                &self,
+                diff: ToolListDiff,
+                context: NotificationContext<RoleClient>,
+            ) -> impl Future<Output = ()> + MaybeSendFuture + '_ {
+                (**self).on_tools_changed(diff, context)
+            }
+
+            fn tool_list_cache(&self) -> Option<&ToolListCache> {
+                (**self).tool_list_cache()
+            }
+
+            fn resource_cache(&self) -> Option<&ResourceCache> {
+                (**self).resource_cache()
+            }
+
             fn on_prompt_list_changed(
                 &self,
                 context: NotificationContext<RoleClient>,
@@ -0,0 +1,201 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    model::{ReadResourceRequestParams, ReadResourceResult, Resource},
+    service::{Peer, RoleClient, ServiceError},
+};
+
+/// A least-recently-used cache of `resources/read` results, keyed by URI.
+///
+/// Read through [`ResourceCache::read_resource`] to skip the round-trip for
+/// resources that are already cached. Entries are dropped when:
+///
+/// - the server sends `notifications/resources/updated` for the URI, if the
+///   cache is returned from
+///   [`ClientHandler::resource_cache`](crate::ClientHandler::resource_cache);
+///   subscribe to the resources you cache so the server sends these;
+/// - [`ResourceCache::observe_resources`] sees a listing whose `lastModified`
+///   annotation differs from the one seen before for that URI;
+/// - the cache is full and the entry is the least recently used one.
+///
+/// Clones share the same entries.
+#[derive(Debug, Clone)]
+pub struct ResourceCache {
+    inner: Arc<Mutex<Lru>>,
+}
+
+#[derive(Debug)]
+struct Lru {
+    capacity: usize,
+    /// Least recently used first.
+    entries: VecDeque<Entry>,
+    /// Bumped on every invalidation, so a read that raced with one is not
+    /// cached.
+    generation: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    uri: String,
+    result: ReadResourceResult,
+    last_modified: Option<DateTime<Utc>>,
+}
+
+impl Default for ResourceCache {
+    fn default() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl ResourceCache {
+    pub const DEFAULT_CAPACITY: usize = 64;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A cache holding at most `capacity` resources. A capacity of zero
+    /// disables caching.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Lru {
+                capacity,
+                entries: VecDeque::new(),
+                generation: 0,
+            })),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Lru> {
+        self.inner.lock().expect("resource cache lock poisoned")
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.lock().capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().entries.is_empty()
+    }
+
+    /// Return the cached result for `params.uri`, or read it from `peer` and
+    /// cache it.
+    pub async fn read_resource(
+        &self,
+        peer: &Peer<RoleClient>,
+        params: ReadResourceRequestParams,
+    ) -> Result<ReadResourceResult, ServiceError> {
+        if let Some(result) = self.get(&params.uri) {
+            return Ok(result);
+        }
+        let generation = self.lock().generation;
+        let uri = params.uri.clone();
+        let result = peer.read_resource(params).await?;
+        let mut lru = self.lock();
+        if lru.generation == generation {
+            lru.insert(uri, result.clone());
+        }
+        Ok(result)
+    }
+
+    /// The cached result for `uri`, marking it as recently used.
+    pub fn get(&self, uri: &str) -> Option<ReadResourceResult> {
+        let mut lru = self.lock();
+        let index = lru.position(uri)?;
+        let entry = lru.entries.remove(index)?;
+        let result = entry.result.clone();
+        lru.entries.push_back(entry);
+        Some(result)
+    }
+
+    /// Cache `result` as the contents of `uri`.
+    pub fn insert(&self, uri: impl Into<String>, result: ReadResourceResult) {
+        self.lock().insert(uri.into(), result);
+    }
+
+    /// Drop the cached result for `uri`. Returns whether one was cached.
+    pub fn invalidate(&self, uri: &str) -> bool {
+        let mut lru = self.lock();
+        lru.generation += 1;
+        match lru.position(uri) {
+            Some(index) => lru.entries.remove(index).is_some(),
+            None => false,
+        }
+    }
+
+    /// Drop every cached result.
+    pub fn clear(&self) {
+        let mut lru = self.lock();
+        lru.generation += 1;
+        lru.entries.clear();
+    }
+
+    /// Compare a resource listing against the cache and drop entries whose
+    /// `lastModified` annotation changed since the previous listing.
+    ///
+    /// The first listing that mentions a cached URI only records its
+    /// `lastModified`. Returns the number of entries dropped.
+    pub fn observe_resources<'a>(
+        &self,
+        resources: impl IntoIterator<Item = &'a Resource>,
+    ) -> usize {
+        let mut lru = self.lock();
+        let mut dropped = 0;
+        for resource in resources {
+            let Some(last_modified) = resource
+                .annotations
+                .as_ref()
+                .and_then(|annotations| annotations.last_modified)
+            else {
+                continue;
+            };
+            let Some(index) = lru.position(&resource.uri) else {
+                continue;
+            };
+            let entry = &mut lru.entries[index];
+            match entry.last_modified {
+                Some(seen) if seen != last_modified => {
+                    lru.entries.remove(index);
+                    lru.generation += 1;
+                    dropped += 1;
+                }
+                _ => entry.last_modified = Some(last_modified),
+            }
+        }
+        dropped
+    }
+}
+
+impl Lru {
+    fn position(&self, uri: &str) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.uri == uri)
+    }
+
+    fn insert(&mut self, uri: String, result: ReadResourceResult) {
+        if self.capacity == 0 {
+            return;
+        }
+        let last_modified = self.position(&uri).and_then(|index| {
+            self.entries
+                .remove(index)
+                .and_then(|entry| entry.last_modified)
+        });
+        while self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(Entry {
+            uri,
+            result,
+            last_modified,
+        });
+    }
+}
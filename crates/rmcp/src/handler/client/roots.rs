@@ -0,0 +1,139 @@
+use std::sync::{Arc, OnceLock};
+
+use tokio::sync::Mutex;
+
+use crate::{
+    model::{ListRootsResult, Root},
+    service::{Peer, RoleClient, ServiceError},
+};
+
+/// The set of filesystem roots a client exposes, which notifies the server
+/// when it changes.
+///
+/// Serve `roots/list` from [`RootsRegistry::list_roots_result`] and call
+/// [`RootsRegistry::attach`] with the client's peer once connected; from then
+/// on [`add`](RootsRegistry::add), [`remove`](RootsRegistry::remove) and
+/// [`set`](RootsRegistry::set) send `notifications/roots/list_changed`
+/// whenever they change the set.
+///
+/// Notifications are only sent if the client advertises `roots.listChanged`,
+/// which the registry assumes by default; use
+/// [`RootsRegistry::with_list_changed`] to match the capabilities returned
+/// from [`ClientHandler::get_info`](crate::ClientHandler::get_info).
+#[derive(Debug, Clone)]
+#[deprecated(
+    since = "2.0.0",
+    note = "Roots is deprecated by SEP-2577 and will be removed in a future release. See https://github.com/modelcontextprotocol/modelcontextprotocol/pull/2577"
+)]
+pub struct RootsRegistry {
+    roots: Arc<Mutex<Vec<Root>>>,
+    peer: Arc<OnceLock<Peer<RoleClient>>>,
+    list_changed: bool,
+}
+
+impl Default for RootsRegistry {
+    fn default() -> Self {
+        Self {
+            roots: Default::default(),
+            peer: Default::default(),
+            list_changed: true,
+        }
+    }
+}
+
+impl RootsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start with `roots`, without notifying anyone.
+    pub fn with_roots(self, roots: impl IntoIterator<Item = Root>) -> Self {
+        Self {
+            roots: Arc::new(Mutex::new(roots.into_iter().collect())),
+            ..self
+        }
+    }
+
+    /// Whether the client advertises `roots.listChanged`. When `false`, changes
+    /// are recorded but never announced.
+    pub fn with_list_changed(mut self, list_changed: bool) -> Self {
+        self.list_changed = list_changed;
+        self
+    }
+
+    /// Send change notifications to `peer`. Returns `false` if a peer was
+    /// already attached.
+    pub fn attach(&self, peer: Peer<RoleClient>) -> bool {
+        self.peer.set(peer).is_ok()
+    }
+
+    /// A snapshot of the current roots.
+    pub async fn roots(&self) -> Vec<Root> {
+        self.roots.lock().await.clone()
+    }
+
+    /// The current roots as a `roots/list` response.
+    pub async fn list_roots_result(&self) -> ListRootsResult {
+        ListRootsResult::new(self.roots().await)
+    }
+
+    /// Add `root`, replacing any root with the same URI.
+    ///
+    /// Returns whether the set changed.
+    pub async fn add(&self, root: Root) -> Result<bool, ServiceError> {
+        let changed = {
+            let mut roots = self.roots.lock().await;
+            match roots.iter_mut().find(|r| r.uri == root.uri) {
+                Some(existing) if *existing == root => false,
+                Some(existing) => {
+                    *existing = root;
+                    true
+                }
+                None => {
+                    roots.push(root);
+                    true
+                }
+            }
+        };
+        self.notify_if(changed).await
+    }
+
+    /// Remove the root with `uri`.
+    ///
+    /// Returns whether the set changed.
+    pub async fn remove(&self, uri: &str) -> Result<bool, ServiceError> {
+        let changed = {
+            let mut roots = self.roots.lock().await;
+            let len = roots.len();
+            roots.retain(|r| r.uri != uri);
+            roots.len() != len
+        };
+        self.notify_if(changed).await
+    }
+
+    /// Replace all roots.
+    ///
+    /// Returns whether the set changed.
+    pub async fn set(
+        &self,
+        new_roots: impl IntoIterator<Item = Root>,
+    ) -> Result<bool, ServiceError> {
+        let new_roots: Vec<Root> = new_roots.into_iter().collect();
+        let changed = {
+            let mut roots = self.roots.lock().await;
+            let changed = *roots != new_roots;
+            *roots = new_roots;
+            changed
+        };
+        self.notify_if(changed).await
+    }
+
+    async fn notify_if(&self, changed: bool) -> Result<bool, ServiceError> {
+        if changed && self.list_changed {
+            if let Some(peer) = self.peer.get() {
+                peer.notify_roots_list_changed().await?;
+            }
+        }
+        Ok(changed)
+    }
+}
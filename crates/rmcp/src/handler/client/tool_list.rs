@@ -0,0 +1,80 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use tokio::sync::Mutex;
+
+use crate::{
+    model::Tool,
+    service::{Peer, RoleClient, ServiceError},
+};
+
+/// The difference between two tool lists, keyed by tool name.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct ToolListDiff {
+    /// Tools that were not in the previous list.
+    pub added: Vec<Tool>,
+    /// Tools that are no longer listed.
+    pub removed: Vec<Tool>,
+    /// Tools whose definition changed, with their new definition.
+    pub modified: Vec<Tool>,
+}
+
+impl ToolListDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// A cache of the server's tool list that reports changes as a [`ToolListDiff`].
+///
+/// Return it from [`ClientHandler::tool_list_cache`](crate::ClientHandler::tool_list_cache)
+/// to have `notifications/tools/list_changed` re-fetch the list and call
+/// [`ClientHandler::on_tools_changed`](crate::ClientHandler::on_tools_changed)
+/// with just the delta. Until the first refresh the cache is empty, so the
+/// first diff reports every tool as added; call [`ToolListCache::refresh`]
+/// after connecting to seed it.
+#[derive(Debug, Clone, Default)]
+pub struct ToolListCache {
+    tools: Arc<Mutex<BTreeMap<String, Tool>>>,
+}
+
+impl ToolListCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Re-fetch all tools from `peer` and return what changed since the last refresh.
+    pub async fn refresh(&self, peer: &Peer<RoleClient>) -> Result<ToolListDiff, ServiceError> {
+        let tools = peer.list_all_tools().await?;
+        Ok(self.replace(tools).await)
+    }
+
+    /// Replace the cached list with `tools` and return what changed.
+    pub async fn replace(&self, tools: Vec<Tool>) -> ToolListDiff {
+        let mut new_tools: BTreeMap<String, Tool> = tools
+            .into_iter()
+            .map(|tool| (tool.name.to_string(), tool))
+            .collect();
+        let mut cached = self.tools.lock().await;
+        let mut diff = ToolListDiff::default();
+        for (name, old) in cached.iter() {
+            match new_tools.get(name) {
+                None => diff.removed.push(old.clone()),
+                Some(new) if new != old => diff.modified.push(new.clone()),
+                Some(_) => {}
+            }
+        }
+        diff.added = new_tools
+            .iter()
+            .filter(|(name, _)| !cached.contains_key(*name))
+            .map(|(_, tool)| tool.clone())
+            .collect();
+        std::mem::swap(&mut *cached, &mut new_tools);
+        diff
+    }
+
+    /// A snapshot of the cached tools, ordered by name.
+    pub async fn tools(&self) -> Vec<Tool> {
+        self.tools.lock().await.values().cloned().collect()
+    }
+}
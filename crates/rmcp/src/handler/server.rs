@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use crate::{
     error::ErrorData as McpError,
+    handler::server::{response_filter::ResponseFilter, router::resource::ResourceRouter},
     model::{TaskSupport, *},
     service::{
         MaybeSendFuture, NotificationContext, RequestContext, RoleServer, Service, ServiceRole,
@@ -13,7 +14,8 @@ use crate::{
 
 pub mod common;
 pub mod prompt;
-mod resource;
+pub mod resource;
+pub mod response_filter;
 pub mod router;
 pub mod tool;
 pub mod tool_name_validation;
@@ -27,7 +29,7 @@ impl<H: ServerHandler> Service<RoleServer> for H {
     ) -> Result<<RoleServer as ServiceRole>::Resp, McpError> {
         // `context` is moved into the dispatch below, so read the negotiated version first.
         let protocol_version = context.protocol_version();
-        let result = match request {
+        let mut result = match request {
             ClientRequest::InitializeRequest(request) => self
                 .initialize(request.params, context)
                 .await
@@ -39,10 +41,23 @@ impl<H: ServerHandler> Service<RoleServer> for H {
                 .complete(request.params, context)
                 .await
                 .map(ServerResult::CompleteResult),
-            ClientRequest::SetLevelRequest(request) => self
-                .set_level(request.params, context)
-                .await
-                .map(ServerResult::empty),
+            ClientRequest::SetLevelRequest(request) => {
+                // A throttle owned by the server follows the requested level
+                // even if the handler does not implement `set_level`.
+                let throttled = match context.peer.logging_throttle() {
+                    Some(throttle) => {
+                        throttle.set_level(request.params.level);
+                        true
+                    }
+                    None => false,
+                };
+                match self.set_level(request.params, context).await {
+                    Err(error) if throttled && error.code == ErrorCode::METHOD_NOT_FOUND => {
+                        Ok(ServerResult::empty(()))
+                    }
+                    result => result.map(ServerResult::empty),
+                }
+            }
             ClientRequest::GetPromptRequest(request) => self
                 .get_prompt(request.params, context)
                 .await
@@ -133,6 +148,9 @@ impl<H: ServerHandler> Service<RoleServer> for H {
                 .await
                 .map(ServerResult::CancelTaskResult),
         };
+        if let (Some(filter), Ok(result)) = (self.response_filter(), &mut result) {
+            response_filter::filter_result(filter, result);
+        }
         // SEP-2164: peers negotiating 2026-07-28+ get the standard INVALID_PARAMS code for
         // resource-not-found; older peers keep RESOURCE_NOT_FOUND. ISO `YYYY-MM-DD` versions
         // compare lexically the same as chronologically.
@@ -239,13 +257,23 @@ macro_rules! server_handler_methods {
         ) -> impl Future<Output = Result<ListPromptsResult, McpError>> + MaybeSendFuture + '_ {
             std::future::ready(Ok(ListPromptsResult::default()))
         }
+        /// Lists the fixed resources of [`resource_router`](Self::resource_router)
+        /// by default, or none without one.
         fn list_resources(
             &self,
             request: Option<PaginatedRequestParams>,
             context: RequestContext<RoleServer>,
         ) -> impl Future<Output = Result<ListResourcesResult, McpError>> + MaybeSendFuture + '_ {
-            std::future::ready(Ok(ListResourcesResult::default()))
+            std::future::ready(Ok(ListResourcesResult {
+                resources: self
+                    .resource_router()
+                    .map(ResourceRouter::list_resources)
+                    .unwrap_or_default(),
+                ..Default::default()
+            }))
         }
+        /// Lists the templates of [`resource_router`](Self::resource_router)
+        /// by default, or none without one.
         fn list_resource_templates(
             &self,
             request: Option<PaginatedRequestParams>,
@@ -253,16 +281,29 @@ macro_rules! server_handler_methods {
         ) -> impl Future<Output = Result<ListResourceTemplatesResult, McpError>>
                + MaybeSendFuture
                + '_ {
-            std::future::ready(Ok(ListResourceTemplatesResult::default()))
+            std::future::ready(Ok(ListResourceTemplatesResult {
+                resource_templates: self
+                    .resource_router()
+                    .map(ResourceRouter::list_templates)
+                    .unwrap_or_default(),
+                ..Default::default()
+            }))
         }
+        /// Reads through [`resource_router`](Self::resource_router) by
+        /// default, or fails with `-32601` without one.
         fn read_resource(
             &self,
             request: ReadResourceRequestParams,
             context: RequestContext<RoleServer>,
         ) -> impl Future<Output = Result<ReadResourceResult, McpError>> + MaybeSendFuture + '_ {
-            std::future::ready(Err(
-                McpError::method_not_found::<ReadResourceRequestMethod>(),
-            ))
+            async move {
+                let Some(router) = self.resource_router() else {
+                    return Err(McpError::method_not_found::<ReadResourceRequestMethod>());
+                };
+                router
+                    .read(resource::ResourceContext::new(self, request.uri, context))
+                    .await
+            }
         }
         fn subscribe(
             &self,
@@ -329,18 +370,61 @@ macro_rules! server_handler_methods {
         fn get_tool(&self, _name: &str) -> Option<Tool> {
             None
         }
+        /// The filter applied to tool, resource and prompt results before they are sent.
+        ///
+        /// It also covers results answered by a [`Router`](router::Router)'s
+        /// routes and tool results delivered as task payloads.
+        ///
+        /// Returns `None` by default, in which case results are sent as the
+        /// handlers return them.
+        fn response_filter(&self) -> Option<&dyn ResponseFilter> {
+            None
+        }
+        /// The router answering `resources/list`, `resources/templates/list`
+        /// and `resources/read` in the default implementations of
+        /// [`list_resources`](Self::list_resources),
+        /// [`list_resource_templates`](Self::list_resource_templates) and
+        /// [`read_resource`](Self::read_resource).
+        ///
+        /// Returns `None` by default. `#[server_handler(resources)]` implements
+        /// it to return the server's `resource_router` field.
+        fn resource_router(&self) -> Option<&ResourceRouter<Self>> {
+            None
+        }
+        /// Handle a request whose method is not part of the MCP specification.
+        ///
+        /// Use this to serve experimental or vendor methods without changing
+        /// the dispatcher: match on `request.method`, read the raw
+        /// `request.params`, and return any JSON value as the result. For
+        /// methods you do not recognize, return
+        /// [`McpError::method_not_found_for`] as the default implementation
+        /// does, so clients get `-32601` with the method name in `data`.
+        ///
+        /// Advertise experimental methods to clients with
+        /// [`ServerCapabilities::builder`](crate::model::ServerCapabilities::builder)`().enable_experimental_with(..)`,
+        /// keyed by a name of your choosing, e.g.
+        /// `{"com.example/search": {"version": 1}}`. Clients can then check
+        /// `peer_info().capabilities.experimental` before sending the method.
+        ///
+        /// ```rust,ignore
+        /// async fn on_custom_request(
+        ///     &self,
+        ///     request: CustomRequest,
+        ///     _context: RequestContext<RoleServer>,
+        /// ) -> Result<CustomResult, ErrorData> {
+        ///     match request.method.as_str() {
+        ///         "com.example/search" => Ok(CustomResult::new(self.search(request.params)?)),
+        ///         _ => Err(ErrorData::method_not_found_for(request.method)),
+        ///     }
+        /// }
+        /// ```
         fn on_custom_request(
             &self,
             request: CustomRequest,
             context: RequestContext<RoleServer>,
         ) -> impl Future<Output = Result<CustomResult, McpError>> + MaybeSendFuture + '_ {
-            let CustomRequest { method, .. } = request;
             let _ = context;
-            std::future::ready(Err(McpError::new(
-                ErrorCode::METHOD_NOT_FOUND,
-                method,
-                None,
-            )))
+            std::future::ready(Err(McpError::method_not_found_for(request.method)))
         }
 
         fn on_cancelled(
@@ -558,6 +642,10 @@ macro_rules! impl_server_handler_for_wrapper {
                 (**self).get_tool(name)
             }
 
+            fn response_filter(&self) -> Option<&dyn ResponseFilter> {
+                (**self).response_filter()
+            }
+
             fn on_custom_request(
                 &self,
                 request: CustomRequest,
@@ -9,22 +9,55 @@ use std::{
 use schemars::JsonSchema;
 
 use crate::{
-    RoleServer, model::JsonObject, schemars::generate::SchemaSettings, service::RequestContext,
+    RoleServer,
+    model::JsonObject,
+    schemars::generate::SchemaSettings,
+    service::{MaybeSendFuture, RequestContext},
 };
 
 /// Generates a JSON schema for a type
+///
+/// Schemas are cached per thread and keyed by [`TypeId`], so distinct types
+/// that share a name (e.g. `Params` in two modules) never share an entry.
+/// Nesting is not limited; see [`schema_for_type_with_max_depth`].
 pub fn schema_for_type<T: JsonSchema + std::any::Any>() -> Arc<JsonObject> {
+    cached_schema_for_type::<T>(None)
+}
+
+/// Generates a JSON schema for a type, limiting how deeply subschemas nest.
+///
+/// Recursive types never recurse during generation: schemars emits them once
+/// under `$defs` and points at them with `$ref`. Deeply nested types can still
+/// produce very large schemas, so any subschema more than `max_depth` levels
+/// below the root (counting `properties`, `items`, `anyOf`, `$defs` and the
+/// other subschema keywords) is replaced by an unconstrained `{}` schema with
+/// a `$comment`.
+///
+/// Schemas are cached per thread and keyed by [`TypeId`] and `max_depth`.
+pub fn schema_for_type_with_max_depth<T: JsonSchema + std::any::Any>(
+    max_depth: usize,
+) -> Arc<JsonObject> {
+    cached_schema_for_type::<T>(Some(max_depth))
+}
+
+/// Generated schemas keyed by type and optional depth limit.
+type SchemaCache = HashMap<(TypeId, Option<usize>), Arc<JsonObject>>;
+
+fn cached_schema_for_type<T: JsonSchema + std::any::Any>(
+    max_depth: Option<usize>,
+) -> Arc<JsonObject> {
     thread_local! {
-        static CACHE_FOR_TYPE: std::sync::RwLock<HashMap<TypeId, Arc<JsonObject>>> = Default::default();
+        static CACHE_FOR_TYPE: std::sync::RwLock<SchemaCache> = Default::default();
     };
+    let key = (TypeId::of::<T>(), max_depth);
     CACHE_FOR_TYPE.with(|cache| {
-        if let Some(x) = cache
-            .read()
-            .expect("schema cache lock poisoned")
-            .get(&TypeId::of::<T>())
-        {
+        if let Some(x) = cache.read().expect("schema cache lock poisoned").get(&key) {
+            #[cfg(feature = "schema-metrics")]
+            schema_metrics::record_hit();
             x.clone()
         } else {
+            #[cfg(feature = "schema-metrics")]
+            schema_metrics::record_miss(TypeId::of::<T>());
             // explicitly to align json schema version to official specifications.
             // refer to https://github.com/modelcontextprotocol/modelcontextprotocol/pull/655 for details.
             let settings = SchemaSettings::draft2020_12();
@@ -34,22 +67,152 @@ pub fn schema_for_type<T: JsonSchema + std::any::Any>() -> Arc<JsonObject> {
             let generator = settings.into_generator();
             let schema = generator.into_root_schema_for::<T>();
             let object = serde_json::to_value(schema).expect("failed to serialize schema");
-            let serde_json::Value::Object(object) = object else {
+            let serde_json::Value::Object(mut object) = object else {
                 panic!(
                     "Schema serialization produced non-object value: expected JSON object but got {object:?}"
                 );
             };
+            if let Some(max_depth) = max_depth {
+                if limit_schema_depth(&mut object, max_depth) {
+                    tracing::warn!(
+                        "schema for {} nests deeper than {max_depth} levels and was truncated",
+                        std::any::type_name::<T>()
+                    );
+                }
+            }
             let schema = Arc::new(object);
             cache
                 .write()
                 .expect("schema cache lock poisoned")
-                .insert(TypeId::of::<T>(), schema.clone());
+                .insert(key, schema.clone());
 
             schema
         }
     })
 }
 
+/// Replace subschemas nested deeper than `max_depth` with placeholders.
+/// Returns whether anything was replaced.
+pub(crate) fn limit_schema_depth(schema: &mut JsonObject, max_depth: usize) -> bool {
+    const SUBSCHEMA: &[&str] = &[
+        "items",
+        "additionalItems",
+        "additionalProperties",
+        "unevaluatedItems",
+        "unevaluatedProperties",
+        "propertyNames",
+        "contains",
+        "not",
+        "if",
+        "then",
+        "else",
+    ];
+    const SUBSCHEMA_LIST: &[&str] = &["allOf", "anyOf", "oneOf", "prefixItems"];
+    const SUBSCHEMA_MAP: &[&str] = &[
+        "properties",
+        "patternProperties",
+        "dependentSchemas",
+        "$defs",
+        "definitions",
+    ];
+
+    fn limit(value: &mut serde_json::Value, depth: usize, max_depth: usize) -> bool {
+        let serde_json::Value::Object(schema) = value else {
+            // `true`/`false` schemas have nothing below them.
+            return false;
+        };
+        if depth > max_depth {
+            *value = serde_json::json!({
+                "$comment": format!("truncated: nested deeper than {max_depth} levels"),
+            });
+            return true;
+        }
+        limit_children(schema, depth, max_depth)
+    }
+
+    fn limit_children(schema: &mut JsonObject, depth: usize, max_depth: usize) -> bool {
+        let mut truncated = false;
+        for (key, child) in schema.iter_mut() {
+            let key = key.as_str();
+            let children: Vec<&mut serde_json::Value> = match child {
+                // Covers the pre-2020-12 tuple form of `items` too.
+                serde_json::Value::Array(list)
+                    if SUBSCHEMA.contains(&key) || SUBSCHEMA_LIST.contains(&key) =>
+                {
+                    list.iter_mut().collect()
+                }
+                serde_json::Value::Object(map) if SUBSCHEMA_MAP.contains(&key) => {
+                    map.values_mut().collect()
+                }
+                _ if SUBSCHEMA.contains(&key) => vec![child],
+                _ => continue,
+            };
+            for child in children {
+                truncated |= limit(child, depth + 1, max_depth);
+            }
+        }
+        truncated
+    }
+
+    limit_children(schema, 0, max_depth)
+}
+
+#[cfg(feature = "schema-metrics")]
+pub use schema_metrics::{SchemaCacheStats, schema_cache_stats};
+
+#[cfg(feature = "schema-metrics")]
+mod schema_metrics {
+    use std::{
+        any::TypeId,
+        collections::HashSet,
+        sync::{
+            LazyLock, Mutex,
+            atomic::{AtomicU64, Ordering},
+        },
+    };
+
+    static HITS: AtomicU64 = AtomicU64::new(0);
+    static MISSES: AtomicU64 = AtomicU64::new(0);
+    static TYPES: LazyLock<Mutex<HashSet<TypeId>>> = LazyLock::new(Default::default);
+
+    /// Counters for the schema cache behind [`schema_for_type`](super::schema_for_type).
+    ///
+    /// The cache is per thread, so each thread that needs a schema generates it
+    /// once: on a multi-threaded runtime `misses` can exceed `entries` by up to
+    /// one per worker thread and type.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    #[non_exhaustive]
+    pub struct SchemaCacheStats {
+        /// Lookups answered from the cache.
+        pub hits: u64,
+        /// Lookups that generated a schema.
+        pub misses: u64,
+        /// Distinct types whose schema has been generated.
+        pub entries: usize,
+    }
+
+    /// Snapshot of the schema cache counters since the process started.
+    pub fn schema_cache_stats() -> SchemaCacheStats {
+        SchemaCacheStats {
+            hits: HITS.load(Ordering::Relaxed),
+            misses: MISSES.load(Ordering::Relaxed),
+            entries: TYPES.lock().expect("schema metrics lock poisoned").len(),
+        }
+    }
+
+    pub(super) fn record_hit() {
+        HITS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn record_miss(type_id: TypeId) {
+        MISSES.fetch_add(1, Ordering::Relaxed);
+        TYPES
+            .lock()
+            .expect("schema metrics lock poisoned")
+            .insert(type_id);
+    }
+}
+
 /// Validate that the schema root is `type: "object"` (per MCP spec) and strip top-level
 /// `title`/`description` (the wrapper type name and doc, which are noise to the LLM).
 fn validate_and_strip(raw: &Arc<JsonObject>, purpose: &str) -> Result<Arc<JsonObject>, String> {
@@ -140,6 +303,48 @@ pub trait FromContextPart<C>: Sized {
     fn from_context_part(context: &mut C) -> Result<Self, crate::ErrorData>;
 }
 
+/// Asynchronous counterpart of [`FromContextPart`], used by async tool handlers
+///
+/// Extraction is split in two: [`from_context_part_async`](Self::from_context_part_async)
+/// runs synchronously and takes what it needs from the context (ids from `_meta`,
+/// handles from [`Extensions`](crate::model::Extensions), ...), and the returned
+/// future performs the I/O. Extractor futures are awaited in parameter order
+/// before the tool body runs, and the first error is returned to the client.
+///
+/// Every [`FromContextPart`] type is also an `AsyncFromContextPart`, so existing
+/// extractors keep working unchanged. Sync tool handlers only accept
+/// [`FromContextPart`] extractors.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// struct Session(SessionData);
+///
+/// impl<S> AsyncFromContextPart<ToolCallContext<'_, S>> for Session {
+///     type Future = BoxFuture<'static, Result<Self, ErrorData>>;
+///
+///     fn from_context_part_async(context: &mut ToolCallContext<'_, S>) -> Self::Future {
+///         let id = context.request_meta().get("sessionId").cloned();
+///         let db = context.request_context.extensions.get::<Db>().cloned();
+///         Box::pin(async move { Ok(Session(db.unwrap().load(id).await?)) })
+///     }
+/// }
+/// ```
+pub trait AsyncFromContextPart<C>: Sized {
+    type Future: Future<Output = Result<Self, crate::ErrorData>> + MaybeSendFuture + 'static;
+    fn from_context_part_async(context: &mut C) -> Self::Future;
+}
+
+impl<C, T> AsyncFromContextPart<C> for T
+where
+    T: FromContextPart<C> + MaybeSendFuture + 'static,
+{
+    type Future = std::future::Ready<Result<Self, crate::ErrorData>>;
+    fn from_context_part_async(context: &mut C) -> Self::Future {
+        std::future::ready(T::from_context_part(context))
+    }
+}
+
 /// Common extractors that can be used by both tool and prompt handlers
 impl<C> FromContextPart<C> for RequestContext<RoleServer>
 where
@@ -297,6 +502,64 @@ mod tests {
         assert!(!Arc::ptr_eq(&schema1, &schema2));
     }
 
+    #[cfg(feature = "schema-metrics")]
+    #[test]
+    fn test_schema_cache_stats_count_hits_and_misses() {
+        #[derive(JsonSchema)]
+        #[allow(dead_code)]
+        struct Counted {
+            value: i32,
+        }
+
+        let before = schema_cache_stats();
+        schema_for_type::<Counted>();
+        schema_for_type::<Counted>();
+        schema_for_type::<Counted>();
+        let after = schema_cache_stats();
+
+        // other tests share the counters, so only check lower bounds
+        assert!(after.misses > before.misses);
+        assert!(after.hits >= before.hits + 2);
+        assert!(after.entries > before.entries);
+    }
+
+    mod first {
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        pub struct Params {
+            pub path: String,
+        }
+    }
+
+    mod second {
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        pub struct Params {
+            pub count: u32,
+        }
+    }
+
+    #[test]
+    fn test_schema_cache_keys_same_named_types_separately() {
+        let first = schema_for_input::<first::Params>().unwrap();
+        let second = schema_for_input::<second::Params>().unwrap();
+
+        let properties = |schema: &JsonObject| {
+            schema["properties"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(properties(&first), ["path"]);
+        assert_eq!(properties(&second), ["count"]);
+        assert_eq!(
+            schema_for_type::<first::Params>().get("title"),
+            schema_for_type::<second::Params>().get("title")
+        );
+    }
+
     #[test]
     fn test_schema_for_type_arc_can_be_shared() {
         let schema = schema_for_type::<TestObject>();
@@ -305,6 +568,76 @@ mod tests {
         assert!(Arc::ptr_eq(&schema, &cloned));
     }
 
+    #[test]
+    fn test_recursive_type_schema_uses_ref() {
+        #[derive(JsonSchema)]
+        #[allow(dead_code)]
+        struct Tree {
+            label: String,
+            children: Vec<Tree>,
+        }
+
+        let schema = serde_json::Value::Object(schema_for_type::<Tree>().as_ref().clone());
+        assert_eq!(schema["properties"]["children"]["items"]["$ref"], "#");
+        assert!(!schema.to_string().contains("truncated"));
+    }
+
+    #[test]
+    fn test_schema_cache_is_keyed_by_max_depth() {
+        #[derive(JsonSchema)]
+        #[allow(dead_code)]
+        struct Outer {
+            inner: Vec<Vec<String>>,
+        }
+
+        let shallow = schema_for_type_with_max_depth::<Outer>(2);
+        let full = schema_for_type::<Outer>();
+        assert!(shallow["properties"]["inner"]["items"]["items"]["$comment"].is_string());
+        assert_eq!(
+            full["properties"]["inner"]["items"]["items"]["type"],
+            "string"
+        );
+        assert!(Arc::ptr_eq(&full, &schema_for_type::<Outer>()));
+        assert!(Arc::ptr_eq(
+            &shallow,
+            &schema_for_type_with_max_depth::<Outer>(2)
+        ));
+    }
+
+    #[test]
+    fn test_limit_schema_depth_replaces_deep_subschemas() {
+        let mut schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "a": {
+                    "type": "array",
+                    "items": {
+                        "anyOf": [{ "type": "object", "properties": { "b": { "type": "string" } } }]
+                    }
+                }
+            }
+        })
+        .as_object()
+        .cloned()
+        .unwrap();
+
+        assert!(!limit_schema_depth(&mut schema.clone(), 5));
+        assert!(limit_schema_depth(&mut schema, 3));
+        let schema = serde_json::Value::Object(schema);
+        let placeholder = &schema["properties"]["a"]["items"]["anyOf"][0]["properties"]["b"];
+        assert_eq!(placeholder.as_object().map(|o| o.len()), Some(1));
+        assert!(
+            placeholder["$comment"]
+                .as_str()
+                .unwrap()
+                .contains("3 levels")
+        );
+        assert_eq!(
+            schema["properties"]["a"]["items"]["anyOf"][0]["type"],
+            "object"
+        );
+    }
+
     #[rstest]
     #[case::output(schema_for_output::<i32>)]
     #[case::input(schema_for_input::<i32>)]
@@ -1 +1,132 @@
+//! Resource reading infrastructure for MCP servers
+//!
+//! This module provides the types used by
+//! [`ResourceRouter`](super::router::resource::ResourceRouter) to dispatch
+//! `resources/read` requests to handlers registered for a fixed URI or for a
+//! URI template.
 
+use std::collections::HashMap;
+
+#[cfg(not(feature = "local"))]
+use futures::future::BoxFuture;
+
+use super::common::AsRequestContext;
+use crate::{RoleServer, model::ReadResourceResult, service::RequestContext};
+
+/// Context for resource read operations
+#[non_exhaustive]
+pub struct ResourceContext<'a, S> {
+    pub server: &'a S,
+    pub uri: String,
+    /// Values of the template variables matched in `uri`, keyed by variable
+    /// name. Empty for resources registered under a fixed URI.
+    pub variables: HashMap<String, String>,
+    pub context: RequestContext<RoleServer>,
+}
+
+impl<'a, S> ResourceContext<'a, S> {
+    pub fn new(server: &'a S, uri: String, context: RequestContext<RoleServer>) -> Self {
+        Self {
+            server,
+            uri,
+            variables: HashMap::new(),
+            context,
+        }
+    }
+
+    /// The value of template variable `name`, if the URI matched a template.
+    pub fn variable(&self, name: &str) -> Option<&str> {
+        self.variables.get(name).map(String::as_str)
+    }
+}
+
+impl<S> AsRequestContext for ResourceContext<'_, S> {
+    fn as_request_context(&self) -> &RequestContext<RoleServer> {
+        &self.context
+    }
+
+    fn as_request_context_mut(&mut self) -> &mut RequestContext<RoleServer> {
+        &mut self.context
+    }
+}
+
+/// Type alias for dynamic resource read handlers
+#[cfg(not(feature = "local"))]
+pub type DynReadResourceHandler<S> = dyn for<'a> Fn(
+        ResourceContext<'a, S>,
+    ) -> BoxFuture<'a, Result<ReadResourceResult, crate::ErrorData>>
+    + Send
+    + Sync;
+
+#[cfg(feature = "local")]
+pub type DynReadResourceHandler<S> = dyn for<'a> Fn(
+    ResourceContext<'a, S>,
+) -> futures::future::LocalBoxFuture<
+    'a,
+    Result<ReadResourceResult, crate::ErrorData>,
+>;
+
+/// Match `uri` against a URI template, returning the variable values.
+///
+/// Supports the expressions of RFC 6570 level 1 and 2 that make sense for
+/// matching: `{name}` matches a non-empty run of characters other than `/`,
+/// `?` and `#`, and `{+name}` matches any non-empty run, so `file:///{+path}`
+/// matches nested paths. A variable extends up to the first occurrence of the
+/// literal text that follows it. Values are returned as they appear in the
+/// URI, without percent-decoding.
+pub(crate) fn match_uri_template(template: &str, uri: &str) -> Option<HashMap<String, String>> {
+    let mut variables = HashMap::new();
+    let mut template = template;
+    let mut uri = uri;
+    while !template.is_empty() {
+        let Some(start) = template.find('{') else {
+            return (template == uri).then_some(variables);
+        };
+        uri = uri.strip_prefix(&template[..start])?;
+        let end = start + template[start..].find('}')?;
+        let (name, reserved) = match &template[start + 1..end] {
+            expression if expression.starts_with('+') => (&expression[1..], true),
+            expression => (expression, false),
+        };
+        template = &template[end + 1..];
+        let literal = &template[..template.find('{').unwrap_or(template.len())];
+        let len = match literal {
+            "" if template.is_empty() => uri.len(),
+            "" => return None,
+            literal => uri.find(literal)?,
+        };
+        let value = &uri[..len];
+        if value.is_empty() || (!reserved && value.contains(['/', '?', '#'])) {
+            return None;
+        }
+        variables.insert(name.to_string(), value.to_string());
+        uri = &uri[len..];
+    }
+    uri.is_empty().then_some(variables)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_uri_template() {
+        let vars = match_uri_template("weather://{city}/current", "weather://paris/current")
+            .expect("matches");
+        assert_eq!(vars["city"], "paris");
+
+        let vars =
+            match_uri_template("repo://{owner}/{repo}", "repo://rust-lang/rust").expect("matches");
+        assert_eq!(vars["owner"], "rust-lang");
+        assert_eq!(vars["repo"], "rust");
+
+        let vars = match_uri_template("file:///{+path}", "file:///src/lib.rs").expect("matches");
+        assert_eq!(vars["path"], "src/lib.rs");
+
+        assert!(match_uri_template("file:///{path}", "file:///src/lib.rs").is_none());
+        assert!(match_uri_template("weather://{city}/current", "weather:///current").is_none());
+        assert!(match_uri_template("weather://{city}/current", "weather://paris/daily").is_none());
+        assert!(match_uri_template("weather://{city}{unit}", "weather://paris").is_none());
+        assert!(match_uri_template("static://x", "static://x").is_some());
+    }
+}
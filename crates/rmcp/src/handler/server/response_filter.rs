@@ -0,0 +1,162 @@
+//! Last-chance rewriting of results before they leave the server.
+//!
+//! Return a [`ResponseFilter`] from
+//! [`ServerHandler::response_filter`](crate::ServerHandler::response_filter)
+//! to redact secrets or personal data from tool, resource and prompt results,
+//! whichever handler or [`Router`](super::router::Router) route produced them.
+//! Task payloads carrying a tool result are filtered like the result itself.
+//! Error responses are not filtered.
+
+use serde_json::Value;
+
+use crate::model::{CallToolResult, GetPromptResult, ReadResourceResult, ServerResult};
+
+/// Rewrites results right before the server sends them.
+///
+/// Every method defaults to leaving the result untouched, so implement only
+/// the ones you need. Filters run after the handler, so they see the final
+/// result, including `structured_content` of tool results.
+///
+/// Only successful results pass through the filter. A handler that returns
+/// `Err(ErrorData)` sends its `message` and `data` as they are, so keep
+/// secrets out of error messages, or map errors before returning them.
+///
+/// # Example
+/// ```rust
+/// use rmcp::{
+///     handler::server::response_filter::ResponseFilter,
+///     model::{CallToolResult, ContentBlock},
+/// };
+///
+/// struct RedactApiKeys;
+///
+/// impl ResponseFilter for RedactApiKeys {
+///     fn filter_content(&self, result: &mut CallToolResult) {
+///         for content in &mut result.content {
+///             if let ContentBlock::Text(text) = content {
+///                 text.text = text.text.replace("sk-live-1234", "[REDACTED]");
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub trait ResponseFilter {
+    /// Filter the result of `tools/call`.
+    fn filter_content(&self, result: &mut CallToolResult) {
+        let _ = result;
+    }
+
+    /// Filter the result of `resources/read`.
+    fn filter_resource(&self, result: &mut ReadResourceResult) {
+        let _ = result;
+    }
+
+    /// Filter the result of `prompts/get`.
+    fn filter_prompt(&self, result: &mut GetPromptResult) {
+        let _ = result;
+    }
+}
+
+/// Run `filter` over `result` if it is one of the results filters cover.
+pub(crate) fn filter_result(filter: &dyn ResponseFilter, result: &mut ServerResult) {
+    match result {
+        ServerResult::CallToolResult(result) => filter.filter_content(result),
+        ServerResult::ReadResourceResult(result) => filter.filter_resource(result),
+        ServerResult::GetPromptResult(result) => filter.filter_prompt(result),
+        // A task-based tool call delivers its result as the task payload. The
+        // payload does not say which request produced it, and any object with
+        // just `_meta` reads as a tool result, so only the tool result fields
+        // are written back, and only when the filter changed them.
+        ServerResult::GetTaskPayloadResult(payload) => {
+            let Ok(original) = serde_json::from_value::<CallToolResult>(payload.0.clone()) else {
+                return;
+            };
+            let mut filtered = original.clone();
+            filter.filter_content(&mut filtered);
+            if filtered == original {
+                return;
+            }
+            let (Ok(Value::Object(before)), Ok(Value::Object(after)), Value::Object(target)) = (
+                serde_json::to_value(original),
+                serde_json::to_value(filtered),
+                &mut payload.0,
+            ) else {
+                return;
+            };
+            for key in before.keys() {
+                target.remove(key);
+            }
+            target.extend(after);
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ContentBlock, GetTaskPayloadResult};
+
+    struct Redact;
+
+    impl ResponseFilter for Redact {
+        fn filter_content(&self, result: &mut CallToolResult) {
+            result.content = vec![ContentBlock::text("[REDACTED]")];
+        }
+    }
+
+    #[test]
+    fn test_filter_result_covers_task_payloads() {
+        let payload = serde_json::to_value(CallToolResult::success(vec![ContentBlock::text(
+            "sk-live-1234",
+        )]))
+        .unwrap();
+        let mut result = ServerResult::GetTaskPayloadResult(GetTaskPayloadResult::new(payload));
+        filter_result(&Redact, &mut result);
+        let ServerResult::GetTaskPayloadResult(GetTaskPayloadResult(payload)) = result else {
+            panic!("expected a task payload");
+        };
+        assert_eq!(payload["content"][0]["text"], "[REDACTED]");
+
+        // Payloads that are not tool results pass through untouched.
+        let mut result = ServerResult::GetTaskPayloadResult(GetTaskPayloadResult::new(
+            serde_json::json!({ "answer": 42 }),
+        ));
+        filter_result(&Redact, &mut result);
+        let ServerResult::GetTaskPayloadResult(GetTaskPayloadResult(payload)) = result else {
+            panic!("expected a task payload");
+        };
+        assert_eq!(payload, serde_json::json!({ "answer": 42 }));
+    }
+
+    #[test]
+    fn test_filter_result_keeps_other_task_payload_fields() {
+        struct Noop;
+        impl ResponseFilter for Noop {}
+
+        // Reads as a tool result, but is the payload of some other request.
+        let other = serde_json::json!({
+            "_meta": { "progressToken": 1 },
+            "messages": [{ "role": "user" }],
+        });
+        let mut result =
+            ServerResult::GetTaskPayloadResult(GetTaskPayloadResult::new(other.clone()));
+        filter_result(&Noop, &mut result);
+        let ServerResult::GetTaskPayloadResult(GetTaskPayloadResult(payload)) = result else {
+            panic!("expected a task payload");
+        };
+        assert_eq!(payload, other);
+
+        let mut result =
+            ServerResult::GetTaskPayloadResult(GetTaskPayloadResult::new(serde_json::json!({
+                "content": [{ "type": "text", "text": "sk-live-1234" }],
+                "extra": true,
+            })));
+        filter_result(&Redact, &mut result);
+        let ServerResult::GetTaskPayloadResult(GetTaskPayloadResult(payload)) = result else {
+            panic!("expected a task payload");
+        };
+        assert_eq!(payload["content"][0]["text"], "[REDACTED]");
+        assert_eq!(payload["extra"], true);
+    }
+}
@@ -1,22 +1,28 @@
 use std::sync::Arc;
 
 use prompt::{IntoPromptRoute, PromptRoute};
+use resource::ResourceRoute;
 use tool::{IntoToolRoute, ToolRoute};
 
 use super::ServerHandler;
 use crate::{
     RoleServer, Service,
-    model::{ClientNotification, ClientRequest, ListPromptsResult, ListToolsResult, ServerResult},
+    model::{
+        ClientNotification, ClientRequest, ListPromptsResult, ListResourceTemplatesResult,
+        ListResourcesResult, ServerCapabilities, ServerResult,
+    },
     service::NotificationContext,
 };
 
 pub mod prompt;
+pub mod resource;
 pub mod tool;
 
 #[non_exhaustive]
 pub struct Router<S> {
     pub tool_router: tool::ToolRouter<S>,
     pub prompt_router: prompt::PromptRouter<S>,
+    pub resource_router: resource::ResourceRouter<S>,
     pub service: Arc<S>,
     peer_slot: Arc<std::sync::OnceLock<crate::service::Peer<RoleServer>>>,
 }
@@ -32,6 +38,7 @@ where
         Self {
             tool_router,
             prompt_router: prompt::PromptRouter::new(),
+            resource_router: resource::ResourceRouter::new(),
             service: Arc::new(service),
             peer_slot,
         }
@@ -66,6 +73,39 @@ where
         }
         self
     }
+
+    pub fn with_resource(mut self, route: ResourceRoute<S>) -> Self {
+        self.resource_router.add_route(route);
+        self
+    }
+
+    pub fn with_resources(mut self, routes: impl IntoIterator<Item = ResourceRoute<S>>) -> Self {
+        for route in routes {
+            self.resource_router.add_route(route);
+        }
+        self
+    }
+
+    /// Advertise what the routes serve on top of the service's own
+    /// capabilities.
+    fn add_route_capabilities(&self, capabilities: &mut ServerCapabilities) {
+        capabilities
+            .tools
+            .get_or_insert_with(Default::default)
+            .list_changed = Some(true);
+        if !self.resource_router.is_empty() {
+            capabilities.resources.get_or_insert_with(Default::default);
+        }
+    }
+
+    /// Apply the service's response filter to a result produced by a route.
+    /// Requests handed to the service are filtered there.
+    fn filtered(&self, mut result: ServerResult) -> ServerResult {
+        if let Some(filter) = self.service.response_filter() {
+            crate::handler::server::response_filter::filter_result(filter, &mut result);
+        }
+        result
+    }
 }
 
 impl<S> Service<RoleServer> for Router<S>
@@ -93,6 +133,16 @@ where
         context: crate::service::RequestContext<RoleServer>,
     ) -> Result<<RoleServer as crate::service::ServiceRole>::Resp, crate::ErrorData> {
         match request {
+            ClientRequest::InitializeRequest(request) => {
+                let mut result = self
+                    .service
+                    .handle_request(ClientRequest::InitializeRequest(request), context)
+                    .await?;
+                if let ServerResult::InitializeResult(info) = &mut result {
+                    self.add_route_capabilities(&mut info.capabilities);
+                }
+                Ok(result)
+            }
             ClientRequest::CallToolRequest(request) => {
                 if self
                     .tool_router
@@ -106,19 +156,18 @@ where
                         context,
                     );
                     let result = self.tool_router.call(tool_call_context).await?;
-                    Ok(ServerResult::CallToolResult(result))
+                    Ok(self.filtered(ServerResult::CallToolResult(result)))
                 } else {
                     self.service
                         .handle_request(ClientRequest::CallToolRequest(request), context)
                         .await
                 }
             }
-            ClientRequest::ListToolsRequest(_) => {
-                let tools = self.tool_router.list_all();
-                Ok(ServerResult::ListToolsResult(ListToolsResult {
-                    tools,
-                    ..Default::default()
-                }))
+            ClientRequest::ListToolsRequest(request) => {
+                let cursor = request.params.and_then(|params| params.cursor);
+                Ok(ServerResult::ListToolsResult(
+                    self.tool_router.list_page(cursor.as_deref()),
+                ))
             }
             ClientRequest::GetPromptRequest(request) => {
                 if self.prompt_router.has_route(request.params.name.as_ref()) {
@@ -129,7 +178,7 @@ where
                         context,
                     );
                     let result = self.prompt_router.get_prompt(prompt_context).await?;
-                    Ok(ServerResult::GetPromptResult(result))
+                    Ok(self.filtered(ServerResult::GetPromptResult(result)))
                 } else {
                     self.service
                         .handle_request(ClientRequest::GetPromptRequest(request), context)
@@ -143,16 +192,44 @@ where
                     ..Default::default()
                 }))
             }
+            ClientRequest::ReadResourceRequest(request)
+                if self.resource_router.has_route(&request.params.uri) =>
+            {
+                let resource_context = crate::handler::server::resource::ResourceContext::new(
+                    self.service.as_ref(),
+                    request.params.uri,
+                    context,
+                );
+                let result = self.resource_router.read(resource_context).await?;
+                Ok(self.filtered(ServerResult::ReadResourceResult(result)))
+            }
+            ClientRequest::ListResourcesRequest(_)
+                if !self.resource_router.resources.is_empty() =>
+            {
+                let resources = self.resource_router.list_resources();
+                Ok(ServerResult::ListResourcesResult(ListResourcesResult {
+                    resources,
+                    ..Default::default()
+                }))
+            }
+            ClientRequest::ListResourceTemplatesRequest(_)
+                if !self.resource_router.templates.is_empty() =>
+            {
+                let resource_templates = self.resource_router.list_templates();
+                Ok(ServerResult::ListResourceTemplatesResult(
+                    ListResourceTemplatesResult {
+                        resource_templates,
+                        ..Default::default()
+                    },
+                ))
+            }
             rest => self.service.handle_request(rest, context).await,
         }
     }
 
     fn get_info(&self) -> <RoleServer as crate::service::ServiceRole>::Info {
         let mut info = ServerHandler::get_info(&self.service);
-        info.capabilities
-            .tools
-            .get_or_insert_with(Default::default)
-            .list_changed = Some(true);
+        self.add_route_capabilities(&mut info.capabilities);
         info
     }
 }
@@ -187,6 +187,7 @@ where
         (item.get)(context).await
     }
 
+    /// All prompts, sorted by name so the order is the same on every call.
     pub fn list_all(&self) -> Vec<crate::model::Prompt> {
         let mut prompts: Vec<_> = self.map.values().map(|item| item.attr.clone()).collect();
         prompts.sort_by(|a, b| a.name.cmp(&b.name));
@@ -0,0 +1,238 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use crate::{
+    handler::server::resource::{DynReadResourceHandler, ResourceContext, match_uri_template},
+    model::{ReadResourceResult, Resource, ResourceTemplate},
+    service::{MaybeBoxFuture, MaybeSend},
+};
+
+/// What a [`ResourceRoute`] answers for: one fixed URI, or every URI
+/// matching a template.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ResourceRouteAttr {
+    Resource(Resource),
+    Template(ResourceTemplate),
+}
+
+#[non_exhaustive]
+pub struct ResourceRoute<S> {
+    #[allow(clippy::type_complexity)]
+    pub read: Arc<DynReadResourceHandler<S>>,
+    pub attr: ResourceRouteAttr,
+}
+
+impl<S> std::fmt::Debug for ResourceRoute<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResourceRoute")
+            .field("attr", &self.attr)
+            .finish()
+    }
+}
+
+impl<S> Clone for ResourceRoute<S> {
+    fn clone(&self) -> Self {
+        Self {
+            read: self.read.clone(),
+            attr: self.attr.clone(),
+        }
+    }
+}
+
+impl<S: MaybeSend + 'static> ResourceRoute<S> {
+    /// A route reading the resource at `resource.uri`.
+    pub fn new_dyn<H>(resource: Resource, handler: H) -> Self
+    where
+        H: for<'a> Fn(
+                ResourceContext<'a, S>,
+            )
+                -> MaybeBoxFuture<'a, Result<ReadResourceResult, crate::ErrorData>>
+            + MaybeSend
+            + 'static,
+    {
+        Self {
+            read: Arc::new(handler),
+            attr: ResourceRouteAttr::Resource(resource),
+        }
+    }
+
+    /// A route reading every URI that matches `template.uri_template`.
+    ///
+    /// The handler receives the matched variables in
+    /// [`ResourceContext::variables`]; see
+    /// [`ResourceRouter`] for the supported template syntax.
+    pub fn template_dyn<H>(template: ResourceTemplate, handler: H) -> Self
+    where
+        H: for<'a> Fn(
+                ResourceContext<'a, S>,
+            )
+                -> MaybeBoxFuture<'a, Result<ReadResourceResult, crate::ErrorData>>
+            + MaybeSend
+            + 'static,
+    {
+        Self {
+            read: Arc::new(handler),
+            attr: ResourceRouteAttr::Template(template),
+        }
+    }
+
+    /// The URI or URI template this route is registered under.
+    pub fn key(&self) -> &str {
+        match &self.attr {
+            ResourceRouteAttr::Resource(resource) => &resource.uri,
+            ResourceRouteAttr::Template(template) => &template.uri_template,
+        }
+    }
+}
+
+/// Dispatches `resources/list`, `resources/templates/list` and
+/// `resources/read` to routes registered for fixed URIs and URI templates.
+///
+/// A read is answered by the route registered for exactly that URI if there
+/// is one, and otherwise by the first template, in name order, that matches
+/// it. Templates support `{name}`, matching one path segment, and `{+name}`,
+/// matching any non-empty text including `/`.
+///
+/// # Example
+/// ```rust,ignore
+/// let router = ResourceRouter::new()
+///     .with_route(ResourceRoute::template_dyn(
+///         ResourceTemplate::new("weather://{city}/current", "current-weather")
+///             .with_mime_type("application/json"),
+///         |ctx| Box::pin(async move {
+///             let city = ctx.variable("city").unwrap_or_default().to_string();
+///             Ok(ReadResourceResult::new(vec![ResourceContents::text(forecast(&city), ctx.uri)]))
+///         }),
+///     ));
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ResourceRouter<S> {
+    #[allow(clippy::type_complexity)]
+    pub resources: BTreeMap<String, ResourceRoute<S>>,
+    #[allow(clippy::type_complexity)]
+    pub templates: BTreeMap<String, ResourceRoute<S>>,
+}
+
+impl<S> Default for ResourceRouter<S> {
+    fn default() -> Self {
+        Self {
+            resources: BTreeMap::new(),
+            templates: BTreeMap::new(),
+        }
+    }
+}
+
+impl<S> Clone for ResourceRouter<S> {
+    fn clone(&self) -> Self {
+        Self {
+            resources: self.resources.clone(),
+            templates: self.templates.clone(),
+        }
+    }
+}
+
+impl<S> ResourceRouter<S>
+where
+    S: MaybeSend + 'static,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_route(mut self, route: ResourceRoute<S>) -> Self {
+        self.add_route(route);
+        self
+    }
+
+    pub fn add_route(&mut self, route: ResourceRoute<S>) {
+        let key = route.key().to_string();
+        match route.attr {
+            ResourceRouteAttr::Resource(_) => self.resources.insert(key, route),
+            ResourceRouteAttr::Template(_) => self.templates.insert(key, route),
+        };
+    }
+
+    pub fn merge(&mut self, other: ResourceRouter<S>) {
+        self.resources.extend(other.resources);
+        self.templates.extend(other.templates);
+    }
+
+    /// Remove the route registered under a URI or URI template.
+    pub fn remove_route(&mut self, key: &str) {
+        self.resources.remove(key);
+        self.templates.remove(key);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.resources.is_empty() && self.templates.is_empty()
+    }
+
+    /// Whether some route would answer a read of `uri`.
+    pub fn has_route(&self, uri: &str) -> bool {
+        self.resources.contains_key(uri)
+            || self
+                .templates
+                .keys()
+                .any(|template| match_uri_template(template, uri).is_some())
+    }
+
+    pub async fn read(
+        &self,
+        mut context: ResourceContext<'_, S>,
+    ) -> Result<ReadResourceResult, crate::ErrorData> {
+        if let Some(route) = self.resources.get(&context.uri) {
+            return (route.read)(context).await;
+        }
+        for (template, route) in &self.templates {
+            if let Some(variables) = match_uri_template(template, &context.uri) {
+                context.variables = variables;
+                return (route.read)(context).await;
+            }
+        }
+        Err(crate::ErrorData::resource_not_found_for(context.uri))
+    }
+
+    /// All fixed resources, sorted by URI.
+    pub fn list_resources(&self) -> Vec<Resource> {
+        self.resources
+            .values()
+            .filter_map(|route| match &route.attr {
+                ResourceRouteAttr::Resource(resource) => Some(resource.clone()),
+                ResourceRouteAttr::Template(_) => None,
+            })
+            .collect()
+    }
+
+    /// All resource templates, sorted by URI template.
+    pub fn list_templates(&self) -> Vec<ResourceTemplate> {
+        self.templates
+            .values()
+            .filter_map(|route| match &route.attr {
+                ResourceRouteAttr::Template(template) => Some(template.clone()),
+                ResourceRouteAttr::Resource(_) => None,
+            })
+            .collect()
+    }
+}
+
+impl<S> std::ops::Add<ResourceRouter<S>> for ResourceRouter<S>
+where
+    S: MaybeSend + 'static,
+{
+    type Output = Self;
+
+    fn add(mut self, other: ResourceRouter<S>) -> Self::Output {
+        self.merge(other);
+        self
+    }
+}
+
+impl<S> std::ops::AddAssign<ResourceRouter<S>> for ResourceRouter<S>
+where
+    S: MaybeSend + 'static,
+{
+    fn add_assign(&mut self, other: ResourceRouter<S>) {
+        self.merge(other);
+    }
+}
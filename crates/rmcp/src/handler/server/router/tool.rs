@@ -124,21 +124,28 @@
 //! [`ToolRouter`] implements [`Add`][std::ops::Add], you can add two tool routers into final
 //! router as showed in [the documentation of `tool_router`][crate::tool_router].
 
+mod coalesce;
+mod idempotency;
+mod rate_limit;
 mod tool_traits;
 
 use std::{borrow::Cow, sync::Arc};
 
+pub use rate_limit::{RATE_LIMITED, ToolRateLimit};
 use schemars::JsonSchema;
 pub use tool_traits::{AsyncTool, SyncTool, ToolBase};
 
 use crate::{
     handler::server::{
-        common::schema_for_input,
+        common::{limit_schema_depth, schema_for_input},
         tool::{CallToolHandler, DynCallToolHandler, ToolCallContext},
         tool_name_validation::validate_and_warn_tool_name,
     },
-    model::{CallToolResult, ContentBlock, ErrorCode, Tool, ToolAnnotations},
-    service::{MaybeBoxFuture, MaybeSend},
+    model::{
+        CallToolResult, ContentBlock, ErrorCode, JsonObject, Tool, ToolAnnotations,
+        validate_against_schema,
+    },
+    service::{MaybeBoxFuture, MaybeSend, MaybeSendFuture},
 };
 
 const TOOL_ARGUMENT_DESERIALIZATION_ERROR_PREFIX: &str = "failed to deserialize parameters:";
@@ -157,6 +164,173 @@ fn into_tool_argument_error(error: crate::ErrorData) -> Result<CallToolResult, c
     Err(error)
 }
 
+fn validate_structured_output(
+    name: &str,
+    schema: &JsonObject,
+    result: &CallToolResult,
+) -> Result<(), crate::ErrorData> {
+    if result.is_error == Some(true) {
+        return Ok(());
+    }
+    let Some(structured) = &result.structured_content else {
+        return Err(crate::ErrorData::internal_error(
+            format!("tool `{name}` declares an output schema but returned no structured content"),
+            None,
+        ));
+    };
+    validate_against_schema(schema, structured).map_err(|errors| {
+        let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        crate::ErrorData::internal_error(
+            format!(
+                "tool `{name}` returned structured content that does not match its output schema: {}",
+                messages.join("; ")
+            ),
+            Some(serde_json::json!({ "errors": errors })),
+        )
+    })
+}
+
+/// The session the session manager routed this call to, if any.
+///
+/// Only sessions confirmed by the streamable HTTP server count; the raw
+/// `Mcp-Session-Id` header is chosen by the client and is not trusted.
+fn session_id(context: &crate::service::RequestContext<crate::RoleServer>) -> Option<&str> {
+    #[cfg(feature = "transport-streamable-http-server")]
+    {
+        context
+            .extensions
+            .get::<crate::transport::common::server_side_http::ValidatedSession>()
+            .map(|session| session.id.as_ref())
+    }
+    #[cfg(not(feature = "transport-streamable-http-server"))]
+    {
+        let _ = context;
+        None
+    }
+}
+
+fn validate_input(tool: &Tool, arguments: Option<&JsonObject>) -> Result<(), crate::ErrorData> {
+    let arguments = serde_json::Value::Object(arguments.cloned().unwrap_or_default());
+    validate_against_schema(&tool.input_schema, &arguments).map_err(|errors| {
+        let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        crate::ErrorData::invalid_params(
+            format!(
+                "arguments for tool `{}` do not match its input schema: {}",
+                tool.name,
+                messages.join("; ")
+            ),
+            Some(serde_json::json!({ "errors": errors })),
+        )
+    })
+}
+
+/// What [`ToolRouter::call`] does with a result that exceeds its [`ToolResultLimits`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ToolResultLimitAction {
+    /// Drop the trailing content blocks that do not fit and append a text
+    /// block noting how many were omitted. The note counts towards both
+    /// limits and is left out if it cannot fit on its own.
+    #[default]
+    Truncate,
+    /// Fail the call with an internal error.
+    Error,
+}
+
+/// Caps on the content of a [`CallToolResult`], enforced by [`ToolRouter::call`].
+///
+/// Content size is measured as the serialized JSON length of each block.
+/// `structured_content` is not counted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ToolResultLimits {
+    /// Maximum total bytes across all content blocks.
+    pub max_content_bytes: Option<usize>,
+    /// Maximum number of content blocks.
+    pub max_content_blocks: Option<usize>,
+    pub on_exceeded: ToolResultLimitAction,
+}
+
+impl ToolResultLimits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_content_bytes(mut self, max_content_bytes: usize) -> Self {
+        self.max_content_bytes = Some(max_content_bytes);
+        self
+    }
+
+    pub fn with_max_content_blocks(mut self, max_content_blocks: usize) -> Self {
+        self.max_content_blocks = Some(max_content_blocks);
+        self
+    }
+
+    pub fn with_on_exceeded(mut self, on_exceeded: ToolResultLimitAction) -> Self {
+        self.on_exceeded = on_exceeded;
+        self
+    }
+
+    fn enforce(&self, mut result: CallToolResult) -> Result<CallToolResult, crate::ErrorData> {
+        let max_bytes = self.max_content_bytes.unwrap_or(usize::MAX);
+        let max_blocks = self.max_content_blocks.unwrap_or(usize::MAX);
+        // prefix_bytes[i] is the size of the first i blocks.
+        let mut prefix_bytes = vec![0usize];
+        for block in &result.content {
+            let size = block_size(block);
+            let total = prefix_bytes[prefix_bytes.len() - 1].saturating_add(size);
+            if prefix_bytes.len() > max_blocks || total > max_bytes {
+                break;
+            }
+            prefix_bytes.push(total);
+        }
+        let fits = prefix_bytes.len() - 1;
+        let total_blocks = result.content.len();
+        if fits == total_blocks {
+            return Ok(result);
+        }
+        match self.on_exceeded {
+            ToolResultLimitAction::Error => Err(crate::ErrorData::internal_error(
+                "tool result exceeds configured content limits",
+                Some(serde_json::json!({
+                    "maxContentBytes": self.max_content_bytes,
+                    "maxContentBlocks": self.max_content_blocks,
+                    "contentBlocks": total_blocks,
+                })),
+            )),
+            ToolResultLimitAction::Truncate => {
+                // Give up trailing blocks until the marker fits within both
+                // caps; if even the marker alone does not, leave it out. With
+                // no blocks allowed at all there is no room for it either.
+                let with_marker = (0..(fits + 1).min(max_blocks))
+                    .rev()
+                    .map(|keep| (keep, truncation_marker(total_blocks - keep, total_blocks)))
+                    .find(|(keep, marker)| {
+                        prefix_bytes[*keep].saturating_add(block_size(marker)) <= max_bytes
+                    });
+                match with_marker {
+                    Some((keep, marker)) => {
+                        result.content.truncate(keep);
+                        result.content.push(marker);
+                    }
+                    None => result.content.truncate(fits),
+                }
+                Ok(result)
+            }
+        }
+    }
+}
+
+fn block_size(block: &ContentBlock) -> usize {
+    serde_json::to_vec(block).map_or(0, |bytes| bytes.len())
+}
+
+fn truncation_marker(omitted: usize, total: usize) -> ContentBlock {
+    ContentBlock::text(format!(
+        "[content truncated: {omitted} of {total} blocks omitted]"
+    ))
+}
+
 #[non_exhaustive]
 pub struct ToolRoute<S> {
     #[allow(clippy::type_complexity)]
@@ -296,6 +470,23 @@ where
     }
 }
 
+fn limit_tool_schemas(tool: &mut Tool, max_depth: usize) {
+    if limit_schema_depth(Arc::make_mut(&mut tool.input_schema), max_depth) {
+        tracing::warn!(
+            "input schema of tool {} nests deeper than {max_depth} levels and was truncated",
+            tool.name
+        );
+    }
+    if let Some(output_schema) = &mut tool.output_schema {
+        if limit_schema_depth(Arc::make_mut(output_schema), max_depth) {
+            tracing::warn!(
+                "output schema of tool {} nests deeper than {max_depth} levels and was truncated",
+                tool.name
+            );
+        }
+    }
+}
+
 impl<C, S, A> WithToolAttr<C, S, A>
 where
     C: CallToolHandler<S, A> + MaybeSend + Clone + 'static,
@@ -332,6 +523,26 @@ pub struct ToolRouter<S> {
     disabled: std::collections::HashSet<Cow<'static, str>>,
 
     notifier: Option<Arc<dyn Fn() + Send + Sync>>,
+
+    result_limits: Option<ToolResultLimits>,
+
+    coalescer: Option<Arc<coalesce::Coalescer>>,
+
+    validate_output: bool,
+
+    validate_input: bool,
+
+    rate_limits: std::collections::HashMap<Cow<'static, str>, ToolRateLimit>,
+
+    rate_limiter: Arc<rate_limit::RateLimiter>,
+
+    idempotency_ttls: std::collections::HashMap<Cow<'static, str>, std::time::Duration>,
+
+    idempotency_cache: Arc<idempotency::IdempotencyCache>,
+
+    page_size: Option<usize>,
+
+    max_schema_depth: Option<usize>,
 }
 
 impl<S> std::fmt::Debug for ToolRouter<S> {
@@ -344,6 +555,14 @@ impl<S> std::fmt::Debug for ToolRouter<S> {
             )
             .field("disabled", &self.disabled)
             .field("notifier", &self.notifier.as_ref().map(|_| "..."))
+            .field("result_limits", &self.result_limits)
+            .field("coalescing", &self.coalescer.is_some())
+            .field("validate_output", &self.validate_output)
+            .field("validate_input", &self.validate_input)
+            .field("rate_limits", &self.rate_limits)
+            .field("idempotency_ttls", &self.idempotency_ttls)
+            .field("page_size", &self.page_size)
+            .field("max_schema_depth", &self.max_schema_depth)
             .finish()
     }
 }
@@ -355,6 +574,16 @@ impl<S> Default for ToolRouter<S> {
             transparent_when_not_found: false,
             disabled: std::collections::HashSet::new(),
             notifier: None,
+            result_limits: None,
+            coalescer: None,
+            validate_output: cfg!(debug_assertions),
+            validate_input: false,
+            rate_limits: std::collections::HashMap::new(),
+            rate_limiter: Default::default(),
+            idempotency_ttls: std::collections::HashMap::new(),
+            idempotency_cache: Default::default(),
+            page_size: None,
+            max_schema_depth: None,
         }
     }
 }
@@ -366,6 +595,16 @@ impl<S> Clone for ToolRouter<S> {
             transparent_when_not_found: self.transparent_when_not_found,
             disabled: self.disabled.clone(),
             notifier: self.notifier.clone(),
+            result_limits: self.result_limits.clone(),
+            coalescer: self.coalescer.clone(),
+            validate_output: self.validate_output,
+            validate_input: self.validate_input,
+            rate_limits: self.rate_limits.clone(),
+            rate_limiter: self.rate_limiter.clone(),
+            idempotency_ttls: self.idempotency_ttls.clone(),
+            idempotency_cache: self.idempotency_cache.clone(),
+            page_size: self.page_size,
+            max_schema_depth: self.max_schema_depth,
         }
     }
 }
@@ -434,14 +673,47 @@ where
         }
     }
 
-    pub fn add_route(&mut self, item: ToolRoute<S>) {
-        let new_name = &item.attr.name;
-        validate_and_warn_tool_name(new_name);
-        self.map.insert(new_name.clone(), item);
+    pub fn add_route(&mut self, mut item: ToolRoute<S>) {
+        validate_and_warn_tool_name(&item.attr.name);
+        if let Some(max_depth) = self.max_schema_depth {
+            limit_tool_schemas(&mut item.attr, max_depth);
+        }
+        self.map.insert(item.attr.name.clone(), item);
+    }
+
+    /// Register a tool whose definition is only known at runtime, e.g. one
+    /// loaded from a plugin directory.
+    ///
+    /// `handler` must not hold on to the [`ToolCallContext`] past its own
+    /// call: take what it needs (usually `arguments`) and return a future that
+    /// owns it. A route with the same name is replaced. Unless the name is
+    /// disabled, the notifier (see [`bind_peer_notifier`](Self::bind_peer_notifier))
+    /// is invoked so clients receive `notifications/tools/list_changed`.
+    ///
+    /// ```rust,ignore
+    /// router.add_dynamic(Tool::builder("echo").build(), |context| {
+    ///     let arguments = context.arguments.unwrap_or_default();
+    ///     async move { Ok(CallToolResult::structured(arguments.into())) }
+    /// });
+    /// ```
+    pub fn add_dynamic<F, Fut>(&mut self, tool: Tool, handler: F)
+    where
+        F: Fn(ToolCallContext<'_, S>) -> Fut + MaybeSend + 'static,
+        Fut: Future<Output = Result<CallToolResult, crate::ErrorData>> + MaybeSendFuture + 'static,
+    {
+        let name = tool.name.clone();
+        self.add_route(ToolRoute::new_dyn(tool, move |context| {
+            Box::pin(handler(context))
+        }));
+        if !self.disabled.contains(&name) {
+            self.notify_if_visible(&name);
+        }
     }
 
     pub fn merge(&mut self, other: ToolRouter<S>) {
         self.disabled.extend(other.disabled);
+        self.rate_limits.extend(other.rate_limits);
+        self.idempotency_ttls.extend(other.idempotency_ttls);
         for item in other.map.into_values() {
             self.add_route(item);
         }
@@ -506,6 +778,135 @@ where
         self
     }
 
+    /// Enforce `limits` on the content of every tool result returned by [`call`](Self::call).
+    pub fn with_result_limits(mut self, limits: ToolResultLimits) -> Self {
+        self.result_limits = Some(limits);
+        self
+    }
+
+    pub fn set_result_limits(&mut self, limits: Option<ToolResultLimits>) {
+        self.result_limits = limits;
+    }
+
+    /// Share one execution among identical concurrent calls to idempotent tools.
+    ///
+    /// When enabled, a call to a tool annotated with `idempotentHint: true` whose
+    /// name and arguments (compared with object keys sorted) match a call that is
+    /// still running waits for that call and receives a copy of its result,
+    /// instead of running the tool again. Only the leading call's request context
+    /// is used, so waiters receive no progress notifications of their own.
+    ///
+    /// Clones of this router share the set of in-flight calls, so calls from
+    /// different sessions coalesce when each session's server is cloned from
+    /// the same router. Disabled by default.
+    pub fn with_coalescing(mut self, enabled: bool) -> Self {
+        self.coalescer = enabled.then(Default::default);
+        self
+    }
+
+    /// Check structured tool output against the tool's declared output schema.
+    ///
+    /// When enabled, a successful result from a tool with an `output_schema`
+    /// must carry `structured_content` that matches the schema; otherwise
+    /// [`call`](Self::call) fails with an internal error listing the
+    /// violations, so drift between a tool's schema and what it serializes is
+    /// caught before it reaches a client. Error results are not checked.
+    ///
+    /// Enabled by default in debug builds and disabled in release builds.
+    pub fn with_output_validation(mut self, enabled: bool) -> Self {
+        self.validate_output = enabled;
+        self
+    }
+
+    /// Check call arguments against the tool's declared input schema.
+    ///
+    /// When enabled, [`call`](Self::call) rejects arguments that do not match
+    /// the tool's `input_schema` with an `invalid_params` (`-32602`) error
+    /// listing the violations, before the handler runs. This covers tools
+    /// whose handlers do not deserialize into a typed struct, such as those
+    /// added with [`add_dynamic`](Self::add_dynamic). Missing arguments are
+    /// checked as an empty object.
+    ///
+    /// Disabled by default, since typed handlers already reject malformed
+    /// arguments while deserializing them.
+    pub fn with_input_validation(mut self, enabled: bool) -> Self {
+        self.validate_input = enabled;
+        self
+    }
+
+    /// Limit how often each session may call the tool `name`.
+    ///
+    /// Every session gets its own token bucket per rate-limited tool. Sessions
+    /// are the ones the stateful streamable HTTP server routed the call to, so
+    /// a client cannot get a fresh bucket by sending a made-up
+    /// `Mcp-Session-Id`. Calls outside such a session, such as those over
+    /// stdio or stateless HTTP, share a single bucket per tool. A call that
+    /// finds its bucket empty is rejected with [`RATE_LIMITED`] (`-32000`)
+    /// before the handler runs, and the error `data` carries `retryAfterMs`
+    /// and `retryAfter` (whole seconds) telling the client when the next call
+    /// will be accepted.
+    ///
+    /// Clones of this router share their buckets, so limits hold across the
+    /// per-session server instances created from one router.
+    pub fn with_rate_limit(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        limit: ToolRateLimit,
+    ) -> Self {
+        self.rate_limits.insert(name.into(), limit);
+        self
+    }
+
+    /// Replay the result of retried calls to the tool `name`.
+    ///
+    /// A call whose `_meta` carries an idempotency key (see
+    /// [`ToolCallContext::idempotency_key`]) runs once; its successful result
+    /// is kept for `ttl`, and calls with the same key in the same session
+    /// receive that result without running the tool again. A retry arriving
+    /// while the first call is still running waits for it. Protocol errors are
+    /// not kept, so a failed call runs again when retried, and calls without a
+    /// key are never replayed. Reusing a key with different arguments is
+    /// rejected with `invalid_params` (`-32602`). Replayed calls do not count
+    /// towards a [rate limit](Self::with_rate_limit) on the tool.
+    ///
+    /// Results are only replayed within a session, told apart as in
+    /// [`with_rate_limit`](Self::with_rate_limit); calls outside one, such as
+    /// stateless HTTP requests, always run. Clones of this router share the
+    /// stored results.
+    pub fn with_idempotency(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        ttl: std::time::Duration,
+    ) -> Self {
+        self.idempotency_ttls.insert(name.into(), ttl);
+        self
+    }
+
+    /// Split `tools/list` responses into pages of at most `page_size` tools.
+    ///
+    /// Pages follow [`list_all`](Self::list_all)'s name order and the cursor is
+    /// the name of the last tool on the previous page, so a cursor stays valid
+    /// when tools are added or removed between requests. By default all tools
+    /// are returned in one response.
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = Some(page_size.max(1));
+        self
+    }
+
+    /// Limit how deeply subschemas nest in the input and output schemas of
+    /// this router's tools, including tools added later.
+    ///
+    /// Subschemas nested deeper than `max_depth` levels are replaced as
+    /// described in [`schema_for_type_with_max_depth`](crate::handler::server::common::schema_for_type_with_max_depth).
+    /// Schemas generated by `#[tool]` are not limited otherwise.
+    pub fn with_max_schema_depth(mut self, max_depth: usize) -> Self {
+        self.max_schema_depth = Some(max_depth);
+        for route in self.map.values_mut() {
+            limit_tool_schemas(&mut route.attr, max_depth);
+        }
+        self
+    }
+
     /// Install a callback invoked when the visible tool list changes.
     pub fn set_notifier(&mut self, f: impl Fn() + Send + Sync + 'static) {
         self.notifier = Some(Arc::new(f));
@@ -564,21 +965,84 @@ where
     ) -> Result<CallToolResult, crate::ErrorData> {
         let name = context.name();
         if self.disabled.contains(name) {
-            return Err(crate::ErrorData::invalid_params("tool not found", None));
+            return Err(crate::ErrorData::tool_not_found(name));
         }
         let item = self
             .map
             .get(name)
-            .ok_or_else(|| crate::ErrorData::invalid_params("tool not found", None))?;
-
-        let result = match (item.call)(context).await {
+            .ok_or_else(|| crate::ErrorData::tool_not_found(name))?;
+        if self.validate_input {
+            validate_input(&item.attr, context.arguments.as_ref())?;
+        }
+        let idempotent = item
+            .attr
+            .annotations
+            .as_ref()
+            .and_then(|annotations| annotations.idempotent_hint)
+            == Some(true);
+        let replay = self.idempotency_ttls.get(name).and_then(|ttl| {
+            let key = context.idempotency_key()?.to_owned();
+            let session = session_id(&context.request_context)?.to_owned();
+            Some((key, session, context.arguments.clone(), *ttl))
+        });
+        // Taken inside `execute` so that a replayed idempotent call does not
+        // spend a token.
+        let rate_limit = self.rate_limits.get(name).map(|limit| {
+            let session = session_id(&context.request_context).map(str::to_owned);
+            (limit, session)
+        });
+        let execute = async {
+            if let Some((limit, session)) = rate_limit {
+                self.rate_limiter
+                    .acquire(item.attr.name.clone(), session.as_deref(), limit)
+                    .map_err(|retry_after| {
+                        rate_limit::rate_limited(&item.attr.name, retry_after)
+                    })?;
+            }
+            match &self.coalescer {
+                Some(coalescer) if idempotent => {
+                    let name = item.attr.name.clone();
+                    let arguments = context.arguments.clone();
+                    coalescer
+                        .run(name, arguments.as_ref(), (item.call)(context))
+                        .await
+                }
+                _ => (item.call)(context).await,
+            }
+        };
+        let result = match replay {
+            Some((key, session, arguments, ttl)) => {
+                self.idempotency_cache
+                    .run(
+                        item.attr.name.clone(),
+                        &session,
+                        &key,
+                        arguments.as_ref(),
+                        ttl,
+                        execute,
+                    )
+                    .await
+            }
+            None => execute.await,
+        };
+        let result = match result {
             Ok(result) => result,
             Err(error) => return into_tool_argument_error(error),
         };
+        if self.validate_output {
+            if let Some(schema) = &item.attr.output_schema {
+                validate_structured_output(&item.attr.name, schema, &result)?;
+            }
+        }
 
-        Ok(result)
+        match &self.result_limits {
+            Some(limits) => limits.enforce(result),
+            None => Ok(result),
+        }
     }
 
+    /// All enabled tools, sorted by name so that the order (and with it any
+    /// pagination cursor) is the same on every call and every server instance.
     pub fn list_all(&self) -> Vec<crate::model::Tool> {
         let mut tools: Vec<_> = self
             .map
@@ -590,6 +1054,36 @@ where
         tools
     }
 
+    /// The page of tools following `cursor`, see [`with_page_size`](Self::with_page_size).
+    pub fn list_page(&self, cursor: Option<&str>) -> crate::model::ListToolsResult {
+        let mut remaining: Vec<_> = self
+            .map
+            .values()
+            .map(|item| &item.attr)
+            .filter(|tool| !self.disabled.contains(&tool.name))
+            .filter(|tool| cursor.is_none_or(|cursor| tool.name.as_ref() > cursor))
+            .collect();
+        let by_name = |a: &&crate::model::Tool, b: &&crate::model::Tool| a.name.cmp(&b.name);
+        // Only the tools on this page are sorted and cloned.
+        let has_more = match self.page_size {
+            Some(page_size) if remaining.len() > page_size => {
+                remaining.select_nth_unstable_by(page_size, by_name);
+                remaining.truncate(page_size);
+                true
+            }
+            _ => false,
+        };
+        remaining.sort_unstable_by(by_name);
+        let next_cursor = has_more
+            .then(|| remaining.last().map(|tool| tool.name.to_string()))
+            .flatten();
+        crate::model::ListToolsResult {
+            tools: remaining.into_iter().cloned().collect(),
+            next_cursor,
+            meta: None,
+        }
+    }
+
     /// Get a tool definition by name.
     ///
     /// Returns the tool if found and enabled, or `None` if the tool does not
@@ -625,7 +1119,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
+    use std::{sync::Arc, time::Duration};
 
     use super::*;
     use crate::{
@@ -689,6 +1183,543 @@ mod tests {
         assert!(text.contains("missing field `project`"));
     }
 
+    #[test]
+    fn test_parameters_error_names_field() {
+        use crate::handler::server::common::FromContextPart;
+
+        let service = DummyService;
+        let id_provider: Arc<dyn crate::service::RequestIdProvider> =
+            Arc::new(AtomicU32RequestIdProvider::default());
+        let (peer, _rx) = Peer::<RoleServer>::new(id_provider, None);
+        for (arguments, expected) in [
+            (serde_json::json!({}), "missing field `project`"),
+            (
+                serde_json::json!({ "project": 5 }),
+                "invalid type: integer `5`",
+            ),
+        ] {
+            let mut ctx = crate::handler::server::tool::ToolCallContext::new(
+                &service,
+                CallToolRequestParams::new("requires_params")
+                    .with_arguments(arguments.as_object().cloned().unwrap()),
+                RequestContext::new(NumberOrString::Number(1), peer.clone()),
+            );
+            let Err(error) = Parameters::<RequiredParams>::from_context_part(&mut ctx) else {
+                panic!("{arguments} should fail to deserialize");
+            };
+            assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+            let data = error.data.expect("error should carry field data");
+            assert_eq!(data["field"], "project");
+            assert!(data["error"].as_str().unwrap().contains(expected), "{data}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_coalescing_shares_identical_idempotent_calls() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let service = DummyService;
+        let executions = Arc::new(AtomicUsize::new(0));
+        let router = ToolRouter::new()
+            .with_route(ToolRoute::new_dyn(
+                crate::model::Tool::new("embed", "embed text", Arc::new(Default::default()))
+                    .with_annotations(ToolAnnotations::new().idempotent(true)),
+                {
+                    let executions = executions.clone();
+                    move |_ctx| {
+                        let executions = executions.clone();
+                        Box::pin(async move {
+                            executions.fetch_add(1, Ordering::SeqCst);
+                            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                            Ok(CallToolResult::success(vec![ContentBlock::text("vector")]))
+                        })
+                    }
+                },
+            ))
+            .with_coalescing(true);
+
+        let id_provider: Arc<dyn crate::service::RequestIdProvider> =
+            Arc::new(AtomicU32RequestIdProvider::default());
+        let (peer, _rx) = Peer::<RoleServer>::new(id_provider, None);
+        let ctx = |id, arguments: serde_json::Value| {
+            crate::handler::server::tool::ToolCallContext::new(
+                &service,
+                CallToolRequestParams::new("embed")
+                    .with_arguments(arguments.as_object().cloned().unwrap()),
+                RequestContext::new(NumberOrString::Number(id), peer.clone()),
+            )
+        };
+
+        let (first, second) = tokio::join!(
+            router.call(ctx(1, serde_json::json!({ "text": "hi", "model": "m" }))),
+            router.call(ctx(2, serde_json::json!({ "model": "m", "text": "hi" }))),
+        );
+        assert_eq!(executions.load(Ordering::SeqCst), 1);
+        assert_eq!(first.unwrap(), second.unwrap());
+
+        // once the shared call has finished, a new call runs again
+        router
+            .call(ctx(3, serde_json::json!({ "text": "hi", "model": "m" })))
+            .await
+            .unwrap();
+        assert_eq!(executions.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_output_validation_rejects_schema_violations() {
+        let service = DummyService;
+        let schema: JsonObject = serde_json::from_value(serde_json::json!({
+            "type": "object",
+            "properties": { "count": { "type": "integer" } },
+            "required": ["count"]
+        }))
+        .unwrap();
+        let tool = |name: &'static str, output: serde_json::Value| {
+            ToolRoute::new_dyn(
+                crate::model::Tool::new(name, "counts", Arc::new(Default::default()))
+                    .with_raw_output_schema(Arc::new(schema.clone())),
+                move |_ctx| {
+                    let output = output.clone();
+                    Box::pin(async move { Ok(CallToolResult::structured(output)) })
+                },
+            )
+        };
+        let router = ToolRouter::new()
+            .with_route(tool("good", serde_json::json!({ "count": 1 })))
+            .with_route(tool("bad", serde_json::json!({ "count": "one" })))
+            .with_output_validation(true);
+
+        let id_provider: Arc<dyn crate::service::RequestIdProvider> =
+            Arc::new(AtomicU32RequestIdProvider::default());
+        let (peer, _rx) = Peer::<RoleServer>::new(id_provider, None);
+        let ctx = |name: &'static str| {
+            crate::handler::server::tool::ToolCallContext::new(
+                &service,
+                CallToolRequestParams::new(name),
+                RequestContext::new(NumberOrString::Number(1), peer.clone()),
+            )
+        };
+
+        router.call(ctx("good")).await.unwrap();
+        let error = router.call(ctx("bad")).await.unwrap_err();
+        assert_eq!(error.code, ErrorCode::INTERNAL_ERROR);
+        assert!(error.message.contains("/count"), "{}", error.message);
+
+        let router = router.with_output_validation(false);
+        router.call(ctx("bad")).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_input_validation_rejects_before_handler_runs() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let service = DummyService;
+        let executions = Arc::new(AtomicUsize::new(0));
+        let schema: JsonObject = serde_json::from_value(serde_json::json!({
+            "type": "object",
+            "properties": { "count": { "type": "integer" } },
+            "required": ["count"]
+        }))
+        .unwrap();
+        let router = ToolRouter::new()
+            .with_route(ToolRoute::new_dyn(
+                crate::model::Tool::new("count", "counts", Arc::new(schema)),
+                {
+                    let executions = executions.clone();
+                    move |_ctx| {
+                        executions.fetch_add(1, Ordering::SeqCst);
+                        Box::pin(async { Ok(CallToolResult::default()) })
+                    }
+                },
+            ))
+            .with_input_validation(true);
+
+        let id_provider: Arc<dyn crate::service::RequestIdProvider> =
+            Arc::new(AtomicU32RequestIdProvider::default());
+        let (peer, _rx) = Peer::<RoleServer>::new(id_provider, None);
+        let ctx = |arguments: serde_json::Value| {
+            crate::handler::server::tool::ToolCallContext::new(
+                &service,
+                CallToolRequestParams::new("count")
+                    .with_arguments(arguments.as_object().cloned().unwrap()),
+                RequestContext::new(NumberOrString::Number(1), peer.clone()),
+            )
+        };
+
+        router
+            .call(ctx(serde_json::json!({ "count": 1 })))
+            .await
+            .unwrap();
+        for arguments in [serde_json::json!({ "count": "one" }), serde_json::json!({})] {
+            let error = router.call(ctx(arguments)).await.unwrap_err();
+            assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+            assert!(error.message.contains("input schema"), "{}", error.message);
+        }
+        assert_eq!(executions.load(Ordering::SeqCst), 1);
+
+        let router = router.with_input_validation(false);
+        router
+            .call(ctx(serde_json::json!({ "count": "one" })))
+            .await
+            .unwrap();
+        assert_eq!(executions.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_rejects_until_bucket_refills() {
+        let service = DummyService;
+        let router = ToolRouter::new()
+            .with_route(ToolRoute::new_dyn(
+                crate::model::Tool::new("expensive", "costly", Arc::new(JsonObject::new())),
+                |_ctx| Box::pin(async { Ok(CallToolResult::default()) }),
+            ))
+            .with_rate_limit(
+                "expensive",
+                ToolRateLimit::new(2, Duration::from_millis(200)),
+            );
+
+        let id_provider: Arc<dyn crate::service::RequestIdProvider> =
+            Arc::new(AtomicU32RequestIdProvider::default());
+        let (peer, _rx) = Peer::<RoleServer>::new(id_provider, None);
+        let ctx = |session: Option<&str>| {
+            #[allow(unused_mut)]
+            let mut request_context = RequestContext::new(NumberOrString::Number(1), peer.clone());
+            #[cfg(feature = "transport-streamable-http-server")]
+            if let Some(session) = session {
+                request_context.extensions.insert(
+                    crate::transport::common::server_side_http::ValidatedSession {
+                        id: session.into(),
+                    },
+                );
+            }
+            #[cfg(not(feature = "transport-streamable-http-server"))]
+            let _ = session;
+            crate::handler::server::tool::ToolCallContext::new(
+                &service,
+                CallToolRequestParams::new("expensive"),
+                request_context,
+            )
+        };
+
+        router.call(ctx(Some("a"))).await.unwrap();
+        router.call(ctx(Some("a"))).await.unwrap();
+        let error = router.call(ctx(Some("a"))).await.unwrap_err();
+        assert_eq!(error.code, RATE_LIMITED);
+        let retry_after_ms = error.data.as_ref().unwrap()["retryAfterMs"]
+            .as_u64()
+            .unwrap();
+        assert!((1..=100).contains(&retry_after_ms), "{retry_after_ms}");
+
+        #[cfg(feature = "transport-streamable-http-server")]
+        router.clone().call(ctx(Some("b"))).await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(retry_after_ms + 20)).await;
+        router.call(ctx(Some("a"))).await.unwrap();
+    }
+
+    #[cfg(feature = "transport-streamable-http-server")]
+    #[tokio::test]
+    async fn test_rate_limit_ignores_unvalidated_session_header() {
+        let service = DummyService;
+        let router = ToolRouter::new()
+            .with_route(ToolRoute::new_dyn(
+                crate::model::Tool::new("expensive", "costly", Arc::new(JsonObject::new())),
+                |_ctx| Box::pin(async { Ok(CallToolResult::default()) }),
+            ))
+            .with_rate_limit("expensive", ToolRateLimit::new(1, Duration::from_secs(60)));
+
+        let id_provider: Arc<dyn crate::service::RequestIdProvider> =
+            Arc::new(AtomicU32RequestIdProvider::default());
+        let (peer, _rx) = Peer::<RoleServer>::new(id_provider, None);
+        // A stateless request may carry any `Mcp-Session-Id` it likes.
+        let ctx = |session: &str| {
+            let mut request_context = RequestContext::new(NumberOrString::Number(1), peer.clone());
+            let (parts, _) = http::Request::builder()
+                .header("Mcp-Session-Id", session)
+                .body(())
+                .unwrap()
+                .into_parts();
+            request_context.extensions.insert(parts);
+            crate::handler::server::tool::ToolCallContext::new(
+                &service,
+                CallToolRequestParams::new("expensive"),
+                request_context,
+            )
+        };
+
+        router.call(ctx("a")).await.unwrap();
+        let error = router.call(ctx("b")).await.unwrap_err();
+        assert_eq!(error.code, RATE_LIMITED);
+    }
+
+    #[cfg(feature = "transport-streamable-http-server")]
+    #[tokio::test]
+    async fn test_idempotency_key_replays_first_result() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let service = DummyService;
+        let executions = Arc::new(AtomicUsize::new(0));
+        let router = ToolRouter::new()
+            .with_route(ToolRoute::new_dyn(
+                crate::model::Tool::new("charge", "charges a card", Arc::new(JsonObject::new())),
+                {
+                    let executions = executions.clone();
+                    move |_ctx| {
+                        let n = executions.fetch_add(1, Ordering::SeqCst);
+                        Box::pin(async move {
+                            Ok(CallToolResult::success(vec![ContentBlock::text(format!(
+                                "charge #{n}"
+                            ))]))
+                        })
+                    }
+                },
+            ))
+            .with_idempotency("charge", Duration::from_secs(60));
+
+        let id_provider: Arc<dyn crate::service::RequestIdProvider> =
+            Arc::new(AtomicU32RequestIdProvider::default());
+        let (peer, _rx) = Peer::<RoleServer>::new(id_provider, None);
+        let ctx = |session: Option<&str>, key: Option<&str>, amount: u64| {
+            let mut request_context = RequestContext::new(NumberOrString::Number(1), peer.clone());
+            if let Some(session) = session {
+                request_context.extensions.insert(
+                    crate::transport::common::server_side_http::ValidatedSession {
+                        id: session.into(),
+                    },
+                );
+            }
+            if let Some(key) = key {
+                request_context.meta.set_idempotency_key(key);
+            }
+            let arguments = serde_json::json!({ "amount": amount });
+            crate::handler::server::tool::ToolCallContext::new(
+                &service,
+                CallToolRequestParams::new("charge")
+                    .with_arguments(arguments.as_object().cloned().unwrap()),
+                request_context,
+            )
+        };
+
+        let first = router
+            .call(ctx(Some("a"), Some("order-1"), 5))
+            .await
+            .unwrap();
+        let replayed = router
+            .clone()
+            .call(ctx(Some("a"), Some("order-1"), 5))
+            .await
+            .unwrap();
+        assert_eq!(executions.load(Ordering::SeqCst), 1);
+        assert_eq!(first, replayed);
+
+        router
+            .call(ctx(Some("a"), Some("order-2"), 5))
+            .await
+            .unwrap();
+        router.call(ctx(Some("a"), None, 5)).await.unwrap();
+        assert_eq!(executions.load(Ordering::SeqCst), 3);
+
+        // Another session, or a call outside any session, does not see the
+        // stored result.
+        router
+            .call(ctx(Some("b"), Some("order-1"), 5))
+            .await
+            .unwrap();
+        router.call(ctx(None, Some("order-1"), 5)).await.unwrap();
+        router.call(ctx(None, Some("order-1"), 5)).await.unwrap();
+        assert_eq!(executions.load(Ordering::SeqCst), 6);
+    }
+
+    #[cfg(feature = "transport-streamable-http-server")]
+    #[tokio::test]
+    async fn test_idempotency_key_reused_with_different_arguments_is_rejected() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let service = DummyService;
+        let executions = Arc::new(AtomicUsize::new(0));
+        let router = ToolRouter::new()
+            .with_route(ToolRoute::new_dyn(
+                crate::model::Tool::new("charge", "charges a card", Arc::new(JsonObject::new())),
+                {
+                    let executions = executions.clone();
+                    move |_ctx| {
+                        executions.fetch_add(1, Ordering::SeqCst);
+                        Box::pin(async { Ok(CallToolResult::default()) })
+                    }
+                },
+            ))
+            .with_idempotency("charge", Duration::from_secs(60));
+
+        let id_provider: Arc<dyn crate::service::RequestIdProvider> =
+            Arc::new(AtomicU32RequestIdProvider::default());
+        let (peer, _rx) = Peer::<RoleServer>::new(id_provider, None);
+        let ctx = |amount: u64| {
+            let mut request_context = RequestContext::new(NumberOrString::Number(1), peer.clone());
+            request_context.extensions.insert(
+                crate::transport::common::server_side_http::ValidatedSession { id: "a".into() },
+            );
+            request_context.meta.set_idempotency_key("order-1");
+            let arguments = serde_json::json!({ "amount": amount });
+            crate::handler::server::tool::ToolCallContext::new(
+                &service,
+                CallToolRequestParams::new("charge")
+                    .with_arguments(arguments.as_object().cloned().unwrap()),
+                request_context,
+            )
+        };
+
+        router.call(ctx(5)).await.unwrap();
+        let error = router.call(ctx(500)).await.unwrap_err();
+        assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+        assert!(error.message.contains("order-1"), "{}", error.message);
+        assert_eq!(executions.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(feature = "transport-streamable-http-server")]
+    #[tokio::test]
+    async fn test_idempotent_replay_bypasses_rate_limit() {
+        let service = DummyService;
+        let router = ToolRouter::new()
+            .with_route(ToolRoute::new_dyn(
+                crate::model::Tool::new("charge", "charges a card", Arc::new(JsonObject::new())),
+                |_ctx| Box::pin(async { Ok(CallToolResult::default()) }),
+            ))
+            .with_idempotency("charge", Duration::from_secs(60))
+            .with_rate_limit("charge", ToolRateLimit::new(1, Duration::from_secs(60)));
+
+        let id_provider: Arc<dyn crate::service::RequestIdProvider> =
+            Arc::new(AtomicU32RequestIdProvider::default());
+        let (peer, _rx) = Peer::<RoleServer>::new(id_provider, None);
+        let ctx = |key: &str| {
+            let mut request_context = RequestContext::new(NumberOrString::Number(1), peer.clone());
+            request_context.extensions.insert(
+                crate::transport::common::server_side_http::ValidatedSession { id: "a".into() },
+            );
+            request_context.meta.set_idempotency_key(key);
+            crate::handler::server::tool::ToolCallContext::new(
+                &service,
+                CallToolRequestParams::new("charge"),
+                request_context,
+            )
+        };
+
+        let first = router.call(ctx("order-1")).await.unwrap();
+        // The bucket is now empty: a new key is rejected, a retry is not.
+        let error = router.call(ctx("order-2")).await.unwrap_err();
+        assert_eq!(error.code, RATE_LIMITED);
+        let replayed = router.call(ctx("order-1")).await.unwrap();
+        assert_eq!(first, replayed);
+    }
+
+    #[test]
+    fn test_list_order_is_stable_across_router_instances() {
+        let names = [
+            "zeta", "alpha", "mu", "omega", "beta", "kappa", "delta", "sigma",
+        ];
+        let build = || {
+            names
+                .iter()
+                .fold(ToolRouter::<DummyService>::new(), |router, name| {
+                    router.with_route(ToolRoute::new_dyn(
+                        crate::model::Tool::new(*name, "tool", Arc::new(Default::default())),
+                        |_ctx| Box::pin(async { Ok(CallToolResult::default()) }),
+                    ))
+                })
+        };
+        let listed = |router: &ToolRouter<DummyService>| {
+            router
+                .list_all()
+                .into_iter()
+                .map(|tool| tool.name.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let mut sorted = names.map(String::from).to_vec();
+        sorted.sort();
+        // every router gets its own randomly seeded HashMap
+        for _ in 0..16 {
+            let router = build().with_page_size(3);
+            assert_eq!(listed(&router), sorted);
+
+            let mut paged = Vec::new();
+            let mut cursor = None;
+            loop {
+                let page = router.list_page(cursor.as_deref());
+                paged.extend(page.tools.into_iter().map(|tool| tool.name.to_string()));
+                cursor = page.next_cursor;
+                if cursor.is_none() {
+                    break;
+                }
+            }
+            assert_eq!(paged, sorted);
+        }
+    }
+
+    #[test]
+    fn test_max_schema_depth_limits_existing_and_later_tools() {
+        let deep = || {
+            crate::model::object(serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "a": { "type": "array", "items": { "type": "string" } }
+                }
+            }))
+        };
+        let noop =
+            |_: ToolCallContext<'_, DummyService>| async { Ok(CallToolResult::success(vec![])) };
+        let mut router = ToolRouter::<DummyService>::new();
+        router.add_dynamic(Tool::builder("before").input_schema(deep()).build(), noop);
+        let mut router = router.with_max_schema_depth(1);
+        router.add_dynamic(Tool::builder("after").input_schema(deep()).build(), noop);
+
+        for name in ["before", "after"] {
+            let schema = &router.get(name).expect("tool is listed").input_schema;
+            assert_eq!(schema["properties"]["a"]["type"], "array");
+            assert!(schema["properties"]["a"]["items"]["$comment"].is_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_add_dynamic_registers_callable_tool_and_notifies() {
+        let service = DummyService;
+        let notified = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut router = ToolRouter::<DummyService>::new();
+        router.set_notifier({
+            let notified = notified.clone();
+            move || {
+                notified.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            }
+        });
+
+        router.add_dynamic(crate::model::Tool::builder("echo").build(), |context| {
+            let arguments = context.arguments.unwrap_or_default();
+            async move {
+                Ok(CallToolResult::success(vec![ContentBlock::text(
+                    serde_json::Value::Object(arguments).to_string(),
+                )]))
+            }
+        });
+        assert_eq!(notified.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert!(router.has_route("echo"));
+
+        let id_provider: Arc<dyn crate::service::RequestIdProvider> =
+            Arc::new(AtomicU32RequestIdProvider::default());
+        let (peer, _rx) = Peer::<RoleServer>::new(id_provider, None);
+        let ctx = crate::handler::server::tool::ToolCallContext::new(
+            &service,
+            CallToolRequestParams::new("echo").with_arguments(
+                serde_json::json!({ "message": "hi" })
+                    .as_object()
+                    .cloned()
+                    .unwrap(),
+            ),
+            RequestContext::new(NumberOrString::Number(1), peer),
+        );
+        let result = router.call(ctx).await.expect("dynamic tool result");
+        let text = result.content[0].as_text().expect("text content");
+        assert_eq!(text.text, r#"{"message":"hi"}"#);
+    }
+
     #[tokio::test]
     async fn test_call_disabled_tool_returns_error() {
         let service = DummyService;
@@ -717,6 +1748,107 @@ mod tests {
             .await
             .expect_err("disabled tool should reject");
         assert_eq!(err.code, ErrorCode::INVALID_PARAMS);
-        assert_eq!(err.message, "tool not found");
+        assert_eq!(err.message, "tool not found: test_tool");
+    }
+
+    fn oversized_router(limits: ToolResultLimits) -> ToolRouter<DummyService> {
+        ToolRouter::new()
+            .with_route(ToolRoute::new_dyn(
+                crate::model::Tool::new("flood", "returns a lot", Arc::new(Default::default())),
+                |_ctx| {
+                    Box::pin(async {
+                        Ok(CallToolResult::success(
+                            (0..100)
+                                .map(|_| ContentBlock::text("x".repeat(1024)))
+                                .collect(),
+                        ))
+                    })
+                },
+            ))
+            .with_result_limits(limits)
+    }
+
+    async fn call_flood(
+        router: &ToolRouter<DummyService>,
+    ) -> Result<CallToolResult, crate::ErrorData> {
+        let service = DummyService;
+        let id_provider: Arc<dyn crate::service::RequestIdProvider> =
+            Arc::new(AtomicU32RequestIdProvider::default());
+        let (peer, _rx) = Peer::<RoleServer>::new(id_provider, None);
+        let ctx = crate::handler::server::tool::ToolCallContext::new(
+            &service,
+            CallToolRequestParams::new("flood"),
+            RequestContext::new(NumberOrString::Number(1), peer),
+        );
+        router.call(ctx).await
+    }
+
+    fn content_bytes(result: &CallToolResult) -> usize {
+        result.content.iter().map(block_size).sum()
+    }
+
+    #[tokio::test]
+    async fn test_oversized_result_is_truncated() {
+        let router = oversized_router(ToolResultLimits::new().with_max_content_bytes(4096));
+        let result = call_flood(&router).await.expect("truncated result");
+        // Each block serializes to a little over 1KiB, so three fit next to
+        // the marker.
+        assert_eq!(result.content.len(), 4);
+        assert!(content_bytes(&result) <= 4096);
+        let marker = result.content[3].as_text().expect("truncation marker");
+        assert_eq!(marker.text, "[content truncated: 97 of 100 blocks omitted]");
+
+        // Three blocks fit exactly, so one gives way for the marker.
+        let block = block_size(&ContentBlock::text("x".repeat(1024)));
+        let router = oversized_router(ToolResultLimits::new().with_max_content_bytes(3 * block));
+        let result = call_flood(&router).await.expect("truncated result");
+        assert_eq!(result.content.len(), 3);
+        assert!(content_bytes(&result) <= 3 * block);
+        let marker = result.content[2].as_text().expect("truncation marker");
+        assert_eq!(marker.text, "[content truncated: 98 of 100 blocks omitted]");
+
+        let router = oversized_router(ToolResultLimits::new().with_max_content_blocks(10));
+        let result = call_flood(&router).await.expect("truncated result");
+        assert_eq!(result.content.len(), 10);
+        let marker = result.content[9].as_text().expect("truncation marker");
+        assert_eq!(marker.text, "[content truncated: 91 of 100 blocks omitted]");
+
+        // Not even the marker fits: the result is emptied without one.
+        let router = oversized_router(ToolResultLimits::new().with_max_content_bytes(16));
+        let result = call_flood(&router).await.expect("truncated result");
+        assert!(result.content.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_zero_block_limit_truncates_without_marker() {
+        let router = oversized_router(ToolResultLimits::new().with_max_content_blocks(0));
+        let result = call_flood(&router).await.expect("truncated result");
+        assert!(result.content.is_empty());
+
+        let router = oversized_router(ToolResultLimits::new().with_max_content_blocks(1));
+        let result = call_flood(&router).await.expect("truncated result");
+        assert_eq!(result.content.len(), 1);
+        let marker = result.content[0].as_text().expect("truncation marker");
+        assert_eq!(
+            marker.text,
+            "[content truncated: 100 of 100 blocks omitted]"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_oversized_result_errors() {
+        let router = oversized_router(
+            ToolResultLimits::new()
+                .with_max_content_blocks(10)
+                .with_on_exceeded(ToolResultLimitAction::Error),
+        );
+        let err = call_flood(&router)
+            .await
+            .expect_err("oversized result should fail");
+        assert_eq!(err.code, ErrorCode::INTERNAL_ERROR);
+
+        let router = oversized_router(ToolResultLimits::new().with_max_content_blocks(100));
+        let result = call_flood(&router).await.expect("within limits");
+        assert_eq!(result.content.len(), 100);
     }
 }
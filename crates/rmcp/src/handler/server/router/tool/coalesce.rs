@@ -0,0 +1,144 @@
+//! Sharing one in-flight execution among identical concurrent tool calls.
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use serde_json::Value;
+use tokio::sync::watch;
+
+use crate::model::{CallToolResult, JsonObject};
+
+type CallResult = Result<CallToolResult, crate::ErrorData>;
+type Key = (Cow<'static, str>, String);
+
+/// In-flight calls keyed by `(tool name, canonical arguments)`.
+#[derive(Debug, Default)]
+pub(super) struct Coalescer {
+    in_flight: Mutex<HashMap<Key, watch::Receiver<Option<CallResult>>>>,
+}
+
+impl Coalescer {
+    /// Run `call`, or wait for an identical call that is already running and
+    /// share its result.
+    ///
+    /// If the running call is cancelled before it completes, waiters fall back
+    /// to running their own `call`.
+    pub(super) async fn run<F>(
+        self: &Arc<Self>,
+        name: Cow<'static, str>,
+        arguments: Option<&JsonObject>,
+        call: F,
+    ) -> CallResult
+    where
+        F: Future<Output = CallResult>,
+    {
+        let key = (name, canonical_arguments(arguments));
+        let tx = {
+            let mut in_flight = self.in_flight.lock().expect("coalescer lock poisoned");
+            match in_flight.get(&key) {
+                Some(rx) => Err(rx.clone()),
+                None => {
+                    let (tx, rx) = watch::channel(None);
+                    in_flight.insert(key.clone(), rx);
+                    Ok(tx)
+                }
+            }
+        };
+        match tx {
+            Ok(tx) => {
+                let _guard = InFlightEntry {
+                    coalescer: self.clone(),
+                    key,
+                };
+                let result = call.await;
+                tx.send_replace(Some(result.clone()));
+                result
+            }
+            Err(mut rx) => {
+                let shared = rx
+                    .wait_for(Option::is_some)
+                    .await
+                    .map(|result| result.clone());
+                match shared {
+                    Ok(Some(result)) => result,
+                    _ => call.await,
+                }
+            }
+        }
+    }
+}
+
+/// Removes the in-flight entry when the leading call finishes or is dropped.
+struct InFlightEntry {
+    coalescer: Arc<Coalescer>,
+    key: Key,
+}
+
+impl Drop for InFlightEntry {
+    fn drop(&mut self) {
+        if let Ok(mut in_flight) = self.coalescer.in_flight.lock() {
+            in_flight.remove(&self.key);
+        }
+    }
+}
+
+/// Serialize arguments with object keys sorted, so that key order does not
+/// affect whether two calls are considered identical.
+fn canonical_arguments(arguments: Option<&JsonObject>) -> String {
+    fn write_object(object: &JsonObject, out: &mut String) {
+        let mut entries: Vec<_> = object.iter().collect();
+        entries.sort_by_key(|(key, _)| *key);
+        out.push('{');
+        for (i, (key, value)) in entries.into_iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push_str(&Value::String(key.clone()).to_string());
+            out.push(':');
+            write(value, out);
+        }
+        out.push('}');
+    }
+    fn write(value: &Value, out: &mut String) {
+        match value {
+            Value::Object(object) => write_object(object, out),
+            Value::Array(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    write(item, out);
+                }
+                out.push(']');
+            }
+            scalar => out.push_str(&scalar.to_string()),
+        }
+    }
+    let mut out = String::new();
+    write_object(arguments.unwrap_or(&JsonObject::new()), &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_arguments_ignore_key_order() {
+        let a = serde_json::json!({ "b": 1, "a": { "y": [1, 2], "x": null } });
+        let b = serde_json::json!({ "a": { "x": null, "y": [1, 2] }, "b": 1 });
+        assert_eq!(
+            canonical_arguments(a.as_object()),
+            canonical_arguments(b.as_object())
+        );
+        assert_eq!(
+            canonical_arguments(None),
+            canonical_arguments(Some(&JsonObject::new()))
+        );
+    }
+}
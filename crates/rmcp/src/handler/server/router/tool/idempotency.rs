@@ -0,0 +1,155 @@
+//! Replaying the stored result of a tool call retried with the same idempotency key.
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tokio::sync::watch;
+
+use crate::model::{CallToolResult, JsonObject};
+
+type CallResult = Result<CallToolResult, crate::ErrorData>;
+type Key = (Cow<'static, str>, String, String);
+
+#[derive(Debug)]
+struct Entry {
+    /// The arguments of the call that claimed the key.
+    arguments: Option<JsonObject>,
+    state: EntryState,
+}
+
+#[derive(Debug)]
+enum EntryState {
+    Running(watch::Receiver<Option<CallResult>>),
+    Done {
+        result: CallToolResult,
+        expires: Instant,
+    },
+}
+
+/// Calls keyed by `(tool name, session id, idempotency key)`.
+#[derive(Debug, Default)]
+pub(super) struct IdempotencyCache {
+    entries: Mutex<HashMap<Key, Entry>>,
+}
+
+impl IdempotencyCache {
+    /// Run `call` and keep its result for `ttl`, or return the result already
+    /// kept for this key.
+    ///
+    /// A retry that arrives while the first call is still running waits for
+    /// it. Only successful results are kept, so a call that failed with a
+    /// protocol error, or was cancelled, runs again when retried. Reusing a
+    /// key with different arguments is rejected with `invalid_params`.
+    pub(super) async fn run<F>(
+        self: &Arc<Self>,
+        tool: Cow<'static, str>,
+        session: &str,
+        idempotency_key: &str,
+        arguments: Option<&JsonObject>,
+        ttl: Duration,
+        call: F,
+    ) -> CallResult
+    where
+        F: Future<Output = CallResult>,
+    {
+        let key = (tool, session.to_owned(), idempotency_key.to_owned());
+        let tx = {
+            let now = Instant::now();
+            let mut entries = self.entries.lock().expect("idempotency lock poisoned");
+            entries.retain(|_, entry| {
+                !matches!(entry.state, EntryState::Done { expires, .. } if expires <= now)
+            });
+            match entries.get(&key) {
+                Some(entry) if entry.arguments.as_ref() != arguments => {
+                    return Err(crate::ErrorData::invalid_params(
+                        format!(
+                            "idempotency key `{idempotency_key}` was already used for a call to `{}` with different arguments",
+                            key.0
+                        ),
+                        None,
+                    ));
+                }
+                Some(Entry {
+                    state: EntryState::Done { result, .. },
+                    ..
+                }) => return Ok(result.clone()),
+                Some(Entry {
+                    state: EntryState::Running(rx),
+                    ..
+                }) => Err(rx.clone()),
+                None => {
+                    let (tx, rx) = watch::channel(None);
+                    entries.insert(
+                        key.clone(),
+                        Entry {
+                            arguments: arguments.cloned(),
+                            state: EntryState::Running(rx),
+                        },
+                    );
+                    Ok(tx)
+                }
+            }
+        };
+        match tx {
+            Ok(tx) => {
+                let mut running = RunningEntry {
+                    cache: self.clone(),
+                    key,
+                    arguments: arguments.cloned(),
+                    result: None,
+                };
+                let result = call.await;
+                if let Ok(result) = &result {
+                    running.result = Some(EntryState::Done {
+                        result: result.clone(),
+                        expires: Instant::now() + ttl,
+                    });
+                }
+                drop(running);
+                tx.send_replace(Some(result.clone()));
+                result
+            }
+            Err(mut rx) => {
+                let shared = rx
+                    .wait_for(Option::is_some)
+                    .await
+                    .map(|result| result.clone());
+                match shared {
+                    Ok(Some(result)) => result,
+                    _ => call.await,
+                }
+            }
+        }
+    }
+}
+
+/// Replaces the running entry with the stored result, or removes it if the
+/// call failed or was dropped.
+struct RunningEntry {
+    cache: Arc<IdempotencyCache>,
+    key: Key,
+    arguments: Option<JsonObject>,
+    result: Option<EntryState>,
+}
+
+impl Drop for RunningEntry {
+    fn drop(&mut self) {
+        if let Ok(mut entries) = self.cache.entries.lock() {
+            match self.result.take() {
+                Some(done) => entries.insert(
+                    self.key.clone(),
+                    Entry {
+                        arguments: self.arguments.take(),
+                        state: done,
+                    },
+                ),
+                None => entries.remove(&self.key),
+            };
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Token-bucket rate limits on individual tools, kept per session.
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::model::ErrorCode;
+
+/// A token-bucket limit on calls to one tool, see
+/// [`ToolRouter::with_rate_limit`](super::ToolRouter::with_rate_limit).
+///
+/// A fresh bucket holds `burst` calls and refills at `burst` calls per
+/// `period`, so a client may spend the whole allowance at once and then gets
+/// one more call every `period / burst`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ToolRateLimit {
+    pub burst: u32,
+    pub period: Duration,
+}
+
+impl ToolRateLimit {
+    /// Allow `calls` calls per `period`. Both are clamped to be non-zero.
+    pub fn new(calls: u32, period: Duration) -> Self {
+        Self {
+            burst: calls.max(1),
+            period: period.max(Duration::from_millis(1)),
+        }
+    }
+
+    fn tokens_per_second(&self) -> f64 {
+        self.burst as f64 / self.period.as_secs_f64()
+    }
+}
+
+/// Error code returned when a call exceeds its tool's rate limit.
+pub const RATE_LIMITED: ErrorCode = ErrorCode(-32000);
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    /// When the bucket will have refilled to `burst` tokens.
+    full_at: Instant,
+}
+
+impl Bucket {
+    fn refill(&mut self, limit: &ToolRateLimit, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * limit.tokens_per_second()).min(limit.burst as f64);
+        self.updated = now;
+    }
+
+    fn take(&mut self, limit: &ToolRateLimit, now: Instant) -> Result<(), Duration> {
+        self.refill(limit, now);
+        let result = if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            let wait = (1.0 - self.tokens) / limit.tokens_per_second();
+            Err(Duration::from_secs_f64(wait))
+        };
+        let missing = limit.burst as f64 - self.tokens;
+        self.full_at = now + Duration::from_secs_f64(missing / limit.tokens_per_second());
+        result
+    }
+}
+
+type Key = (Cow<'static, str>, Option<String>);
+
+/// How often full buckets are dropped.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug)]
+struct Buckets {
+    buckets: HashMap<Key, Bucket>,
+    next_prune: Instant,
+}
+
+/// Buckets keyed by `(tool name, session id)`.
+#[derive(Debug)]
+pub(super) struct RateLimiter {
+    buckets: Mutex<Buckets>,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self {
+            buckets: Mutex::new(Buckets {
+                buckets: HashMap::new(),
+                next_prune: Instant::now() + PRUNE_INTERVAL,
+            }),
+        }
+    }
+}
+
+impl RateLimiter {
+    /// Take one token from the bucket of `tool` in `session`, or return how
+    /// long until one is available.
+    pub(super) fn acquire(
+        &self,
+        tool: Cow<'static, str>,
+        session: Option<&str>,
+        limit: &ToolRateLimit,
+    ) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut state = self.buckets.lock().expect("rate limiter lock poisoned");
+        // Full buckets carry no state, so drop them now and then to keep
+        // sessions that have gone away from accumulating.
+        if state.next_prune <= now {
+            state.buckets.retain(|_, bucket| bucket.full_at > now);
+            state.next_prune = now + PRUNE_INTERVAL;
+        }
+        state
+            .buckets
+            .entry((tool, session.map(str::to_owned)))
+            .or_insert(Bucket {
+                tokens: limit.burst as f64,
+                updated: now,
+                full_at: now,
+            })
+            .take(limit, now)
+    }
+}
+
+/// The error returned for a call rejected by `tool`'s rate limit.
+pub(super) fn rate_limited(tool: &str, retry_after: Duration) -> crate::ErrorData {
+    let retry_after_ms = retry_after.as_millis().max(1) as u64;
+    crate::ErrorData::new(
+        RATE_LIMITED,
+        format!("rate limit exceeded for tool `{tool}`"),
+        Some(serde_json::json!({
+            "retryAfterMs": retry_after_ms,
+            "retryAfter": retry_after_ms.div_ceil(1000),
+        })),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_buckets_are_pruned_periodically() {
+        let limiter = RateLimiter::default();
+        let limit = ToolRateLimit::new(1, Duration::from_millis(1));
+        limiter.acquire("tool".into(), Some("a"), &limit).unwrap();
+        std::thread::sleep(Duration::from_millis(5));
+
+        // The bucket of `a` is full again but kept until the next prune.
+        limiter.acquire("tool".into(), Some("b"), &limit).unwrap();
+        assert_eq!(limiter.buckets.lock().unwrap().buckets.len(), 2);
+
+        std::thread::sleep(Duration::from_millis(5));
+        limiter.buckets.lock().unwrap().next_prune = Instant::now();
+        limiter.acquire("tool".into(), Some("c"), &limit).unwrap();
+        let state = limiter.buckets.lock().unwrap();
+        assert_eq!(state.buckets.len(), 1);
+        assert!(state.next_prune > Instant::now());
+    }
+}
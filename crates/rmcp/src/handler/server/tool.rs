@@ -8,10 +8,10 @@ use std::{
 use futures::future::BoxFuture;
 use serde::de::DeserializeOwned;
 
-use super::common::{AsRequestContext, FromContextPart};
+use super::common::{AsRequestContext, AsyncFromContextPart, FromContextPart};
 pub use super::{
     common::{Extension, RequestId, schema_for_input, schema_for_output, schema_for_type},
-    router::tool::{ToolRoute, ToolRouter},
+    router::tool::{ToolResultLimitAction, ToolResultLimits, ToolRoute, ToolRouter},
 };
 use crate::{
     RoleServer,
@@ -21,13 +21,35 @@ use crate::{
 };
 
 /// Deserialize a JSON object into a type
+///
+/// On failure, returns an invalid-params (`-32602`) error whose `data` names the
+/// offending field, e.g. `{"field": "options.limit", "error": "invalid type: ..."}`.
+/// `field` is `null` when the error is not tied to a single field.
 pub fn parse_json_object<T: DeserializeOwned>(input: JsonObject) -> Result<T, crate::ErrorData> {
-    serde_json::from_value(serde_json::Value::Object(input)).map_err(|e| {
-        crate::ErrorData::invalid_params(
-            format!("failed to deserialize parameters: {error}", error = e),
-            None,
-        )
-    })
+    serde_path_to_error::deserialize(serde_json::Value::Object(input))
+        .map_err(invalid_params_from_path_error)
+}
+
+fn invalid_params_from_path_error(
+    error: serde_path_to_error::Error<serde_json::Error>,
+) -> crate::ErrorData {
+    let path = error.path().to_string();
+    let message = format!("failed to deserialize parameters: {error}");
+    let inner = error.into_inner().to_string();
+    // a missing field is reported against its parent, so append the field name
+    let missing = inner
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.split('`').next());
+    let field = match (path.as_str(), missing) {
+        (".", Some(name)) => Some(name.to_owned()),
+        (".", None) => None,
+        (parent, Some(name)) => Some(format!("{parent}.{name}")),
+        (parent, None) => Some(parent.to_owned()),
+    };
+    crate::ErrorData::invalid_params(
+        message,
+        Some(serde_json::json!({ "field": field, "error": inner })),
+    )
 }
 #[non_exhaustive]
 pub struct ToolCallContext<'s, S> {
@@ -63,6 +85,17 @@ impl<'s, S> ToolCallContext<'s, S> {
     pub fn request_context(&self) -> &RequestContext<RoleServer> {
         &self.request_context
     }
+    /// The `_meta` the client sent with this `tools/call` request.
+    pub fn request_meta(&self) -> &crate::model::Meta {
+        &self.request_context.meta
+    }
+    /// The idempotency key the client sent in `_meta`, if any.
+    ///
+    /// See [`ToolRouter::with_idempotency`](crate::handler::server::router::tool::ToolRouter::with_idempotency)
+    /// for replaying results of retried calls automatically.
+    pub fn idempotency_key(&self) -> Option<&str> {
+        self.request_context.meta.get_idempotency_key()
+    }
 }
 
 impl<S> AsRequestContext for ToolCallContext<'_, S> {
@@ -75,6 +108,19 @@ impl<S> AsRequestContext for ToolCallContext<'_, S> {
     }
 }
 
+/// Conversion from a tool's return value into the result of `tools/call`.
+///
+/// A tool can fail in two ways, and hosts treat them differently:
+///
+/// - A tool result with `is_error: true` is part of the conversation. The host
+///   typically shows the error content to the model so it can correct itself and
+///   retry. `Result<T, E>` produces this when `E` converts into content, e.g.
+///   `Result<_, String>`.
+/// - A protocol-level error ([`ErrorData`](crate::ErrorData)) is a JSON-RPC error
+///   response carrying a code and optional structured `data`. The host usually
+///   surfaces it to the application rather than the model. `Result<T, ErrorData>`
+///   (also spelled `Result<T, McpError>`) produces this, keeping the error code
+///   and `data` intact.
 pub trait IntoCallToolResult {
     fn into_call_tool_result(self) -> Result<CallToolResult, crate::ErrorData>;
 }
@@ -184,14 +230,7 @@ where
 {
     fn from_context_part(context: &mut ToolCallContext<S>) -> Result<Self, crate::ErrorData> {
         let arguments = context.arguments.take().unwrap_or_default();
-        let value: P =
-            serde_json::from_value(serde_json::Value::Object(arguments)).map_err(|e| {
-                crate::ErrorData::invalid_params(
-                    format!("failed to deserialize parameters: {error}", error = e),
-                    None,
-                )
-            })?;
-        Ok(Parameters(value))
+        parse_json_object(arguments).map(Parameters)
     }
 }
 
@@ -234,9 +273,9 @@ macro_rules! impl_for {
         impl<$($Tn,)* S, F,  R> CallToolHandler<S, AsyncMethodAdapter<($($Tn,)*), R>> for F
         where
             $(
-                $Tn: for<'a> FromContextPart<ToolCallContext<'a, S>> ,
+                $Tn: for<'a> AsyncFromContextPart<ToolCallContext<'a, S>> + MaybeSendFuture,
             )*
-            F: FnOnce(&S, $($Tn,)*) -> MaybeBoxFuture<'_, R>,
+            F: FnOnce(&S, $($Tn,)*) -> MaybeBoxFuture<'_, R> + MaybeSendFuture + 'static,
 
             // Need RTN support here(I guess), https://github.com/rust-lang/rust/pull/138424
             // Fut: Future<Output = R> + Send + 'a,
@@ -249,16 +288,14 @@ macro_rules! impl_for {
                 mut context: ToolCallContext<'_, S>,
             ) -> MaybeBoxFuture<'_, Result<CallToolResult, crate::ErrorData>>{
                 $(
-                    let result = $Tn::from_context_part(&mut context);
-                    let $Tn = match result {
-                        Ok(value) => value,
-                        Err(e) => return Box::pin(std::future::ready(Err(e))),
-                    };
+                    let $Tn = $Tn::from_context_part_async(&mut context);
                 )*
                 let service = context.service;
-                let fut = self(service, $($Tn,)*);
                 Box::pin(async move {
-                    let result = fut.await;
+                    $(
+                        let $Tn = $Tn.await?;
+                    )*
+                    let result = self(service, $($Tn,)*).await;
                     result.into_call_tool_result()
                 })
             }
@@ -267,9 +304,9 @@ macro_rules! impl_for {
         impl<$($Tn,)* S, F, Fut, R> CallToolHandler<S, AsyncAdapter<($($Tn,)*), Fut, R>> for F
         where
             $(
-                $Tn: for<'a> FromContextPart<ToolCallContext<'a, S>> ,
+                $Tn: for<'a> AsyncFromContextPart<ToolCallContext<'a, S>> + MaybeSendFuture,
             )*
-            F: FnOnce($($Tn,)*) -> Fut + MaybeSendFuture,
+            F: FnOnce($($Tn,)*) -> Fut + MaybeSendFuture + 'static,
             Fut: Future<Output = R> + MaybeSendFuture + 'static,
             R: IntoCallToolResult + MaybeSendFuture + 'static,
             S: MaybeSend,
@@ -280,15 +317,13 @@ macro_rules! impl_for {
                 mut context: ToolCallContext<S>,
             ) -> MaybeBoxFuture<'static, Result<CallToolResult, crate::ErrorData>>{
                 $(
-                    let result = $Tn::from_context_part(&mut context);
-                    let $Tn = match result {
-                        Ok(value) => value,
-                        Err(e) => return Box::pin(std::future::ready(Err(e))),
-                    };
+                    let $Tn = $Tn::from_context_part_async(&mut context);
                 )*
-                let fut = self($($Tn,)*);
                 Box::pin(async move {
-                    let result = fut.await;
+                    $(
+                        let $Tn = $Tn.await?;
+                    )*
+                    let result = self($($Tn,)*).await;
                     result.into_call_tool_result()
                 })
             }
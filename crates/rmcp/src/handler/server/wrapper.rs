@@ -1,4 +1,6 @@
 mod json;
 mod parameters;
+mod tool_error;
 pub use json::*;
 pub use parameters::*;
+pub use tool_error::*;
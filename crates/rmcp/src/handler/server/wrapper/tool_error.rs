@@ -0,0 +1,44 @@
+use crate::{handler::server::tool::IntoCallToolResult, model::CallToolResult};
+
+/// Error wrapper for tool-level failures
+///
+/// Wrapping an error in `ToolError` turns it into a tool result with
+/// `is_error: true` (see [`CallToolResult::from_error`]), so a tool can return
+/// `Result<T, ToolError<E>>` and use `?` on fallible calls without mapping
+/// each error by hand. `E` is anything that converts into
+/// `Box<dyn Error + Send + Sync>`: any `Send + Sync` [`std::error::Error`],
+/// `anyhow::Error`, the boxed error itself, or a `String`. The error's source
+/// chain is kept.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use rmcp::handler::server::wrapper::ToolError;
+///
+/// #[tool(description = "Read a file")]
+/// async fn read(&self, Parameters(path): Parameters<String>) -> Result<String, ToolError<std::io::Error>> {
+///     Ok(tokio::fs::read_to_string(path).await?)
+/// }
+/// ```
+#[derive(Debug)]
+#[expect(clippy::exhaustive_structs, reason = "intentionally exhaustive")]
+pub struct ToolError<E>(pub E);
+
+impl<E> From<E> for ToolError<E>
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+    fn from(error: E) -> Self {
+        ToolError(error)
+    }
+}
+
+impl<E> IntoCallToolResult for ToolError<E>
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+    fn into_call_tool_result(self) -> Result<CallToolResult, crate::ErrorData> {
+        let error: Box<dyn std::error::Error + Send + Sync + 'static> = self.0.into();
+        Ok(CallToolResult::from_error(&*error))
+    }
+}
@@ -23,9 +23,15 @@ pub use service::{RoleClient, serve_client};
 #[cfg(feature = "server")]
 pub use service::{RoleServer, serve_server};
 
+#[cfg(all(feature = "blocking", not(feature = "local")))]
+pub mod blocking;
 pub mod handler;
+#[cfg(feature = "client")]
+pub mod oneshot;
 #[cfg(feature = "server")]
 pub mod task_manager;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 #[cfg(any(feature = "client", feature = "server"))]
 pub mod transport;
 
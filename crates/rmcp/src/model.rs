@@ -12,8 +12,11 @@ mod content;
 mod elicitation_schema;
 mod extension;
 mod meta;
+#[cfg(feature = "mime-guess")]
+mod mime;
 mod prompt;
 mod resource;
+mod schema_validation;
 mod serde_impl;
 mod task;
 mod tool;
@@ -23,8 +26,11 @@ pub use content::*;
 pub use elicitation_schema::*;
 pub use extension::*;
 pub use meta::*;
+#[cfg(feature = "mime-guess")]
+pub use mime::*;
 pub use prompt::*;
 pub use resource::*;
+pub use schema_validation::*;
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json::Value;
 pub use task::*;
@@ -540,6 +546,12 @@ pub struct ErrorData {
 }
 
 impl ErrorData {
+    /// The `data` member deserialized as `T`, or `None` if it is absent or
+    /// has a different shape.
+    pub fn data_as<T: serde::de::DeserializeOwned>(&self) -> Option<T> {
+        T::deserialize(self.data.as_ref()?).ok()
+    }
+
     pub fn new(
         code: ErrorCode,
         message: impl Into<Cow<'static, str>>,
@@ -578,6 +590,44 @@ impl ErrorData {
     ) -> Self {
         Self::new(ErrorCode::URL_ELICITATION_REQUIRED, message, data)
     }
+
+    /// Internal error (`-32603`) with no data.
+    pub fn internal(message: impl Into<Cow<'static, str>>) -> Self {
+        Self::internal_error(message, None)
+    }
+    /// Method-not-found error (`-32601`) for a method name only known at runtime.
+    pub fn method_not_found_for(method: impl Into<String>) -> Self {
+        let method = method.into();
+        Self::new(
+            ErrorCode::METHOD_NOT_FOUND,
+            format!("method not found: {method}"),
+            Some(serde_json::json!({ "method": method })),
+        )
+    }
+    /// Resource-not-found error for `uri`, see [`ErrorData::resource_not_found`].
+    pub fn resource_not_found_for(uri: impl Into<String>) -> Self {
+        let uri = uri.into();
+        Self::resource_not_found(
+            format!("resource not found: {uri}"),
+            Some(serde_json::json!({ "uri": uri })),
+        )
+    }
+    /// Invalid-params error (`-32602`) for a tool name that is not registered.
+    pub fn tool_not_found(name: impl Into<String>) -> Self {
+        let name = name.into();
+        Self::invalid_params(
+            format!("tool not found: {name}"),
+            Some(serde_json::json!({ "name": name })),
+        )
+    }
+    /// Invalid-params error (`-32602`) for a prompt name that is not registered.
+    pub fn prompt_not_found(name: impl Into<String>) -> Self {
+        let name = name.into();
+        Self::invalid_params(
+            format!("prompt not found: {name}"),
+            Some(serde_json::json!({ "name": name })),
+        )
+    }
 }
 
 /// Represents any JSON-RPC message that can be sent or received.
@@ -1290,6 +1340,11 @@ impl ReadResourceResult {
             meta: None,
         }
     }
+
+    /// Create a ReadResourceResult holding a single item.
+    pub fn single(contents: ResourceContents) -> Self {
+        Self::new(vec![contents])
+    }
 }
 
 /// Request to read a specific resource
@@ -1487,7 +1542,8 @@ pub type ToolListChangedNotification = NotificationNoParam<ToolListChangedNotifi
 // =============================================================================
 
 /// Logging levels supported by the MCP protocol
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Copy)]
+// Variants are declared from least to most severe, which the derived `Ord` relies on.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "lowercase")] //match spec
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[expect(clippy::exhaustive_enums, reason = "intentionally exhaustive")]
@@ -2084,7 +2140,11 @@ impl CreateMessageRequestParams {
     /// - ToolResult content is only allowed in user messages
     /// - Messages with tool result content MUST NOT contain other content types
     /// - Every assistant ToolUse must be balanced with a corresponding user ToolResult
+    /// - Model preference priorities are within `0.0..=1.0`
     pub fn validate(&self) -> Result<(), String> {
+        if let Some(model_preferences) = &self.model_preferences {
+            model_preferences.validate()?;
+        }
         for msg in &self.messages {
             for content in msg.content.iter() {
                 // ToolUse only in assistant messages, ToolResult only in user messages
@@ -2155,6 +2215,114 @@ impl CreateMessageRequestParams {
     }
 }
 
+impl CreateMessageRequestParams {
+    /// Create a builder for a sampling request with a conversation history.
+    pub fn builder() -> CreateMessageRequestParamsBuilder {
+        CreateMessageRequestParamsBuilder::default()
+    }
+}
+
+/// Builder for [`CreateMessageRequestParams`] that accumulates the message
+/// history turn by turn.
+///
+/// # Example
+/// ```rust
+/// # #![allow(deprecated)]
+/// use rmcp::model::*;
+///
+/// let params = CreateMessageRequestParams::builder()
+///     .system_prompt("You are a terse assistant.")
+///     .add_user_text("What is the capital of France?")
+///     .add_assistant_text("Paris.")
+///     .add_user_text("And of Italy?")
+///     .max_tokens(64)
+///     .build()
+///     .unwrap();
+/// assert_eq!(params.messages.len(), 3);
+/// ```
+#[derive(Debug, Clone, Default)]
+#[deprecated(
+    since = "2.0.0",
+    note = "Sampling is deprecated by SEP-2577 and will be removed in a future release. See https://github.com/modelcontextprotocol/modelcontextprotocol/pull/2577"
+)]
+pub struct CreateMessageRequestParamsBuilder {
+    messages: Vec<SamplingMessage>,
+    max_tokens: Option<u32>,
+    system_prompt: Option<String>,
+    model_preferences: Option<ModelPreferences>,
+    include_context: Option<ContextInclusion>,
+    temperature: Option<f32>,
+    stop_sequences: Option<Vec<String>>,
+}
+
+impl CreateMessageRequestParamsBuilder {
+    /// Append a message to the history.
+    pub fn add_message(mut self, message: SamplingMessage) -> Self {
+        self.messages.push(message);
+        self
+    }
+
+    /// Append a user text message to the history.
+    pub fn add_user_text(self, text: impl Into<String>) -> Self {
+        self.add_message(SamplingMessage::user_text(text))
+    }
+
+    /// Append an assistant text message to the history.
+    pub fn add_assistant_text(self, text: impl Into<String>) -> Self {
+        self.add_message(SamplingMessage::assistant_text(text))
+    }
+
+    /// Set the maximum number of tokens to generate. Required.
+    pub fn max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    pub fn system_prompt(mut self, system_prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(system_prompt.into());
+        self
+    }
+
+    pub fn model_preferences(mut self, model_preferences: ModelPreferences) -> Self {
+        self.model_preferences = Some(model_preferences);
+        self
+    }
+
+    pub fn include_context(mut self, include_context: ContextInclusion) -> Self {
+        self.include_context = Some(include_context);
+        self
+    }
+
+    pub fn temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    pub fn stop_sequences(mut self, stop_sequences: Vec<String>) -> Self {
+        self.stop_sequences = Some(stop_sequences);
+        self
+    }
+
+    /// Build the request parameters.
+    ///
+    /// Fails if `max_tokens` was not set, if no message was added, or if the
+    /// history does not pass [`CreateMessageRequestParams::validate`].
+    pub fn build(self) -> Result<CreateMessageRequestParams, String> {
+        let max_tokens = self.max_tokens.ok_or("max_tokens is required")?;
+        if self.messages.is_empty() {
+            return Err("at least one message is required".into());
+        }
+        let mut params = CreateMessageRequestParams::new(self.messages, max_tokens);
+        params.system_prompt = self.system_prompt;
+        params.model_preferences = self.model_preferences;
+        params.include_context = self.include_context;
+        params.temperature = self.temperature;
+        params.stop_sequences = self.stop_sequences;
+        params.validate()?;
+        Ok(params)
+    }
+}
+
 /// Deprecated: Use [`CreateMessageRequestParams`] instead (SEP-1319 compliance).
 #[deprecated(since = "0.13.0", note = "Use CreateMessageRequestParams instead")]
 pub type CreateMessageRequestParam = CreateMessageRequestParams;
@@ -2221,6 +2389,32 @@ impl ModelPreferences {
         self.intelligence_priority = Some(intelligence_priority);
         self
     }
+
+    /// Append a hint for a model name or family, keeping earlier hints first.
+    pub fn with_hint(mut self, name: impl Into<String>) -> Self {
+        self.hints
+            .get_or_insert_with(Vec::new)
+            .push(ModelHint::new(name));
+        self
+    }
+
+    /// Check that every priority is within `0.0..=1.0`.
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, priority) in [
+            ("costPriority", self.cost_priority),
+            ("speedPriority", self.speed_priority),
+            ("intelligencePriority", self.intelligence_priority),
+        ] {
+            if let Some(priority) = priority {
+                if !(0.0..=1.0).contains(&priority) {
+                    return Err(format!(
+                        "{name} must be between 0.0 and 1.0, got {priority}"
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Default for ModelPreferences {
@@ -2606,6 +2800,148 @@ impl Root {
         self.meta = Some(meta);
         self
     }
+
+    /// Creates a root for a local directory, building a `file://` URI from
+    /// `path` with any reserved characters percent-encoded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RootError::RelativePath`] if `path` is not absolute, and
+    /// [`RootError::NonUtf8Path`] on platforms where non-UTF-8 paths cannot be
+    /// represented.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #![allow(deprecated)]
+    /// # #[cfg(unix)] {
+    /// use rmcp::model::Root;
+    ///
+    /// let root = Root::from_path("/home/me/my project").unwrap();
+    /// assert_eq!(root.uri, "file:///home/me/my%20project");
+    /// assert_eq!(root.to_path().unwrap(), std::path::Path::new("/home/me/my project"));
+    /// # }
+    /// ```
+    pub fn from_path(path: impl AsRef<std::path::Path>) -> Result<Self, RootError> {
+        let path = path.as_ref();
+        if !path.is_absolute() {
+            return Err(RootError::RelativePath(path.to_path_buf()));
+        }
+        Ok(Self::new(path_to_file_uri(path)?))
+    }
+
+    /// The local path this root refers to, or `None` if the URI is not a
+    /// valid `file://` URI for this machine.
+    pub fn to_path(&self) -> Option<std::path::PathBuf> {
+        file_uri_to_path(&self.uri)
+    }
+}
+
+/// Why a path could not be turned into a [`Root`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum RootError {
+    #[error("root path must be absolute: {}", .0.display())]
+    RelativePath(std::path::PathBuf),
+    #[error("root path is not valid UTF-8: {}", .0.display())]
+    NonUtf8Path(std::path::PathBuf),
+}
+
+/// Percent-encode everything but RFC 3986 `pchar`s and `/`.
+fn percent_encode_path(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len());
+    for &byte in bytes {
+        if byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@/".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+fn percent_decode(input: &str) -> Option<Vec<u8>> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    Some(decoded)
+}
+
+#[cfg(unix)]
+fn path_to_file_uri(path: &std::path::Path) -> Result<String, RootError> {
+    use std::os::unix::ffi::OsStrExt;
+    Ok(format!(
+        "file://{}",
+        percent_encode_path(path.as_os_str().as_bytes())
+    ))
+}
+
+#[cfg(not(unix))]
+fn path_to_file_uri(path: &std::path::Path) -> Result<String, RootError> {
+    let path = path
+        .to_str()
+        .ok_or_else(|| RootError::NonUtf8Path(path.to_path_buf()))?
+        .replace('\\', "/");
+    // Verbatim prefixes (`\\?\C:\`, `\\?\UNC\server\share`) name the same files.
+    let path = match path.strip_prefix("//?/UNC/") {
+        Some(unc) => format!("//{unc}"),
+        None => path
+            .strip_prefix("//?/")
+            .unwrap_or(path.as_str())
+            .to_owned(),
+    };
+    Ok(match path.strip_prefix("//") {
+        // `\\server\share\dir` becomes `file://server/share/dir`
+        Some(_) => format!("file:{}", percent_encode_path(path.as_bytes())),
+        // `C:\dir` becomes `file:///C:/dir`
+        None => format!("file:///{}", percent_encode_path(path.as_bytes())),
+    })
+}
+
+/// Split a `file://` URI into its host and percent-decoded path.
+fn split_file_uri(uri: &str) -> Option<(&str, Vec<u8>)> {
+    let scheme = uri.get(..7)?;
+    if !scheme.eq_ignore_ascii_case("file://") {
+        return None;
+    }
+    let rest = &uri[7..];
+    let rest = rest.split(['?', '#']).next().unwrap_or(rest);
+    let (host, path) = rest.split_at(rest.find('/')?);
+    Some((host, percent_decode(path)?))
+}
+
+#[cfg(unix)]
+fn file_uri_to_path(uri: &str) -> Option<std::path::PathBuf> {
+    use std::os::unix::ffi::OsStringExt;
+    let (host, path) = split_file_uri(uri)?;
+    if !(host.is_empty() || host.eq_ignore_ascii_case("localhost")) {
+        return None;
+    }
+    Some(std::ffi::OsString::from_vec(path).into())
+}
+
+#[cfg(not(unix))]
+fn file_uri_to_path(uri: &str) -> Option<std::path::PathBuf> {
+    let (host, path) = split_file_uri(uri)?;
+    let path = String::from_utf8(path).ok()?;
+    if !(host.is_empty() || host.eq_ignore_ascii_case("localhost")) {
+        return Some(format!("\\\\{host}{}", path.replace('/', "\\")).into());
+    }
+    // `/C:/dir` becomes `C:\dir`
+    let path = match path.as_bytes() {
+        [b'/', drive, b':', ..] if drive.is_ascii_alphabetic() => &path[1..],
+        _ => path.as_str(),
+    };
+    Some(path.replace('/', "\\").into())
 }
 
 const_string!(ListRootsRequestMethod = "roots/list");
@@ -3100,6 +3436,36 @@ impl CallToolResult {
             meta: None,
         }
     }
+    /// Create an error tool result from a Rust error
+    ///
+    /// The error's `Display` becomes the text content, and the structured
+    /// content records the message along with each level of its `source()`
+    /// chain.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use rmcp::model::CallToolResult;
+    ///
+    /// let error = std::fs::read("missing.txt").unwrap_err();
+    /// let result = CallToolResult::from_error(&error);
+    /// // structured_content: {"message": "No such file or directory (os error 2)", "sources": []}
+    /// ```
+    pub fn from_error(error: &(dyn std::error::Error + 'static)) -> Self {
+        let message = error.to_string();
+        let sources: Vec<Value> = std::iter::successors(error.source(), |source| source.source())
+            .map(|source| Value::String(source.to_string()))
+            .collect();
+        CallToolResult {
+            content: vec![ContentBlock::text(message.clone())],
+            structured_content: Some(serde_json::json!({
+                "message": message,
+                "sources": sources,
+            })),
+            is_error: Some(true),
+            meta: None,
+        }
+    }
 
     /// Set the metadata on this result
     pub fn with_meta(mut self, meta: Option<Meta>) -> Self {
@@ -3107,6 +3473,33 @@ impl CallToolResult {
         self
     }
 
+    /// Returns `true` unless the tool reported an error (`is_error: true`).
+    pub fn is_success(&self) -> bool {
+        self.is_error != Some(true)
+    }
+
+    /// The text content blocks joined with newlines, or `None` if there are none.
+    pub fn text(&self) -> Option<String> {
+        let texts: Vec<&str> = self
+            .content
+            .iter()
+            .filter_map(|content| content.as_text())
+            .map(|text| text.text.as_str())
+            .collect();
+        (!texts.is_empty()).then(|| texts.join("\n"))
+    }
+
+    /// Deserialize `structured_content` into `T`, or `None` if there is none.
+    ///
+    /// Unlike [`into_typed`](Self::into_typed), this borrows the result and does
+    /// not fall back to parsing the text content.
+    pub fn structured_content_as<T>(&self) -> Option<Result<T, serde_json::Error>>
+    where
+        T: DeserializeOwned,
+    {
+        self.structured_content.as_ref().map(T::deserialize)
+    }
+
     /// Convert the `structured_content` part of response into a certain type.
     ///
     /// # About json schema validation
@@ -3753,6 +4146,48 @@ mod tests {
         assert_eq!(back.request_id, Some(RequestId::Number(1)));
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn root_file_uri_round_trip() {
+        let path = std::path::Path::new("/tmp/my dir/ä#1%");
+        let root = Root::from_path(path).unwrap();
+        assert_eq!(root.uri, "file:///tmp/my%20dir/%C3%A4%231%25");
+        assert_eq!(root.to_path().as_deref(), Some(path));
+
+        assert_eq!(
+            Root::new("file://localhost/etc").to_path().as_deref(),
+            Some(std::path::Path::new("/etc"))
+        );
+        assert_eq!(
+            Root::from_path("relative/dir"),
+            Err(RootError::RelativePath("relative/dir".into()))
+        );
+        // Only local `file://` URIs convert back to paths.
+        assert_eq!(Root::new("/etc").to_path(), None);
+        assert_eq!(Root::new("https://example.com/etc").to_path(), None);
+        assert_eq!(Root::new("file://server/share").to_path(), None);
+        assert_eq!(Root::new("file:///bad%zzescape").to_path(), None);
+    }
+
+    #[test]
+    fn named_error_constructors_set_codes() {
+        assert_eq!(ErrorData::internal("boom").code, ErrorCode::INTERNAL_ERROR);
+        let err = ErrorData::method_not_found_for("tools/frobnicate");
+        assert_eq!(err.code, ErrorCode::METHOD_NOT_FOUND);
+        assert_eq!(err.message, "method not found: tools/frobnicate");
+        let err = ErrorData::resource_not_found_for("file:///missing");
+        assert_eq!(err.code, ErrorCode::RESOURCE_NOT_FOUND);
+        assert_eq!(err.data, Some(json!({ "uri": "file:///missing" })));
+        assert_eq!(
+            ErrorData::tool_not_found("echo").code,
+            ErrorCode::INVALID_PARAMS
+        );
+        assert_eq!(
+            ErrorData::prompt_not_found("greet").message,
+            "prompt not found: greet"
+        );
+    }
+
     #[test]
     fn test_notification_serde() {
         let raw = json!( {
@@ -4182,6 +4617,30 @@ mod tests {
         assert_eq!(implementation.website_url, None);
     }
 
+    #[test]
+    fn test_implementation_title_round_trip() {
+        let implementation: Implementation = serde_json::from_value(json!({
+            "name": "test-server",
+            "title": "Test Server",
+            "version": "1.0.0"
+        }))
+        .unwrap();
+        assert_eq!(implementation.title.as_deref(), Some("Test Server"));
+        assert_eq!(
+            serde_json::to_value(&implementation).unwrap()["title"],
+            "Test Server"
+        );
+
+        // Legacy JSON has no title, and none is written back
+        let old_json = json!({
+            "name": "legacy-server",
+            "version": "0.9.0"
+        });
+        let implementation: Implementation = serde_json::from_value(old_json.clone()).unwrap();
+        assert_eq!(implementation.title, None);
+        assert_eq!(serde_json::to_value(&implementation).unwrap(), old_json);
+    }
+
     #[test]
     fn test_initialize_with_icons() {
         let init_result = InitializeResult {
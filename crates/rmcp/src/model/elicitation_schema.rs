@@ -66,6 +66,75 @@ pub enum PrimitiveSchemaDefinition {
 #[deprecated(since = "2.0.0", note = "Renamed to PrimitiveSchemaDefinition")]
 pub type PrimitiveSchema = PrimitiveSchemaDefinition;
 
+impl PrimitiveSchemaDefinition {
+    /// The JSON Schema `type` of this property: `"string"`, `"number"`,
+    /// `"integer"`, `"boolean"`, or `"array"` for multi-select enums.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            PrimitiveSchemaDefinition::Enum(EnumSchema::Multi(_)) => "array",
+            PrimitiveSchemaDefinition::Enum(_) | PrimitiveSchemaDefinition::String(_) => "string",
+            PrimitiveSchemaDefinition::Number(_) => "number",
+            PrimitiveSchemaDefinition::Integer(_) => "integer",
+            PrimitiveSchemaDefinition::Boolean(_) => "boolean",
+        }
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        match self {
+            PrimitiveSchemaDefinition::Enum(schema) => schema.title(),
+            PrimitiveSchemaDefinition::String(schema) => schema.title.as_deref(),
+            PrimitiveSchemaDefinition::Number(schema) => schema.title.as_deref(),
+            PrimitiveSchemaDefinition::Integer(schema) => schema.title.as_deref(),
+            PrimitiveSchemaDefinition::Boolean(schema) => schema.title.as_deref(),
+        }
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            PrimitiveSchemaDefinition::Enum(schema) => schema.description(),
+            PrimitiveSchemaDefinition::String(schema) => schema.description.as_deref(),
+            PrimitiveSchemaDefinition::Number(schema) => schema.description.as_deref(),
+            PrimitiveSchemaDefinition::Integer(schema) => schema.description.as_deref(),
+            PrimitiveSchemaDefinition::Boolean(schema) => schema.description.as_deref(),
+        }
+    }
+
+    pub fn as_string(&self) -> Option<&StringSchema> {
+        match self {
+            PrimitiveSchemaDefinition::String(schema) => Some(schema),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<&NumberSchema> {
+        match self {
+            PrimitiveSchemaDefinition::Number(schema) => Some(schema),
+            _ => None,
+        }
+    }
+
+    pub fn as_integer(&self) -> Option<&IntegerSchema> {
+        match self {
+            PrimitiveSchemaDefinition::Integer(schema) => Some(schema),
+            _ => None,
+        }
+    }
+
+    pub fn as_boolean(&self) -> Option<&BooleanSchema> {
+        match self {
+            PrimitiveSchemaDefinition::Boolean(schema) => Some(schema),
+            _ => None,
+        }
+    }
+
+    pub fn as_enum(&self) -> Option<&EnumSchema> {
+        match self {
+            PrimitiveSchemaDefinition::Enum(schema) => Some(schema),
+            _ => None,
+        }
+    }
+}
+
 // =============================================================================
 // STRING SCHEMA
 // =============================================================================
@@ -785,6 +854,36 @@ pub enum EnumSchema {
     Legacy(LegacyEnumSchema),
 }
 
+impl EnumSchema {
+    pub fn title(&self) -> Option<&str> {
+        match self {
+            EnumSchema::Single(SingleSelectEnumSchema::Untitled(schema)) => schema.title.as_deref(),
+            EnumSchema::Single(SingleSelectEnumSchema::Titled(schema)) => schema.title.as_deref(),
+            EnumSchema::Multi(MultiSelectEnumSchema::Untitled(schema)) => schema.title.as_deref(),
+            EnumSchema::Multi(MultiSelectEnumSchema::Titled(schema)) => schema.title.as_deref(),
+            EnumSchema::Legacy(schema) => schema.title.as_deref(),
+        }
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            EnumSchema::Single(SingleSelectEnumSchema::Untitled(schema)) => {
+                schema.description.as_deref()
+            }
+            EnumSchema::Single(SingleSelectEnumSchema::Titled(schema)) => {
+                schema.description.as_deref()
+            }
+            EnumSchema::Multi(MultiSelectEnumSchema::Untitled(schema)) => {
+                schema.description.as_deref()
+            }
+            EnumSchema::Multi(MultiSelectEnumSchema::Titled(schema)) => {
+                schema.description.as_deref()
+            }
+            EnumSchema::Legacy(schema) => schema.description.as_deref(),
+        }
+    }
+}
+
 /// Marker type for single-select enum builder
 #[derive(Debug)]
 #[expect(clippy::exhaustive_structs, reason = "intentionally exhaustive")]
@@ -1137,6 +1236,24 @@ pub struct ElicitationSchema {
     /// Optional description of what this schema represents
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<Cow<'static, str>>,
+
+    /// Properties that become required when a trigger property is present
+    /// (JSON Schema `dependentRequired`). Clients that do not validate
+    /// responses against the schema ignore this keyword.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependent_required: Option<BTreeMap<String, Vec<String>>>,
+}
+
+fn json_value_kind(value: &serde_json::Value) -> serde::de::Unexpected<'_> {
+    use serde::de::Unexpected;
+    match value {
+        serde_json::Value::Null => Unexpected::Unit,
+        serde_json::Value::Bool(b) => Unexpected::Bool(*b),
+        serde_json::Value::Number(_) => Unexpected::Other("number"),
+        serde_json::Value::String(s) => Unexpected::Str(s),
+        serde_json::Value::Array(_) => Unexpected::Seq,
+        serde_json::Value::Object(_) => Unexpected::Map,
+    }
 }
 
 impl ElicitationSchema {
@@ -1148,6 +1265,7 @@ impl ElicitationSchema {
             properties,
             required: None,
             description: None,
+            dependent_required: None,
         }
     }
 
@@ -1173,6 +1291,58 @@ impl ElicitationSchema {
         serde_json::from_value(serde_json::Value::Object(schema))
     }
 
+    /// Create an ElicitationSchema from an arbitrary JSON value
+    ///
+    /// Same as [`from_json_schema`](Self::from_json_schema), for schemas that arrive
+    /// as a generic [`serde_json::Value`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`serde_json::Error`] if the value is not a JSON object or cannot
+    /// be deserialized into a valid ElicitationSchema.
+    pub fn from_value(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        match value {
+            serde_json::Value::Object(object) => Self::from_json_schema(object),
+            other => Err(serde::de::Error::invalid_type(
+                json_value_kind(&other),
+                &"a JSON Schema object",
+            )),
+        }
+    }
+
+    /// Convert this schema into a plain JSON Schema object.
+    ///
+    /// The inverse of [`from_json_schema`](Self::from_json_schema). The result always
+    /// contains `type: "object"`, `properties` and `required` (empty if no field is
+    /// required), so it can be fed directly to external JSON Schema validators.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`serde_json::Error`] if the schema does not serialize into a
+    /// JSON object.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rmcp::model::*;
+    ///
+    /// let schema = ElicitationSchema::builder()
+    ///     .required_email("email")
+    ///     .build()
+    ///     .unwrap();
+    /// let json_schema = schema.to_json_schema().unwrap();
+    /// assert_eq!(json_schema["type"], "object");
+    /// assert_eq!(ElicitationSchema::from_json_schema(json_schema).unwrap(), schema);
+    /// ```
+    pub fn to_json_schema(&self) -> Result<crate::model::JsonObject, serde_json::Error> {
+        let mut object: crate::model::JsonObject =
+            serde_json::from_value(serde_json::to_value(self)?)?;
+        object
+            .entry("required")
+            .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+        Ok(object)
+    }
+
     /// Generate an ElicitationSchema from a Rust type that implements JsonSchema
     ///
     /// This is a convenience method that combines schema generation and conversion.
@@ -1225,6 +1395,18 @@ impl ElicitationSchema {
         self
     }
 
+    /// Require `required_fields` whenever `trigger_field` is present
+    pub fn with_dependent_required(
+        mut self,
+        trigger_field: impl Into<String>,
+        required_fields: Vec<String>,
+    ) -> Self {
+        self.dependent_required
+            .get_or_insert_with(BTreeMap::new)
+            .insert(trigger_field.into(), required_fields);
+        self
+    }
+
     /// Set the title
     pub fn with_title(mut self, title: impl Into<Cow<'static, str>>) -> Self {
         self.title = Some(title.into());
@@ -1262,12 +1444,12 @@ impl ElicitationSchema {
 ///     .build();
 /// ```
 #[derive(Debug, Default)]
-#[expect(clippy::exhaustive_structs, reason = "intentionally exhaustive")]
 pub struct ElicitationSchemaBuilder {
     pub properties: BTreeMap<String, PrimitiveSchemaDefinition>,
     pub required: Vec<String>,
     pub title: Option<Cow<'static, str>>,
     pub description: Option<Cow<'static, str>>,
+    dependent_required: BTreeMap<String, Vec<String>>,
 }
 
 impl ElicitationSchemaBuilder {
@@ -1646,6 +1828,24 @@ impl ElicitationSchemaBuilder {
         self
     }
 
+    /// Require `required_fields` whenever `trigger_field` is present
+    /// (JSON Schema `dependentRequired`)
+    pub fn dependent_required<I, S>(
+        mut self,
+        trigger_field: impl Into<String>,
+        required_fields: I,
+    ) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.dependent_required.insert(
+            trigger_field.into(),
+            required_fields.into_iter().map(Into::into).collect(),
+        );
+        self
+    }
+
     /// Set the schema title
     pub fn title(mut self, title: impl Into<Cow<'static, str>>) -> Self {
         self.title = Some(title.into());
@@ -1664,6 +1864,7 @@ impl ElicitationSchemaBuilder {
     ///
     /// Returns an error if:
     /// - Required fields reference non-existent properties
+    /// - Dependent required fields or their triggers reference non-existent properties
     /// - No properties are defined (empty schema)
     pub fn build(self) -> Result<ElicitationSchema, &'static str> {
         // Validate that all required fields exist in properties
@@ -1674,6 +1875,15 @@ impl ElicitationSchemaBuilder {
                 }
             }
         }
+        for (trigger, fields) in &self.dependent_required {
+            if !self.properties.contains_key(trigger)
+                || fields
+                    .iter()
+                    .any(|field| !self.properties.contains_key(field))
+            {
+                return Err("Dependent required field does not exist in properties");
+            }
+        }
 
         Ok(ElicitationSchema {
             type_: ObjectTypeConst,
@@ -1685,6 +1895,11 @@ impl ElicitationSchemaBuilder {
                 Some(self.required)
             },
             description: self.description,
+            dependent_required: if self.dependent_required.is_empty() {
+                None
+            } else {
+                Some(self.dependent_required)
+            },
         })
     }
 
@@ -2153,6 +2368,100 @@ mod tests {
         assert_eq!(result.unwrap_err(), "minimum must be <= maximum");
     }
 
+    #[test]
+    fn test_primitive_schema_accessors() {
+        let schema = ElicitationSchema::builder()
+            .required_email("email")
+            .optional_integer("age", 0, 150)
+            .optional_enum_schema(
+                "colors",
+                EnumSchema::builder(vec!["red".into(), "blue".into()])
+                    .multiselect()
+                    .description("Favourite colors")
+                    .build(),
+            )
+            .build()
+            .unwrap();
+
+        let email = &schema.properties["email"];
+        assert_eq!(email.type_name(), "string");
+        assert!(email.as_string().is_some());
+        assert!(email.as_integer().is_none());
+
+        let age = &schema.properties["age"];
+        assert_eq!(age.type_name(), "integer");
+        assert_eq!(age.as_integer().and_then(|s| s.maximum), Some(150));
+
+        let colors = &schema.properties["colors"];
+        assert_eq!(colors.type_name(), "array");
+        assert!(colors.as_enum().is_some());
+        assert_eq!(colors.description(), Some("Favourite colors"));
+        assert_eq!(colors.title(), None);
+    }
+
+    #[test]
+    fn test_from_value() {
+        let schema = ElicitationSchema::from_value(json!({
+            "type": "object",
+            "properties": { "name": { "type": "string" } },
+            "required": ["name"]
+        }))
+        .unwrap();
+        assert_eq!(schema.required, Some(vec!["name".to_string()]));
+
+        let error = ElicitationSchema::from_value(json!(["not", "an", "object"])).unwrap_err();
+        assert!(error.to_string().contains("expected a JSON Schema object"));
+    }
+
+    #[test]
+    fn test_dependent_required() {
+        let schema = ElicitationSchema::builder()
+            .optional_bool("needs_shipping", false)
+            .optional_string("shipping_address")
+            .dependent_required("needs_shipping", ["shipping_address"])
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&schema).unwrap();
+        assert_eq!(
+            json["dependentRequired"],
+            json!({ "needs_shipping": ["shipping_address"] })
+        );
+        let parsed: ElicitationSchema = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, schema);
+
+        let result = ElicitationSchema::builder()
+            .optional_bool("needs_shipping", false)
+            .dependent_required("needs_shipping", ["shipping_address"])
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_to_json_schema_round_trip() {
+        let schema = ElicitationSchema::builder()
+            .title("Signup")
+            .required_email("email")
+            .optional_integer("age", 0, 150)
+            .build()
+            .unwrap();
+        let json_schema = schema.to_json_schema().unwrap();
+        assert_eq!(json_schema["type"], "object");
+        assert_eq!(json_schema["required"], json!(["email"]));
+        assert_eq!(json_schema["properties"]["age"]["maximum"], 150);
+        assert_eq!(
+            ElicitationSchema::from_json_schema(json_schema).unwrap(),
+            schema
+        );
+
+        let json_schema = ElicitationSchema::builder()
+            .optional_bool("newsletter", false)
+            .build()
+            .unwrap()
+            .to_json_schema()
+            .unwrap();
+        assert_eq!(json_schema["required"], json!([]));
+    }
+
     #[cfg(feature = "schemars")]
     mod schemars_tests {
         use anyhow::Result;
@@ -2276,5 +2585,38 @@ mod tests {
             );
             Ok(())
         }
+
+        fn default_nickname() -> String {
+            "anon".to_string()
+        }
+
+        fn default_retries() -> i64 {
+            3
+        }
+
+        fn default_ratio() -> f64 {
+            0.5
+        }
+
+        #[derive(Debug, Serialize, Deserialize, JsonSchema)]
+        struct Preferences {
+            #[serde(default = "default_nickname")]
+            pub nickname: String,
+            #[serde(default = "default_retries")]
+            pub retries: i64,
+            #[serde(default = "default_ratio")]
+            pub ratio: f64,
+        }
+
+        #[test]
+        fn test_schema_inference_for_primitive_defaults() -> Result<()> {
+            let schema = ElicitationSchema::from_type::<Preferences>()?;
+
+            let json = serde_json::to_value(&schema)?;
+            assert_eq!(json["properties"]["nickname"]["default"], "anon");
+            assert_eq!(json["properties"]["retries"]["default"], 3);
+            assert_eq!(json["properties"]["ratio"]["default"], 0.5);
+            Ok(())
+        }
     }
 }
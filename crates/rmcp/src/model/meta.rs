@@ -4,9 +4,10 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use super::{
-    ClientCapabilities, ClientNotification, ClientRequest, CustomNotification, CustomRequest,
-    Extensions, Implementation, JsonObject, JsonRpcMessage, LoggingLevel, NumberOrString,
-    ProgressToken, ProtocolVersion, ServerNotification, ServerRequest, TaskMetadata,
+    ClientCapabilities, ClientNotification, ClientRequest, ConstString, CustomNotification,
+    CustomRequest, Extensions, Implementation, JsonObject, JsonRpcMessage, LoggingLevel,
+    NumberOrString, ProgressToken, ProtocolVersion, ServerNotification, ServerRequest,
+    TaskMetadata,
 };
 
 pub trait GetMeta {
@@ -19,6 +20,11 @@ pub trait GetExtensions {
     fn extensions_mut(&mut self) -> &mut Extensions;
 }
 
+/// Access the JSON-RPC method name of a request or notification.
+pub trait GetMethod {
+    fn method(&self) -> &str;
+}
+
 /// Trait for request params that contain the `_meta` field.
 ///
 /// Per the MCP 2025-11-25 spec, all request params should have an optional `_meta`
@@ -162,6 +168,15 @@ macro_rules! variant_extension {
                 self.extensions().get::<Meta>().unwrap_or(Meta::static_empty())
             }
         }
+        impl GetMethod for $Enum {
+            fn method(&self) -> &str {
+                match self {
+                    $(
+                        $Enum::$variant(v) => v.method.as_str(),
+                    )*
+                }
+            }
+        }
     };
 }
 
@@ -241,6 +256,8 @@ impl Meta {
     const TRACESTATE_FIELD: &str = "tracestate";
     /// Reserved `_meta` key for the W3C Baggage value (SEP-414).
     const BAGGAGE_FIELD: &str = "baggage";
+    /// `_meta` key identifying retries of the same side-effecting request.
+    const IDEMPOTENCY_KEY_FIELD: &str = "idempotencyKey";
 
     pub fn new() -> Self {
         Self(JsonObject::new())
@@ -390,6 +407,20 @@ impl Meta {
         self.set_str(Self::BAGGAGE_FIELD, value);
     }
 
+    /// Get the idempotency key, if present.
+    pub fn get_idempotency_key(&self) -> Option<&str> {
+        self.get_str(Self::IDEMPOTENCY_KEY_FIELD)
+    }
+
+    /// Set the idempotency key.
+    ///
+    /// Send the same key when retrying a `tools/call` so that a server which
+    /// enabled `ToolRouter::with_idempotency` for the tool replays the first
+    /// result instead of running it again.
+    pub fn set_idempotency_key(&mut self, value: impl Into<String>) {
+        self.set_str(Self::IDEMPOTENCY_KEY_FIELD, value);
+    }
+
     pub fn extend(&mut self, other: Meta) {
         for (k, v) in other.0.into_iter() {
             self.0.insert(k, v);
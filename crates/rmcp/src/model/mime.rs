@@ -0,0 +1,88 @@
+//! Guessing MIME types for file-based resources.
+//!
+//! Enabled by the `mime-guess` feature, which pulls in [`mime_guess`] for
+//! file extensions and [`infer`] for file contents. Unknown formats yield
+//! `None`, so callers can fall back to leaving `mimeType` unset.
+
+use std::path::Path;
+
+use super::Resource;
+
+/// Guess the MIME type of a file from its extension.
+///
+/// Matching is case-insensitive. Returns `None` for paths without an
+/// extension or with one [`mime_guess`] does not know.
+pub fn guess_mime_type(path: impl AsRef<Path>) -> Option<String> {
+    mime_guess::from_path(path).first_raw().map(str::to_owned)
+}
+
+/// Guess the MIME type of file contents from their leading bytes.
+///
+/// Recognises binary formats by their signature through [`infer`], and
+/// reports `text/plain` for valid UTF-8 without control characters other
+/// than whitespace. Returns `None` otherwise, including for empty input.
+pub fn sniff_mime_type(bytes: &[u8]) -> Option<String> {
+    if bytes.is_empty() {
+        return None;
+    }
+    if let Some(kind) = infer::get(bytes) {
+        return Some(kind.mime_type().to_owned());
+    }
+    let text = std::str::from_utf8(bytes).ok()?;
+    text.chars()
+        .all(|c| !c.is_control() || c.is_whitespace())
+        .then(|| "text/plain".into())
+}
+
+impl Resource {
+    /// Fill in `mime_type` from the extension of the resource URI, unless it
+    /// is already set.
+    pub fn with_guessed_mime_type(mut self) -> Self {
+        if self.mime_type.is_none() {
+            let path = self.uri.split(['?', '#']).next().unwrap_or_default();
+            self.mime_type = guess_mime_type(path);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guess_mime_type() {
+        assert_eq!(
+            guess_mime_type("notes/README.MD").as_deref(),
+            Some("text/markdown")
+        );
+        assert_eq!(
+            guess_mime_type("/tmp/photo.jpeg").as_deref(),
+            Some("image/jpeg")
+        );
+        assert_eq!(guess_mime_type("Makefile"), None);
+        assert_eq!(guess_mime_type("archive.unknown"), None);
+
+        assert_eq!(
+            sniff_mime_type(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").as_deref(),
+            Some("image/png")
+        );
+        assert_eq!(
+            sniff_mime_type(b"RIFF\0\0\0\0WEBPVP8 ").as_deref(),
+            Some("image/webp")
+        );
+        assert_eq!(
+            sniff_mime_type(b"hello\nworld").as_deref(),
+            Some("text/plain")
+        );
+        assert_eq!(sniff_mime_type(&[0, 159, 146, 150]), None);
+        assert_eq!(sniff_mime_type(b""), None);
+
+        let resource = Resource::new("file:///src/main.rs?rev=1", "main").with_guessed_mime_type();
+        assert_eq!(resource.mime_type.as_deref(), Some("text/x-rust"));
+        let resource = Resource::new("file:///data.bin", "data")
+            .with_mime_type("application/x-custom")
+            .with_guessed_mime_type();
+        assert_eq!(resource.mime_type.as_deref(), Some("application/x-custom"));
+    }
+}
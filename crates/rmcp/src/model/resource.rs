@@ -208,6 +208,40 @@ impl ResourceContents {
         }
     }
 
+    /// Text contents for `uri` with an explicit MIME type.
+    pub fn text_with_mime_type(
+        uri: impl Into<String>,
+        mime_type: impl Into<String>,
+        text: impl Into<String>,
+    ) -> Self {
+        Self::TextResourceContents {
+            uri: uri.into(),
+            mime_type: Some(mime_type.into()),
+            text: text.into(),
+            meta: None,
+        }
+    }
+
+    /// Blob contents for `uri`, base64-encoding `bytes`.
+    ///
+    /// Unlike [`blob`](Self::blob), which expects an already encoded string,
+    /// this takes the raw bytes.
+    #[cfg(feature = "base64")]
+    pub fn blob_from_bytes(
+        uri: impl Into<String>,
+        mime_type: impl Into<String>,
+        bytes: impl AsRef<[u8]>,
+    ) -> Self {
+        use base64::{Engine, prelude::BASE64_STANDARD};
+
+        Self::BlobResourceContents {
+            uri: uri.into(),
+            mime_type: Some(mime_type.into()),
+            blob: BASE64_STANDARD.encode(bytes),
+            meta: None,
+        }
+    }
+
     pub fn with_mime_type(mut self, mime_type: impl Into<String>) -> Self {
         match &mut self {
             Self::TextResourceContents { mime_type: mt, .. } => *mt = Some(mime_type.into()),
@@ -223,6 +257,30 @@ impl ResourceContents {
         }
         self
     }
+
+    /// Extension identifier (SEP-1724) a client declares in its capabilities
+    /// to accept blob contents delivered as a separate HTTP download instead
+    /// of inline base64.
+    pub const BINARY_DOWNLOAD_EXTENSION: &str = "rmcp/binary-download";
+
+    /// `_meta` key describing where to download blob contents delivered out
+    /// of band: `{"path": "/downloads/<token>", "size": <bytes>}`.
+    pub const DOWNLOAD_META_KEY: &str = "rmcp/download";
+
+    /// The download path of blob contents delivered out of band, if any.
+    ///
+    /// The path is relative to the MCP endpoint: append it to the endpoint
+    /// URL and fetch it with a plain `GET` to obtain the raw bytes. The `blob`
+    /// field of such contents is empty.
+    pub fn download_path(&self) -> Option<&str> {
+        let meta = match self {
+            Self::BlobResourceContents {
+                meta: Some(meta), ..
+            } => meta,
+            _ => return None,
+        };
+        meta.get(Self::DOWNLOAD_META_KEY)?.get("path")?.as_str()
+    }
 }
 
 #[cfg(test)]
@@ -307,4 +365,23 @@ mod tests {
         let json = serde_json::to_value(&resource_template).unwrap();
         assert!(json.get("_meta").is_some());
     }
+
+    #[test]
+    fn test_resource_contents_helpers() {
+        let text = ResourceContents::text_with_mime_type("file:///a.md", "text/markdown", "# Hi");
+        let json = serde_json::to_value(&text).unwrap();
+        assert_eq!(json["mimeType"], "text/markdown");
+        assert_eq!(json["text"], "# Hi");
+
+        #[cfg(feature = "base64")]
+        {
+            let blob = ResourceContents::blob_from_bytes("file:///a.bin", "image/png", [0u8, 1, 2]);
+            let json = serde_json::to_value(&blob).unwrap();
+            assert_eq!(json["mimeType"], "image/png");
+            assert_eq!(json["blob"], "AAEC");
+        }
+
+        let result = crate::model::ReadResourceResult::single(text.clone());
+        assert_eq!(result.contents, vec![text]);
+    }
 }
@@ -0,0 +1,555 @@
+//! Lightweight JSON Schema validation for tool arguments and similar payloads.
+//!
+//! This covers the subset of JSON Schema that MCP tool schemas use in practice:
+//! `type`, `enum`, `const`, `properties`, `required`, `additionalProperties`,
+//! `items`, `minItems`/`maxItems`, `minLength`/`maxLength`,
+//! `minimum`/`maximum` (and their exclusive forms), `allOf`/`anyOf`/`oneOf`,
+//! and local `$ref`s into `$defs`/`definitions`. Other keywords are ignored.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::JsonObject;
+
+/// A single way in which a value fails to match a schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ValidationError {
+    /// JSON Pointer to the offending value; empty for the root.
+    pub path: String,
+    /// What is wrong with the value.
+    pub message: String,
+}
+
+impl ValidationError {
+    pub fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Validate `value` against `schema`, collecting every violation found.
+pub fn validate_against_schema(
+    schema: &JsonObject,
+    value: &Value,
+) -> Result<(), Vec<ValidationError>> {
+    let mut errors = Vec::new();
+    Validator { root: schema }.validate(schema, value, &mut String::new(), &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// The schemas entered through `$ref` at the current instance location.
+///
+/// Following a reference back into one of them validates the same value
+/// against the same schema again, so it can only loop. References followed
+/// after descending into a property or item start afresh, since the value
+/// they check is strictly smaller.
+type ActiveRefs<'a> = Vec<&'a JsonObject>;
+
+struct Validator<'a> {
+    root: &'a JsonObject,
+}
+
+impl<'a> Validator<'a> {
+    fn validate(
+        &self,
+        schema: &'a JsonObject,
+        value: &Value,
+        path: &mut String,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        self.validate_with_refs(schema, value, path, errors, &mut ActiveRefs::new())
+    }
+
+    fn validate_with_refs(
+        &self,
+        schema: &'a JsonObject,
+        value: &Value,
+        path: &mut String,
+        errors: &mut Vec<ValidationError>,
+        refs: &mut ActiveRefs<'a>,
+    ) {
+        if let Some(Value::String(reference)) = schema.get("$ref") {
+            match self.resolve(reference) {
+                Some(target) if refs.iter().any(|active| std::ptr::eq(*active, target)) => {
+                    errors.push(ValidationError::new(
+                        path.clone(),
+                        format!("schema reference `{reference}` is cyclic"),
+                    ));
+                }
+                Some(target) => {
+                    refs.push(target);
+                    self.validate_with_refs(target, value, path, errors, refs);
+                    refs.pop();
+                }
+                None => errors.push(ValidationError::new(
+                    path.clone(),
+                    format!("unresolvable schema reference `{reference}`"),
+                )),
+            }
+        }
+
+        if let Some(expected) = schema.get("type") {
+            if !type_matches(expected, value) {
+                errors.push(ValidationError::new(
+                    path.clone(),
+                    format!(
+                        "expected {}, got {}",
+                        describe_type(expected),
+                        type_of(value)
+                    ),
+                ));
+                // Keyword checks below assume the right type.
+                return;
+            }
+        }
+        if let Some(Value::Array(options)) = schema.get("enum") {
+            if !options.contains(value) {
+                errors.push(ValidationError::new(
+                    path.clone(),
+                    format!("{value} is not one of {}", Value::Array(options.clone())),
+                ));
+            }
+        }
+        if let Some(expected) = schema.get("const") {
+            if expected != value {
+                errors.push(ValidationError::new(
+                    path.clone(),
+                    format!("expected {expected}, got {value}"),
+                ));
+            }
+        }
+
+        match value {
+            Value::Object(object) => self.validate_object(schema, object, path, errors),
+            Value::Array(items) => self.validate_array(schema, items, path, errors),
+            Value::String(string) => validate_string(schema, string, path, errors),
+            Value::Number(number) => validate_number(schema, number, path, errors),
+            Value::Bool(_) | Value::Null => {}
+        }
+
+        if let Some(Value::Array(all_of)) = schema.get("allOf") {
+            for sub_schema in all_of.iter().filter_map(Value::as_object) {
+                self.validate_with_refs(sub_schema, value, path, errors, refs);
+            }
+        }
+        if let Some(Value::Array(any_of)) = schema.get("anyOf") {
+            if self.count_matches(any_of, value, refs) == 0 {
+                errors.push(ValidationError::new(
+                    path.clone(),
+                    "does not match any of the allowed schemas",
+                ));
+            }
+        }
+        if let Some(Value::Array(one_of)) = schema.get("oneOf") {
+            let matches = self.count_matches(one_of, value, refs);
+            if matches != 1 {
+                errors.push(ValidationError::new(
+                    path.clone(),
+                    format!("must match exactly one of the allowed schemas, matched {matches}"),
+                ));
+            }
+        }
+    }
+
+    fn validate_object(
+        &self,
+        schema: &'a JsonObject,
+        object: &JsonObject,
+        path: &mut String,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(name) {
+                    errors.push(ValidationError::new(
+                        path.clone(),
+                        format!("missing required property `{name}`"),
+                    ));
+                }
+            }
+        }
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for (name, property_value) in object {
+            let len = path.len();
+            path.push('/');
+            path.push_str(&name.replace('~', "~0").replace('/', "~1"));
+            match properties.and_then(|properties| properties.get(name)) {
+                Some(Value::Object(property_schema)) => {
+                    self.validate(property_schema, property_value, path, errors)
+                }
+                Some(_) => {}
+                None => match schema.get("additionalProperties") {
+                    Some(Value::Bool(false)) => {
+                        errors.push(ValidationError::new(path.clone(), "unexpected property"))
+                    }
+                    Some(Value::Object(additional)) => {
+                        self.validate(additional, property_value, path, errors)
+                    }
+                    _ => {}
+                },
+            }
+            path.truncate(len);
+        }
+    }
+
+    fn validate_array(
+        &self,
+        schema: &'a JsonObject,
+        items: &[Value],
+        path: &mut String,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+            if (items.len() as u64) < min {
+                errors.push(ValidationError::new(
+                    path.clone(),
+                    format!("expected at least {min} items, got {}", items.len()),
+                ));
+            }
+        }
+        if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+            if items.len() as u64 > max {
+                errors.push(ValidationError::new(
+                    path.clone(),
+                    format!("expected at most {max} items, got {}", items.len()),
+                ));
+            }
+        }
+        if let Some(Value::Object(item_schema)) = schema.get("items") {
+            for (index, item) in items.iter().enumerate() {
+                let len = path.len();
+                path.push('/');
+                path.push_str(&index.to_string());
+                self.validate(item_schema, item, path, errors);
+                path.truncate(len);
+            }
+        }
+    }
+
+    fn count_matches(
+        &self,
+        schemas: &'a [Value],
+        value: &Value,
+        refs: &mut ActiveRefs<'a>,
+    ) -> usize {
+        schemas
+            .iter()
+            .filter_map(Value::as_object)
+            .filter(|schema| {
+                let mut errors = Vec::new();
+                self.validate_with_refs(schema, value, &mut String::new(), &mut errors, refs);
+                errors.is_empty()
+            })
+            .count()
+    }
+
+    /// Resolve a local reference such as `#/$defs/Name`.
+    fn resolve(&self, reference: &str) -> Option<&'a JsonObject> {
+        let pointer = reference.strip_prefix('#')?;
+        if pointer.is_empty() {
+            return Some(self.root);
+        }
+        let mut current = self.root;
+        for token in pointer.strip_prefix('/')?.split('/') {
+            let token = token.replace("~1", "/").replace("~0", "~");
+            current = current.get(&token)?.as_object()?;
+        }
+        Some(current)
+    }
+}
+
+fn validate_string(
+    schema: &JsonObject,
+    string: &str,
+    path: &str,
+    errors: &mut Vec<ValidationError>,
+) {
+    let length = string.chars().count() as u64;
+    if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+        if length < min {
+            errors.push(ValidationError::new(
+                path,
+                format!("expected at least {min} characters, got {length}"),
+            ));
+        }
+    }
+    if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+        if length > max {
+            errors.push(ValidationError::new(
+                path,
+                format!("expected at most {max} characters, got {length}"),
+            ));
+        }
+    }
+}
+
+fn validate_number(
+    schema: &JsonObject,
+    number: &serde_json::Number,
+    path: &str,
+    errors: &mut Vec<ValidationError>,
+) {
+    let Some(number) = number.as_f64() else {
+        return;
+    };
+    let bound = |keyword: &str| schema.get(keyword).and_then(Value::as_f64);
+    if let Some(minimum) = bound("minimum").filter(|minimum| number < *minimum) {
+        errors.push(ValidationError::new(
+            path,
+            format!("{number} is less than the minimum of {minimum}"),
+        ));
+    }
+    if let Some(maximum) = bound("maximum").filter(|maximum| number > *maximum) {
+        errors.push(ValidationError::new(
+            path,
+            format!("{number} is greater than the maximum of {maximum}"),
+        ));
+    }
+    if let Some(minimum) = bound("exclusiveMinimum").filter(|minimum| number <= *minimum) {
+        errors.push(ValidationError::new(
+            path,
+            format!("{number} must be greater than {minimum}"),
+        ));
+    }
+    if let Some(maximum) = bound("exclusiveMaximum").filter(|maximum| number >= *maximum) {
+        errors.push(ValidationError::new(
+            path,
+            format!("{number} must be less than {maximum}"),
+        ));
+    }
+}
+
+fn type_matches(expected: &Value, value: &Value) -> bool {
+    match expected {
+        Value::String(name) => is_type(name, value),
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .any(|name| is_type(name, value)),
+        // Not a valid `type`; don't reject values over it.
+        _ => true,
+    }
+}
+
+fn is_type(name: &str, value: &Value) -> bool {
+    match name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => match value {
+            Value::Number(number) => {
+                number.is_i64()
+                    || number.is_u64()
+                    || number.as_f64().is_some_and(|float| float.fract() == 0.0)
+            }
+            _ => false,
+        },
+        _ => true,
+    }
+}
+
+fn describe_type(expected: &Value) -> String {
+    match expected {
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" or "),
+        other => other.as_str().unwrap_or("a valid type").to_owned(),
+    }
+}
+
+fn type_of(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn validate(schema: Value, value: Value) -> Result<(), Vec<ValidationError>> {
+        validate_against_schema(schema.as_object().unwrap(), &value)
+    }
+
+    /// Sorted, so the order doesn't depend on serde_json's `preserve_order`.
+    fn messages(result: Result<(), Vec<ValidationError>>) -> Vec<String> {
+        let mut messages: Vec<String> = result
+            .unwrap_err()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        messages.sort();
+        messages
+    }
+
+    #[test]
+    fn accepts_matching_object() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "minLength": 1 },
+                "count": { "type": "integer", "minimum": 0 },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            },
+            "required": ["name"]
+        });
+        assert_eq!(
+            validate(schema, json!({ "name": "a", "count": 3, "tags": ["x"] })),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn reports_every_violation_with_its_path() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "count": { "type": "integer", "maximum": 10 },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            },
+            "required": ["name", "mode"],
+            "additionalProperties": false
+        });
+        assert_eq!(
+            messages(validate(
+                schema,
+                json!({ "name": 1, "count": 11, "tags": ["ok", 2], "extra": true })
+            )),
+            vec![
+                "/count: 11 is greater than the maximum of 10",
+                "/extra: unexpected property",
+                "/name: expected string, got number",
+                "/tags/1: expected string, got number",
+                "missing required property `mode`",
+            ]
+        );
+    }
+
+    #[test]
+    fn follows_local_refs_and_nullable_types() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "point": { "$ref": "#/$defs/Point" },
+                "label": { "type": ["string", "null"] }
+            },
+            "$defs": {
+                "Point": {
+                    "type": "object",
+                    "properties": { "x": { "type": "number" } },
+                    "required": ["x"]
+                }
+            }
+        });
+        assert_eq!(
+            validate(
+                schema.clone(),
+                json!({ "point": { "x": 1.5 }, "label": null })
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            messages(validate(schema, json!({ "point": {}, "label": 3 }))),
+            vec![
+                "/label: expected string or null, got number",
+                "/point: missing required property `x`",
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_enum_and_combinators() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "level": { "enum": ["low", "high"] },
+                "id": { "anyOf": [{ "type": "string" }, { "type": "integer" }] }
+            }
+        });
+        assert_eq!(
+            messages(validate(schema, json!({ "level": "mid", "id": true }))),
+            vec![
+                "/id: does not match any of the allowed schemas",
+                "/level: \"mid\" is not one of [\"low\",\"high\"]",
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_cyclic_refs() {
+        let schema = json!({ "$ref": "#" });
+        assert_eq!(
+            messages(validate(schema, json!({}))),
+            vec!["schema reference `#` is cyclic"]
+        );
+
+        let schema = json!({
+            "anyOf": [{ "$ref": "#/$defs/A" }],
+            "$defs": {
+                "A": { "$ref": "#/$defs/B" },
+                "B": { "allOf": [{ "$ref": "#/$defs/A" }] },
+            },
+        });
+        assert!(validate(schema, json!(1)).is_err());
+    }
+
+    #[test]
+    fn accepts_deeply_nested_recursive_values() {
+        let schema = json!({
+            "$ref": "#/$defs/Node",
+            "$defs": {
+                "Node": {
+                    "type": "object",
+                    "properties": {
+                        "value": { "type": "integer" },
+                        "next": { "anyOf": [{ "$ref": "#/$defs/Node" }, { "type": "null" }] },
+                    },
+                    "required": ["value"],
+                },
+            },
+        });
+        let mut list = json!({ "value": 0, "next": null });
+        for value in 1..100 {
+            list = json!({ "value": value, "next": list });
+        }
+        assert_eq!(validate(schema.clone(), list.clone()), Ok(()));
+
+        list["next"]["next"]["value"] = json!("two");
+        assert_eq!(
+            messages(validate(schema, list)),
+            vec!["/next: does not match any of the allowed schemas"]
+        );
+    }
+}
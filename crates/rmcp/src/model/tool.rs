@@ -127,7 +127,6 @@ pub struct ToolAnnotations {
     /// (This property is meaningful only when `readOnlyHint == false`)
     ///
     /// Default: true
-    /// A human-readable description of the tool's purpose.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub destructive_hint: Option<bool>,
 
@@ -206,6 +205,11 @@ impl ToolAnnotations {
         }
     }
 
+    /// If not set, defaults to false.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only_hint.unwrap_or(false)
+    }
+
     /// If not set, defaults to true.
     pub fn is_destructive(&self) -> bool {
         self.destructive_hint.unwrap_or(true)
@@ -215,6 +219,11 @@ impl ToolAnnotations {
     pub fn is_idempotent(&self) -> bool {
         self.idempotent_hint.unwrap_or(false)
     }
+
+    /// If not set, defaults to true.
+    pub fn is_open_world(&self) -> bool {
+        self.open_world_hint.unwrap_or(true)
+    }
 }
 
 impl Tool {
@@ -261,6 +270,11 @@ impl Tool {
         }
     }
 
+    /// Start a [`ToolBuilder`] for a tool with the given name
+    pub fn builder(name: impl Into<Cow<'static, str>>) -> ToolBuilder {
+        ToolBuilder::new(name)
+    }
+
     /// Set the human-readable title
     pub fn with_title(mut self, title: impl Into<String>) -> Self {
         self.title = Some(title.into());
@@ -304,6 +318,28 @@ impl Tool {
         self
     }
 
+    /// Whether the tool declares that it does not modify its environment.
+    ///
+    /// False when the tool has no annotations.
+    pub fn is_read_only(&self) -> bool {
+        self.annotations
+            .as_ref()
+            .is_some_and(ToolAnnotations::is_read_only)
+    }
+
+    /// Whether the tool may perform destructive updates, e.g. to decide if a
+    /// host should ask the user for confirmation before calling it.
+    ///
+    /// Read-only tools are never destructive; otherwise this follows
+    /// [`ToolAnnotations::is_destructive`], which defaults to true when the
+    /// tool has no annotations.
+    pub fn is_destructive(&self) -> bool {
+        match &self.annotations {
+            Some(annotations) => !annotations.is_read_only() && annotations.is_destructive(),
+            None => true,
+        }
+    }
+
     /// Returns the task support mode for this tool.
     ///
     /// Returns `TaskSupport::Forbidden` if not explicitly set.
@@ -340,3 +376,145 @@ impl Tool {
         Value::Object(self.input_schema.as_ref().clone())
     }
 }
+
+/// Fluent builder for a [`Tool`], for servers that register tools by hand
+/// rather than through the `#[tool]` macro.
+///
+/// The input schema defaults to an empty object schema.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "server")] {
+/// use rmcp::model::Tool;
+///
+/// #[derive(schemars::JsonSchema)]
+/// struct SearchParams {
+///     query: String,
+/// }
+///
+/// let tool = Tool::builder("search")
+///     .description("Search the index")
+///     .input_schema_for::<SearchParams>()
+///     .read_only()
+///     .build();
+/// assert!(tool.is_read_only());
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct ToolBuilder {
+    tool: Tool,
+}
+
+impl ToolBuilder {
+    /// Start building a tool with the given name.
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        let mut input_schema = JsonObject::new();
+        input_schema.insert("type".into(), Value::from("object"));
+        input_schema.insert("properties".into(), Value::Object(JsonObject::new()));
+        Self {
+            tool: Tool::new_with_raw(name, None, input_schema),
+        }
+    }
+
+    /// Set the human-readable title
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.tool.title = Some(title.into());
+        self
+    }
+
+    /// Set the description
+    pub fn description(mut self, description: impl Into<Cow<'static, str>>) -> Self {
+        self.tool.description = Some(description.into());
+        self
+    }
+
+    /// Set the input schema from a raw JSON Schema object
+    pub fn input_schema(mut self, input_schema: impl Into<Arc<JsonObject>>) -> Self {
+        self.tool.input_schema = input_schema.into();
+        self
+    }
+
+    /// Generate the input schema from a type that implements JsonSchema
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generated schema does not have root type "object".
+    #[cfg(feature = "server")]
+    pub fn input_schema_for<T: JsonSchema + 'static>(mut self) -> Self {
+        self.tool = self.tool.with_input_schema::<T>();
+        self
+    }
+
+    /// Set the output schema from a raw JSON Schema object
+    pub fn output_schema(mut self, output_schema: impl Into<Arc<JsonObject>>) -> Self {
+        self.tool.output_schema = Some(output_schema.into());
+        self
+    }
+
+    /// Generate the output schema from a type that implements JsonSchema
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generated schema does not have root type "object".
+    #[cfg(feature = "server")]
+    pub fn output_schema_for<T: JsonSchema + 'static>(mut self) -> Self {
+        self.tool = self.tool.with_output_schema::<T>();
+        self
+    }
+
+    /// Replace the annotations
+    pub fn annotations(mut self, annotations: ToolAnnotations) -> Self {
+        self.tool.annotations = Some(annotations);
+        self
+    }
+
+    fn annotate(mut self, f: impl FnOnce(ToolAnnotations) -> ToolAnnotations) -> Self {
+        self.tool.annotations = Some(f(self.tool.annotations.take().unwrap_or_default()));
+        self
+    }
+
+    /// Mark the tool as not modifying its environment.
+    pub fn read_only(self) -> Self {
+        self.annotate(|annotations| annotations.read_only(true))
+    }
+
+    /// Declare whether the tool may perform destructive updates (default: true).
+    pub fn destructive(self, destructive: bool) -> Self {
+        self.annotate(|annotations| annotations.destructive(destructive))
+    }
+
+    /// Mark repeated calls with the same arguments as having no additional effect.
+    pub fn idempotent(self) -> Self {
+        self.annotate(|annotations| annotations.idempotent(true))
+    }
+
+    /// Declare whether the tool interacts with external entities (default: true).
+    pub fn open_world(self, open_world: bool) -> Self {
+        self.annotate(|annotations| annotations.open_world(open_world))
+    }
+
+    /// Set the execution configuration
+    pub fn execution(mut self, execution: ToolExecution) -> Self {
+        self.tool.execution = Some(execution);
+        self
+    }
+
+    /// Set the icons
+    pub fn icons(mut self, icons: Vec<Icon>) -> Self {
+        self.tool.icons = Some(icons);
+        self
+    }
+
+    /// Set the metadata
+    pub fn meta(mut self, meta: Meta) -> Self {
+        self.tool.meta = Some(meta);
+        self
+    }
+
+    /// Finish building the tool
+    pub fn build(self) -> Tool {
+        self.tool
+    }
+}
@@ -0,0 +1,136 @@
+//! One-shot client helpers for scripts and command-line tools.
+//!
+//! Each function connects over the given transport, initializes a default
+//! client, performs a single operation and closes the connection again, so the
+//! caller never handles a [`RunningService`](crate::service::RunningService).
+//!
+//! ```rust,ignore
+//! use rmcp::{oneshot, transport::TokioChildProcess};
+//!
+//! let transport = TokioChildProcess::new(tokio::process::Command::new("git-mcp-server"))?;
+//! let result = oneshot::call_tool(transport, "git_status", None).await?;
+//! println!("{}", result.text().unwrap_or_default());
+//! ```
+//!
+//! For several operations on one connection, use [`with_client`] or serve a
+//! client with [`ServiceExt::serve`](crate::ServiceExt::serve).
+
+use std::{borrow::Cow, future::Future};
+
+use crate::{
+    Peer, RmcpError, RoleClient,
+    model::{
+        CallToolRequestParams, CallToolResult, GetPromptRequestParams, GetPromptResult, JsonObject,
+        Prompt, ReadResourceRequestParams, ReadResourceResult, Resource, Tool,
+    },
+    serve_client,
+    transport::IntoTransport,
+};
+
+/// Connect, run `f` with the peer, then close the connection.
+///
+/// The connection is closed even if `f` fails, and `f`'s error takes
+/// precedence over an error while closing.
+pub async fn with_client<T, E, A, F, Fut, R>(transport: T, f: F) -> Result<R, RmcpError>
+where
+    T: IntoTransport<RoleClient, E, A>,
+    E: std::error::Error + Send + Sync + 'static,
+    F: FnOnce(Peer<RoleClient>) -> Fut,
+    Fut: Future<Output = Result<R, RmcpError>>,
+{
+    let client = serve_client((), transport).await?;
+    let result = f(client.peer().clone()).await;
+    let closed = client.cancel().await;
+    let output = result?;
+    closed?;
+    Ok(output)
+}
+
+/// Call the tool `name` with `arguments`.
+pub async fn call_tool<T, E, A>(
+    transport: T,
+    name: impl Into<Cow<'static, str>>,
+    arguments: Option<JsonObject>,
+) -> Result<CallToolResult, RmcpError>
+where
+    T: IntoTransport<RoleClient, E, A>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let mut params = CallToolRequestParams::new(name);
+    params.arguments = arguments;
+    with_client(transport, |peer| async move {
+        Ok(peer.call_tool(params).await?)
+    })
+    .await
+}
+
+/// List every tool, following pagination.
+pub async fn list_tools<T, E, A>(transport: T) -> Result<Vec<Tool>, RmcpError>
+where
+    T: IntoTransport<RoleClient, E, A>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    with_client(
+        transport,
+        |peer| async move { Ok(peer.list_all_tools().await?) },
+    )
+    .await
+}
+
+/// List every prompt, following pagination.
+pub async fn list_prompts<T, E, A>(transport: T) -> Result<Vec<Prompt>, RmcpError>
+where
+    T: IntoTransport<RoleClient, E, A>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    with_client(transport, |peer| async move {
+        Ok(peer.list_all_prompts().await?)
+    })
+    .await
+}
+
+/// Get the prompt `name` rendered with `arguments`.
+pub async fn get_prompt<T, E, A>(
+    transport: T,
+    name: impl Into<String>,
+    arguments: Option<JsonObject>,
+) -> Result<GetPromptResult, RmcpError>
+where
+    T: IntoTransport<RoleClient, E, A>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let mut params = GetPromptRequestParams::new(name);
+    params.arguments = arguments;
+    with_client(transport, |peer| async move {
+        Ok(peer.get_prompt(params).await?)
+    })
+    .await
+}
+
+/// List every resource, following pagination.
+pub async fn list_resources<T, E, A>(transport: T) -> Result<Vec<Resource>, RmcpError>
+where
+    T: IntoTransport<RoleClient, E, A>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    with_client(transport, |peer| async move {
+        Ok(peer.list_all_resources().await?)
+    })
+    .await
+}
+
+/// Read the resource at `uri`.
+pub async fn read_resource<T, E, A>(
+    transport: T,
+    uri: impl Into<String>,
+) -> Result<ReadResourceResult, RmcpError>
+where
+    T: IntoTransport<RoleClient, E, A>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let params = ReadResourceRequestParams::new(uri);
+    with_client(transport, |peer| async move {
+        Ok(peer.read_resource(params).await?)
+    })
+    .await
+}
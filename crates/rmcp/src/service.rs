@@ -51,9 +51,9 @@ use crate::model::ServerNotification;
 use crate::{
     error::ErrorData as McpError,
     model::{
-        CancelledNotification, CancelledNotificationParam, Extensions, GetExtensions, GetMeta,
-        JsonRpcError, JsonRpcMessage, JsonRpcNotification, JsonRpcRequest, JsonRpcResponse, Meta,
-        NumberOrString, ProgressToken, RequestId,
+        CancelledNotification, CancelledNotificationParam, ErrorCode, Extensions, GetExtensions,
+        GetMeta, GetMethod, JsonRpcError, JsonRpcMessage, JsonRpcNotification, JsonRpcRequest,
+        JsonRpcResponse, Meta, NumberOrString, ProgressToken, RequestId,
     },
     transport::{DynamicTransportError, IntoTransport, Transport},
 };
@@ -65,6 +65,14 @@ pub use client::*;
 mod server;
 #[cfg(feature = "server")]
 pub use server::*;
+#[cfg(all(feature = "metrics", feature = "server"))]
+mod metrics;
+#[cfg(all(feature = "metrics", feature = "server"))]
+pub use metrics::*;
+mod interceptor;
+#[cfg(feature = "otel")]
+mod otel;
+pub use interceptor::*;
 #[cfg(feature = "tower")]
 mod tower;
 use tokio_util::sync::{CancellationToken, DropGuard};
@@ -88,6 +96,36 @@ pub enum ServiceError {
     Timeout { timeout: Duration },
 }
 
+impl ServiceError {
+    /// The JSON-RPC error the peer responded with, if this is a protocol error.
+    pub fn as_error_data(&self) -> Option<&McpError> {
+        match self {
+            ServiceError::McpError(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// The JSON-RPC error code, if this is a protocol error.
+    ///
+    /// ```rust
+    /// # use rmcp::{ServiceError, model::{ErrorCode, ErrorData}};
+    /// let error = ServiceError::McpError(ErrorData::method_not_found_for("tools/call"));
+    /// assert_eq!(error.code(), Some(ErrorCode::METHOD_NOT_FOUND));
+    /// assert_eq!(ServiceError::TransportClosed.code(), None);
+    /// ```
+    pub fn code(&self) -> Option<ErrorCode> {
+        self.as_error_data().map(|error| error.code)
+    }
+
+    /// The `data` of a protocol error, deserialized as `T`.
+    ///
+    /// Returns `None` if this is not a protocol error, it carries no data, or
+    /// the data does not deserialize as `T`.
+    pub fn data<T: serde::de::DeserializeOwned>(&self) -> Option<T> {
+        self.as_error_data()?.data_as()
+    }
+}
+
 trait TransferObject:
     std::fmt::Debug + Clone + serde::Serialize + serde::de::DeserializeOwned + Send + Sync + 'static
 {
@@ -111,13 +149,14 @@ pub trait ServiceRole: std::fmt::Debug + Send + Sync + 'static + Copy + Clone {
     type Not: TryInto<CancelledNotification, Error = Self::Not>
         + From<CancelledNotification>
         + TransferObject;
-    type PeerReq: TransferObject + GetMeta + GetExtensions;
+    type PeerReq: TransferObject + GetMeta + GetExtensions + GetMethod;
     type PeerResp: TransferObject;
     type PeerNot: TryInto<CancelledNotification, Error = Self::PeerNot>
         + From<CancelledNotification>
         + TransferObject
         + GetMeta
-        + GetExtensions;
+        + GetExtensions
+        + GetMethod;
     type InitializeError;
     const IS_CLIENT: bool;
     type Info: TransferObject;
@@ -524,8 +563,18 @@ pub struct Peer<R: ServiceRole> {
     progress_token_provider: Arc<dyn ProgressTokenProvider>,
     progress_timeout_watchers: ProgressTimeoutWatchers,
     info: Arc<std::sync::RwLock<Option<Arc<R::PeerInfo>>>>,
+    interceptors: Option<Interceptors<R>>,
+    /// Notifications held back by the peer, sent before the service closes.
+    pending_notifications: Option<PendingNotifications<R>>,
+    /// Input schemas from `tools/list` responses, keyed by tool name.
+    #[cfg(feature = "client")]
+    tool_schemas: ToolSchemas,
+    #[cfg(feature = "server")]
+    logging_throttle: Option<Arc<LoggingThrottle>>,
 }
 
+type PendingNotifications<R> = Arc<dyn Fn() -> Vec<<R as ServiceRole>::Not> + Send + Sync>;
+
 impl<R: ServiceRole> std::fmt::Debug for Peer<R> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("PeerSink")
@@ -585,10 +634,20 @@ impl<R: ServiceRole> Peer<R> {
                 progress_token_provider: Arc::new(AtomicU32ProgressTokenProvider::default()),
                 progress_timeout_watchers: Default::default(),
                 info: Arc::new(std::sync::RwLock::new(peer_info.map(Arc::new))),
+                interceptors: None,
+                pending_notifications: None,
+                #[cfg(feature = "client")]
+                tool_schemas: Default::default(),
+                #[cfg(feature = "server")]
+                logging_throttle: None,
             },
             rx,
         )
     }
+    #[cfg(feature = "client")]
+    pub(crate) fn set_interceptors(&mut self, interceptors: Vec<Arc<dyn PeerInterceptor<R>>>) {
+        self.interceptors = (!interceptors.is_empty()).then(|| interceptors.into());
+    }
     pub async fn send_notification(&self, notification: R::Not) -> Result<(), ServiceError> {
         let (responder, receiver) = tokio::sync::oneshot::channel();
         self.tx
@@ -625,9 +684,16 @@ impl<R: ServiceRole> Peer<R> {
         if let Some(meta) = options.meta.clone() {
             request.get_meta_mut().extend(meta);
         }
+        #[cfg(feature = "otel")]
+        otel::inject_current_context(request.get_meta_mut());
         request
             .get_meta_mut()
             .set_progress_token(progress_token.clone());
+        if let Some(interceptors) = &self.interceptors {
+            for interceptor in interceptors.iter() {
+                interceptor.before_request(&mut request);
+            }
+        }
         let (responder, receiver) = tokio::sync::oneshot::channel();
         let progress_reset_rx = if options.reset_timeout_on_progress && options.timeout.is_some() {
             let (sender, receiver) = mpsc::channel(1);
@@ -711,6 +777,7 @@ pub struct RunningService<R: ServiceRole, S: Service<R>> {
     peer: Peer<R>,
     handle: Option<tokio::task::JoinHandle<QuitReason>>,
     cancellation_token: CancellationToken,
+    shutdown_token: CancellationToken,
     dg: DropGuard,
 }
 impl<R: ServiceRole, S: Service<R>> Deref for RunningService<R, S> {
@@ -811,10 +878,58 @@ impl<R: ServiceRole, S: Service<R>> RunningService<R, S> {
         }
     }
 
+    /// Shut the service down once outstanding requests have settled.
+    ///
+    /// Unlike [`cancel`](Self::cancel) and [`close`](Self::close), which stop the
+    /// service loop immediately and drop any requests still awaiting a reply,
+    /// this stops accepting new work and lets in-flight requests finish first:
+    ///
+    /// - new outbound requests and notifications fail with
+    ///   [`ServiceError::TransportClosed`], while those already queued are sent;
+    /// - new inbound requests are answered with an error;
+    /// - requests we sent keep waiting for the peer's reply, and requests the
+    ///   peer sent keep running until their responses are written.
+    ///
+    /// Once nothing is outstanding the pending responses are flushed and the
+    /// transport is closed. If that takes longer than `timeout`, the service
+    /// falls back to a hard cancel.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut client = ().serve(transport).await?;
+    /// // ... issue requests from other tasks ...
+    /// client.shutdown(Duration::from_secs(5)).await?;
+    /// ```
+    pub async fn shutdown(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<QuitReason, tokio::task::JoinError> {
+        let Some(mut handle) = self.handle.take() else {
+            return Ok(QuitReason::Closed);
+        };
+        self.shutdown_token.cancel();
+        let result = match tokio::time::timeout(timeout, &mut handle).await {
+            Ok(result) => result,
+            Err(_elapsed) => {
+                tracing::warn!(
+                    "shutdown: outstanding requests did not settle within {:?}, cancelling",
+                    timeout
+                );
+                self.cancellation_token.cancel();
+                handle.await
+            }
+        };
+        self.cancellation_token.cancel();
+        result
+    }
+
     /// Cancel the service and wait for cleanup to complete.
     ///
     /// This consumes the `RunningService` and ensures the connection is properly
-    /// closed. For a non-consuming alternative, see [`close`](Self::close).
+    /// closed. Requests still awaiting a reply are dropped; use
+    /// [`shutdown`](Self::shutdown) to let them finish first. For a
+    /// non-consuming alternative, see [`close`](Self::close).
     pub async fn cancel(mut self) -> Result<QuitReason, tokio::task::JoinError> {
         // Disarm the drop guard since we're handling cancellation explicitly
         let _ = std::mem::replace(&mut self.dg, self.cancellation_token.clone().drop_guard());
@@ -988,6 +1103,8 @@ where
     // let message_sink = tokio::sync::
     // let mut stream = std::pin::pin!(stream);
     let serve_loop_ct = ct.child_token();
+    let shutdown_ct = CancellationToken::new();
+    let serve_loop_shutdown_ct = shutdown_ct.clone();
     let peer_return: Peer<R> = peer.clone();
     let current_span = tracing::Span::current();
     let handle = spawn_service_task(async move {
@@ -1015,7 +1132,20 @@ where
             SendTaskResult(SendTaskResult),
         }
 
+        // set once `RunningService::shutdown` is called: stop taking new work
+        // and quit as soon as nothing is outstanding
+        let mut draining = false;
         let quit_reason = loop {
+            if draining
+                && batch_messages.is_empty()
+                && peer_rx.is_empty()
+                && send_task_set.is_empty()
+                && local_responder_pool.is_empty()
+                && local_ct_pool.is_empty()
+            {
+                tracing::info!("outstanding requests settled");
+                break QuitReason::Cancelled;
+            }
             let evt = if let Some(m) = batch_messages.pop_front() {
                 Event::PeerMessage(m)
             } else {
@@ -1036,7 +1166,7 @@ where
                             break QuitReason::Closed
                         }
                     }
-                    m = peer_rx.recv(), if !peer_rx.is_closed() => {
+                    m = peer_rx.recv(), if !(peer_rx.is_closed() && peer_rx.is_empty()) => {
                         if let Some(m) = m {
                             Event::ProxyMessage(m)
                         } else {
@@ -1058,6 +1188,13 @@ where
                             }
                         }
                     }
+                    _ = serve_loop_shutdown_ct.cancelled(), if !draining => {
+                        tracing::info!("shutdown requested, draining outstanding requests");
+                        draining = true;
+                        // already queued messages are still delivered
+                        peer_rx.close();
+                        continue
+                    }
                     _ = serve_loop_ct.cancelled() => {
                         tracing::info!("task cancelled");
                         break QuitReason::Cancelled
@@ -1159,6 +1296,18 @@ where
                     ..
                 })) => {
                     tracing::debug!(%id, ?request, "received request");
+                    if draining {
+                        tracing::debug!(%id, "rejecting request during shutdown");
+                        let error = McpError::internal_error("service is shutting down", None);
+                        let send = transport.send(JsonRpcMessage::error(error, Some(id)));
+                        let current_span = tracing::Span::current();
+                        response_send_tasks.spawn(async move {
+                            if let Err(error) = send.await {
+                                tracing::error!(%error, "fail to response message");
+                            }
+                        }.instrument(current_span));
+                        continue;
+                    }
                     {
                         let service = shared_service.clone();
                         let sink = sink_proxy_tx.clone();
@@ -1171,6 +1320,17 @@ where
                         // swap meta firstly, otherwise progress token will be lost
                         std::mem::swap(&mut meta, request.get_meta_mut());
                         std::mem::swap(&mut extensions, request.extensions_mut());
+                        let request_span = tracing::info_span!(
+                            "mcp.request",
+                            method = request.method(),
+                            id = %id,
+                            progress_token = tracing::field::Empty,
+                        );
+                        if let Some(progress_token) = meta.get_progress_token() {
+                            request_span.record("progress_token", tracing::field::display(&progress_token.0));
+                        }
+                        #[cfg(feature = "otel")]
+                        otel::set_parent_from_meta(&request_span, &meta);
                         let context = RequestContext {
                             ct: context_ct,
                             id: id.clone(),
@@ -1178,7 +1338,6 @@ where
                             meta,
                             extensions,
                         };
-                        let current_span = tracing::Span::current();
                         spawn_service_task(async move {
                             let result = service
                                 .handle_request(request, context)
@@ -1194,7 +1353,7 @@ where
                                 }
                             };
                             let _send_result = sink.send(response).await;
-                        }.instrument(current_span));
+                        }.instrument(request_span));
                     }
                 }
                 Event::PeerMessage(JsonRpcMessage::Notification(JsonRpcNotification {
@@ -1230,13 +1389,16 @@ where
                             meta,
                             extensions,
                         };
-                        let current_span = tracing::Span::current();
+                        let notification_span = tracing::info_span!(
+                            "mcp.notification",
+                            method = notification.method(),
+                        );
                         spawn_service_task(async move {
                             let result = service.handle_notification(notification, context).await;
                             if let Err(error) = result {
                                 tracing::warn!(%error, "Error sending notification");
                             }
-                        }.instrument(current_span));
+                        }.instrument(notification_span));
                     }
                 }
                 Event::PeerMessage(JsonRpcMessage::Response(JsonRpcResponse {
@@ -1245,6 +1407,11 @@ where
                     ..
                 })) => {
                     if let Some(responder) = local_responder_pool.remove(&id) {
+                        if let Some(interceptors) = &peer.interceptors {
+                            for interceptor in interceptors.iter() {
+                                interceptor.after_response(&result);
+                            }
+                        }
                         let response_result = responder.send(Ok(result));
                         if let Err(_error) = response_result {
                             tracing::warn!(%id, "Error sending response");
@@ -1298,6 +1465,20 @@ where
                         break;
                     }
                 }
+                // Finally report what the peer held back, such as coalesced
+                // log messages.
+                let pending = peer
+                    .pending_notifications
+                    .as_ref()
+                    .map(|pending| pending())
+                    .unwrap_or_default();
+                for notification in pending {
+                    let message = TxJsonRpcMessage::<R>::notification(notification);
+                    if let Err(error) = transport.send(message).await {
+                        tracing::error!(%error, "failed to send pending notification during drain");
+                        break;
+                    }
+                }
             })
             .await;
             if drain_result.is_err() {
@@ -1317,6 +1498,7 @@ where
         peer: peer_return,
         handle: Some(handle),
         cancellation_token: ct.clone(),
+        shutdown_token: shutdown_ct,
         dg: ct.drop_guard(),
     }
 }
@@ -11,14 +11,16 @@ use crate::{
         CancelledNotification, CancelledNotificationParam, ClientInfo, ClientJsonRpcMessage,
         ClientNotification, ClientRequest, ClientResult, CompleteRequest, CompleteRequestParams,
         CompleteResult, CompletionContext, CompletionInfo, ErrorData, GetPromptRequest,
-        GetPromptRequestParams, GetPromptResult, InitializeRequest, InitializedNotification,
-        JsonRpcResponse, ListPromptsRequest, ListPromptsResult, ListResourceTemplatesRequest,
-        ListResourceTemplatesResult, ListResourcesRequest, ListResourcesResult, ListToolsRequest,
-        ListToolsResult, PaginatedRequestParams, ProgressNotification, ProgressNotificationParam,
+        GetPromptRequestParams, GetPromptResult, InitializeRequest, InitializeResult,
+        InitializedNotification, JsonObject, JsonRpcResponse, ListPromptsRequest,
+        ListPromptsResult, ListResourceTemplatesRequest, ListResourceTemplatesResult,
+        ListResourcesRequest, ListResourcesResult, ListToolsRequest, ListToolsResult,
+        PaginatedRequestParams, PingRequest, ProgressNotification, ProgressNotificationParam,
         ReadResourceRequest, ReadResourceRequestParams, ReadResourceResult, Reference, RequestId,
         RootsListChangedNotification, ServerInfo, ServerJsonRpcMessage, ServerNotification,
         ServerRequest, ServerResult, SetLevelRequest, SetLevelRequestParams, SubscribeRequest,
-        SubscribeRequestParams, UnsubscribeRequest, UnsubscribeRequestParams,
+        SubscribeRequestParams, UnsubscribeRequest, UnsubscribeRequestParams, ValidationError,
+        validate_against_schema,
     },
     transport::DynamicTransportError,
 };
@@ -52,6 +54,12 @@ pub enum ClientInitializeError {
 
     #[error("Cancelled")]
     Cancelled,
+
+    #[error("no initialize response within {0:?}")]
+    Timeout(std::time::Duration),
+
+    #[error("invalid initialize result: {0}")]
+    InvalidInitResult(String),
 }
 
 impl ClientInitializeError {
@@ -66,18 +74,42 @@ impl ClientInitializeError {
     }
 }
 
+/// Input schemas the client has seen in `tools/list` responses, keyed by tool name.
+pub(crate) type ToolSchemas =
+    Arc<std::sync::RwLock<std::collections::HashMap<String, Arc<JsonObject>>>>;
+
+/// Why [`Peer::validate_tool_args`] could not accept a tool's arguments.
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum ToolArgsError {
+    #[error("failed to list tools: {0}")]
+    Service(#[from] ServiceError),
+
+    #[error("unknown tool `{0}`")]
+    UnknownTool(String),
+
+    #[error("invalid tool arguments: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    Invalid(Vec<ValidationError>),
+}
+
+/// How long the client waits for the server's `initialize` response by default.
+pub const DEFAULT_INITIALIZE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
 /// Helper function to get the next message from the stream
 async fn expect_next_message<T>(
     transport: &mut T,
     context: &str,
 ) -> Result<ServerJsonRpcMessage, ClientInitializeError>
 where
-    T: Transport<RoleClient>,
+    T: Transport<RoleClient> + 'static,
 {
-    transport
-        .receive()
-        .await
-        .ok_or_else(|| ClientInitializeError::ConnectionClosed(context.to_string()))
+    match transport.receive().await {
+        Some(message) => Ok(message),
+        None => Err(match transport.take_error() {
+            Some(error) => ClientInitializeError::transport::<T>(error, context.to_string()),
+            None => ClientInitializeError::ConnectionClosed(context.to_string()),
+        }),
+    }
 }
 
 /// Helper function to expect a response from the stream
@@ -88,7 +120,7 @@ async fn expect_response<T, S>(
     peer: Peer<RoleClient>,
 ) -> Result<(ServerResult, RequestId), ClientInitializeError>
 where
-    T: Transport<RoleClient>,
+    T: Transport<RoleClient> + 'static,
     S: Service<RoleClient>,
 {
     loop {
@@ -130,10 +162,21 @@ where
                 }
             }
             // Server could send pings before handshake
-            ServerJsonRpcMessage::Request(ref request)
+            ServerJsonRpcMessage::Request(request)
                 if matches!(request.request, ServerRequest::PingRequest(_)) =>
             {
-                tracing::trace!("Received ping request. Ignored.")
+                transport
+                    .send(ClientJsonRpcMessage::response(
+                        ClientResult::empty(()),
+                        request.id,
+                    ))
+                    .await
+                    .map_err(|error| {
+                        ClientInitializeError::transport::<T>(
+                            error,
+                            "sending pre-init ping response",
+                        )
+                    })?;
             }
             // Server SHOULD NOT send any other messages before handshake. We ignore them anyway
             _ => tracing::warn!(?message, "Received unexpected message"),
@@ -198,17 +241,86 @@ where
     T: IntoTransport<RoleClient, E, A>,
     E: std::error::Error + Send + Sync + 'static,
 {
+    serve_client_with_interceptors(service, transport, Vec::new(), ct).await
+}
+
+/// Serve a client with [`PeerInterceptor`]s applied to every request sent through its [`Peer`].
+///
+/// The interceptors run in order; with an empty list this is equivalent to
+/// [`serve_client_with_ct`].
+pub async fn serve_client_with_interceptors<S, T, E, A>(
+    service: S,
+    transport: T,
+    interceptors: Vec<Arc<dyn PeerInterceptor<RoleClient>>>,
+    ct: CancellationToken,
+) -> Result<RunningService<RoleClient, S>, ClientInitializeError>
+where
+    S: Service<RoleClient>,
+    T: IntoTransport<RoleClient, E, A>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    serve_client_with_config(
+        service,
+        transport,
+        interceptors,
+        Some(DEFAULT_INITIALIZE_TIMEOUT),
+        ct,
+    )
+    .await
+}
+
+/// Serve a client, giving up with [`ClientInitializeError::Timeout`] if the
+/// server has not answered `initialize` within `init_timeout`.
+///
+/// The other `serve_client*` functions use [`DEFAULT_INITIALIZE_TIMEOUT`];
+/// pass `None` to wait indefinitely.
+pub async fn serve_client_with_init_timeout<S, T, E, A>(
+    service: S,
+    transport: T,
+    init_timeout: Option<std::time::Duration>,
+    ct: CancellationToken,
+) -> Result<RunningService<RoleClient, S>, ClientInitializeError>
+where
+    S: Service<RoleClient>,
+    T: IntoTransport<RoleClient, E, A>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    serve_client_with_config(service, transport, Vec::new(), init_timeout, ct).await
+}
+
+async fn serve_client_with_config<S, T, E, A>(
+    service: S,
+    transport: T,
+    interceptors: Vec<Arc<dyn PeerInterceptor<RoleClient>>>,
+    init_timeout: Option<std::time::Duration>,
+    ct: CancellationToken,
+) -> Result<RunningService<RoleClient, S>, ClientInitializeError>
+where
+    S: Service<RoleClient>,
+    T: IntoTransport<RoleClient, E, A>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let timeout = async {
+        match init_timeout {
+            Some(duration) => tokio::time::sleep(duration).await,
+            None => std::future::pending().await,
+        }
+    };
     tokio::select! {
-        result = serve_client_with_ct_inner(service, transport.into_transport(), ct.clone()) => { result }
+        result = serve_client_with_ct_inner(service, transport.into_transport(), interceptors, ct.clone()) => { result }
         _ = ct.cancelled() => {
             Err(ClientInitializeError::Cancelled)
         }
+        _ = timeout => {
+            Err(ClientInitializeError::Timeout(init_timeout.unwrap_or_default()))
+        }
     }
 }
 
 async fn serve_client_with_ct_inner<S, T>(
     service: S,
     transport: T,
+    interceptors: Vec<Arc<dyn PeerInterceptor<RoleClient>>>,
     ct: CancellationToken,
 ) -> Result<RunningService<RoleClient, S>, ClientInitializeError>
 where
@@ -236,7 +348,8 @@ where
             context: "send initialize request".into(),
         })?;
 
-    let (peer, peer_rx) = Peer::new(id_provider, None);
+    let (mut peer, peer_rx) = Peer::new(id_provider, None);
+    peer.set_interceptors(interceptors);
 
     let (response, response_id) = expect_response(
         &mut transport,
@@ -253,8 +366,19 @@ where
         ));
     }
 
-    let ServerResult::InitializeResult(initialize_result) = response else {
-        return Err(ClientInitializeError::ExpectedInitResult(Some(response)));
+    let initialize_result = match response {
+        ServerResult::InitializeResult(initialize_result) => initialize_result,
+        // A malformed initialize result is parsed as some other variant;
+        // re-parse it to report what is wrong with it.
+        response => {
+            let error = serde_json::to_value(&response)
+                .and_then(serde_json::from_value::<InitializeResult>)
+                .err();
+            return Err(match error {
+                Some(error) => ClientInitializeError::InvalidInitResult(error.to_string()),
+                None => ClientInitializeError::ExpectedInitResult(Some(response)),
+            });
+        }
     };
     peer.set_peer_info(initialize_result);
 
@@ -360,6 +484,17 @@ macro_rules! method {
     };
 }
 
+mod aggregate;
+pub use aggregate::*;
+#[cfg(not(feature = "local"))]
+mod heartbeat;
+#[cfg(not(feature = "local"))]
+pub use heartbeat::*;
+#[cfg(not(feature = "local"))]
+mod reconnect;
+#[cfg(not(feature = "local"))]
+pub use reconnect::*;
+
 impl Peer<RoleClient> {
     method!(peer_req complete CompleteRequest(CompleteRequestParams) => CompleteResult);
     method!(
@@ -377,7 +512,17 @@ impl Peer<RoleClient> {
     method!(peer_req subscribe SubscribeRequest(SubscribeRequestParams) );
     method!(peer_req unsubscribe UnsubscribeRequest(UnsubscribeRequestParams));
     method!(peer_req call_tool CallToolRequest(CallToolRequestParams) => CallToolResult);
-    method!(peer_req list_tools ListToolsRequest(PaginatedRequestParams)? => ListToolsResult);
+
+    /// Send a `ping` and return the round-trip time of the response.
+    ///
+    /// Servers answer pings without any handler code: the default
+    /// [`ServerHandler::ping`](crate::ServerHandler::ping) returns an empty result.
+    pub async fn ping(&self) -> Result<std::time::Duration, ServiceError> {
+        let started = std::time::Instant::now();
+        self.send_request(ClientRequest::PingRequest(PingRequest::default()))
+            .await?;
+        Ok(started.elapsed())
+    }
 
     method!(peer_not notify_cancelled CancelledNotification(CancelledNotificationParam));
     method!(peer_not notify_progress ProgressNotification(ProgressNotificationParam));
@@ -385,7 +530,275 @@ impl Peer<RoleClient> {
     method!(peer_not notify_roots_list_changed RootsListChangedNotification);
 }
 
+/// The capabilities a server declared, borrowed from the peer's stored
+/// [`ServerInfo`] without copying them.
+///
+/// Returned by [`Peer::server_capabilities`]; dereferences to
+/// [`ServerCapabilities`](crate::model::ServerCapabilities).
+#[derive(Debug, Clone)]
+pub struct ServerCapabilitiesRef(Arc<ServerInfo>);
+
+impl std::ops::Deref for ServerCapabilitiesRef {
+    type Target = crate::model::ServerCapabilities;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0.capabilities
+    }
+}
+
 impl Peer<RoleClient> {
+    /// The capabilities the server declared during initialization, or `None`
+    /// before the handshake has completed.
+    ///
+    /// The peer info lives behind a lock and may be replaced, so this hands
+    /// out a shared reference to it rather than a plain borrow of `self`.
+    pub fn server_capabilities(&self) -> Option<ServerCapabilitiesRef> {
+        self.peer_info().map(ServerCapabilitiesRef)
+    }
+
+    fn server_capability(
+        &self,
+        check: impl FnOnce(&crate::model::ServerCapabilities) -> bool,
+    ) -> bool {
+        self.server_capabilities()
+            .is_some_and(|capabilities| check(&capabilities))
+    }
+
+    /// Whether the server supports `tools/list` and `tools/call`.
+    pub fn supports_tools(&self) -> bool {
+        self.server_capability(|c| c.tools.is_some())
+    }
+
+    /// Whether the server sends `notifications/tools/list_changed`.
+    pub fn supports_tool_list_changed(&self) -> bool {
+        self.server_capability(|c| {
+            c.tools
+                .as_ref()
+                .and_then(|t| t.list_changed)
+                .unwrap_or(false)
+        })
+    }
+
+    /// Whether the server supports `resources/list` and `resources/read`.
+    pub fn supports_resources(&self) -> bool {
+        self.server_capability(|c| c.resources.is_some())
+    }
+
+    /// Whether the server supports `resources/subscribe`.
+    pub fn supports_resource_subscription(&self) -> bool {
+        self.server_capability(|c| {
+            c.resources
+                .as_ref()
+                .and_then(|r| r.subscribe)
+                .unwrap_or(false)
+        })
+    }
+
+    /// Whether the server sends `notifications/resources/list_changed`.
+    pub fn supports_resource_list_changed(&self) -> bool {
+        self.server_capability(|c| {
+            c.resources
+                .as_ref()
+                .and_then(|r| r.list_changed)
+                .unwrap_or(false)
+        })
+    }
+
+    /// Whether the server supports `prompts/list` and `prompts/get`.
+    pub fn supports_prompts(&self) -> bool {
+        self.server_capability(|c| c.prompts.is_some())
+    }
+
+    /// Whether the server sends `notifications/prompts/list_changed`.
+    pub fn supports_prompt_list_changed(&self) -> bool {
+        self.server_capability(|c| {
+            c.prompts
+                .as_ref()
+                .and_then(|p| p.list_changed)
+                .unwrap_or(false)
+        })
+    }
+
+    /// Whether the server supports `completion/complete`.
+    pub fn supports_completions(&self) -> bool {
+        self.server_capability(|c| c.completions.is_some())
+    }
+
+    /// Whether the server declared `capability`.
+    pub fn supports(&self, capability: ServerCapability) -> bool {
+        match capability {
+            ServerCapability::Tools => self.supports_tools(),
+            ServerCapability::ToolListChanged => self.supports_tool_list_changed(),
+            ServerCapability::Resources => self.supports_resources(),
+            ServerCapability::ResourceSubscription => self.supports_resource_subscription(),
+            ServerCapability::ResourceListChanged => self.supports_resource_list_changed(),
+            ServerCapability::Prompts => self.supports_prompts(),
+            ServerCapability::PromptListChanged => self.supports_prompt_list_changed(),
+            ServerCapability::Completions => self.supports_completions(),
+            ServerCapability::Logging => self.server_capability(|c| c.logging.is_some()),
+            ServerCapability::Tasks => self.server_capability(|c| c.tasks.is_some()),
+        }
+    }
+
+    /// Check that the server declared every capability in `required`.
+    ///
+    /// Call this right after connecting to fail fast with a message naming
+    /// what the server lacks, instead of on the first request that needs it.
+    /// Before the handshake has completed every capability counts as missing.
+    ///
+    /// ```rust,ignore
+    /// let client = ().serve(transport).await?;
+    /// client.check_required_capabilities(&[ServerCapability::Tools, ServerCapability::Resources])?;
+    /// ```
+    pub fn check_required_capabilities(
+        &self,
+        required: &[ServerCapability],
+    ) -> Result<(), MissingCapabilitiesError> {
+        let missing: Vec<_> = required
+            .iter()
+            .copied()
+            .filter(|capability| !self.supports(*capability))
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        Err(MissingCapabilitiesError {
+            server: self
+                .peer_info()
+                .map(|info| info.server_info.name.clone())
+                .unwrap_or_default(),
+            missing,
+        })
+    }
+}
+
+/// A server capability a client can require, see
+/// [`Peer::check_required_capabilities`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ServerCapability {
+    Tools,
+    ToolListChanged,
+    Resources,
+    ResourceSubscription,
+    ResourceListChanged,
+    Prompts,
+    PromptListChanged,
+    Completions,
+    Logging,
+    Tasks,
+}
+
+impl std::fmt::Display for ServerCapability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ServerCapability::Tools => "tools",
+            ServerCapability::ToolListChanged => "tools.listChanged",
+            ServerCapability::Resources => "resources",
+            ServerCapability::ResourceSubscription => "resources.subscribe",
+            ServerCapability::ResourceListChanged => "resources.listChanged",
+            ServerCapability::Prompts => "prompts",
+            ServerCapability::PromptListChanged => "prompts.listChanged",
+            ServerCapability::Completions => "completions",
+            ServerCapability::Logging => "logging",
+            ServerCapability::Tasks => "tasks",
+        })
+    }
+}
+
+/// The server lacks capabilities the client requires.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error(
+    "server {server:?} does not declare the required capabilities: {}",
+    missing.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+)]
+#[non_exhaustive]
+pub struct MissingCapabilitiesError {
+    /// The server's name from its `initialize` response.
+    pub server: String,
+    /// The required capabilities the server did not declare, in the order given.
+    pub missing: Vec<ServerCapability>,
+}
+
+impl Peer<RoleClient> {
+    /// Call a tool with extra `_meta` entries on the request.
+    ///
+    /// The entries are merged into any `_meta` already set on `params`, with
+    /// `meta` winning on conflict. Servers see them through
+    /// [`RequestContext::meta`] or
+    /// [`ToolCallContext::request_meta`](crate::handler::server::tool::ToolCallContext::request_meta).
+    pub async fn call_tool_with_meta(
+        &self,
+        mut params: CallToolRequestParams,
+        meta: crate::model::JsonObject,
+    ) -> Result<CallToolResult, ServiceError> {
+        params.meta.get_or_insert_default().extend(Meta(meta));
+        self.call_tool(params).await
+    }
+
+    /// List one page of the server's tools.
+    ///
+    /// The input schemas of the returned tools are remembered for
+    /// [`Peer::validate_tool_args`].
+    pub async fn list_tools(
+        &self,
+        params: Option<PaginatedRequestParams>,
+    ) -> Result<ListToolsResult, ServiceError> {
+        let result = self
+            .send_request(ClientRequest::ListToolsRequest(ListToolsRequest {
+                method: Default::default(),
+                params,
+                extensions: Default::default(),
+            }))
+            .await?;
+        match result {
+            ServerResult::ListToolsResult(result) => {
+                let mut schemas = self
+                    .tool_schemas
+                    .write()
+                    .expect("tool schema lock poisoned");
+                for tool in &result.tools {
+                    schemas.insert(tool.name.to_string(), tool.input_schema.clone());
+                }
+                Ok(result)
+            }
+            _ => Err(ServiceError::UnexpectedResponse),
+        }
+    }
+
+    /// Check `arguments` against the input schema of the tool `name` without
+    /// calling it, e.g. to show form errors before submitting.
+    ///
+    /// Uses the schema from the most recent `tools/list` response, listing all
+    /// tools first if `name` has not been seen. Call [`Peer::list_all_tools`]
+    /// after `notifications/tools/list_changed` to pick up changed schemas.
+    /// Missing `arguments` are validated as an empty object.
+    pub async fn validate_tool_args(
+        &self,
+        name: &str,
+        arguments: Option<&JsonObject>,
+    ) -> Result<(), ToolArgsError> {
+        let cached = self.cached_tool_schema(name);
+        let schema = match cached {
+            Some(schema) => schema,
+            None => {
+                self.list_all_tools().await?;
+                self.cached_tool_schema(name)
+                    .ok_or_else(|| ToolArgsError::UnknownTool(name.to_owned()))?
+            }
+        };
+        let arguments = serde_json::Value::Object(arguments.cloned().unwrap_or_default());
+        validate_against_schema(&schema, &arguments).map_err(ToolArgsError::Invalid)
+    }
+
+    fn cached_tool_schema(&self, name: &str) -> Option<Arc<JsonObject>> {
+        self.tool_schemas
+            .read()
+            .expect("tool schema lock poisoned")
+            .get(name)
+            .cloned()
+    }
+
     /// A wrapper method for [`Peer<RoleClient>::list_tools`].
     ///
     /// This function will call [`Peer<RoleClient>::list_tools`] multiple times until all tools are listed.
@@ -402,9 +815,38 @@ impl Peer<RoleClient> {
                 break;
             }
         }
+        // The full list is known now, so drop schemas of tools that went away.
+        *self
+            .tool_schemas
+            .write()
+            .expect("tool schema lock poisoned") = tools
+            .iter()
+            .map(|tool| (tool.name.to_string(), tool.input_schema.clone()))
+            .collect();
         Ok(tools)
     }
 
+    /// Stream every tool, requesting the next page only once the current one
+    /// has been consumed.
+    ///
+    /// Unlike [`Peer::list_all_tools`], this never holds more than one page in
+    /// memory, which matters for servers with very large catalogs. The stream
+    /// ends after the first error.
+    pub fn list_all_tools_stream(
+        &self,
+    ) -> impl futures::Stream<Item = Result<crate::model::Tool, ServiceError>> + 'static {
+        let peer = self.clone();
+        paginate(move |cursor| {
+            let peer = peer.clone();
+            async move {
+                let result = peer
+                    .list_tools(Some(PaginatedRequestParams { meta: None, cursor }))
+                    .await?;
+                Ok((result.tools, result.next_cursor))
+            }
+        })
+    }
+
     /// A wrapper method for [`Peer<RoleClient>::list_prompts`].
     ///
     /// This function will call [`Peer<RoleClient>::list_prompts`] multiple times until all prompts are listed.
@@ -443,6 +885,22 @@ impl Peer<RoleClient> {
         Ok(resources)
     }
 
+    /// Stream every resource page by page, see [`Peer::list_all_tools_stream`].
+    pub fn list_all_resources_stream(
+        &self,
+    ) -> impl futures::Stream<Item = Result<crate::model::Resource, ServiceError>> + 'static {
+        let peer = self.clone();
+        paginate(move |cursor| {
+            let peer = peer.clone();
+            async move {
+                let result = peer
+                    .list_resources(Some(PaginatedRequestParams { meta: None, cursor }))
+                    .await?;
+                Ok((result.resources, result.next_cursor))
+            }
+        })
+    }
+
     /// A wrapper method for [`Peer<RoleClient>::list_resource_templates`].
     ///
     /// This function will call [`Peer<RoleClient>::list_resource_templates`] multiple times until all resource templates are listed.
@@ -558,3 +1016,29 @@ impl Peer<RoleClient> {
         Ok(completion.values)
     }
 }
+
+/// Flatten pages fetched by `fetch_page(cursor)` into a stream of items,
+/// following `next_cursor` until it is `None`.
+fn paginate<T, F, Fut>(
+    fetch_page: F,
+) -> impl futures::Stream<Item = Result<T, ServiceError>> + 'static
+where
+    T: 'static,
+    F: Fn(Option<String>) -> Fut + 'static,
+    Fut: Future<Output = Result<(Vec<T>, Option<String>), ServiceError>> + 'static,
+{
+    use futures::{StreamExt, TryStreamExt, stream};
+
+    stream::try_unfold(Some(None), move |cursor: Option<Option<String>>| {
+        let page = cursor.map(&fetch_page);
+        async move {
+            let Some(page) = page else {
+                return Ok(None);
+            };
+            let (items, next_cursor) = page.await?;
+            let items = stream::iter(items).map(Ok);
+            Ok(Some((items, next_cursor.map(Some))))
+        }
+    })
+    .try_flatten()
+}
@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+
+use super::*;
+use crate::model::Tool;
+
+/// Separator placed between the server name and the tool name by default.
+pub const DEFAULT_TOOL_NAMESPACE_SEPARATOR: &str = "__";
+
+/// Error returned when registering a server with an [`AggregateClient`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum AggregateClientError {
+    #[error("server {0:?} is already registered")]
+    DuplicateServer(String),
+    #[error("server name {name:?} must be non-empty and must not contain {separator:?}")]
+    InvalidServerName { name: String, separator: String },
+}
+
+/// Several server connections addressed as a single tool namespace.
+///
+/// [`list_all_tools`](Self::list_all_tools) lists the tools of every
+/// registered server, renaming each to `<server><separator><tool>`, and
+/// [`call_tool`](Self::call_tool) routes a namespaced name back to the server
+/// that owns it under its original name. Server names may not contain the
+/// separator, so two servers exposing a tool with the same name never
+/// collide.
+///
+/// # Example
+/// ```rust,ignore
+/// use rmcp::service::AggregateClient;
+///
+/// let mut tools = AggregateClient::new();
+/// tools.add_server("git", git_client.peer().clone())?;
+/// tools.add_server("fetch", fetch_client.peer().clone())?;
+/// for tool in tools.list_all_tools().await {
+///     println!("{}", tool.name); // e.g. "git__git_status"
+/// }
+/// let result = tools
+///     .call_tool(CallToolRequestParams::new("fetch__fetch").with_arguments(args))
+///     .await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct AggregateClient {
+    servers: BTreeMap<String, Peer<RoleClient>>,
+    separator: String,
+}
+
+impl Default for AggregateClient {
+    fn default() -> Self {
+        Self {
+            servers: BTreeMap::new(),
+            separator: DEFAULT_TOOL_NAMESPACE_SEPARATOR.to_string(),
+        }
+    }
+}
+
+impl AggregateClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `separator` instead of [`DEFAULT_TOOL_NAMESPACE_SEPARATOR`].
+    ///
+    /// Set it before adding servers: names already registered are not
+    /// re-checked against the new separator.
+    pub fn with_separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Register a server connection under `name`.
+    pub fn add_server(
+        &mut self,
+        name: impl Into<String>,
+        peer: Peer<RoleClient>,
+    ) -> Result<(), AggregateClientError> {
+        let name = name.into();
+        if name.is_empty() || name.contains(&self.separator) {
+            return Err(AggregateClientError::InvalidServerName {
+                name,
+                separator: self.separator.clone(),
+            });
+        }
+        if self.servers.contains_key(&name) {
+            return Err(AggregateClientError::DuplicateServer(name));
+        }
+        self.servers.insert(name, peer);
+        Ok(())
+    }
+
+    /// Unregister a server, returning its connection.
+    pub fn remove_server(&mut self, name: &str) -> Option<Peer<RoleClient>> {
+        self.servers.remove(name)
+    }
+
+    /// The connection registered under `name`.
+    pub fn peer(&self, name: &str) -> Option<&Peer<RoleClient>> {
+        self.servers.get(name)
+    }
+
+    /// Names of the registered servers, in order.
+    pub fn servers(&self) -> impl Iterator<Item = &str> {
+        self.servers.keys().map(String::as_str)
+    }
+
+    /// The namespaced name of `tool` on `server`.
+    pub fn namespaced_name(&self, server: &str, tool: &str) -> String {
+        format!("{server}{}{tool}", self.separator)
+    }
+
+    /// Split a namespaced tool name into the server and the server's own tool name.
+    ///
+    /// Returns `None` if the name has no separator or names an unregistered server.
+    pub fn resolve<'a>(&self, name: &'a str) -> Option<(&str, &'a str)> {
+        let (server, tool) = name.split_once(&self.separator)?;
+        let (server, _) = self.servers.get_key_value(server)?;
+        Some((server.as_str(), tool))
+    }
+
+    /// List the tools of every server concurrently, with namespaced names.
+    ///
+    /// Servers that did not advertise the `tools` capability are skipped. A
+    /// server that fails to answer is logged and left out, so one unreachable
+    /// server does not hide the tools of the others.
+    pub async fn list_all_tools(&self) -> Vec<Tool> {
+        let listings = self
+            .servers
+            .iter()
+            .filter(|(_, peer)| supports_tools(peer))
+            .map(|(server, peer)| async move { (server, peer.list_all_tools().await) });
+        let mut tools = Vec::new();
+        for (server, listing) in futures::future::join_all(listings).await {
+            match listing {
+                Ok(listed) => tools.extend(listed.into_iter().map(|mut tool| {
+                    tool.name = self.namespaced_name(server, &tool.name).into();
+                    tool
+                })),
+                Err(error) => {
+                    tracing::warn!(%server, %error, "failed to list tools");
+                }
+            }
+        }
+        tools
+    }
+
+    /// Call a namespaced tool on the server that owns it.
+    ///
+    /// Fails with an `invalid_params` error for names that do not resolve to a
+    /// registered server, and with `method_not_found` if that server did not
+    /// advertise the `tools` capability.
+    pub async fn call_tool(
+        &self,
+        mut params: CallToolRequestParams,
+    ) -> Result<CallToolResult, ServiceError> {
+        let Some((server, tool)) = self.resolve(&params.name) else {
+            return Err(ServiceError::McpError(ErrorData::invalid_params(
+                format!("tool {} does not belong to any server", params.name),
+                None,
+            )));
+        };
+        let peer = &self.servers[server];
+        if !supports_tools(peer) {
+            return Err(ServiceError::McpError(ErrorData::method_not_found_for(
+                "tools/call",
+            )));
+        }
+        params.name = tool.to_string().into();
+        peer.call_tool(params).await
+    }
+}
+
+/// Whether the server advertised tools; assume so before its info is known.
+fn supports_tools(peer: &Peer<RoleClient>) -> bool {
+    peer.peer_info()
+        .is_none_or(|info| info.capabilities.tools.is_some())
+}
@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use super::*;
+use crate::model::PingRequest;
+
+/// Client-side keep-alive: a JSON-RPC `ping` sent at a fixed interval.
+///
+/// A ping that fails or gets no response within `timeout` is treated as a lost
+/// connection: the service is cancelled, so [`RunningService::waiting`] returns
+/// and a [`ReconnectingClient`] configured with
+/// [`ReconnectConfig::with_heartbeat`] reconnects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct HeartbeatConfig {
+    /// Time between the end of one ping and the start of the next.
+    pub interval: Duration,
+    /// How long to wait for the server's response to a ping.
+    pub timeout: Duration,
+}
+
+impl HeartbeatConfig {
+    /// Ping every `interval`, waiting up to 10 seconds for each response.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// Serve a client and keep the connection alive with periodic pings.
+///
+/// Equivalent to [`serve_client_with_ct`] plus a background task that pings
+/// the server as described in [`HeartbeatConfig`]. The task stops when the
+/// service is closed.
+pub async fn serve_client_with_heartbeat<S, T, E, A>(
+    service: S,
+    transport: T,
+    heartbeat: HeartbeatConfig,
+    ct: CancellationToken,
+) -> Result<RunningService<RoleClient, S>, ClientInitializeError>
+where
+    S: Service<RoleClient>,
+    T: IntoTransport<RoleClient, E, A>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let running = serve_client_with_ct(service, transport, ct).await?;
+    start_heartbeat(&running, heartbeat);
+    Ok(running)
+}
+
+/// Ping the server of `running` in the background until it is closed.
+pub(super) fn start_heartbeat<S: Service<RoleClient>>(
+    running: &RunningService<RoleClient, S>,
+    heartbeat: HeartbeatConfig,
+) {
+    tokio::spawn(run_heartbeat(
+        running.peer().clone(),
+        heartbeat,
+        running.cancellation_token.clone(),
+    ));
+}
+
+async fn run_heartbeat(peer: Peer<RoleClient>, heartbeat: HeartbeatConfig, ct: CancellationToken) {
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(heartbeat.interval) => {}
+            _ = ct.cancelled() => return,
+        }
+        let ping = async {
+            peer.send_request_with_option(
+                ClientRequest::PingRequest(PingRequest::default()),
+                PeerRequestOptions::with_timeout(heartbeat.timeout),
+            )
+            .await?
+            .await_response()
+            .await
+        };
+        let result = tokio::select! {
+            result = ping => result,
+            _ = ct.cancelled() => return,
+        };
+        if let Err(error) = result {
+            tracing::warn!(%error, "heartbeat ping failed, closing connection");
+            ct.cancel();
+            return;
+        }
+    }
+}
@@ -0,0 +1,421 @@
+use std::{collections::BTreeSet, sync::Mutex, time::Duration};
+
+use futures::future::BoxFuture;
+use tokio::sync::watch;
+
+use super::*;
+use crate::handler::client::resource_cache::ResourceCache;
+
+/// What a [`ReconnectingClient`] does with calls issued while it is reconnecting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PendingCallPolicy {
+    /// Wait until the connection is re-established, then send the call.
+    #[default]
+    Queue,
+    /// Fail immediately with [`ServiceError::TransportClosed`].
+    FailFast,
+}
+
+/// Configuration for [`ReconnectingClient`].
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct ReconnectConfig {
+    /// Maximum number of consecutive reconnection attempts, `None` for unlimited.
+    pub max_attempts: Option<usize>,
+    /// Delay before the first reconnection attempt, doubled after each failure.
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay between reconnection attempts.
+    pub max_backoff: Duration,
+    /// How calls issued during reconnection are handled.
+    pub pending_calls: PendingCallPolicy,
+    /// Cache for [`ReconnectingClient::read_resource`], `None` to always read
+    /// from the server.
+    pub resource_cache: Option<ResourceCache>,
+    /// Ping the server periodically and reconnect when a ping goes unanswered.
+    pub heartbeat: Option<HeartbeatConfig>,
+    /// Interceptors installed on every connection, in order; see
+    /// [`serve_client_with_interceptors`].
+    pub interceptors: Vec<Arc<dyn PeerInterceptor<RoleClient>>>,
+}
+
+impl std::fmt::Debug for ReconnectConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReconnectConfig")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("pending_calls", &self.pending_calls)
+            .field("resource_cache", &self.resource_cache)
+            .field("heartbeat", &self.heartbeat)
+            .field("interceptors", &self.interceptors.len())
+            .finish()
+    }
+}
+
+impl Default for ReconnectConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: Some(5),
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            pending_calls: PendingCallPolicy::Queue,
+            resource_cache: None,
+            heartbeat: None,
+            interceptors: Vec::new(),
+        }
+    }
+}
+
+impl ReconnectConfig {
+    pub fn with_max_attempts(mut self, max_attempts: Option<usize>) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    pub fn with_pending_calls(mut self, pending_calls: PendingCallPolicy) -> Self {
+        self.pending_calls = pending_calls;
+        self
+    }
+
+    /// Serve [`ReconnectingClient::read_resource`] from `cache`.
+    ///
+    /// The cache is cleared after every reconnect, since updates may have been
+    /// missed while disconnected. Return a clone of the same cache from the
+    /// service's [`ClientHandler::resource_cache`](crate::ClientHandler::resource_cache)
+    /// so that `notifications/resources/updated` invalidates it too.
+    pub fn with_resource_cache(mut self, cache: ResourceCache) -> Self {
+        self.resource_cache = Some(cache);
+        self
+    }
+
+    /// Keep each connection alive with [`HeartbeatConfig`] pings; a missed
+    /// ping counts as a dropped transport and triggers a reconnect.
+    pub fn with_heartbeat(mut self, heartbeat: HeartbeatConfig) -> Self {
+        self.heartbeat = Some(heartbeat);
+        self
+    }
+
+    /// Install `interceptor` on the first connection and on every reconnect,
+    /// after the interceptors added before it.
+    pub fn with_interceptor(mut self, interceptor: Arc<dyn PeerInterceptor<RoleClient>>) -> Self {
+        self.interceptors.push(interceptor);
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+enum ConnectionState {
+    Connected(Peer<RoleClient>),
+    Reconnecting,
+    /// Reconnection gave up, or the client was closed.
+    Closed,
+}
+
+type Connector<S> = Arc<
+    dyn Fn(S) -> BoxFuture<'static, Result<RunningService<RoleClient, S>, ClientInitializeError>>
+        + Send
+        + Sync,
+>;
+
+/// A client that transparently reconnects when its transport drops.
+///
+/// On transport failure the client creates a new transport with the provided
+/// factory, re-runs `initialize` with the service's [`ClientInfo`], and
+/// re-subscribes to every resource subscribed through
+/// [`ReconnectingClient::subscribe`]. Requests that were in flight when the
+/// transport dropped fail with [`ServiceError::TransportClosed`] and are not
+/// replayed.
+///
+/// # Example
+/// ```rust,ignore
+/// use rmcp::service::{ReconnectConfig, ReconnectingClient};
+/// use rmcp::transport::StreamableHttpClientTransport;
+///
+/// let client = ReconnectingClient::connect(
+///     (),
+///     || async { Ok::<_, std::convert::Infallible>(StreamableHttpClientTransport::from_uri(URL)) },
+///     ReconnectConfig::default(),
+/// )
+/// .await?;
+/// let tools = client.list_all_tools().await?;
+/// ```
+pub struct ReconnectingClient<S: Service<RoleClient>> {
+    state: watch::Receiver<ConnectionState>,
+    subscriptions: Arc<Mutex<BTreeSet<String>>>,
+    pending_calls: PendingCallPolicy,
+    resource_cache: Option<ResourceCache>,
+    monitor: Option<tokio::task::JoinHandle<()>>,
+    cancellation_token: CancellationToken,
+    _marker: std::marker::PhantomData<fn() -> S>,
+}
+
+impl<S: Service<RoleClient>> std::fmt::Debug for ReconnectingClient<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReconnectingClient")
+            .field("state", &*self.state.borrow())
+            .field("pending_calls", &self.pending_calls)
+            .finish()
+    }
+}
+
+impl<S> ReconnectingClient<S>
+where
+    S: Service<RoleClient> + Clone,
+{
+    /// Connect with a transport from `make_transport` and keep reconnecting with it.
+    pub async fn connect<F, Fut, T, TE, E, A>(
+        service: S,
+        make_transport: F,
+        config: ReconnectConfig,
+    ) -> Result<Self, ClientInitializeError>
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, TE>> + Send + 'static,
+        TE: std::fmt::Display,
+        T: IntoTransport<RoleClient, E, A>,
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let cancellation_token = CancellationToken::new();
+        let connector: Connector<S> = {
+            let ct = cancellation_token.clone();
+            let heartbeat = config.heartbeat;
+            let interceptors = config.interceptors.clone();
+            Arc::new(move |service| {
+                let transport = make_transport();
+                let ct = ct.child_token();
+                let interceptors = interceptors.clone();
+                Box::pin(async move {
+                    let transport = transport.await.map_err(|e| {
+                        ClientInitializeError::ConnectionClosed(format!(
+                            "failed to create transport: {e}"
+                        ))
+                    })?;
+                    let running =
+                        serve_client_with_interceptors(service, transport, interceptors, ct)
+                            .await?;
+                    if let Some(heartbeat) = heartbeat {
+                        super::heartbeat::start_heartbeat(&running, heartbeat);
+                    }
+                    Ok(running)
+                })
+            })
+        };
+        let running = connector(service.clone()).await?;
+        let (state_tx, state) = watch::channel(ConnectionState::Connected(running.peer().clone()));
+        let subscriptions = Arc::new(Mutex::new(BTreeSet::new()));
+        let monitor = tokio::spawn(monitor_connection(
+            running,
+            service,
+            connector,
+            config.clone(),
+            state_tx,
+            subscriptions.clone(),
+            cancellation_token.clone(),
+        ));
+        Ok(Self {
+            state,
+            subscriptions,
+            pending_calls: config.pending_calls,
+            resource_cache: config.resource_cache,
+            monitor: Some(monitor),
+            cancellation_token,
+            _marker: std::marker::PhantomData,
+        })
+    }
+}
+
+impl<S: Service<RoleClient>> ReconnectingClient<S> {
+    /// The peer of the current connection.
+    ///
+    /// While reconnecting this waits or fails according to the configured
+    /// [`PendingCallPolicy`].
+    pub async fn peer(&self) -> Result<Peer<RoleClient>, ServiceError> {
+        let mut state = self.state.clone();
+        loop {
+            match &*state.borrow_and_update() {
+                ConnectionState::Connected(peer) if !peer.is_transport_closed() => {
+                    return Ok(peer.clone());
+                }
+                ConnectionState::Closed => return Err(ServiceError::TransportClosed),
+                ConnectionState::Connected(_) | ConnectionState::Reconnecting => {
+                    if self.pending_calls == PendingCallPolicy::FailFast {
+                        return Err(ServiceError::TransportClosed);
+                    }
+                }
+            }
+            state
+                .changed()
+                .await
+                .map_err(|_| ServiceError::TransportClosed)?;
+        }
+    }
+
+    /// Returns true if the client currently has a live connection.
+    pub fn is_connected(&self) -> bool {
+        matches!(
+            &*self.state.borrow(),
+            ConnectionState::Connected(peer) if !peer.is_transport_closed()
+        )
+    }
+
+    pub async fn send_request(&self, request: ClientRequest) -> Result<ServerResult, ServiceError> {
+        self.peer().await?.send_request(request).await
+    }
+
+    pub async fn send_notification(
+        &self,
+        notification: ClientNotification,
+    ) -> Result<(), ServiceError> {
+        self.peer().await?.send_notification(notification).await
+    }
+
+    /// Subscribe to a resource; the subscription is replayed after every reconnect.
+    pub async fn subscribe(&self, params: SubscribeRequestParams) -> Result<(), ServiceError> {
+        let uri = params.uri.clone();
+        self.peer().await?.subscribe(params).await?;
+        self.subscriptions
+            .lock()
+            .expect("subscriptions lock poisoned")
+            .insert(uri);
+        Ok(())
+    }
+
+    pub async fn unsubscribe(&self, params: UnsubscribeRequestParams) -> Result<(), ServiceError> {
+        self.subscriptions
+            .lock()
+            .expect("subscriptions lock poisoned")
+            .remove(&params.uri);
+        self.peer().await?.unsubscribe(params).await
+    }
+
+    method!(peer_req complete CompleteRequest(CompleteRequestParams) => CompleteResult);
+    method!(peer_req get_prompt GetPromptRequest(GetPromptRequestParams) => GetPromptResult);
+    method!(peer_req list_prompts ListPromptsRequest(PaginatedRequestParams)? => ListPromptsResult);
+    method!(peer_req list_resources ListResourcesRequest(PaginatedRequestParams)? => ListResourcesResult);
+    method!(peer_req list_resource_templates ListResourceTemplatesRequest(PaginatedRequestParams)? => ListResourceTemplatesResult);
+    method!(peer_req call_tool CallToolRequest(CallToolRequestParams) => CallToolResult);
+    method!(peer_req list_tools ListToolsRequest(PaginatedRequestParams)? => ListToolsResult);
+
+    method!(peer_not notify_roots_list_changed RootsListChangedNotification);
+
+    /// Read a resource, from the configured [`ResourceCache`] if it holds it.
+    pub async fn read_resource(
+        &self,
+        params: ReadResourceRequestParams,
+    ) -> Result<ReadResourceResult, ServiceError> {
+        let peer = self.peer().await?;
+        match &self.resource_cache {
+            Some(cache) => cache.read_resource(&peer, params).await,
+            None => peer.read_resource(params).await,
+        }
+    }
+
+    /// The cache configured with [`ReconnectConfig::with_resource_cache`].
+    pub fn resource_cache(&self) -> Option<&ResourceCache> {
+        self.resource_cache.as_ref()
+    }
+
+    /// See [`Peer<RoleClient>::list_all_tools`].
+    pub async fn list_all_tools(&self) -> Result<Vec<crate::model::Tool>, ServiceError> {
+        self.peer().await?.list_all_tools().await
+    }
+
+    /// See [`Peer<RoleClient>::list_all_prompts`].
+    pub async fn list_all_prompts(&self) -> Result<Vec<crate::model::Prompt>, ServiceError> {
+        self.peer().await?.list_all_prompts().await
+    }
+
+    /// See [`Peer<RoleClient>::list_all_resources`].
+    pub async fn list_all_resources(&self) -> Result<Vec<crate::model::Resource>, ServiceError> {
+        self.peer().await?.list_all_resources().await
+    }
+
+    /// Stop reconnecting, close the current connection and wait for cleanup.
+    pub async fn close(mut self) -> Result<(), tokio::task::JoinError> {
+        self.cancellation_token.cancel();
+        match self.monitor.take() {
+            Some(monitor) => monitor.await,
+            None => Ok(()),
+        }
+    }
+}
+
+impl<S: Service<RoleClient>> Drop for ReconnectingClient<S> {
+    fn drop(&mut self) {
+        self.cancellation_token.cancel();
+    }
+}
+
+async fn monitor_connection<S>(
+    mut running: RunningService<RoleClient, S>,
+    service: S,
+    connector: Connector<S>,
+    config: ReconnectConfig,
+    state_tx: watch::Sender<ConnectionState>,
+    subscriptions: Arc<Mutex<BTreeSet<String>>>,
+    ct: CancellationToken,
+) where
+    S: Service<RoleClient> + Clone,
+{
+    loop {
+        // The connection shares our cancellation token, so this also returns on close.
+        let quit_reason = running.waiting().await;
+        if ct.is_cancelled() {
+            break;
+        }
+        tracing::warn!(?quit_reason, "connection lost, reconnecting");
+        state_tx.send_replace(ConnectionState::Reconnecting);
+        let mut backoff = config.initial_backoff;
+        let mut attempt = 0;
+        let reconnected = loop {
+            if config.max_attempts.is_some_and(|max| attempt >= max) {
+                break None;
+            }
+            attempt += 1;
+            tokio::select! {
+                _ = tokio::time::sleep(backoff) => {}
+                _ = ct.cancelled() => break None,
+            }
+            match connector(service.clone()).await {
+                Ok(running) => break Some(running),
+                Err(error) => {
+                    tracing::warn!(%error, attempt, "reconnect attempt failed");
+                    backoff = (backoff * 2).min(config.max_backoff);
+                }
+            }
+        };
+        let Some(reconnected) = reconnected else {
+            if !ct.is_cancelled() {
+                tracing::error!(attempt, "giving up reconnecting");
+            }
+            break;
+        };
+        running = reconnected;
+        if let Some(cache) = &config.resource_cache {
+            cache.clear();
+        }
+        let peer = running.peer().clone();
+        let uris = subscriptions
+            .lock()
+            .expect("subscriptions lock poisoned")
+            .clone();
+        for uri in uris {
+            if let Err(error) = peer
+                .subscribe(SubscribeRequestParams::new(uri.clone()))
+                .await
+            {
+                tracing::warn!(%error, %uri, "failed to replay subscription");
+            }
+        }
+        tracing::info!("reconnected");
+        state_tx.send_replace(ConnectionState::Connected(peer));
+    }
+    state_tx.send_replace(ConnectionState::Closed);
+}
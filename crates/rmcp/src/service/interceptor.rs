@@ -0,0 +1,36 @@
+use super::ServiceRole;
+
+/// Hooks that observe or modify the requests a [`Peer`](super::Peer) sends.
+///
+/// On the client side this is `PeerInterceptor<RoleClient>`, which sees every
+/// outgoing [`ClientRequest`](crate::model::ClientRequest) and every
+/// [`ServerResult`](crate::model::ServerResult) received in response.
+/// Register interceptors with
+/// [`serve_client_with_interceptors`](super::serve_client_with_interceptors),
+/// or with [`ReconnectConfig::with_interceptor`](super::ReconnectConfig::with_interceptor)
+/// to keep them across reconnects.
+///
+/// # Example
+/// ```rust,ignore
+/// struct CorrelationId;
+///
+/// impl PeerInterceptor<RoleClient> for CorrelationId {
+///     fn before_request(&self, request: &mut ClientRequest) {
+///         request
+///             .get_meta_mut()
+///             .insert("correlationId".into(), uuid::Uuid::new_v4().to_string().into());
+///     }
+/// }
+/// ```
+pub trait PeerInterceptor<R: ServiceRole>: Send + Sync + 'static {
+    /// Called before a request is sent, after its `_meta` has been filled in.
+    fn before_request(&self, request: &mut R::Req) {
+        let _ = request;
+    }
+    /// Called when a successful response arrives, before it is handed to the caller.
+    fn after_response(&self, response: &R::PeerResp) {
+        let _ = response;
+    }
+}
+
+pub(crate) type Interceptors<R> = std::sync::Arc<[std::sync::Arc<dyn PeerInterceptor<R>>]>;
@@ -0,0 +1,112 @@
+use std::{sync::Arc, time::Duration};
+
+use super::{MaybeSendFuture, NotificationContext, RequestContext, RoleServer, Service};
+use crate::model::{ClientNotification, ClientRequest, ErrorCode, ServerInfo, ServerResult};
+
+/// The outcome of a request observed by [`ServerMetrics`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RequestOutcome {
+    /// The handler returned a result.
+    Success,
+    /// The handler returned an error with the given JSON-RPC error code.
+    Error(ErrorCode),
+    /// The request was cancelled by the client before the handler finished.
+    Cancelled,
+}
+
+/// Hooks for observing requests handled by a server.
+///
+/// Implement this trait to bridge request counts, error rates and latencies to
+/// a metrics backend such as `metrics` or `prometheus`. Every method has a
+/// no-op default, so implementations only override what they need.
+///
+/// The hooks are called inline on the request path and should not block.
+pub trait ServerMetrics: Send + Sync + 'static {
+    /// Called before the handler starts processing a request.
+    fn on_request_start(&self, method: &str) {
+        let _ = method;
+    }
+    /// Called after the handler finished processing a request.
+    fn on_request_end(&self, method: &str, duration: Duration, outcome: RequestOutcome) {
+        let _ = (method, duration, outcome);
+    }
+}
+
+impl std::fmt::Debug for dyn ServerMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("<ServerMetrics>")
+    }
+}
+
+/// A [`ServerMetrics`] implementation that records nothing.
+#[derive(Debug, Clone, Copy, Default)]
+#[expect(clippy::exhaustive_structs, reason = "intentionally exhaustive")]
+pub struct NoopMetrics;
+
+impl ServerMetrics for NoopMetrics {}
+
+/// A server [`Service`] that reports every request to a [`ServerMetrics`].
+///
+/// # Example
+/// ```rust,ignore
+/// use std::sync::Arc;
+/// use rmcp::{ServiceExt, service::MeteredService, transport::stdio};
+///
+/// let server = MeteredService::new(MyHandler, Arc::new(MyMetrics))
+///     .serve(stdio())
+///     .await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct MeteredService<S> {
+    inner: S,
+    metrics: Arc<dyn ServerMetrics>,
+}
+
+impl<S> MeteredService<S> {
+    pub fn new(inner: S, metrics: Arc<dyn ServerMetrics>) -> Self {
+        Self { inner, metrics }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Service<RoleServer>> Service<RoleServer> for MeteredService<S> {
+    async fn handle_request(
+        &self,
+        request: ClientRequest,
+        context: RequestContext<RoleServer>,
+    ) -> Result<ServerResult, crate::ErrorData> {
+        let method = request.method().to_owned();
+        let ct = context.ct.clone();
+        self.metrics.on_request_start(&method);
+        let start = std::time::Instant::now();
+        let result = self.inner.handle_request(request, context).await;
+        let outcome = match &result {
+            _ if ct.is_cancelled() => RequestOutcome::Cancelled,
+            Ok(_) => RequestOutcome::Success,
+            Err(error) => RequestOutcome::Error(error.code),
+        };
+        self.metrics
+            .on_request_end(&method, start.elapsed(), outcome);
+        result
+    }
+
+    fn handle_notification(
+        &self,
+        notification: ClientNotification,
+        context: NotificationContext<RoleServer>,
+    ) -> impl Future<Output = Result<(), crate::ErrorData>> + MaybeSendFuture + '_ {
+        self.inner.handle_notification(notification, context)
+    }
+
+    fn get_info(&self) -> ServerInfo {
+        self.inner.get_info()
+    }
+}
@@ -0,0 +1,56 @@
+//! OpenTelemetry context propagation through the request `_meta` field.
+//!
+//! Outgoing requests carry the current span's context in the reserved SEP-414
+//! keys (`traceparent`, `tracestate`), encoded by the globally installed
+//! [`TextMapPropagator`](opentelemetry::propagation::TextMapPropagator).
+//! Inbound requests carrying those keys get their `mcp.request` span parented
+//! to the remote context.
+//!
+//! Install a propagator such as `opentelemetry_sdk::propagation::TraceContextPropagator`
+//! with [`opentelemetry::global::set_text_map_propagator`] and register a
+//! `tracing_opentelemetry` layer for the context to be propagated.
+
+use opentelemetry::{
+    global,
+    propagation::{Extractor, Injector},
+};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::model::Meta;
+
+impl Injector for Meta {
+    fn set(&mut self, key: &str, value: String) {
+        self.0
+            .insert(key.to_owned(), serde_json::Value::String(value));
+    }
+}
+
+impl Extractor for Meta {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(serde_json::Value::as_str)
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(String::as_str).collect()
+    }
+}
+
+/// Write the current span's context into `meta`, unless the caller already set a `traceparent`.
+pub(crate) fn inject_current_context(meta: &mut Meta) {
+    if meta.get_traceparent().is_some() {
+        return;
+    }
+    let context = tracing::Span::current().context();
+    global::get_text_map_propagator(|propagator| propagator.inject_context(&context, meta));
+}
+
+/// Parent `span` to the remote context carried in `meta`, if any.
+pub(crate) fn set_parent_from_meta(span: &tracing::Span, meta: &Meta) {
+    if meta.get_traceparent().is_none() {
+        return;
+    }
+    let context = global::get_text_map_propagator(|propagator| propagator.extract(meta));
+    if let Err(error) = span.set_parent(context) {
+        tracing::debug!(%error, "failed to set remote trace parent");
+    }
+}
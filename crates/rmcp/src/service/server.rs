@@ -86,6 +86,9 @@ pub enum ServerInitializeError {
 
     #[error("Cancelled")]
     Cancelled,
+
+    #[error("invalid logging throttle: {0}")]
+    InvalidLoggingThrottle(#[from] LoggingThrottleConfigError),
 }
 
 impl ServerInitializeError {
@@ -148,13 +151,47 @@ pub async fn serve_server_with_ct<S, T, E, A>(
     transport: T,
     ct: CancellationToken,
 ) -> Result<RunningService<RoleServer, S>, ServerInitializeError>
+where
+    S: Service<RoleServer>,
+    T: IntoTransport<RoleServer, E, A>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    serve_server_with_config(service, transport, None, ct).await
+}
+
+/// Serve a server whose [`Peer::notify_logging_message`] goes through a
+/// [`LoggingThrottle`] built from `logging_throttle`.
+///
+/// The throttle also applies the client's `logging/setLevel` requests, and
+/// repeats it is still holding back are reported before the service closes.
+pub async fn serve_server_with_logging_throttle<S, T, E, A>(
+    service: S,
+    transport: T,
+    logging_throttle: LoggingThrottleConfig,
+    ct: CancellationToken,
+) -> Result<RunningService<RoleServer, S>, ServerInitializeError>
+where
+    S: Service<RoleServer>,
+    T: IntoTransport<RoleServer, E, A>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let logging_throttle = LoggingThrottle::new(logging_throttle)?;
+    serve_server_with_config(service, transport, Some(logging_throttle), ct).await
+}
+
+async fn serve_server_with_config<S, T, E, A>(
+    service: S,
+    transport: T,
+    logging_throttle: Option<LoggingThrottle>,
+    ct: CancellationToken,
+) -> Result<RunningService<RoleServer, S>, ServerInitializeError>
 where
     S: Service<RoleServer>,
     T: IntoTransport<RoleServer, E, A>,
     E: std::error::Error + Send + Sync + 'static,
 {
     tokio::select! {
-        result = serve_server_with_ct_inner(service, transport.into_transport(), ct.clone()) => { result }
+        result = serve_server_with_ct_inner(service, transport.into_transport(), logging_throttle, ct.clone()) => { result }
         _ = ct.cancelled() => {
             Err(ServerInitializeError::Cancelled)
         }
@@ -181,6 +218,7 @@ pub(crate) fn negotiate_protocol_version(
 async fn serve_server_with_ct_inner<S, T>(
     service: S,
     transport: T,
+    logging_throttle: Option<LoggingThrottle>,
     ct: CancellationToken,
 ) -> Result<RunningService<RoleServer, S>, ServerInitializeError>
 where
@@ -225,7 +263,10 @@ where
             ClientJsonRpcMessage::request(request, id),
         )));
     };
-    let (peer, peer_rx) = Peer::new(id_provider, Some(peer_info.params.clone()));
+    let (mut peer, peer_rx) = Peer::new(id_provider, Some(peer_info.params.clone()));
+    if let Some(logging_throttle) = logging_throttle {
+        peer.set_logging_throttle(logging_throttle);
+    }
     let context = RequestContext {
         ct: ct.child_token(),
         id: id.clone(),
@@ -479,19 +520,67 @@ impl Peer<RoleServer> {
 
     method!(peer_not notify_cancelled CancelledNotification(CancelledNotificationParam));
     method!(peer_not notify_progress ProgressNotification(ProgressNotificationParam));
-    method!(
-        #[deprecated(
-            since = "1.8.0",
-            note = "Logging is deprecated by SEP-2577 and will be removed in a future release. See https://github.com/modelcontextprotocol/modelcontextprotocol/pull/2577"
-        )]
-        peer_not notify_logging_message LoggingMessageNotification(LoggingMessageNotificationParam)
-    );
+    /// Send a `notifications/message` to the client.
+    ///
+    /// If the server was started with
+    /// [`serve_server_with_logging_throttle`], the message goes through its
+    /// [`LoggingThrottle`] and may be held back.
+    #[deprecated(
+        since = "1.8.0",
+        note = "Logging is deprecated by SEP-2577 and will be removed in a future release. See https://github.com/modelcontextprotocol/modelcontextprotocol/pull/2577"
+    )]
+    pub async fn notify_logging_message(
+        &self,
+        params: LoggingMessageNotificationParam,
+    ) -> Result<(), ServiceError> {
+        match &self.logging_throttle {
+            Some(throttle) => throttle.log(self, params).await.map(|_| ()),
+            None => self.send_logging_message(params).await,
+        }
+    }
+
+    /// Send a `notifications/message` without going through the throttle.
+    pub(crate) async fn send_logging_message(
+        &self,
+        params: LoggingMessageNotificationParam,
+    ) -> Result<(), ServiceError> {
+        self.send_notification(ServerNotification::LoggingMessageNotification(
+            LoggingMessageNotification::new(params),
+        ))
+        .await
+    }
+
+    /// The throttle applied to [`notify_logging_message`](Self::notify_logging_message),
+    /// if the server was started with one.
+    pub fn logging_throttle(&self) -> Option<&LoggingThrottle> {
+        self.logging_throttle.as_deref()
+    }
+
+    pub(crate) fn set_logging_throttle(&mut self, throttle: LoggingThrottle) {
+        let throttle = Arc::new(throttle);
+        let pending = throttle.clone();
+        self.pending_notifications = Some(Arc::new(move || {
+            pending
+                .take_summary()
+                .map(|params| {
+                    ServerNotification::LoggingMessageNotification(LoggingMessageNotification::new(
+                        params,
+                    ))
+                })
+                .into_iter()
+                .collect()
+        }));
+        self.logging_throttle = Some(throttle);
+    }
     method!(peer_not notify_resource_updated ResourceUpdatedNotification(ResourceUpdatedNotificationParam));
     method!(peer_not notify_resource_list_changed ResourceListChangedNotification);
     method!(peer_not notify_tool_list_changed ToolListChangedNotification);
     method!(peer_not notify_prompt_list_changed PromptListChangedNotification);
 }
 
+mod logging_throttle;
+pub use logging_throttle::*;
+
 // =============================================================================
 // ELICITATION CONVENIENCE METHODS
 // These methods are specific to server role and provide typed elicitation functionality
@@ -532,6 +621,21 @@ pub enum ElicitationError {
     /// Client does not support elicitation capability
     #[error("Client does not support elicitation - capability not declared during initialization")]
     CapabilityNotSupported,
+
+    /// The user did not respond before the elicitation expired.
+    /// A `notifications/cancelled` has been sent for the request.
+    #[error("User did not respond within {}s; the elicitation was cancelled", timeout.as_secs_f64())]
+    TimedOut { timeout: std::time::Duration },
+}
+
+#[cfg(feature = "elicitation")]
+impl ElicitationError {
+    fn from_request_error(error: ServiceError) -> Self {
+        match error {
+            ServiceError::Timeout { timeout } => ElicitationError::TimedOut { timeout },
+            error => ElicitationError::Service(error),
+        }
+    }
 }
 
 /// Marker trait to ensure that elicitation types generate object-type JSON schemas.
@@ -713,14 +817,15 @@ impl Peer<RoleServer> {
     /// Request typed data from the user with custom timeout.
     ///
     /// Same as `elicit()` but allows specifying a custom timeout for the request.
-    /// If the user doesn't respond within the timeout, the request will be cancelled.
+    /// If the user doesn't respond within the timeout, the request is cancelled with
+    /// `notifications/cancelled` and [`ElicitationError::TimedOut`] is returned.
     ///
     /// # Arguments
     /// * `message` - The prompt message for the user
     /// * `timeout` - Optional timeout duration. If None, uses default timeout behavior
     ///
     /// # Returns
-    /// Same as `elicit()` but may also return `ServiceError::Timeout` if timeout expires
+    /// Same as `elicit()` but may also return `ElicitationError::TimedOut` if timeout expires
     ///
     /// # Example
     /// ```rust,no_run
@@ -755,7 +860,7 @@ impl Peer<RoleServer> {
     ///         println!("User cancelled/dismissed");
     ///         // Handle cancellation
     ///     }
-    ///     Err(ElicitationError::Service(ServiceError::Timeout { .. })) => {
+    ///     Err(ElicitationError::TimedOut { .. }) => {
     ///         println!("User didn't respond in time");
     ///     }
     ///     Err(e) => return Err(e.into()),
@@ -801,7 +906,8 @@ impl Peer<RoleServer> {
                 },
                 timeout,
             )
-            .await?;
+            .await
+            .map_err(ElicitationError::from_request_error)?;
 
         match response.action {
             crate::model::ElicitationAction::Accept => {
@@ -881,6 +987,7 @@ impl Peer<RoleServer> {
     /// # Returns
     /// * `Ok(action)` indicating the user's response action
     /// * `Err(ElicitationError::CapabilityNotSupported)` if client does not support elicitation via URL
+    /// * `Err(ElicitationError::TimedOut { .. })` if the user did not respond in time
     /// * `Err(ElicitationError::Service(_))` if the underlying service call failed
     /// # Example
     /// ```rust,no_run
@@ -935,7 +1042,8 @@ impl Peer<RoleServer> {
                 },
                 timeout,
             )
-            .await?
+            .await
+            .map_err(ElicitationError::from_request_error)?
             .action;
         Ok(action)
     }
@@ -0,0 +1,326 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use super::*;
+use crate::model::LoggingLevel;
+
+/// Limits applied by a [`LoggingThrottle`].
+///
+/// Build one with [`new`](Self::new) and the `with_*` methods; the throttle
+/// checks it with [`validate`](Self::validate) when it is created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LoggingThrottleConfig {
+    /// Messages sent per second on average. Up to this many may be sent in a
+    /// burst; further messages are dropped until the allowance refills.
+    pub max_per_second: u32,
+    /// A message identical to the previous one (same level, logger and data)
+    /// arriving within this window is folded into a repeat count instead of
+    /// being sent. `None` sends repeats like any other message.
+    pub coalesce_window: Option<Duration>,
+    /// Level in effect until the client sends `logging/setLevel`.
+    pub initial_level: LoggingLevel,
+}
+
+impl LoggingThrottleConfig {
+    /// Send at most `max_per_second` messages per second, folding repeats
+    /// within one second, and everything from `debug` up until the client
+    /// picks a level.
+    pub fn new(max_per_second: u32) -> Self {
+        Self {
+            max_per_second,
+            coalesce_window: Some(Duration::from_secs(1)),
+            initial_level: LoggingLevel::Debug,
+        }
+    }
+
+    pub fn with_coalesce_window(mut self, window: Option<Duration>) -> Self {
+        self.coalesce_window = window;
+        self
+    }
+
+    pub fn with_initial_level(mut self, level: LoggingLevel) -> Self {
+        self.initial_level = level;
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), LoggingThrottleConfigError> {
+        if self.max_per_second == 0 {
+            return Err(LoggingThrottleConfigError::ZeroRate);
+        }
+        if self.coalesce_window == Some(Duration::ZERO) {
+            return Err(LoggingThrottleConfigError::ZeroCoalesceWindow);
+        }
+        Ok(())
+    }
+}
+
+/// Why a [`LoggingThrottleConfig`] was rejected.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LoggingThrottleConfigError {
+    #[error("max_per_second must be greater than zero")]
+    ZeroRate,
+    #[error("coalesce_window must be non-zero; use None to disable coalescing")]
+    ZeroCoalesceWindow,
+}
+
+/// What [`LoggingThrottle::log`] did with a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LogDisposition {
+    /// The message was sent.
+    Sent,
+    /// The message is less severe than the level the client asked for.
+    BelowLevel,
+    /// The message repeats the previous one and was counted instead of sent.
+    Coalesced,
+    /// The rate limit was exhausted and the message was discarded.
+    Dropped,
+}
+
+/// Guards the `notifications/message` path against floods.
+///
+/// Route log messages through [`log`](Self::log) instead of calling
+/// [`Peer::notify_logging_message`] directly, and forward the client's
+/// `logging/setLevel` requests to [`set_level`](Self::set_level). The
+/// throttle then:
+///
+/// - skips messages below the level the client asked for,
+/// - folds identical consecutive messages into a single follow-up
+///   notification carrying `{"repeated": n, "data": ...}`, sent before the
+///   next message that goes out or once the coalesce window closes, and
+/// - drops messages beyond [`LoggingThrottleConfig::max_per_second`].
+///
+/// Repeats still pending are also reported when the throttle is dropped or
+/// [`flush`](Self::flush)ed.
+///
+/// Keep one throttle per connection. The simplest way is to let the server
+/// own it: [`serve_server_with_logging_throttle`](super::serve_server_with_logging_throttle)
+/// applies it to every [`Peer::notify_logging_message`] call and to
+/// `logging/setLevel` requests. A throttle held by the handler works too:
+///
+/// # Example
+/// ```rust,ignore
+/// impl ServerHandler for MyServer {
+///     async fn set_level(
+///         &self,
+///         request: SetLevelRequestParams,
+///         _context: RequestContext<RoleServer>,
+///     ) -> Result<(), ErrorData> {
+///         self.logs.set_level(request.level);
+///         Ok(())
+///     }
+/// }
+///
+/// // elsewhere, with `logs: LoggingThrottle::new(LoggingThrottleConfig::new(20))?`
+/// self.logs
+///     .log(&context.peer, LoggingMessageNotificationParam::new(LoggingLevel::Info, data))
+///     .await?;
+/// ```
+#[derive(Debug)]
+pub struct LoggingThrottle {
+    config: LoggingThrottleConfig,
+    state: Arc<Mutex<ThrottleState>>,
+}
+
+#[derive(Debug)]
+struct ThrottleState {
+    level: LoggingLevel,
+    tokens: f64,
+    refilled: Instant,
+    last: Option<LastMessage>,
+    dropped: u64,
+    flush: Option<PendingFlush>,
+}
+
+impl ThrottleState {
+    /// Take the summary of repeats folded so far, cancelling its timer.
+    fn take_summary(&mut self) -> Option<LoggingMessageNotificationParam> {
+        if let Some(flush) = self.flush.take() {
+            flush.task.abort();
+        }
+        let last = self.last.as_mut()?;
+        let summary = last.summary();
+        last.repeats = 0;
+        summary
+    }
+}
+
+/// The timer that reports folded repeats once the coalesce window closes.
+#[derive(Debug)]
+struct PendingFlush {
+    task: tokio::task::AbortHandle,
+    /// Report right away instead of waiting for the window to close.
+    now: CancellationToken,
+}
+
+#[derive(Debug)]
+struct LastMessage {
+    message: LoggingMessageNotificationParam,
+    sent_at: Instant,
+    repeats: u64,
+}
+
+impl LastMessage {
+    fn repeated_by(&self, message: &LoggingMessageNotificationParam) -> bool {
+        self.message.level == message.level
+            && self.message.logger == message.logger
+            && self.message.data == message.data
+    }
+
+    /// The notification reporting folded repeats, if there were any.
+    fn summary(&self) -> Option<LoggingMessageNotificationParam> {
+        (self.repeats > 0).then(|| LoggingMessageNotificationParam {
+            level: self.message.level,
+            logger: self.message.logger.clone(),
+            data: serde_json::json!({
+                "repeated": self.repeats,
+                "data": self.message.data,
+            }),
+            meta: None,
+        })
+    }
+}
+
+impl LoggingThrottle {
+    pub fn new(config: LoggingThrottleConfig) -> Result<Self, LoggingThrottleConfigError> {
+        config.validate()?;
+        Ok(Self {
+            config,
+            state: Arc::new(Mutex::new(ThrottleState {
+                level: config.initial_level,
+                tokens: config.max_per_second as f64,
+                refilled: Instant::now(),
+                last: None,
+                dropped: 0,
+                flush: None,
+            })),
+        })
+    }
+
+    /// Apply the level the client requested with `logging/setLevel`.
+    pub fn set_level(&self, level: LoggingLevel) {
+        self.lock().level = level;
+    }
+
+    /// The least severe level currently sent.
+    pub fn level(&self) -> LoggingLevel {
+        self.lock().level
+    }
+
+    /// How many messages the rate limit has discarded so far.
+    pub fn dropped(&self) -> u64 {
+        self.lock().dropped
+    }
+
+    /// Send `message` to `peer` unless the level, coalescing or rate limit
+    /// holds it back.
+    pub async fn log(
+        &self,
+        peer: &Peer<RoleServer>,
+        message: LoggingMessageNotificationParam,
+    ) -> Result<LogDisposition, ServiceError> {
+        let summary = {
+            let mut state = self.lock();
+            if message.level < state.level {
+                return Ok(LogDisposition::BelowLevel);
+            }
+            let now = Instant::now();
+            if let (Some(window), Some(last)) = (self.config.coalesce_window, &mut state.last) {
+                if last.repeated_by(&message) && now.duration_since(last.sent_at) < window {
+                    last.repeats += 1;
+                    let window_closes = last.sent_at + window;
+                    if state.flush.is_none() {
+                        state.flush = Some(self.schedule_flush(peer, window_closes));
+                    }
+                    return Ok(LogDisposition::Coalesced);
+                }
+            }
+            let rate = self.config.max_per_second as f64;
+            let elapsed = now.duration_since(state.refilled).as_secs_f64();
+            state.tokens = (state.tokens + elapsed * rate).min(rate);
+            state.refilled = now;
+            if state.tokens < 1.0 {
+                state.dropped += 1;
+                return Ok(LogDisposition::Dropped);
+            }
+            state.tokens -= 1.0;
+            let summary = state.take_summary();
+            state.last = Some(LastMessage {
+                message: message.clone(),
+                sent_at: now,
+                repeats: 0,
+            });
+            summary
+        };
+        if let Some(summary) = summary {
+            peer.send_logging_message(summary).await?;
+        }
+        peer.send_logging_message(message).await?;
+        Ok(LogDisposition::Sent)
+    }
+
+    /// Report repeats folded so far without waiting for the coalesce window
+    /// to close.
+    pub async fn flush(&self, peer: &Peer<RoleServer>) -> Result<(), ServiceError> {
+        let summary = self.lock().take_summary();
+        if let Some(summary) = summary {
+            peer.send_logging_message(summary).await?;
+        }
+        Ok(())
+    }
+
+    /// Take the pending repeat summary, for sending while the service shuts
+    /// down.
+    pub(crate) fn take_summary(&self) -> Option<LoggingMessageNotificationParam> {
+        self.lock().take_summary()
+    }
+
+    fn schedule_flush(&self, peer: &Peer<RoleServer>, window_closes: Instant) -> PendingFlush {
+        let now = CancellationToken::new();
+        let state = self.state.clone();
+        let peer = peer.clone();
+        let task = tokio::spawn({
+            let now = now.clone();
+            async move {
+                tokio::select! {
+                    _ = tokio::time::sleep_until(window_closes.into()) => {}
+                    _ = now.cancelled() => {}
+                }
+                let summary = {
+                    let mut state = state.lock().expect("logging throttle lock poisoned");
+                    // Detach first so taking the summary doesn't abort this task.
+                    state.flush = None;
+                    state.take_summary()
+                };
+                if let Some(summary) = summary {
+                    if let Err(error) = peer.send_logging_message(summary).await {
+                        tracing::debug!(%error, "failed to report coalesced log messages");
+                    }
+                }
+            }
+        });
+        PendingFlush {
+            task: task.abort_handle(),
+            now,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ThrottleState> {
+        self.state.lock().expect("logging throttle lock poisoned")
+    }
+}
+
+impl Drop for LoggingThrottle {
+    fn drop(&mut self) {
+        // Let the pending timer report the folded repeats right away.
+        if let Ok(state) = self.state.lock() {
+            if let Some(flush) = &state.flush {
+                flush.now.cancel();
+            }
+        }
+    }
+}
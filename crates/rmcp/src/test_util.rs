@@ -0,0 +1,318 @@
+//! Canned MCP peers for testing code built on this crate.
+//!
+//! [`MockServer`] answers `tools/*`, `resources/*` and `prompts/*` requests
+//! from responses registered up front, and [`MockClient`] answers sampling and
+//! elicitation requests the same way. Both record the requests they receive so
+//! tests can assert on them. [`connect`] runs a server and a client against
+//! each other over an in-memory pipe.
+//!
+//! ```rust
+//! # #![allow(deprecated)]
+//! use rmcp::{
+//!     model::{CallToolRequestParams, CallToolResult, ContentBlock, Tool},
+//!     test_util::{MockClient, MockServer, connect},
+//! };
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # tokio::task::LocalSet::new().run_until(async {
+//! let server = MockServer::new().with_tool(
+//!     Tool::new("echo", "Echo the input", rmcp::model::JsonObject::new()),
+//!     CallToolResult::success(vec![ContentBlock::text("hello")]),
+//! );
+//! let (_server, client) = connect(server.clone(), MockClient::new()).await?;
+//!
+//! let result = client.call_tool(CallToolRequestParams::new("echo")).await?;
+//! assert_eq!(result.content[0].as_text().unwrap().text, "hello");
+//! assert_eq!(server.tool_calls()[0].name, "echo");
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! # }).await
+//! # }
+//! ```
+// Sampling is SEP-2577-deprecated; the mock client still has to answer it.
+#![expect(deprecated)]
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    ClientHandler, ErrorData, RoleClient, RoleServer, ServerHandler, ServiceExt,
+    model::*,
+    service::{ClientInitializeError, RequestContext, RunningService, ServerInitializeError},
+};
+
+/// A [`ServerHandler`] that serves canned tools, resources and prompts.
+///
+/// Calling an unregistered tool, resource or prompt fails with the matching
+/// "not found" error. Clones share the recorded tool calls.
+#[derive(Debug, Clone, Default)]
+pub struct MockServer {
+    tools: Vec<(Tool, Result<CallToolResult, ErrorData>)>,
+    resources: Vec<(Resource, Vec<ResourceContents>)>,
+    prompts: Vec<(Prompt, GetPromptResult)>,
+    tool_calls: Arc<Mutex<Vec<CallToolRequestParams>>>,
+}
+
+impl MockServer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answer calls to `tool` with `result`.
+    pub fn with_tool(self, tool: Tool, result: CallToolResult) -> Self {
+        self.with_tool_response(tool, Ok(result))
+    }
+
+    /// Fail calls to `tool` with `error`.
+    pub fn with_tool_error(self, tool: Tool, error: ErrorData) -> Self {
+        self.with_tool_response(tool, Err(error))
+    }
+
+    fn with_tool_response(
+        mut self,
+        tool: Tool,
+        response: Result<CallToolResult, ErrorData>,
+    ) -> Self {
+        self.tools.retain(|(t, _)| t.name != tool.name);
+        self.tools.push((tool, response));
+        self
+    }
+
+    /// Answer reads of `resource` with `contents`.
+    pub fn with_resource(mut self, resource: Resource, contents: Vec<ResourceContents>) -> Self {
+        self.resources.retain(|(r, _)| r.uri != resource.uri);
+        self.resources.push((resource, contents));
+        self
+    }
+
+    /// Answer `prompts/get` for `prompt` with `result`.
+    pub fn with_prompt(mut self, prompt: Prompt, result: GetPromptResult) -> Self {
+        self.prompts.retain(|(p, _)| p.name != prompt.name);
+        self.prompts.push((prompt, result));
+        self
+    }
+
+    /// Every `tools/call` request received so far, in order.
+    pub fn tool_calls(&self) -> Vec<CallToolRequestParams> {
+        self.tool_calls
+            .lock()
+            .expect("tool calls lock poisoned")
+            .clone()
+    }
+}
+
+impl ServerHandler for MockServer {
+    fn get_info(&self) -> ServerInfo {
+        let mut capabilities = ServerCapabilities::default();
+        if !self.tools.is_empty() {
+            capabilities.tools = Some(ToolsCapability::default());
+        }
+        if !self.resources.is_empty() {
+            capabilities.resources = Some(ResourcesCapability::default());
+        }
+        if !self.prompts.is_empty() {
+            capabilities.prompts = Some(PromptsCapability::default());
+        }
+        ServerInfo::new(capabilities).with_server_info(Implementation::new(
+            "rmcp-mock-server",
+            env!("CARGO_PKG_VERSION"),
+        ))
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, ErrorData> {
+        Ok(ListToolsResult::with_all_items(
+            self.tools.iter().map(|(tool, _)| tool.clone()).collect(),
+        ))
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let name = request.name.clone();
+        self.tool_calls
+            .lock()
+            .expect("tool calls lock poisoned")
+            .push(request);
+        self.tools
+            .iter()
+            .find(|(tool, _)| tool.name == name)
+            .map(|(_, response)| response.clone())
+            .unwrap_or_else(|| Err(ErrorData::tool_not_found(name)))
+    }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, ErrorData> {
+        Ok(ListResourcesResult::with_all_items(
+            self.resources
+                .iter()
+                .map(|(resource, _)| resource.clone())
+                .collect(),
+        ))
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, ErrorData> {
+        self.resources
+            .iter()
+            .find(|(resource, _)| resource.uri == request.uri)
+            .map(|(_, contents)| ReadResourceResult::new(contents.clone()))
+            .ok_or_else(|| ErrorData::resource_not_found_for(request.uri))
+    }
+
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, ErrorData> {
+        Ok(ListPromptsResult::with_all_items(
+            self.prompts
+                .iter()
+                .map(|(prompt, _)| prompt.clone())
+                .collect(),
+        ))
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, ErrorData> {
+        self.prompts
+            .iter()
+            .find(|(prompt, _)| prompt.name == request.name)
+            .map(|(_, result)| result.clone())
+            .ok_or_else(|| ErrorData::prompt_not_found(request.name))
+    }
+}
+
+/// A [`ClientHandler`] that answers sampling and elicitation with canned results.
+///
+/// A capability is only advertised once a response for it is registered;
+/// otherwise the request gets the default [`ClientHandler`] behavior. Clones
+/// share the recorded requests.
+#[derive(Debug, Clone, Default)]
+pub struct MockClient {
+    sampling_response: Option<CreateMessageResult>,
+    elicitation_response: Option<ElicitResult>,
+    sampling_requests: Arc<Mutex<Vec<CreateMessageRequestParams>>>,
+    elicitation_requests: Arc<Mutex<Vec<ElicitRequestParams>>>,
+}
+
+impl MockClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answer every `sampling/createMessage` request with `result`.
+    pub fn with_sampling_response(mut self, result: CreateMessageResult) -> Self {
+        self.sampling_response = Some(result);
+        self
+    }
+
+    /// Answer every `elicitation/create` request with `result`.
+    pub fn with_elicitation_response(mut self, result: ElicitResult) -> Self {
+        self.elicitation_response = Some(result);
+        self
+    }
+
+    /// Every sampling request received so far, in order.
+    pub fn sampling_requests(&self) -> Vec<CreateMessageRequestParams> {
+        self.sampling_requests
+            .lock()
+            .expect("sampling requests lock poisoned")
+            .clone()
+    }
+
+    /// Every elicitation request received so far, in order.
+    pub fn elicitation_requests(&self) -> Vec<ElicitRequestParams> {
+        self.elicitation_requests
+            .lock()
+            .expect("elicitation requests lock poisoned")
+            .clone()
+    }
+}
+
+impl ClientHandler for MockClient {
+    fn get_info(&self) -> ClientInfo {
+        let mut capabilities = ClientCapabilities::default();
+        if self.sampling_response.is_some() {
+            capabilities.sampling = Some(SamplingCapability::default());
+        }
+        if self.elicitation_response.is_some() {
+            capabilities.elicitation =
+                Some(ElicitationCapability::new().with_form(FormElicitationCapability::default()));
+        }
+        ClientInfo::new(
+            capabilities,
+            Implementation::new("rmcp-mock-client", env!("CARGO_PKG_VERSION")),
+        )
+    }
+
+    async fn create_message(
+        &self,
+        params: CreateMessageRequestParams,
+        context: RequestContext<RoleClient>,
+    ) -> Result<CreateMessageResult, ErrorData> {
+        self.sampling_requests
+            .lock()
+            .expect("sampling requests lock poisoned")
+            .push(params.clone());
+        match &self.sampling_response {
+            Some(result) => Ok(result.clone()),
+            None => ClientHandler::create_message(&(), params, context).await,
+        }
+    }
+
+    async fn create_elicitation(
+        &self,
+        request: ElicitRequestParams,
+        context: RequestContext<RoleClient>,
+    ) -> Result<ElicitResult, ErrorData> {
+        self.elicitation_requests
+            .lock()
+            .expect("elicitation requests lock poisoned")
+            .push(request.clone());
+        match &self.elicitation_response {
+            Some(result) => Ok(result.clone()),
+            None => ClientHandler::create_elicitation(&(), request, context).await,
+        }
+    }
+}
+
+/// Why [`connect`] failed.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ConnectError {
+    #[error("server failed to initialize: {0}")]
+    Server(#[from] ServerInitializeError),
+    #[error("client failed to initialize: {0}")]
+    Client(#[from] ClientInitializeError),
+}
+
+/// Serve `server` and `client` to each other over an in-memory pipe and
+/// complete the initialization handshake.
+pub async fn connect<S, C>(
+    server: S,
+    client: C,
+) -> Result<(RunningService<RoleServer, S>, RunningService<RoleClient, C>), ConnectError>
+where
+    S: ServerHandler,
+    C: ClientHandler,
+{
+    let (server_transport, client_transport) = tokio::io::duplex(64 * 1024);
+    let (server, client) = tokio::join!(
+        server.serve(server_transport),
+        client.serve(client_transport)
+    );
+    Ok((server?, client?))
+}
@@ -27,6 +27,13 @@
 //!
 //! This could be very helpful when you want to create a transport from a duplex object stream, such as a websocket connection.
 //!
+//! ### [Observed Transport](`observer::ObservedTransport`)
+//! You need to enable `transport-observer` feature to use this transport.
+//!
+//! This transport wraps another transport and reports a copy of every message it sends or receives, which is useful for protocol debugging tools.
+//!
+//! The same feature provides [`record::SessionRecorder`], which writes these messages to a log, and [`record::ReplayTransport`], which plays a recorded server back to a client.
+//!
 //! ## [IntoTransport](`IntoTransport`) trait
 //! [`IntoTransport`] is a helper trait that implicitly convert a type into a transport type.
 //!
@@ -73,6 +80,11 @@ use crate::service::{RxJsonRpcMessage, ServiceRole, TxJsonRpcMessage};
 
 pub mod sink_stream;
 
+#[cfg(feature = "transport-observer")]
+pub mod observer;
+#[cfg(feature = "transport-observer")]
+pub mod record;
+
 #[cfg(feature = "transport-async-rw")]
 pub mod async_rw;
 
@@ -93,6 +105,20 @@ pub mod io;
 #[cfg(feature = "transport-io")]
 pub use io::stdio;
 
+#[cfg(all(unix, feature = "transport-unix"))]
+pub mod unix_socket;
+#[cfg(all(unix, feature = "transport-unix", feature = "client"))]
+pub use unix_socket::UnixSocketClientTransport;
+#[cfg(all(unix, feature = "transport-unix"))]
+pub use unix_socket::{UnixSocketServerTransport, UnixSocketTransport};
+
+#[cfg(all(windows, feature = "transport-named-pipe"))]
+pub mod named_pipe;
+#[cfg(all(windows, feature = "transport-named-pipe", feature = "client"))]
+pub use named_pipe::NamedPipeClientTransport;
+#[cfg(all(windows, feature = "transport-named-pipe"))]
+pub use named_pipe::{NamedPipeServerTransport, NamedPipeTransport};
+
 #[cfg(feature = "auth")]
 pub mod auth;
 #[cfg(feature = "auth-client-credentials-jwt")]
@@ -144,6 +170,14 @@ where
     /// Receive a message from the transport, this operation is sequential.
     fn receive(&mut self) -> impl Future<Output = Option<RxJsonRpcMessage<R>>> + Send;
 
+    /// Take the error that ended the receive stream, if the transport recorded one.
+    ///
+    /// Called after [`Transport::receive`] returns `None`, so the caller can report
+    /// why the stream ended instead of a bare "connection closed".
+    fn take_error(&mut self) -> Option<Self::Error> {
+        None
+    }
+
     /// Close the transport
     fn close(&mut self) -> impl Future<Output = Result<(), Self::Error>> + Send;
 }
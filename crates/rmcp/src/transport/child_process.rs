@@ -1,4 +1,4 @@
-use std::process::Stdio;
+use std::{collections::HashMap, ffi::OsStr, path::PathBuf, process::Stdio, time::Duration};
 
 use futures::future::Future;
 use process_wrap::tokio::{ChildWrapper, CommandWrap};
@@ -39,6 +39,16 @@ fn child_process(mut child: Box<dyn ChildWrapper>) -> std::io::Result<ChildProce
 pub struct TokioChildProcess {
     child: ChildWithCleanup,
     transport: AsyncRwTransport<RoleClient, ChildStdout, ChildStdin>,
+    startup: Option<Startup>,
+    startup_error: Option<std::io::Error>,
+}
+
+/// Startup state, kept until the child sends its first message.
+struct Startup {
+    server_name: String,
+    current_dir: PathBuf,
+    timeout: Option<Duration>,
+    deadline: Option<tokio::time::Instant>,
 }
 
 pub struct ChildWithCleanup {
@@ -97,6 +107,32 @@ impl TokioChildProcess {
         TokioChildProcessBuilder::new(command)
     }
 
+    /// Spawn a Python server published on PyPI with `uvx --quiet <package> [args..]`.
+    ///
+    /// The child inherits the current environment, so `uv` configuration
+    /// (index URLs, cache dir, ...) is passed through.
+    pub fn uvx(package: impl AsRef<OsStr>, args: &[&str]) -> std::io::Result<Self> {
+        Self::new(uvx_command(package, args))
+    }
+
+    /// Spawn a Node server published on npm with `npx --yes --quiet <package> [args..]`.
+    ///
+    /// `--yes` skips the install prompt, which would otherwise block on stdin.
+    /// The child inherits the current environment, so npm configuration
+    /// (registry, proxy, ...) is passed through.
+    pub fn npx(package: impl AsRef<OsStr>, args: &[&str]) -> std::io::Result<Self> {
+        Self::new(npx_command(package, args))
+    }
+
+    /// Spawn a containerized server with `docker run -i --rm <image> [args..]`.
+    ///
+    /// `args` are passed to the container, not to `docker run`. The container
+    /// does not see the parent's environment; for `docker run` options such
+    /// as `-e` or `-v`, build the command yourself and use [`new`](Self::new).
+    pub fn docker(image: impl AsRef<OsStr>, args: &[&str]) -> std::io::Result<Self> {
+        Self::new(docker_command(image, args))
+    }
+
     /// Get the process ID of the child process.
     pub fn id(&self) -> Option<u32> {
         self.child.inner.as_ref()?.id()
@@ -135,6 +171,49 @@ impl TokioChildProcess {
         Ok(())
     }
 
+    /// Record why the child closed its stdout before sending anything.
+    async fn report_startup_failure(&mut self, startup: Startup) {
+        let server = startup.server_name;
+        let dir = startup.current_dir.display();
+        let status = match self.child.inner.as_mut() {
+            Some(child) => tokio::time::timeout(Duration::from_millis(100), child.wait())
+                .await
+                .ok()
+                .and_then(Result::ok),
+            None => None,
+        };
+        let message = match status {
+            Some(status) => format!(
+                "child process `{server}` exited with {status} before sending any message (working directory: {dir})"
+            ),
+            None => format!(
+                "child process `{server}` closed stdout before sending any message (working directory: {dir})"
+            ),
+        };
+        tracing::error!("{message}");
+        self.startup_error = Some(std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            message,
+        ));
+    }
+
+    /// Kill a child that sent nothing within its startup timeout, and record why.
+    async fn report_startup_timeout(&mut self, startup: Startup) {
+        let server = startup.server_name;
+        let dir = startup.current_dir.display();
+        let timeout = startup.timeout.unwrap_or_default();
+        let message = format!(
+            "child process `{server}` sent nothing within {timeout:?} of starting (working directory: {dir})"
+        );
+        tracing::error!("{message}, killing it");
+        if let Some(mut child) = self.child.inner.take() {
+            if let Err(e) = Box::into_pin(child.kill()).await {
+                tracing::warn!("Error killing child process: {}", e);
+            }
+        }
+        self.startup_error = Some(std::io::Error::new(std::io::ErrorKind::TimedOut, message));
+    }
+
     /// Take ownership of the inner child process
     pub fn into_inner(mut self) -> Option<Box<dyn ChildWrapper>> {
         self.child.inner.take()
@@ -150,12 +229,17 @@ impl TokioChildProcess {
     }
 }
 
-/// Builder for `TokioChildProcess` allowing custom `Stdio` configuration.
+/// Builder for `TokioChildProcess` allowing custom `Stdio` and environment configuration.
 pub struct TokioChildProcessBuilder {
     cmd: CommandWrap,
     stdin: Stdio,
     stdout: Stdio,
     stderr: Stdio,
+    envs: HashMap<String, String>,
+    clear_env: bool,
+    current_dir: Option<PathBuf>,
+    stderr_level: Option<tracing::Level>,
+    startup_timeout: Option<Duration>,
 }
 
 impl TokioChildProcessBuilder {
@@ -165,6 +249,11 @@ impl TokioChildProcessBuilder {
             stdin: Stdio::piped(),
             stdout: Stdio::piped(),
             stderr: Stdio::inherit(),
+            envs: HashMap::new(),
+            clear_env: false,
+            current_dir: None,
+            stderr_level: None,
+            startup_timeout: None,
         }
     }
 
@@ -184,25 +273,149 @@ impl TokioChildProcessBuilder {
         self
     }
 
+    /// Set an environment variable for the child process.
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.envs.insert(key.into(), value.into());
+        self
+    }
+    /// Set several environment variables for the child process.
+    pub fn envs<K, V>(mut self, vars: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.envs
+            .extend(vars.into_iter().map(|(k, v)| (k.into(), v.into())));
+        self
+    }
+    /// Start the child from an empty environment instead of inheriting the parent's.
+    ///
+    /// By default a child process inherits every environment variable of the
+    /// current process, which can leak unrelated secrets (cloud credentials,
+    /// API tokens, ...) to a third-party MCP server. With `clear_env` set, the
+    /// child only sees the variables passed through [`env`](Self::env) and
+    /// [`envs`](Self::envs). Note that this also clears `PATH`, so the command
+    /// should be an absolute path or `PATH` should be set explicitly.
+    pub fn clear_env(mut self, clear_env: bool) -> Self {
+        self.clear_env = clear_env;
+        self
+    }
+
+    /// Run the child in `dir` instead of the current process's working directory.
+    ///
+    /// [`spawn`](Self::spawn) fails with [`NotFound`](std::io::ErrorKind::NotFound)
+    /// if `dir` does not exist.
+    pub fn current_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.current_dir = Some(dir.into());
+        self
+    }
+
+    /// Give up on the child if it sends nothing within `timeout` of being spawned.
+    ///
+    /// A server that crashes or stalls during startup otherwise leaves the
+    /// client waiting for its `initialize` response. Once the timeout elapses
+    /// the child is killed, the transport closes and the reason is logged with
+    /// the command and working directory.
+    pub fn startup_timeout(mut self, timeout: Duration) -> Self {
+        self.startup_timeout = Some(timeout);
+        self
+    }
+
+    /// Forward each line the child writes to stderr to `tracing` at `level`.
+    ///
+    /// Lines are emitted with the target `rmcp::child_process::stderr` and a
+    /// `server` field holding the command's file name. This overrides
+    /// [`stderr`](Self::stderr), and [`spawn`](Self::spawn) returns no stderr handle.
+    pub fn capture_stderr(mut self, level: tracing::Level) -> Self {
+        self.stderr_level = Some(level);
+        self
+    }
+
     /// Spawn the child process. Returns the transport plus an optional captured stderr handle.
     pub fn spawn(mut self) -> std::io::Result<(TokioChildProcess, Option<ChildStderr>)> {
-        self.cmd
-            .command_mut()
+        let command = self.cmd.command_mut();
+        if self.clear_env {
+            command.env_clear();
+        }
+        if let Some(dir) = &self.current_dir {
+            if !dir.is_dir() {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("working directory {} does not exist", dir.display()),
+                ));
+            }
+            command.current_dir(dir);
+        }
+        let stderr = match self.stderr_level {
+            Some(_) => Stdio::piped(),
+            None => self.stderr,
+        };
+        command
+            .envs(self.envs)
             .stdin(self.stdin)
             .stdout(self.stdout)
-            .stderr(self.stderr);
-
-        let (child, stdout, stdin, stderr_opt) = child_process(self.cmd.spawn()?)?;
+            .stderr(stderr);
+        let server_name = std::path::Path::new(command.as_std().get_program())
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let (child, stdout, stdin, mut stderr_opt) = child_process(self.cmd.spawn()?)?;
+        if let Some(level) = self.stderr_level {
+            if let Some(stderr) = stderr_opt.take() {
+                tokio::spawn(forward_stderr(stderr, server_name.clone(), level));
+            }
+        }
 
         let transport = AsyncRwTransport::new(stdout, stdin);
         let proc = TokioChildProcess {
             child: ChildWithCleanup { inner: Some(child) },
             transport,
+            startup: Some(Startup {
+                server_name,
+                current_dir: self
+                    .current_dir
+                    .or_else(|| std::env::current_dir().ok())
+                    .unwrap_or_default(),
+                timeout: self.startup_timeout,
+                deadline: self
+                    .startup_timeout
+                    .map(|timeout| tokio::time::Instant::now() + timeout),
+            }),
+            startup_error: None,
         };
         Ok((proc, stderr_opt))
     }
 }
 
+async fn forward_stderr(stderr: ChildStderr, server: String, level: tracing::Level) {
+    use tokio::io::AsyncBufReadExt;
+
+    let mut lines = tokio::io::BufReader::new(stderr).lines();
+    loop {
+        let line = match lines.next_line().await {
+            Ok(Some(line)) => line,
+            Ok(None) => break,
+            Err(e) => {
+                tracing::debug!(%server, "stopped reading child stderr: {e}");
+                break;
+            }
+        };
+        macro_rules! emit {
+            ($level:ident) => {
+                tracing::$level!(target: "rmcp::child_process::stderr", %server, "{line}")
+            };
+        }
+        match level {
+            tracing::Level::ERROR => emit!(error),
+            tracing::Level::WARN => emit!(warn),
+            tracing::Level::INFO => emit!(info),
+            tracing::Level::DEBUG => emit!(debug),
+            tracing::Level::TRACE => emit!(trace),
+        }
+    }
+}
+
 impl Transport<RoleClient> for TokioChildProcess {
     type Error = std::io::Error;
 
@@ -213,8 +426,30 @@ impl Transport<RoleClient> for TokioChildProcess {
         self.transport.send(item)
     }
 
-    fn receive(&mut self) -> impl Future<Output = Option<RxJsonRpcMessage<RoleClient>>> + Send {
-        self.transport.receive()
+    async fn receive(&mut self) -> Option<RxJsonRpcMessage<RoleClient>> {
+        let Some(startup) = &self.startup else {
+            return self.transport.receive().await;
+        };
+        let received = match startup.deadline {
+            Some(deadline) => tokio::time::timeout_at(deadline, self.transport.receive()).await,
+            None => Ok(self.transport.receive().await),
+        };
+        let startup = self.startup.take()?;
+        match received {
+            Ok(Some(message)) => Some(message),
+            Ok(None) => {
+                self.report_startup_failure(startup).await;
+                None
+            }
+            Err(_elapsed) => {
+                self.report_startup_timeout(startup).await;
+                None
+            }
+        }
+    }
+
+    fn take_error(&mut self) -> Option<Self::Error> {
+        self.startup_error.take()
     }
 
     fn close(&mut self) -> impl Future<Output = Result<(), Self::Error>> + Send {
@@ -222,6 +457,26 @@ impl Transport<RoleClient> for TokioChildProcess {
     }
 }
 
+fn uvx_command(package: impl AsRef<OsStr>, args: &[&str]) -> tokio::process::Command {
+    tokio::process::Command::new("uvx").configure(|cmd| {
+        cmd.arg("--quiet").arg(package).args(args);
+    })
+}
+
+fn npx_command(package: impl AsRef<OsStr>, args: &[&str]) -> tokio::process::Command {
+    // npx is a `.cmd` shim on Windows, which `Command` does not resolve
+    let program = if cfg!(windows) { "npx.cmd" } else { "npx" };
+    tokio::process::Command::new(program).configure(|cmd| {
+        cmd.arg("--yes").arg("--quiet").arg(package).args(args);
+    })
+}
+
+fn docker_command(image: impl AsRef<OsStr>, args: &[&str]) -> tokio::process::Command {
+    tokio::process::Command::new("docker").configure(|cmd| {
+        cmd.args(["run", "-i", "--rm"]).arg(image).args(args);
+    })
+}
+
 pub trait ConfigureCommandExt {
     fn configure(self, f: impl FnOnce(&mut Self)) -> Self;
 }
@@ -356,4 +611,194 @@ mod tests {
             }
         }
     }
+
+    fn program_and_args(cmd: &Command) -> Vec<&OsStr> {
+        let cmd = cmd.as_std();
+        std::iter::once(cmd.get_program())
+            .chain(cmd.get_args())
+            .collect()
+    }
+
+    #[test]
+    fn test_launcher_commands() {
+        assert_eq!(
+            program_and_args(&uvx_command(
+                "mcp-server-time",
+                &["--local-timezone", "UTC"]
+            )),
+            [
+                "uvx",
+                "--quiet",
+                "mcp-server-time",
+                "--local-timezone",
+                "UTC"
+            ]
+        );
+        assert_eq!(
+            program_and_args(&npx_command("@modelcontextprotocol/server-everything", &[])),
+            [
+                "npx",
+                "--yes",
+                "--quiet",
+                "@modelcontextprotocol/server-everything"
+            ]
+        );
+        assert_eq!(
+            program_and_args(&docker_command("mcp/fetch", &["--ignore-robots-txt"])),
+            [
+                "docker",
+                "run",
+                "-i",
+                "--rm",
+                "mcp/fetch",
+                "--ignore-robots-txt"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tokio_child_process_clear_env() {
+        use tokio::io::AsyncReadExt;
+
+        let (_child_process, stderr) =
+            TokioChildProcess::builder(Command::new("/bin/sh").configure(|cmd| {
+                cmd.arg("-c").arg(r#"echo "${HOME}:${RMCP_TEST_VAR}" >&2"#);
+            }))
+            .clear_env(true)
+            .env("RMCP_TEST_VAR", "set")
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut output = String::new();
+        stderr
+            .expect("stderr is piped")
+            .read_to_string(&mut output)
+            .await
+            .unwrap();
+        assert_eq!(output.trim(), ":set");
+    }
+
+    #[tokio::test]
+    async fn test_tokio_child_process_current_dir() {
+        use tokio::io::AsyncReadExt;
+
+        let (_child_process, stderr) =
+            TokioChildProcess::builder(Command::new("/bin/sh").configure(|cmd| {
+                cmd.arg("-c").arg("pwd >&2");
+            }))
+            .current_dir("/")
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut output = String::new();
+        stderr
+            .expect("stderr is piped")
+            .read_to_string(&mut output)
+            .await
+            .unwrap();
+        assert_eq!(output.trim(), "/");
+
+        let error = TokioChildProcess::builder(Command::new("/bin/sh"))
+            .current_dir("/this/directory/does/not/exist")
+            .spawn()
+            .err()
+            .expect("missing working directory");
+        assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+        assert!(error.to_string().contains("/this/directory/does/not/exist"));
+    }
+
+    #[tokio::test]
+    async fn test_tokio_child_process_startup_timeout() {
+        let (mut child_process, _) =
+            TokioChildProcess::builder(Command::new("sleep").configure(|cmd| {
+                cmd.arg("30");
+            }))
+            .startup_timeout(std::time::Duration::from_millis(200))
+            .spawn()
+            .unwrap();
+        let received =
+            tokio::time::timeout(std::time::Duration::from_secs(5), child_process.receive())
+                .await
+                .expect("receive gives up after the startup timeout");
+        assert!(received.is_none());
+        assert!(child_process.id().is_none(), "child was killed");
+        let error = child_process
+            .take_error()
+            .expect("the startup timeout is recorded");
+        assert_eq!(error.kind(), std::io::ErrorKind::TimedOut);
+        assert!(error.to_string().contains("200ms"));
+        assert!(error.to_string().contains("working directory"));
+    }
+
+    #[tokio::test]
+    async fn test_serve_client_reports_early_exit() {
+        let dir = std::env::temp_dir();
+        let child_process = TokioChildProcess::builder(Command::new("sh").configure(|cmd| {
+            cmd.arg("-c").arg("exit 3");
+        }))
+        .current_dir(&dir)
+        .spawn()
+        .unwrap()
+        .0;
+        let Err(error) = crate::serve_client((), child_process).await else {
+            panic!("initialize fails when the child exits");
+        };
+        let message = error.to_string();
+        assert!(
+            matches!(
+                error,
+                crate::service::ClientInitializeError::TransportError { .. }
+            ),
+            "{message}"
+        );
+        assert!(message.contains("exit status: 3"), "{message}");
+        assert!(message.contains(&dir.display().to_string()), "{message}");
+    }
+
+    #[derive(Clone, Default)]
+    struct CapturedLogs(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tokio_child_process_capture_stderr() {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .with_max_level(tracing::Level::TRACE)
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let (_child_process, stderr) =
+            TokioChildProcess::builder(Command::new("/bin/sh").configure(|cmd| {
+                cmd.arg("-c").arg("echo 'server crashed' >&2");
+            }))
+            .capture_stderr(tracing::Level::WARN)
+            .spawn()
+            .unwrap();
+        assert!(stderr.is_none());
+
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while !String::from_utf8_lossy(&logs.0.lock().unwrap()).contains("server crashed") {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("stderr line forwarded to tracing");
+        let output = String::from_utf8_lossy(&logs.0.lock().unwrap()).into_owned();
+        assert!(output.contains("WARN"));
+        assert!(output.contains("rmcp::child_process::stderr"));
+        assert!(output.contains("server=sh"));
+    }
 }
@@ -3,8 +3,21 @@ pub mod server_side_http;
 
 pub mod http_header;
 
+#[cfg(all(
+    feature = "compression",
+    any(
+        feature = "transport-streamable-http-server",
+        feature = "transport-streamable-http-client-reqwest"
+    )
+))]
+#[cfg_attr(
+    not(all(feature = "transport-streamable-http-server", not(feature = "local"))),
+    allow(dead_code)
+)]
+pub(crate) mod compression;
+
 #[cfg(feature = "__reqwest")]
-mod reqwest;
+pub(crate) mod reqwest;
 
 // Note: This module provides SSE stream parsing and auto-reconnect utilities.
 // It's used by the streamable HTTP client (which receives SSE-formatted responses),
@@ -0,0 +1,124 @@
+//! `Content-Encoding` support shared by the streamable HTTP client and server.
+//!
+//! Only complete JSON bodies are ever compressed. SSE streams are left as
+//! they are: a compressor buffers its input, which would hold back events
+//! until enough data accumulated.
+use std::io::{Read, Write};
+
+use flate2::{
+    Compression,
+    read::{GzDecoder, ZlibDecoder},
+    write::GzEncoder,
+};
+
+pub(crate) const GZIP: &str = "gzip";
+pub(crate) const DEFLATE: &str = "deflate";
+
+/// Why a request body could not be decoded.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum DecodeError {
+    #[error("unsupported content encoding: {0}")]
+    Unsupported(String),
+    #[error("decompressed body exceeds {0} bytes")]
+    TooLarge(usize),
+    #[error("invalid {encoding} body: {source}")]
+    Invalid {
+        encoding: &'static str,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+pub(crate) fn gzip(bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(bytes)?;
+    encoder.finish()
+}
+
+/// Decode a body sent with `Content-Encoding: encoding`, refusing to inflate
+/// it past `limit` bytes.
+pub(crate) fn decode(encoding: &str, bytes: &[u8], limit: usize) -> Result<Vec<u8>, DecodeError> {
+    let encoding = encoding.trim();
+    if encoding.eq_ignore_ascii_case("identity") {
+        return Ok(bytes.to_vec());
+    }
+    let (name, reader): (_, Box<dyn Read + '_>) = if encoding.eq_ignore_ascii_case(GZIP) {
+        (GZIP, Box::new(GzDecoder::new(bytes)))
+    } else if encoding.eq_ignore_ascii_case(DEFLATE) {
+        // HTTP `deflate` is the zlib format (RFC 9110 §8.4.1.2).
+        (DEFLATE, Box::new(ZlibDecoder::new(bytes)))
+    } else {
+        return Err(DecodeError::Unsupported(encoding.to_owned()));
+    };
+    let mut decoded = Vec::new();
+    reader
+        .take(limit as u64 + 1)
+        .read_to_end(&mut decoded)
+        .map_err(|source| DecodeError::Invalid {
+            encoding: name,
+            source,
+        })?;
+    if decoded.len() > limit {
+        return Err(DecodeError::TooLarge(limit));
+    }
+    Ok(decoded)
+}
+
+/// Whether an `Accept-Encoding` header value allows a gzip response.
+pub(crate) fn accepts_gzip(accept_encoding: &str) -> bool {
+    accept_encoding.split(',').any(|item| {
+        let mut parts = item.split(';');
+        let coding = parts.next().unwrap_or_default().trim();
+        if !(coding.eq_ignore_ascii_case(GZIP) || coding == "*") {
+            return false;
+        }
+        // `q=0` explicitly refuses the coding.
+        !parts.any(|param| {
+            param
+                .trim()
+                .strip_prefix("q=")
+                .and_then(|q| q.trim().parse::<f32>().ok())
+                .is_some_and(|q| q == 0.0)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gzip_round_trip() {
+        let body = br#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#;
+        let compressed = gzip(body).unwrap();
+        assert_eq!(decode("gzip", &compressed, 1024).unwrap(), body);
+        assert_eq!(decode("identity", body, 1024).unwrap(), body);
+    }
+
+    #[test]
+    fn decode_rejects_bombs_and_unknown_encodings() {
+        let compressed = gzip(&[b' '; 4096]).unwrap();
+        assert!(matches!(
+            decode("gzip", &compressed, 1024),
+            Err(DecodeError::TooLarge(1024))
+        ));
+        assert!(matches!(
+            decode("br", b"", 1024),
+            Err(DecodeError::Unsupported(_))
+        ));
+        assert!(matches!(
+            decode("gzip", b"not gzip", 1024),
+            Err(DecodeError::Invalid { .. })
+        ));
+    }
+
+    #[test]
+    fn accept_encoding_parsing() {
+        assert!(accepts_gzip("gzip"));
+        assert!(accepts_gzip("br, GZIP;q=0.5"));
+        assert!(accepts_gzip("*"));
+        assert!(!accepts_gzip("gzip;q=0"));
+        assert!(!accepts_gzip("deflate, br"));
+        assert!(!accepts_gzip(""));
+    }
+}
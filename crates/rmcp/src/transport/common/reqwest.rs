@@ -1,2 +1,9 @@
 #[cfg(feature = "transport-streamable-http-client-reqwest")]
 mod streamable_http_client;
+#[cfg(all(
+    feature = "transport-streamable-http-client-reqwest",
+    feature = "compression"
+))]
+pub use streamable_http_client::GzipRequestClient;
+#[cfg(feature = "transport-streamable-http-client-reqwest")]
+pub use streamable_http_client::JsonFormatClient;
@@ -84,7 +84,7 @@ impl StreamableHttpClient for reqwest::Client {
                 return Err(StreamableHttpError::UnexpectedContentType(None));
             }
         }
-        let event_stream = SseStream::from_byte_stream(response.bytes_stream()).boxed();
+        let event_stream = SseStream::from_bytes_stream(response.bytes_stream()).boxed();
         Ok(event_stream)
     }
 
@@ -120,135 +120,355 @@ impl StreamableHttpClient for reqwest::Client {
         auth_token: Option<String>,
         custom_headers: HashMap<HeaderName, HeaderValue>,
     ) -> Result<StreamableHttpPostResponse, StreamableHttpError<Self::Error>> {
-        let mut request = self
-            .post(uri.as_ref())
-            .header(ACCEPT, [EVENT_STREAM_MIME_TYPE, JSON_MIME_TYPE].join(", "));
-        if let Some(auth_header) = auth_token {
-            request = request.bearer_auth(auth_header);
-        }
+        post_message(
+            self,
+            uri,
+            message,
+            session_id,
+            auth_token,
+            custom_headers,
+            None,
+            JsonFormat::Compact,
+        )
+        .await
+    }
+}
 
-        request = apply_custom_headers(request, custom_headers)?;
-        let session_was_attached = session_id.is_some();
-        if let Some(session_id) = session_id {
-            request = request.header(HEADER_SESSION_ID, session_id.as_ref());
-        }
-        let response = request.json(&message).send().await?;
-        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
-            if let Some(header) = response.headers().get(WWW_AUTHENTICATE) {
-                let header = header
-                    .to_str()
-                    .map_err(|_| {
-                        StreamableHttpError::UnexpectedServerResponse(Cow::from(
-                            "invalid www-authenticate header value",
-                        ))
-                    })?
-                    .to_string();
-                return Err(StreamableHttpError::AuthRequired(AuthRequiredError {
-                    www_authenticate_header: header,
-                }));
+/// Send `message` as a POST request serialized with `json_format`, gzipping
+/// bodies of at least `request_compression_threshold` bytes.
+#[allow(clippy::too_many_arguments)]
+async fn post_message(
+    client: &reqwest::Client,
+    uri: Arc<str>,
+    message: ClientJsonRpcMessage,
+    session_id: Option<Arc<str>>,
+    auth_token: Option<String>,
+    custom_headers: HashMap<HeaderName, HeaderValue>,
+    request_compression_threshold: Option<usize>,
+    json_format: JsonFormat,
+) -> Result<StreamableHttpPostResponse, StreamableHttpError<reqwest::Error>> {
+    let mut request = client
+        .post(uri.as_ref())
+        .header(ACCEPT, [EVENT_STREAM_MIME_TYPE, JSON_MIME_TYPE].join(", "));
+    if let Some(auth_header) = auth_token {
+        request = request.bearer_auth(auth_header);
+    }
+
+    request = apply_custom_headers(request, custom_headers)?;
+    let session_was_attached = session_id.is_some();
+    if let Some(session_id) = session_id {
+        request = request.header(HEADER_SESSION_ID, session_id.as_ref());
+    }
+    let response = match request_compression_threshold {
+        #[cfg(feature = "compression")]
+        Some(threshold) => {
+            let body = json_format.to_vec(&message)?;
+            request = request.header(reqwest::header::CONTENT_TYPE, JSON_MIME_TYPE);
+            if body.len() >= threshold {
+                use crate::transport::common::compression::{GZIP, gzip};
+                request = request
+                    .header(reqwest::header::CONTENT_ENCODING, GZIP)
+                    .body(gzip(&body)?);
+            } else {
+                request = request.body(body);
             }
+            request.send().await?
+        }
+        _ if json_format == JsonFormat::Compact => request.json(&message).send().await?,
+        _ => {
+            request
+                .header(reqwest::header::CONTENT_TYPE, JSON_MIME_TYPE)
+                .body(json_format.to_vec(&message)?)
+                .send()
+                .await?
         }
-        if response.status() == reqwest::StatusCode::FORBIDDEN {
-            if let Some(header) = response.headers().get(WWW_AUTHENTICATE) {
-                let header_str = header.to_str().map_err(|_| {
+    };
+    if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        if let Some(header) = response.headers().get(WWW_AUTHENTICATE) {
+            let header = header
+                .to_str()
+                .map_err(|_| {
                     StreamableHttpError::UnexpectedServerResponse(Cow::from(
                         "invalid www-authenticate header value",
                     ))
-                })?;
-                let scope = extract_scope_from_header(header_str);
-                return Err(StreamableHttpError::InsufficientScope(
-                    InsufficientScopeError {
-                        www_authenticate_header: header_str.to_string(),
-                        required_scope: scope,
-                    },
-                ));
-            }
-        }
-        let status = response.status();
-        if matches!(
-            status,
-            reqwest::StatusCode::ACCEPTED | reqwest::StatusCode::NO_CONTENT
-        ) {
-            return Ok(StreamableHttpPostResponse::Accepted);
+                })?
+                .to_string();
+            return Err(StreamableHttpError::AuthRequired(AuthRequiredError {
+                www_authenticate_header: header,
+            }));
         }
-        if status == reqwest::StatusCode::NOT_FOUND && session_was_attached {
-            return Err(StreamableHttpError::SessionExpired);
+    }
+    if response.status() == reqwest::StatusCode::FORBIDDEN {
+        if let Some(header) = response.headers().get(WWW_AUTHENTICATE) {
+            let header_str = header.to_str().map_err(|_| {
+                StreamableHttpError::UnexpectedServerResponse(Cow::from(
+                    "invalid www-authenticate header value",
+                ))
+            })?;
+            let scope = extract_scope_from_header(header_str);
+            return Err(StreamableHttpError::InsufficientScope(
+                InsufficientScopeError {
+                    www_authenticate_header: header_str.to_string(),
+                    required_scope: scope,
+                },
+            ));
         }
-        let content_type = response
-            .headers()
-            .get(reqwest::header::CONTENT_TYPE)
-            .map(|ct| String::from_utf8_lossy(ct.as_bytes()).to_string());
-        let content_length = response.content_length();
-        let session_id = response
-            .headers()
-            .get(HEADER_SESSION_ID)
-            .and_then(|v| v.to_str().ok())
-            .map(|s| s.to_string());
-        // Spec requires 202 Accepted for these, but some servers return an empty 200.
-        // Treat empty success responses as equivalent to Accepted.
-        if status.is_success()
-            && content_length == Some(0)
-            && matches!(
-                message,
-                ClientJsonRpcMessage::Notification(_)
-                    | ClientJsonRpcMessage::Response(_)
-                    | ClientJsonRpcMessage::Error(_)
-            )
+    }
+    let status = response.status();
+    if matches!(
+        status,
+        reqwest::StatusCode::ACCEPTED | reqwest::StatusCode::NO_CONTENT
+    ) {
+        return Ok(StreamableHttpPostResponse::Accepted);
+    }
+    if status == reqwest::StatusCode::NOT_FOUND && session_was_attached {
+        return Err(StreamableHttpError::SessionExpired);
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .map(|ct| String::from_utf8_lossy(ct.as_bytes()).to_string());
+    let content_length = response.content_length();
+    let session_id = response
+        .headers()
+        .get(HEADER_SESSION_ID)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
+    // Spec requires 202 Accepted for these, but some servers return an empty 200.
+    // Treat empty success responses as equivalent to Accepted.
+    if status.is_success()
+        && content_length == Some(0)
+        && matches!(
+            message,
+            ClientJsonRpcMessage::Notification(_)
+                | ClientJsonRpcMessage::Response(_)
+                | ClientJsonRpcMessage::Error(_)
+        )
+    {
+        return Ok(StreamableHttpPostResponse::Accepted);
+    }
+    // Non-success responses may carry valid JSON-RPC error payloads that
+    // should be surfaced as McpError rather than lost in TransportSend.
+    if !status.is_success() {
+        let body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "<failed to read response body>".to_owned());
+        if content_type
+            .as_deref()
+            .is_some_and(|ct| ct.as_bytes().starts_with(JSON_MIME_TYPE.as_bytes()))
         {
-            return Ok(StreamableHttpPostResponse::Accepted);
-        }
-        // Non-success responses may carry valid JSON-RPC error payloads that
-        // should be surfaced as McpError rather than lost in TransportSend.
-        if !status.is_success() {
-            let body = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "<failed to read response body>".to_owned());
-            if content_type
-                .as_deref()
-                .is_some_and(|ct| ct.as_bytes().starts_with(JSON_MIME_TYPE.as_bytes()))
-            {
-                match parse_json_rpc_error(&body) {
-                    Some(message) => {
-                        return Ok(StreamableHttpPostResponse::Json(message, session_id));
-                    }
-                    None => tracing::warn!(
-                        "HTTP {status}: could not parse JSON body as a JSON-RPC error"
-                    ),
+            match parse_json_rpc_error(&body) {
+                Some(message) => {
+                    return Ok(StreamableHttpPostResponse::Json(message, session_id));
+                }
+                None => {
+                    tracing::warn!("HTTP {status}: could not parse JSON body as a JSON-RPC error")
                 }
             }
-            return Err(StreamableHttpError::UnexpectedServerResponse(Cow::Owned(
-                format!("HTTP {status}: {body}"),
-            )));
         }
-        match content_type.as_deref() {
-            Some(ct) if ct.as_bytes().starts_with(EVENT_STREAM_MIME_TYPE.as_bytes()) => {
-                let event_stream = SseStream::from_byte_stream(response.bytes_stream()).boxed();
-                Ok(StreamableHttpPostResponse::Sse(event_stream, session_id))
-            }
-            Some(ct) if ct.as_bytes().starts_with(JSON_MIME_TYPE.as_bytes()) => {
-                // Try to parse as a valid JSON-RPC message. If the body is
-                // malformed (e.g. a 200 response to a notification that lacks
-                // an `id` field), treat it as accepted rather than failing.
-                match response.json::<ServerJsonRpcMessage>().await {
-                    Ok(message) => Ok(StreamableHttpPostResponse::Json(message, session_id)),
-                    Err(e) => {
-                        tracing::warn!(
-                            "could not parse JSON response as ServerJsonRpcMessage, treating as accepted: {e}"
-                        );
-                        Ok(StreamableHttpPostResponse::Accepted)
-                    }
+        return Err(StreamableHttpError::UnexpectedServerResponse(Cow::Owned(
+            format!("HTTP {status}: {body}"),
+        )));
+    }
+    match content_type.as_deref() {
+        Some(ct) if ct.as_bytes().starts_with(EVENT_STREAM_MIME_TYPE.as_bytes()) => {
+            let event_stream = SseStream::from_bytes_stream(response.bytes_stream()).boxed();
+            Ok(StreamableHttpPostResponse::Sse(event_stream, session_id))
+        }
+        Some(ct) if ct.as_bytes().starts_with(JSON_MIME_TYPE.as_bytes()) => {
+            // Try to parse as a valid JSON-RPC message. If the body is
+            // malformed (e.g. a 200 response to a notification that lacks
+            // an `id` field), treat it as accepted rather than failing.
+            match response.json::<ServerJsonRpcMessage>().await {
+                Ok(message) => Ok(StreamableHttpPostResponse::Json(message, session_id)),
+                Err(e) => {
+                    tracing::warn!(
+                        "could not parse JSON response as ServerJsonRpcMessage, treating as accepted: {e}"
+                    );
+                    Ok(StreamableHttpPostResponse::Accepted)
                 }
             }
-            _ => {
-                // unexpected content type
-                tracing::error!("unexpected content type: {:?}", content_type);
-                Err(StreamableHttpError::UnexpectedContentType(content_type))
-            }
+        }
+        _ => {
+            // unexpected content type
+            tracing::error!("unexpected content type: {:?}", content_type);
+            Err(StreamableHttpError::UnexpectedContentType(content_type))
+        }
+    }
+}
+
+/// A [`reqwest::Client`] that gzips POST bodies of at least `threshold` bytes.
+///
+/// Only use this with servers that accept `Content-Encoding: gzip`, such as
+/// an rmcp server built with the `compression` feature. Compressed responses
+/// are decoded by any [`reqwest::Client`] once the feature is enabled.
+///
+/// ```rust,no_run
+/// use rmcp::transport::{
+///     StreamableHttpClientTransport,
+///     streamable_http_client::{GzipRequestClient, StreamableHttpClientTransportConfig},
+/// };
+///
+/// let transport = StreamableHttpClientTransport::with_client(
+///     GzipRequestClient::new(reqwest::Client::new(), 1024),
+///     StreamableHttpClientTransportConfig::with_uri("http://localhost:8000/mcp"),
+/// );
+/// ```
+#[cfg(feature = "compression")]
+#[derive(Debug, Clone)]
+pub struct GzipRequestClient {
+    client: reqwest::Client,
+    threshold: usize,
+    json_format: JsonFormat,
+}
+
+#[cfg(feature = "compression")]
+impl GzipRequestClient {
+    pub fn new(client: reqwest::Client, threshold: usize) -> Self {
+        Self {
+            client,
+            threshold,
+            json_format: JsonFormat::Compact,
+        }
+    }
+
+    /// Serialize request bodies with `json_format` before compressing them.
+    pub fn with_json_format(mut self, json_format: JsonFormat) -> Self {
+        self.json_format = json_format;
+        self
+    }
+}
+
+#[cfg(feature = "compression")]
+impl StreamableHttpClient for GzipRequestClient {
+    type Error = reqwest::Error;
+
+    async fn get_stream(
+        &self,
+        uri: Arc<str>,
+        session_id: Arc<str>,
+        last_event_id: Option<String>,
+        auth_token: Option<String>,
+        custom_headers: HashMap<HeaderName, HeaderValue>,
+    ) -> Result<BoxStream<'static, Result<Sse, SseError>>, StreamableHttpError<Self::Error>> {
+        self.client
+            .get_stream(uri, session_id, last_event_id, auth_token, custom_headers)
+            .await
+    }
+
+    async fn delete_session(
+        &self,
+        uri: Arc<str>,
+        session: Arc<str>,
+        auth_token: Option<String>,
+        custom_headers: HashMap<HeaderName, HeaderValue>,
+    ) -> Result<(), StreamableHttpError<Self::Error>> {
+        self.client
+            .delete_session(uri, session, auth_token, custom_headers)
+            .await
+    }
+
+    async fn post_message(
+        &self,
+        uri: Arc<str>,
+        message: ClientJsonRpcMessage,
+        session_id: Option<Arc<str>>,
+        auth_token: Option<String>,
+        custom_headers: HashMap<HeaderName, HeaderValue>,
+    ) -> Result<StreamableHttpPostResponse, StreamableHttpError<Self::Error>> {
+        post_message(
+            &self.client,
+            uri,
+            message,
+            session_id,
+            auth_token,
+            custom_headers,
+            Some(self.threshold),
+            self.json_format,
+        )
+        .await
+    }
+}
+
+/// A [`reqwest::Client`] that serializes POST bodies with a chosen [`JsonFormat`].
+///
+/// ```rust,no_run
+/// use rmcp::transport::{
+///     StreamableHttpClientTransport,
+///     streamable_http_client::{JsonFormat, JsonFormatClient, StreamableHttpClientTransportConfig},
+/// };
+///
+/// let transport = StreamableHttpClientTransport::with_client(
+///     JsonFormatClient::new(reqwest::Client::new(), JsonFormat::Pretty),
+///     StreamableHttpClientTransportConfig::with_uri("http://localhost:8000/mcp"),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct JsonFormatClient {
+    client: reqwest::Client,
+    json_format: JsonFormat,
+}
+
+impl JsonFormatClient {
+    pub fn new(client: reqwest::Client, json_format: JsonFormat) -> Self {
+        Self {
+            client,
+            json_format,
         }
     }
 }
 
+impl StreamableHttpClient for JsonFormatClient {
+    type Error = reqwest::Error;
+
+    async fn get_stream(
+        &self,
+        uri: Arc<str>,
+        session_id: Arc<str>,
+        last_event_id: Option<String>,
+        auth_token: Option<String>,
+        custom_headers: HashMap<HeaderName, HeaderValue>,
+    ) -> Result<BoxStream<'static, Result<Sse, SseError>>, StreamableHttpError<Self::Error>> {
+        self.client
+            .get_stream(uri, session_id, last_event_id, auth_token, custom_headers)
+            .await
+    }
+
+    async fn delete_session(
+        &self,
+        uri: Arc<str>,
+        session: Arc<str>,
+        auth_token: Option<String>,
+        custom_headers: HashMap<HeaderName, HeaderValue>,
+    ) -> Result<(), StreamableHttpError<Self::Error>> {
+        self.client
+            .delete_session(uri, session, auth_token, custom_headers)
+            .await
+    }
+
+    async fn post_message(
+        &self,
+        uri: Arc<str>,
+        message: ClientJsonRpcMessage,
+        session_id: Option<Arc<str>>,
+        auth_token: Option<String>,
+        custom_headers: HashMap<HeaderName, HeaderValue>,
+    ) -> Result<StreamableHttpPostResponse, StreamableHttpError<Self::Error>> {
+        post_message(
+            &self.client,
+            uri,
+            message,
+            session_id,
+            auth_token,
+            custom_headers,
+            None,
+            self.json_format,
+        )
+        .await
+    }
+}
+
 impl StreamableHttpClientTransport<reqwest::Client> {
     /// Creates a new transport using reqwest with the specified URI.
     ///
@@ -287,27 +507,95 @@ impl StreamableHttpClientTransport<reqwest::Client> {
 
     /// Build this transport form a config
     ///
+    /// To reuse an existing [`reqwest::Client`] (and its connection pool, proxy
+    /// and DNS settings), pass it to [`with_client`](StreamableHttpClientTransport::with_client)
+    /// instead:
+    ///
+    /// ```rust,no_run
+    /// use rmcp::transport::{
+    ///     StreamableHttpClientTransport, streamable_http_client::StreamableHttpClientTransportConfig,
+    /// };
+    ///
+    /// let shared = reqwest::Client::new();
+    /// let transport = StreamableHttpClientTransport::with_client(
+    ///     shared.clone(),
+    ///     StreamableHttpClientTransportConfig::with_uri("http://localhost:8000/mcp"),
+    /// );
+    /// ```
+    ///
+    /// Headers the transport sets itself (`Accept`, `Mcp-Session-Id`, ...) take
+    /// precedence over the shared client's default headers, and `custom_headers`
+    /// are still checked against the reserved names. Note that a shared client
+    /// keeps its own redirect policy, whereas the default client never follows
+    /// redirects so custom headers are not replayed to another host.
+    ///
+    /// Proxy settings in `config.proxy` apply only to the client built here.
+    ///
     /// # Arguments
     ///
     /// * `config` - The config to use with this transport
+    ///
+    /// # Panics
+    ///
+    /// Panics if `config.proxy` contains an invalid proxy URL. Use
+    /// [`try_from_config`](Self::try_from_config) to handle that as an error.
     pub fn from_config(config: StreamableHttpClientTransportConfig) -> Self {
-        StreamableHttpClientTransport::with_client(Self::default_http_client(), config)
+        Self::try_from_config(config).expect("invalid proxy configuration")
+    }
+
+    /// Build this transport from a config, like [`from_config`](Self::from_config),
+    /// but return an error instead of panicking if `config.proxy` contains an
+    /// invalid proxy URL.
+    pub fn try_from_config(config: StreamableHttpClientTransportConfig) -> reqwest::Result<Self> {
+        let client = match &config.proxy {
+            Some(proxy) => Self::proxied_http_client(proxy)?,
+            None => Self::default_http_client(),
+        };
+        Ok(StreamableHttpClientTransport::with_client(client, config))
     }
 
     /// Build the default reqwest client for this transport.
-    ///
+    fn default_http_client() -> reqwest::Client {
+        Self::default_http_client_builder()
+            .build()
+            .expect("failed to build default reqwest client")
+    }
+
     /// Disables idle connection pooling to avoid ~40 ms stalls caused by
     /// TCP Delayed ACK on Linux when the previous response body was not
     /// fully consumed before the pool attempts to reuse the connection.
     ///
     /// Automatic redirects are disabled so caller-supplied custom headers
     /// cannot be replayed to a redirect target.
-    fn default_http_client() -> reqwest::Client {
+    fn default_http_client_builder() -> reqwest::ClientBuilder {
         reqwest::Client::builder()
             .pool_max_idle_per_host(0)
             .redirect(reqwest::redirect::Policy::none())
-            .build()
-            .expect("failed to build default reqwest client")
+    }
+
+    /// Build the default client with `proxy` in place of the environment's proxies.
+    fn proxied_http_client(proxy: &ProxyConfig) -> reqwest::Result<reqwest::Client> {
+        let no_proxy = proxy
+            .no_proxy
+            .as_deref()
+            .and_then(reqwest::NoProxy::from_string);
+        let configure = |mut matcher: reqwest::Proxy| {
+            matcher = matcher.no_proxy(no_proxy.clone());
+            if let Some((username, password)) = &proxy.credentials {
+                matcher = matcher.basic_auth(username, password);
+            }
+            matcher
+        };
+        let mut builder = Self::default_http_client_builder().no_proxy();
+        if let Some(url) = &proxy.http_proxy {
+            let matcher = reqwest::Proxy::http(url.as_str())?;
+            builder = builder.proxy(configure(matcher));
+        }
+        if let Some(url) = &proxy.https_proxy {
+            let matcher = reqwest::Proxy::https(url.as_str())?;
+            builder = builder.proxy(configure(matcher));
+        }
+        builder.build()
     }
 }
 
@@ -494,4 +782,173 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn proxied_http_client_routes_through_proxy_with_credentials() -> anyhow::Result<()> {
+        use std::{collections::HashMap, sync::Arc};
+
+        use axum::{
+            Router, extract::State, http::StatusCode, response::IntoResponse, routing::post,
+        };
+        use http::HeaderMap;
+        use tokio::sync::Mutex;
+
+        use super::StreamableHttpClientTransport;
+        use crate::transport::streamable_http_client::{ProxyConfig, StreamableHttpClient};
+
+        type ProxyAuth = Arc<Mutex<Vec<Option<String>>>>;
+
+        async fn proxy_handler(
+            State(seen): State<ProxyAuth>,
+            headers: HeaderMap,
+        ) -> impl IntoResponse {
+            let auth = headers
+                .get(http::header::PROXY_AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned);
+            seen.lock().await.push(auth);
+            (
+                StatusCode::OK,
+                [(http::header::CONTENT_TYPE, "application/json")],
+                r#"{"jsonrpc":"2.0","id":1,"result":{}}"#,
+            )
+        }
+
+        let seen = ProxyAuth::default();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let proxy_addr = listener.local_addr()?;
+        let proxy_server = tokio::spawn({
+            let app = Router::new()
+                .route("/mcp", post(proxy_handler))
+                .with_state(seen.clone());
+            async move { axum::serve(listener, app).await }
+        });
+        // Nothing listens here, so a direct connection fails.
+        let closed_port = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await?
+            .local_addr()?
+            .port();
+
+        let client = StreamableHttpClientTransport::<reqwest::Client>::proxied_http_client(
+            &ProxyConfig::default()
+                .http_proxy(format!("http://{proxy_addr}"))
+                .no_proxy("127.0.0.1")
+                .credentials("alice", "secret"),
+        )?;
+        let ping = || {
+            ClientJsonRpcMessage::request(
+                ClientRequest::PingRequest(PingRequest::default()),
+                RequestId::Number(1),
+            )
+        };
+
+        client
+            .post_message(
+                Arc::from("http://mcp.example.invalid/mcp"),
+                ping(),
+                None,
+                None,
+                HashMap::new(),
+            )
+            .await?;
+        assert_eq!(
+            *seen.lock().await,
+            vec![Some("Basic YWxpY2U6c2VjcmV0".to_owned())]
+        );
+
+        // Hosts in `no_proxy` are contacted directly.
+        let direct = client
+            .post_message(
+                Arc::from(format!("http://127.0.0.1:{closed_port}/mcp")),
+                ping(),
+                None,
+                None,
+                HashMap::new(),
+            )
+            .await;
+        assert!(direct.is_err());
+        assert_eq!(seen.lock().await.len(), 1);
+
+        proxy_server.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn try_from_config_rejects_invalid_proxy_url() {
+        use super::StreamableHttpClientTransport;
+        use crate::transport::streamable_http_client::{
+            ProxyConfig, StreamableHttpClientTransportConfig,
+        };
+
+        let config = |proxy: ProxyConfig| {
+            StreamableHttpClientTransportConfig::with_uri("http://localhost:8000/mcp").proxy(proxy)
+        };
+        let invalid = StreamableHttpClientTransport::<reqwest::Client>::try_from_config(config(
+            ProxyConfig::default().https_proxy("http://[not-a-host"),
+        ));
+        assert!(invalid.is_err());
+        let valid = StreamableHttpClientTransport::<reqwest::Client>::try_from_config(config(
+            ProxyConfig::default().https_proxy("http://proxy.example:3128"),
+        ));
+        assert!(valid.is_ok());
+    }
+
+    #[tokio::test]
+    async fn json_format_client_sends_pretty_body() -> anyhow::Result<()> {
+        use std::{collections::HashMap, net::SocketAddr, sync::Arc};
+
+        use axum::{
+            Router, extract::State, http::StatusCode, response::IntoResponse, routing::post,
+        };
+        use tokio::sync::Mutex;
+
+        use super::{JsonFormat, JsonFormatClient};
+        use crate::transport::streamable_http_client::StreamableHttpClient;
+
+        async fn capture_body(
+            State(captured): State<Arc<Mutex<Option<String>>>>,
+            body: String,
+        ) -> impl IntoResponse {
+            *captured.lock().await = Some(body);
+            (
+                StatusCode::OK,
+                [(http::header::CONTENT_TYPE, "application/json")],
+                r#"{"jsonrpc":"2.0","id":1,"result":{}}"#,
+            )
+        }
+
+        let captured = Arc::new(Mutex::new(None));
+        let listener = tokio::net::TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))).await?;
+        let addr = listener.local_addr()?;
+        let server = tokio::spawn({
+            let app = Router::new()
+                .route("/mcp", post(capture_body))
+                .with_state(captured.clone());
+            async move { axum::serve(listener, app).await }
+        });
+
+        let message = ClientJsonRpcMessage::request(
+            ClientRequest::PingRequest(PingRequest::default()),
+            RequestId::Number(1),
+        );
+        JsonFormatClient::new(reqwest::Client::new(), JsonFormat::Pretty)
+            .post_message(
+                Arc::<str>::from(format!("http://{addr}/mcp")),
+                message.clone(),
+                None,
+                None,
+                HashMap::new(),
+            )
+            .await?;
+        server.abort();
+
+        let body = captured
+            .lock()
+            .await
+            .take()
+            .expect("server received a body");
+        assert!(body.contains('\n'), "expected pretty output, got {body}");
+        assert_eq!(body, serde_json::to_string_pretty(&message)?);
+        Ok(())
+    }
 }
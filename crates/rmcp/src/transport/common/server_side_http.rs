@@ -8,7 +8,7 @@ use http_body_util::{BodyExt, Empty, Full, combinators::BoxBody};
 use sse_stream::{KeepAlive, Sse, SseBody};
 use tokio_util::sync::CancellationToken;
 
-use super::http_header::EVENT_STREAM_MIME_TYPE;
+use super::http_header::{EVENT_STREAM_MIME_TYPE, JSON_MIME_TYPE};
 use crate::model::{ClientJsonRpcMessage, ServerJsonRpcMessage};
 
 pub type SessionId = Arc<str>;
@@ -17,6 +17,18 @@ pub fn session_id() -> SessionId {
     uuid::Uuid::new_v4().to_string().into()
 }
 
+/// Request extension naming the session a message was routed to.
+///
+/// Inserted by the streamable HTTP server in stateful mode once the session
+/// manager has confirmed that the `Mcp-Session-Id` header names a live
+/// session. Unlike the header itself, which any client can set, it is only
+/// present for sessions the server knows; stateless requests never carry it.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatedSession {
+    pub id: SessionId,
+}
+
 pub const DEFAULT_AUTO_PING_INTERVAL: Duration = Duration::from_secs(15);
 
 pub(crate) type BoxResponse = Response<BoxBody<Bytes, Infallible>>;
@@ -144,6 +156,43 @@ pub(crate) fn sse_stream_response(
         .expect("valid response")
 }
 
+/// Wait for the JSON-RPC response or error on `stream` and return it as a
+/// single `application/json` body. Anything else on `stream` is skipped; in
+/// stateful mode the session routes a call's requests and progress to the
+/// standalone stream instead.
+pub(crate) async fn json_stream_response(
+    stream: impl futures::Stream<Item = ServerJsonRpcMessage> + Send,
+    ct: CancellationToken,
+) -> Result<Response<BoxBody<Bytes, Infallible>>, Response<BoxBody<Bytes, Infallible>>> {
+    use futures::StreamExt;
+    let mut stream = std::pin::pin!(stream.filter(|message| {
+        std::future::ready(matches!(
+            message,
+            ServerJsonRpcMessage::Response(_) | ServerJsonRpcMessage::Error(_)
+        ))
+    }));
+    let message = tokio::select! {
+        message = stream.next() => message,
+        _ = ct.cancelled() => None,
+    };
+    let Some(message) = message else {
+        return Err(internal_error_response("empty response")(
+            std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "no response message received from handler",
+            ),
+        ));
+    };
+    tracing::trace!(?message);
+    let body = serde_json::to_vec(&message)
+        .map_err(|e| internal_error_response("serialize json response")(e))?;
+    Ok(Response::builder()
+        .status(http::StatusCode::OK)
+        .header(http::header::CONTENT_TYPE, JSON_MIME_TYPE)
+        .body(Full::new(Bytes::from(body)).boxed())
+        .expect("valid response"))
+}
+
 pub(crate) const fn internal_error_response<E: Display>(
     context: &str,
 ) -> impl FnOnce(E) -> Response<BoxBody<Bytes, Infallible>> {
@@ -201,6 +250,99 @@ where
     }
 }
 
+/// Upper bound for a request body after decompression.
+#[cfg(feature = "compression")]
+pub(crate) const MAX_DECOMPRESSED_REQUEST_BODY: usize = 16 * 1024 * 1024;
+
+/// Decode a request body sent with a `Content-Encoding`, replacing it with the
+/// decoded bytes.
+#[cfg(feature = "compression")]
+pub(crate) async fn decode_request_body<B>(
+    request: http::Request<B>,
+) -> Result<http::Request<Full<Bytes>>, BoxResponse>
+where
+    B: Body + Send + 'static,
+    B::Error: Display,
+{
+    use super::compression::{DecodeError, decode};
+
+    let (mut parts, body) = request.into_parts();
+    let encoding = parts
+        .headers
+        .remove(http::header::CONTENT_ENCODING)
+        .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned())
+        .unwrap_or_default();
+    let bytes = body.collect().await.map_err(|e| {
+        Response::builder()
+            .status(http::StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Full::new(Bytes::from(format!("Failed to read request body: {e}"))).boxed())
+            .expect("valid response")
+    })?;
+    let decoded =
+        decode(&encoding, &bytes.to_bytes(), MAX_DECOMPRESSED_REQUEST_BODY).map_err(|e| {
+            let status = match e {
+                DecodeError::Unsupported(_) => http::StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                DecodeError::TooLarge(_) => http::StatusCode::PAYLOAD_TOO_LARGE,
+                DecodeError::Invalid { .. } => http::StatusCode::BAD_REQUEST,
+            };
+            Response::builder()
+                .status(status)
+                .body(Full::new(Bytes::from(e.to_string())).boxed())
+                .expect("valid response")
+        })?;
+    parts.headers.remove(http::header::CONTENT_LENGTH);
+    Ok(http::Request::from_parts(
+        parts,
+        Full::new(Bytes::from(decoded)),
+    ))
+}
+
+/// Gzip a complete `application/json` response of at least `threshold` bytes.
+///
+/// Any other response, notably SSE streams, is returned unchanged.
+#[cfg(feature = "compression")]
+pub(crate) async fn compress_json_response(response: BoxResponse, threshold: usize) -> BoxResponse {
+    use super::{compression::gzip, http_header::JSON_MIME_TYPE};
+
+    let is_json = response
+        .headers()
+        .get(http::header::CONTENT_TYPE)
+        .is_some_and(|ct| ct.as_bytes().starts_with(JSON_MIME_TYPE.as_bytes()));
+    if !is_json
+        || response
+            .headers()
+            .contains_key(http::header::CONTENT_ENCODING)
+    {
+        return response;
+    }
+    let (mut parts, body) = response.into_parts();
+    let bytes = match body.collect().await {
+        Ok(collected) => collected.to_bytes(),
+        Err(never) => match never {},
+    };
+    if bytes.len() < threshold {
+        return Response::from_parts(parts, Full::new(bytes).boxed());
+    }
+    match gzip(&bytes) {
+        Ok(compressed) => {
+            parts.headers.insert(
+                http::header::CONTENT_ENCODING,
+                http::HeaderValue::from_static(super::compression::GZIP),
+            );
+            parts.headers.remove(http::header::CONTENT_LENGTH);
+            parts.headers.append(
+                http::header::VARY,
+                http::HeaderValue::from_static("accept-encoding"),
+            );
+            Response::from_parts(parts, Full::new(Bytes::from(compressed)).boxed())
+        }
+        Err(error) => {
+            tracing::warn!(%error, "failed to compress response, sending it uncompressed");
+            Response::from_parts(parts, Full::new(bytes).boxed())
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
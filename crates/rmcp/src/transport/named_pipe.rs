@@ -0,0 +1,112 @@
+//! # Windows named pipe transport
+//!
+//! Serves and connects to local MCP servers over a Windows named pipe, using
+//! the same newline-delimited JSON framing as stdio. This is the Windows
+//! counterpart of the Unix domain socket transport.
+//!
+//! Pipe names have the form `\\.\pipe\<name>`. Remote clients are rejected, and
+//! the pipe uses the default security descriptor, which grants full access to
+//! the creating user, administrators and LocalSystem and read access to
+//! everyone else.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use rmcp::{ServiceExt, transport::{NamedPipeClientTransport, NamedPipeServerTransport}};
+//!
+//! const PIPE_NAME: &str = r"\\.\pipe\my-mcp-server";
+//!
+//! // server
+//! let mut listener = NamedPipeServerTransport::bind(PIPE_NAME)?;
+//! loop {
+//!     let transport = listener.accept().await?;
+//!     tokio::spawn(async move { MyServer::new().serve(transport).await?.waiting().await });
+//! }
+//!
+//! // client
+//! let transport = NamedPipeClientTransport::connect(PIPE_NAME).await?;
+//! let client = ().serve(transport).await?;
+//! ```
+use std::{
+    ffi::{OsStr, OsString},
+    io,
+    time::Duration,
+};
+
+use tokio::{
+    io::{ReadHalf, WriteHalf},
+    net::windows::named_pipe::{ClientOptions, NamedPipeClient, NamedPipeServer, ServerOptions},
+};
+
+use super::async_rw::AsyncRwTransport;
+
+/// `ERROR_PIPE_BUSY`: every instance of the pipe is connected to another client.
+const ERROR_PIPE_BUSY: i32 = 231;
+
+/// One connection over a named pipe, where `P` is the client or server end.
+pub type NamedPipeTransport<Role, P> = AsyncRwTransport<Role, ReadHalf<P>, WriteHalf<P>>;
+
+/// Client side of a named pipe connection.
+#[cfg(feature = "client")]
+pub type NamedPipeClientTransport = NamedPipeTransport<crate::RoleClient, NamedPipeClient>;
+
+#[cfg(feature = "client")]
+impl NamedPipeClientTransport {
+    /// Connect to a server listening on the pipe `name`, waiting while all of
+    /// its instances are busy.
+    pub async fn connect(name: impl AsRef<OsStr>) -> io::Result<Self> {
+        let client = loop {
+            match ClientOptions::new().open(name.as_ref()) {
+                Ok(client) => break client,
+                Err(error) if error.raw_os_error() == Some(ERROR_PIPE_BUSY) => {}
+                Err(error) => return Err(error),
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        };
+        let (read, write) = tokio::io::split(client);
+        Ok(Self::new(read, write))
+    }
+}
+
+/// Listens for MCP clients on a named pipe.
+///
+/// A pipe instance is always kept open, so clients connecting between two
+/// calls to [`accept`](Self::accept) wait instead of failing.
+#[derive(Debug)]
+pub struct NamedPipeServerTransport {
+    name: OsString,
+    next: NamedPipeServer,
+}
+
+impl NamedPipeServerTransport {
+    /// Create the pipe `name` and start listening.
+    ///
+    /// Fails if another process already serves a pipe with this name.
+    pub fn bind(name: impl AsRef<OsStr>) -> io::Result<Self> {
+        let name = name.as_ref().to_os_string();
+        let next = ServerOptions::new()
+            .first_pipe_instance(true)
+            .reject_remote_clients(true)
+            .create(&name)?;
+        Ok(Self { name, next })
+    }
+
+    /// The name of the pipe.
+    pub fn name(&self) -> &OsStr {
+        &self.name
+    }
+
+    /// Wait for the next client and return a transport for serving it.
+    #[cfg(feature = "server")]
+    pub async fn accept(
+        &mut self,
+    ) -> io::Result<NamedPipeTransport<crate::RoleServer, NamedPipeServer>> {
+        self.next.connect().await?;
+        let next = ServerOptions::new()
+            .reject_remote_clients(true)
+            .create(&self.name)?;
+        let connected = std::mem::replace(&mut self.next, next);
+        let (read, write) = tokio::io::split(connected);
+        Ok(AsyncRwTransport::new(read, write))
+    }
+}
@@ -0,0 +1,142 @@
+//! Observe the messages flowing through a transport without changing them.
+//!
+//! Wrap any [`Transport`] in an [`ObservedTransport`] to hand a copy of every
+//! inbound and outbound message to a [`TransportObserver`], e.g. to drive an
+//! inspector-style UI or to log raw protocol traffic.
+//!
+//! ```rust,no_run
+//! use rmcp::{
+//!     RoleServer, ServerHandler, ServiceExt,
+//!     service::{RxJsonRpcMessage, TxJsonRpcMessage},
+//!     transport::{
+//!         Transport,
+//!         observer::{ObservedMessage, ObservedTransport, TransportObserver},
+//!     },
+//! };
+//!
+//! struct MyServer;
+//! impl ServerHandler for MyServer {}
+//!
+//! struct PrintTraffic;
+//!
+//! impl TransportObserver<RoleServer> for PrintTraffic {
+//!     fn on_outbound(&self, message: ObservedMessage<TxJsonRpcMessage<RoleServer>>) {
+//!         eprintln!("--> {}", String::from_utf8_lossy(&message.raw));
+//!     }
+//!     fn on_inbound(&self, message: ObservedMessage<RxJsonRpcMessage<RoleServer>>) {
+//!         eprintln!("<-- {}", String::from_utf8_lossy(&message.raw));
+//!     }
+//! }
+//!
+//! # async fn example(transport: impl Transport<RoleServer> + 'static) -> Result<(), Box<dyn std::error::Error>> {
+//! let server = MyServer.serve(ObservedTransport::new(transport, PrintTraffic)).await?;
+//! # Ok(())
+//! # }
+//! ```
+use std::{borrow::Cow, sync::Arc};
+
+use super::Transport;
+use crate::service::{RxJsonRpcMessage, ServiceRole, TxJsonRpcMessage};
+
+/// A copy of a message seen by a [`TransportObserver`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ObservedMessage<M> {
+    /// The parsed message.
+    pub message: M,
+    /// The message serialized as JSON.
+    ///
+    /// This is re-encoded from [`message`](Self::message), so whitespace and
+    /// key order may differ from what the peer actually wrote.
+    pub raw: Vec<u8>,
+}
+
+impl<M: serde::Serialize + Clone> ObservedMessage<M> {
+    fn new(message: &M) -> Self {
+        Self {
+            message: message.clone(),
+            raw: serde_json::to_vec(message).unwrap_or_default(),
+        }
+    }
+}
+
+/// Receives a copy of every message passing through an [`ObservedTransport`].
+///
+/// Callbacks run inline on the transport's send and receive paths, so they
+/// should return quickly; hand the message off to a channel for anything slow.
+pub trait TransportObserver<R: ServiceRole>: Send + Sync + 'static {
+    /// Called with each message received from the peer, before the service
+    /// sees it.
+    fn on_inbound(&self, message: ObservedMessage<RxJsonRpcMessage<R>>) {
+        let _ = message;
+    }
+
+    /// Called with each message sent to the peer, before it is written to the
+    /// underlying transport.
+    fn on_outbound(&self, message: ObservedMessage<TxJsonRpcMessage<R>>) {
+        let _ = message;
+    }
+}
+
+impl<R: ServiceRole, O: TransportObserver<R>> TransportObserver<R> for Arc<O> {
+    fn on_inbound(&self, message: ObservedMessage<RxJsonRpcMessage<R>>) {
+        (**self).on_inbound(message)
+    }
+
+    fn on_outbound(&self, message: ObservedMessage<TxJsonRpcMessage<R>>) {
+        (**self).on_outbound(message)
+    }
+}
+
+/// A [`Transport`] that reports every message to a [`TransportObserver`]
+/// and otherwise behaves exactly like the transport it wraps.
+#[derive(Debug)]
+pub struct ObservedTransport<T, O> {
+    inner: T,
+    observer: O,
+}
+
+impl<T, O> ObservedTransport<T, O> {
+    pub fn new(inner: T, observer: O) -> Self {
+        Self { inner, observer }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<R, T, O> Transport<R> for ObservedTransport<T, O>
+where
+    R: ServiceRole,
+    T: Transport<R>,
+    O: TransportObserver<R>,
+{
+    type Error = T::Error;
+
+    fn name() -> Cow<'static, str> {
+        T::name()
+    }
+
+    fn send(
+        &mut self,
+        item: TxJsonRpcMessage<R>,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send + 'static {
+        self.observer.on_outbound(ObservedMessage::new(&item));
+        self.inner.send(item)
+    }
+
+    async fn receive(&mut self) -> Option<RxJsonRpcMessage<R>> {
+        let message = self.inner.receive().await?;
+        self.observer.on_inbound(ObservedMessage::new(&message));
+        Some(message)
+    }
+
+    fn take_error(&mut self) -> Option<Self::Error> {
+        self.inner.take_error()
+    }
+
+    fn close(&mut self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        self.inner.close()
+    }
+}
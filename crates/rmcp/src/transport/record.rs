@@ -0,0 +1,333 @@
+//! Record MCP sessions to disk and replay them later.
+//!
+//! [`SessionRecorder`] is a [`TransportObserver`] that appends every message
+//! it sees to a log. [`ReplayTransport`] reads such a log back and plays the
+//! server's side of it to a client, so an interaction captured once against a
+//! real server can be re-run in CI without that server.
+//!
+//! # Format
+//!
+//! A recording is newline-delimited JSON: one [`RecordedMessage`] object per
+//! line, in the order the messages passed through the transport.
+//!
+//! ```json
+//! {"direction":"client_to_server","timestamp":"2025-06-18T12:00:00.000Z","message":{"jsonrpc":"2.0","id":0,"method":"initialize","params":{...}}}
+//! {"direction":"server_to_client","timestamp":"2025-06-18T12:00:00.012Z","message":{"jsonrpc":"2.0","id":0,"result":{...}}}
+//! ```
+//!
+//! - `direction` is `"client_to_server"` or `"server_to_client"`, regardless
+//!   of which side the recorder was attached to.
+//! - `timestamp` is an RFC 3339 UTC timestamp taken when the message passed
+//!   through the transport.
+//! - `message` is the JSON-RPC message exactly as it is sent on the wire.
+//!
+//! Blank lines are ignored.
+use std::{
+    io::{BufRead, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::observer::{ObservedMessage, TransportObserver};
+use crate::service::{RxJsonRpcMessage, ServiceRole, TxJsonRpcMessage};
+
+/// Which way a [`RecordedMessage`] travelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[expect(clippy::exhaustive_enums, reason = "a message only has two directions")]
+pub enum Direction {
+    ClientToServer,
+    ServerToClient,
+}
+
+impl Direction {
+    fn outbound<R: ServiceRole>() -> Self {
+        if R::IS_CLIENT {
+            Self::ClientToServer
+        } else {
+            Self::ServerToClient
+        }
+    }
+
+    fn inbound<R: ServiceRole>() -> Self {
+        match Self::outbound::<R>() {
+            Self::ClientToServer => Self::ServerToClient,
+            Self::ServerToClient => Self::ClientToServer,
+        }
+    }
+}
+
+/// One line of a session recording.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct RecordedMessage {
+    pub direction: Direction,
+    pub timestamp: DateTime<Utc>,
+    pub message: serde_json::Value,
+}
+
+impl RecordedMessage {
+    pub fn new(direction: Direction, message: serde_json::Value) -> Self {
+        Self {
+            direction,
+            timestamp: Utc::now(),
+            message,
+        }
+    }
+}
+
+/// Errors reading a session recording.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum RecordingError {
+    #[error("failed to read recording: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid recording at line {line}: {source}")]
+    InvalidLine {
+        line: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("recorded message {index} is not a valid server message: {source}")]
+    InvalidServerMessage {
+        index: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// Read every [`RecordedMessage`] from a recording.
+pub fn read_recording(reader: impl BufRead) -> Result<Vec<RecordedMessage>, RecordingError> {
+    let mut messages = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let message =
+            serde_json::from_str(&line).map_err(|source| RecordingError::InvalidLine {
+                line: index + 1,
+                source,
+            })?;
+        messages.push(message);
+    }
+    Ok(messages)
+}
+
+/// A [`TransportObserver`] that writes every message to a recording.
+///
+/// Attach it to either side of a connection with
+/// [`ObservedTransport`](super::observer::ObservedTransport). Each message is
+/// written as soon as it is observed; write errors are logged and otherwise
+/// ignored so that recording never disturbs the session. Clones share the
+/// same writer.
+#[derive(Clone)]
+pub struct SessionRecorder {
+    writer: Arc<Mutex<Box<dyn Write + Send>>>,
+}
+
+impl std::fmt::Debug for SessionRecorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionRecorder").finish_non_exhaustive()
+    }
+}
+
+impl SessionRecorder {
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            writer: Arc::new(Mutex::new(Box::new(writer))),
+        }
+    }
+
+    /// Record to a new file at `path`, truncating any existing one.
+    pub fn create(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = std::fs::File::create(path)?;
+        Ok(Self::new(std::io::LineWriter::new(file)))
+    }
+
+    fn record<M: Serialize>(&self, direction: Direction, message: &M) {
+        let result = serde_json::to_value(message)
+            .map(|message| RecordedMessage::new(direction, message))
+            .map_err(std::io::Error::from)
+            .and_then(|record| {
+                let mut writer = self.writer.lock().expect("recorder lock poisoned");
+                serde_json::to_writer(&mut *writer, &record)?;
+                writer.write_all(b"\n")
+            });
+        if let Err(error) = result {
+            tracing::warn!(%error, "failed to record message");
+        }
+    }
+}
+
+impl<R: ServiceRole> TransportObserver<R> for SessionRecorder {
+    fn on_inbound(&self, message: ObservedMessage<RxJsonRpcMessage<R>>) {
+        self.record(Direction::inbound::<R>(), &message.message);
+    }
+
+    fn on_outbound(&self, message: ObservedMessage<TxJsonRpcMessage<R>>) {
+        self.record(Direction::outbound::<R>(), &message.message);
+    }
+}
+
+#[cfg(feature = "client")]
+pub use replay::ReplayTransport;
+
+#[cfg(feature = "client")]
+mod replay {
+    use std::{collections::VecDeque, convert::Infallible, io::BufRead, path::Path};
+
+    use tokio::sync::mpsc;
+
+    use super::{Direction, RecordedMessage, RecordingError, read_recording};
+    use crate::{
+        RoleClient,
+        model::{ClientJsonRpcMessage, JsonRpcMessage, RequestId, ServerJsonRpcMessage},
+        transport::Transport,
+    };
+
+    enum Step {
+        Deliver(Box<ServerJsonRpcMessage>),
+        Expect(serde_json::Value),
+    }
+
+    /// A client-side [`Transport`] that plays back the server's half of a
+    /// recording.
+    ///
+    /// Recorded server messages are delivered in order. Whenever the recording
+    /// shows a client message next, the transport waits for the client to send
+    /// one and checks that its `method` matches; request ids are translated so
+    /// recorded responses answer the client's actual requests. Timing is not
+    /// reproduced.
+    ///
+    /// Once the recording runs out the transport stays open until the client
+    /// closes it. The session ends early if the client sends anything the
+    /// recording does not expect; this is logged as an error.
+    pub struct ReplayTransport {
+        script: VecDeque<Step>,
+        sent_tx: mpsc::UnboundedSender<ClientJsonRpcMessage>,
+        sent_rx: mpsc::UnboundedReceiver<ClientJsonRpcMessage>,
+        ids: std::collections::HashMap<RequestId, RequestId>,
+    }
+
+    impl ReplayTransport {
+        /// Replay `recording`, failing if any server message in it is invalid.
+        pub fn new(
+            recording: impl IntoIterator<Item = RecordedMessage>,
+        ) -> Result<Self, RecordingError> {
+            let script = recording
+                .into_iter()
+                .enumerate()
+                .map(|(index, record)| match record.direction {
+                    Direction::ServerToClient => serde_json::from_value(record.message)
+                        .map(|message| Step::Deliver(Box::new(message)))
+                        .map_err(|source| RecordingError::InvalidServerMessage { index, source }),
+                    Direction::ClientToServer => Ok(Step::Expect(record.message)),
+                })
+                .collect::<Result<_, _>>()?;
+            let (sent_tx, sent_rx) = mpsc::unbounded_channel();
+            Ok(Self {
+                script,
+                sent_tx,
+                sent_rx,
+                ids: Default::default(),
+            })
+        }
+
+        /// Replay a recording read from `reader`.
+        pub fn from_reader(reader: impl BufRead) -> Result<Self, RecordingError> {
+            Self::new(read_recording(reader)?)
+        }
+
+        /// Replay the recording stored at `path`.
+        pub fn open(path: impl AsRef<Path>) -> Result<Self, RecordingError> {
+            let file = std::fs::File::open(path)?;
+            Self::from_reader(std::io::BufReader::new(file))
+        }
+
+        fn remap_id(&self, message: &mut ServerJsonRpcMessage) {
+            let id = match message {
+                JsonRpcMessage::Response(response) => Some(&mut response.id),
+                JsonRpcMessage::Error(error) => error.id.as_mut(),
+                _ => None,
+            };
+            if let Some(id) = id {
+                if let Some(actual) = self.ids.get(id) {
+                    *id = actual.clone();
+                }
+            }
+        }
+
+        fn accept(&mut self, expected: &serde_json::Value, actual: &ClientJsonRpcMessage) -> bool {
+            let Ok(actual) = serde_json::to_value(actual) else {
+                return false;
+            };
+            if expected.get("method") != actual.get("method") {
+                tracing::error!(%expected, %actual, "client message does not match the recording");
+                return false;
+            }
+            if expected.get("method").is_some() {
+                let id = |message: &serde_json::Value| {
+                    message
+                        .get("id")
+                        .and_then(|id| serde_json::from_value::<RequestId>(id.clone()).ok())
+                };
+                if let (Some(recorded), Some(actual)) = (id(expected), id(&actual)) {
+                    self.ids.insert(recorded, actual);
+                }
+            }
+            true
+        }
+    }
+
+    impl Transport<RoleClient> for ReplayTransport {
+        type Error = Infallible;
+
+        fn send(
+            &mut self,
+            item: ClientJsonRpcMessage,
+        ) -> impl Future<Output = Result<(), Self::Error>> + Send + 'static {
+            // The receiver lives in `self`, so this cannot fail.
+            let _ = self.sent_tx.send(item);
+            std::future::ready(Ok(()))
+        }
+
+        async fn receive(&mut self) -> Option<ServerJsonRpcMessage> {
+            // The service may drop this future and call again, so a step is
+            // only consumed once it has been fully handled.
+            loop {
+                match self.script.front() {
+                    Some(Step::Deliver(_)) => {
+                        let Some(Step::Deliver(mut message)) = self.script.pop_front() else {
+                            unreachable!()
+                        };
+                        self.remap_id(&mut message);
+                        return Some(*message);
+                    }
+                    Some(Step::Expect(_)) => {
+                        let actual = self.sent_rx.recv().await?;
+                        let Some(Step::Expect(expected)) = self.script.pop_front() else {
+                            unreachable!()
+                        };
+                        if !self.accept(&expected, &actual) {
+                            return None;
+                        }
+                    }
+                    None => {
+                        let actual = self.sent_rx.recv().await?;
+                        tracing::error!(?actual, "client sent a message after the recording ended");
+                        return None;
+                    }
+                }
+            }
+        }
+
+        async fn close(&mut self) -> Result<(), Self::Error> {
+            self.script.clear();
+            Ok(())
+        }
+    }
+}
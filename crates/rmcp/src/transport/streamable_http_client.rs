@@ -9,6 +9,13 @@ use tokio_util::sync::CancellationToken;
 use tracing::debug;
 
 use super::common::client_side_sse::{ExponentialBackoff, SseRetryPolicy, SseStreamReconnect};
+#[cfg(all(
+    feature = "transport-streamable-http-client-reqwest",
+    feature = "compression"
+))]
+pub use crate::transport::common::reqwest::GzipRequestClient;
+#[cfg(feature = "transport-streamable-http-client-reqwest")]
+pub use crate::transport::common::reqwest::JsonFormatClient;
 use crate::{
     RoleClient,
     model::{
@@ -1078,6 +1085,98 @@ pub struct StreamableHttpClientTransportConfig {
     /// This recovery is best-effort and bounded to a single attempt. If recovery fails,
     /// the original failure path is preserved and the error is returned to the caller.
     pub reinit_on_expired_session: bool,
+    /// Proxy settings for the HTTP client built by `from_config` and
+    /// `try_from_config`.
+    ///
+    /// `None` (the default) uses the standard `HTTP_PROXY`, `HTTPS_PROXY` and
+    /// `NO_PROXY` environment variables. Ignored when the transport is created
+    /// with [`StreamableHttpClientTransport::with_client`]; configure the proxy
+    /// on that client instead.
+    pub proxy: Option<ProxyConfig>,
+}
+
+/// How JSON-RPC messages are serialized into request bodies.
+///
+/// Object key order follows [`serde_json::Map`]: sorted by default, insertion
+/// order when rmcp's `json-preserve-order` feature is enabled.
+/// Line-delimited transports such as stdio always write compact JSON, since
+/// each message must fit on one line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum JsonFormat {
+    /// A single line with no insignificant whitespace.
+    #[default]
+    Compact,
+    /// Indented, multi-line output; useful when inspecting traffic.
+    Pretty,
+}
+
+impl JsonFormat {
+    pub fn to_vec<T: serde::Serialize + ?Sized>(self, value: &T) -> serde_json::Result<Vec<u8>> {
+        match self {
+            JsonFormat::Compact => serde_json::to_vec(value),
+            JsonFormat::Pretty => serde_json::to_vec_pretty(value),
+        }
+    }
+}
+
+/// Explicit HTTP(S) proxy settings, replacing the proxy environment variables.
+///
+/// A `ProxyConfig` with neither `http_proxy` nor `https_proxy` set disables
+/// proxying altogether.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rmcp::transport::streamable_http_client::{
+///     ProxyConfig, StreamableHttpClientTransportConfig,
+/// };
+///
+/// let config = StreamableHttpClientTransportConfig::with_uri("https://mcp.example.com/mcp")
+///     .proxy(
+///         ProxyConfig::default()
+///             .https_proxy("http://proxy.corp.example:3128")
+///             .no_proxy("localhost,.corp.example")
+///             .credentials("alice", "secret"),
+///     );
+/// ```
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct ProxyConfig {
+    /// Proxy URL for `http://` requests
+    pub http_proxy: Option<String>,
+    /// Proxy URL for `https://` requests
+    pub https_proxy: Option<String>,
+    /// Hosts that bypass the proxy, in the comma-separated `NO_PROXY` format
+    pub no_proxy: Option<String>,
+    /// Basic auth username and password sent to the proxy
+    pub credentials: Option<(String, String)>,
+}
+
+impl ProxyConfig {
+    /// Set the proxy for `http://` requests
+    pub fn http_proxy(mut self, url: impl Into<String>) -> Self {
+        self.http_proxy = Some(url.into());
+        self
+    }
+
+    /// Set the proxy for `https://` requests
+    pub fn https_proxy(mut self, url: impl Into<String>) -> Self {
+        self.https_proxy = Some(url.into());
+        self
+    }
+
+    /// Set the hosts that bypass the proxy, e.g. `"localhost,.internal.example"`
+    pub fn no_proxy(mut self, hosts: impl Into<String>) -> Self {
+        self.no_proxy = Some(hosts.into());
+        self
+    }
+
+    /// Authenticate to the proxy with basic auth
+    pub fn credentials(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.credentials = Some((username.into(), password.into()));
+        self
+    }
 }
 
 impl StreamableHttpClientTransportConfig {
@@ -1138,6 +1237,13 @@ impl StreamableHttpClientTransportConfig {
         self.reinit_on_expired_session = enable;
         self
     }
+
+    /// Route requests through an explicit proxy instead of the environment's.
+    /// See [`Self::proxy`] for details.
+    pub fn proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
 }
 
 impl Default for StreamableHttpClientTransportConfig {
@@ -1150,6 +1256,7 @@ impl Default for StreamableHttpClientTransportConfig {
             auth_header: None,
             custom_headers: HashMap::new(),
             reinit_on_expired_session: true,
+            proxy: None,
         }
     }
 }
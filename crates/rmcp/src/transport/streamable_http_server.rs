@@ -1,6 +1,10 @@
+#[cfg(all(feature = "transport-streamable-http-server", not(feature = "local")))]
+pub mod download;
 pub mod session;
 #[cfg(all(feature = "transport-streamable-http-server", not(feature = "local")))]
 pub mod tower;
+#[cfg(all(feature = "transport-streamable-http-server", not(feature = "local")))]
+pub use download::BinaryDownloads;
 pub use session::{RestoreOutcome, SessionId, SessionManager, SessionRestoreMarker};
 #[cfg(all(feature = "transport-streamable-http-server", not(feature = "local")))]
-pub use tower::{StreamableHttpServerConfig, StreamableHttpService};
+pub use tower::{AuthContext, StreamableHttpServerConfig, StreamableHttpService};
@@ -0,0 +1,251 @@
+//! Delivering binary resource contents as raw HTTP bodies.
+//!
+//! `resources/read` normally carries binary contents inline as base64, which
+//! inflates them by a third and keeps the whole encoded string in memory on
+//! both ends. With [`BinaryDownloads`] configured, a server can instead park
+//! the bytes under a short-lived download route of the same
+//! [`StreamableHttpService`](super::StreamableHttpService) and return blob
+//! contents that only describe where to fetch them. Clients opt in by
+//! declaring the
+//! [`ResourceContents::BINARY_DOWNLOAD_EXTENSION`](crate::model::ResourceContents::BINARY_DOWNLOAD_EXTENSION)
+//! extension;
+//! all others keep receiving inline base64.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use http::Response;
+use http_body_util::{BodyExt, Full};
+
+#[cfg(feature = "base64")]
+use crate::model::ResourceContents;
+use crate::{
+    RoleServer, service::RequestContext, transport::common::server_side_http::BoxResponse,
+};
+
+#[derive(Debug)]
+struct Download {
+    bytes: Bytes,
+    mime_type: Option<String>,
+    expires: Instant,
+}
+
+#[derive(Debug, Default)]
+struct Store {
+    downloads: HashMap<String, Download>,
+    /// The combined size of `downloads`.
+    total_bytes: usize,
+}
+
+impl Store {
+    fn remove_expired(&mut self, now: Instant) {
+        let total_bytes = &mut self.total_bytes;
+        self.downloads.retain(|_, download| {
+            let keep = download.expires > now;
+            if !keep {
+                *total_bytes -= download.bytes.len();
+            }
+            keep
+        });
+    }
+}
+
+/// Short-lived downloads served next to the MCP endpoint.
+///
+/// Enable with
+/// [`StreamableHttpServerConfig::with_binary_downloads`](super::StreamableHttpServerConfig::with_binary_downloads).
+/// The service then answers `GET <path>/<token>` with the registered bytes
+/// and makes this store available to handlers, which use
+/// [`offer`](Self::offer) to build the contents of a `resources/read` result.
+///
+/// Tokens are random and unguessable, and each can be fetched once before
+/// [`with_ttl`](Self::with_ttl) runs out. They are the only credential
+/// checked when a download is fetched, so keep the TTL short. Parked bytes
+/// are capped by [`with_max_total_bytes`](Self::with_max_total_bytes);
+/// blobs that do not fit are sent inline instead.
+///
+/// # Example
+/// ```rust,ignore
+/// async fn read_resource(
+///     &self,
+///     request: ReadResourceRequestParams,
+///     context: RequestContext<RoleServer>,
+/// ) -> Result<ReadResourceResult, ErrorData> {
+///     let bytes = std::fs::read(&self.path).map_err(|e| ErrorData::internal_error(e.to_string(), None))?;
+///     let contents = BinaryDownloads::offer(&context, request.uri, Some("image/png".into()), bytes);
+///     Ok(ReadResourceResult::new(vec![contents]))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BinaryDownloads {
+    path: String,
+    ttl: Duration,
+    min_size: usize,
+    max_total_bytes: usize,
+    store: Arc<Mutex<Store>>,
+}
+
+impl BinaryDownloads {
+    /// Serve downloads under `path`, e.g. `"/downloads"`.
+    ///
+    /// Like [`health_path`](super::StreamableHttpServerConfig::health_path),
+    /// the path is matched against the request URI as seen by the service,
+    /// after any router prefix has been stripped. Downloads expire after five
+    /// minutes by default, and at most 64 MiB are parked at once.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into().trim_end_matches('/').to_string(),
+            ttl: Duration::from_secs(300),
+            min_size: 0,
+            max_total_bytes: 64 * 1024 * 1024,
+            store: Default::default(),
+        }
+    }
+
+    /// How long a registered download can be fetched.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Keep blobs smaller than `min_size` bytes inline, where the extra
+    /// request costs more than the base64 overhead saves.
+    pub fn with_min_size(mut self, min_size: usize) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Park at most `max_total_bytes` across all downloads not yet fetched or
+    /// expired.
+    pub fn with_max_total_bytes(mut self, max_total_bytes: usize) -> Self {
+        self.max_total_bytes = max_total_bytes;
+        self
+    }
+
+    /// The downloads store of the service handling this request, if any.
+    pub fn from_context(context: &RequestContext<RoleServer>) -> Option<&Self> {
+        context
+            .extensions
+            .get::<http::request::Parts>()?
+            .extensions
+            .get::<Self>()
+    }
+
+    /// Park `bytes` for a single download and return the path to fetch them
+    /// from, or `None` if they would exceed
+    /// [`with_max_total_bytes`](Self::with_max_total_bytes).
+    pub fn register(&self, bytes: impl Into<Bytes>, mime_type: Option<String>) -> Option<String> {
+        let bytes = bytes.into();
+        let now = Instant::now();
+        let mut store = self.store.lock().expect("downloads lock poisoned");
+        store.remove_expired(now);
+        let total_bytes = store.total_bytes.checked_add(bytes.len())?;
+        if total_bytes > self.max_total_bytes {
+            return None;
+        }
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        store.total_bytes = total_bytes;
+        store.downloads.insert(
+            token.clone(),
+            Download {
+                bytes,
+                mime_type,
+                expires: now + self.ttl,
+            },
+        );
+        Some(format!("{}/{token}", self.path))
+    }
+
+    /// Blob contents for `uri`, delivered as a download when possible.
+    ///
+    /// The bytes are registered for download if the service has
+    /// [`BinaryDownloads`] enabled, the client declared
+    /// [`ResourceContents::BINARY_DOWNLOAD_EXTENSION`], and they are at least
+    /// [`with_min_size`](Self::with_min_size) long, and they fit within
+    /// [`with_max_total_bytes`](Self::with_max_total_bytes). The returned contents then
+    /// have an empty `blob` and describe the download in `_meta` (see
+    /// [`ResourceContents::download_path`]). Otherwise they carry the bytes
+    /// inline as base64.
+    #[cfg(feature = "base64")]
+    pub fn offer(
+        context: &RequestContext<RoleServer>,
+        uri: impl Into<String>,
+        mime_type: Option<String>,
+        bytes: impl Into<Bytes>,
+    ) -> ResourceContents {
+        use base64::{Engine, prelude::BASE64_STANDARD};
+
+        let bytes = bytes.into();
+        let downloads = Self::from_context(context)
+            .filter(|downloads| bytes.len() >= downloads.min_size && client_accepts(context));
+        let size = bytes.len();
+        let path =
+            downloads.and_then(|downloads| downloads.register(bytes.clone(), mime_type.clone()));
+        let (blob, meta) = match path {
+            Some(path) => {
+                let mut meta = crate::model::Meta::new();
+                meta.insert(
+                    ResourceContents::DOWNLOAD_META_KEY.to_string(),
+                    serde_json::json!({ "path": path, "size": size }),
+                );
+                (String::new(), Some(meta))
+            }
+            None => (BASE64_STANDARD.encode(&bytes), None),
+        };
+        ResourceContents::BlobResourceContents {
+            uri: uri.into(),
+            mime_type,
+            blob,
+            meta,
+        }
+    }
+
+    /// Answer `GET <path>/<token>`, or return `None` for other paths.
+    ///
+    /// A download is removed once it has been served.
+    pub(super) fn serve(&self, request_path: &str) -> Option<BoxResponse> {
+        let token = request_path.strip_prefix(&self.path)?.strip_prefix('/')?;
+        let now = Instant::now();
+        let download = {
+            let mut store = self.store.lock().expect("downloads lock poisoned");
+            store.remove_expired(now);
+            let download = store.downloads.remove(token);
+            if let Some(download) = &download {
+                store.total_bytes -= download.bytes.len();
+            }
+            download
+        };
+        let response = match download {
+            Some(Download {
+                bytes, mime_type, ..
+            }) => Response::builder()
+                .status(http::StatusCode::OK)
+                .header(
+                    http::header::CONTENT_TYPE,
+                    mime_type.as_deref().unwrap_or("application/octet-stream"),
+                )
+                .header(http::header::CACHE_CONTROL, "no-store")
+                .body(Full::new(bytes).boxed()),
+            None => Response::builder()
+                .status(http::StatusCode::NOT_FOUND)
+                .body(Full::new(Bytes::from("Not Found")).boxed()),
+        };
+        Some(response.expect("valid response"))
+    }
+}
+
+#[cfg(feature = "base64")]
+fn client_accepts(context: &RequestContext<RoleServer>) -> bool {
+    context.peer.peer_info().is_some_and(|info| {
+        info.capabilities
+            .extensions
+            .as_ref()
+            .is_some_and(|extensions| {
+                extensions.contains_key(ResourceContents::BINARY_DOWNLOAD_EXTENSION)
+            })
+    })
+}
@@ -22,7 +22,9 @@
 
 use futures::Stream;
 
-pub use crate::transport::common::server_side_http::{ServerSseMessage, SessionId};
+pub use crate::transport::common::server_side_http::{
+    ServerSseMessage, SessionId, ValidatedSession,
+};
 use crate::{
     RoleServer,
     model::{ClientJsonRpcMessage, ServerJsonRpcMessage},
@@ -108,6 +110,23 @@ pub trait SessionManager: Send + Sync + 'static {
         Output = Result<impl Stream<Item = ServerSseMessage> + Send + Sync + 'static, Self::Error>,
     > + Send;
 
+    /// Route a client request whose response will be returned as a single
+    /// `application/json` body rather than an SSE stream.
+    ///
+    /// Only the final response or error on the returned stream reaches the
+    /// client, so managers should send everything else the handler emits for
+    /// this request, such as progress notifications, to the standalone stream
+    /// instead. The default forwards to [`create_stream`](Self::create_stream).
+    fn create_json_stream(
+        &self,
+        id: &SessionId,
+        message: ClientJsonRpcMessage,
+    ) -> impl Future<
+        Output = Result<impl Stream<Item = ServerSseMessage> + Send + Sync + 'static, Self::Error>,
+    > + Send {
+        self.create_stream(id, message)
+    }
+
     /// Accept a notification, response, or error message from the client
     /// without producing a response stream.
     fn accept_message(
@@ -44,6 +44,70 @@ pub enum LocalSessionManagerError {
     #[error("Invalid event id: {0}")]
     InvalidEventId(#[from] EventIdParseError),
 }
+/// A snapshot of the sessions held by a [`LocalSessionManager`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SessionManagerStats {
+    /// Number of sessions currently held by the manager.
+    pub session_count: usize,
+    /// Number of SSE streams open across all sessions.
+    pub total_streams: usize,
+}
+
+impl LocalSessionManager {
+    /// Report how many sessions and SSE streams are currently active.
+    ///
+    /// This is read-only introspection, e.g. for health endpoints. Sessions
+    /// whose worker has already terminated count towards `session_count`
+    /// but contribute no streams.
+    pub async fn stats(&self) -> SessionManagerStats {
+        // Query the workers after releasing the lock, so that a slow worker
+        // does not hold up session creation and removal.
+        let handles: Vec<_> = self.sessions.read().await.values().cloned().collect();
+        let mut total_streams = 0;
+        for handle in &handles {
+            total_streams += handle.active_stream_count().await.unwrap_or(0);
+        }
+        SessionManagerStats {
+            session_count: handles.len(),
+            total_streams,
+        }
+    }
+
+    /// Turn a common-channel receiver into an SSE stream, applying
+    /// [`SessionConfig::shadow_idle_timeout`] if the receiver is a shadow.
+    fn common_stream(
+        &self,
+        receiver: StreamableHttpMessageReceiver,
+        last_event_id: String,
+    ) -> impl Stream<Item = ServerSseMessage> + Send + Sync + 'static + use<> {
+        let stream = ReceiverStream::new(receiver.inner);
+        let Some(idle_timeout) = self
+            .session_config
+            .shadow_idle_timeout
+            .filter(|_| receiver.is_shadow)
+        else {
+            return stream.left_stream();
+        };
+        let closing = self
+            .session_config
+            .sse_retry
+            .map(|retry| ServerSseMessage::priming(last_event_id, retry));
+        futures::stream::unfold(stream, move |mut stream| async move {
+            match tokio::time::timeout(idle_timeout, stream.next()).await {
+                Ok(Some(message)) => Some((message, stream)),
+                Ok(None) => None,
+                Err(_) => {
+                    tracing::debug!(?idle_timeout, "closing idle shadow stream");
+                    None
+                }
+            }
+        })
+        .chain(futures::stream::iter(closing))
+        .right_stream()
+    }
+}
+
 impl SessionManager for LocalSessionManager {
     type Error = LocalSessionManagerError;
     type Transport = WorkerTransport<LocalSessionWorker>;
@@ -106,6 +170,22 @@ impl SessionManager for LocalSessionManager {
         Ok(futures::stream::iter(priming).chain(ReceiverStream::new(receiver.inner)))
     }
 
+    async fn create_json_stream(
+        &self,
+        id: &SessionId,
+        message: ClientJsonRpcMessage,
+    ) -> Result<impl Stream<Item = ServerSseMessage> + Send + 'static, Self::Error> {
+        let sessions = self.sessions.read().await;
+        let handle = sessions
+            .get(id)
+            .ok_or(LocalSessionManagerError::SessionNotFound(id.clone()))?;
+        let receiver = handle.establish_json_request_wise_channel().await?;
+        handle
+            .push_message(message, receiver.http_request_id)
+            .await?;
+        Ok(ReceiverStream::new(receiver.inner))
+    }
+
     async fn create_standalone_stream(
         &self,
         id: &SessionId,
@@ -115,7 +195,7 @@ impl SessionManager for LocalSessionManager {
             .get(id)
             .ok_or(LocalSessionManagerError::SessionNotFound(id.clone()))?;
         let receiver = handle.establish_common_channel().await?;
-        Ok(ReceiverStream::new(receiver.inner))
+        Ok(self.common_stream(receiver, "0".into()))
     }
 
     async fn resume(
@@ -128,7 +208,7 @@ impl SessionManager for LocalSessionManager {
             .get(id)
             .ok_or(LocalSessionManagerError::SessionNotFound(id.clone()))?;
         let receiver = handle.resume(last_event_id.parse()?).await?;
-        Ok(ReceiverStream::new(receiver.inner))
+        Ok(self.common_stream(receiver, last_event_id))
     }
 
     async fn accept_message(
@@ -307,6 +387,9 @@ struct HttpRequestWise {
     resources: HashSet<ResourceKey>,
     tx: CachedTx,
     completed_at: Option<Instant>,
+    /// Only the final response is delivered to the client, so progress for
+    /// the request goes to the common channel instead.
+    json_response: bool,
 }
 
 type HttpRequestId = u64;
@@ -336,6 +419,17 @@ impl LocalSessionWorker {
     pub fn id(&self) -> &SessionId {
         &self.id
     }
+
+    fn active_stream_count(&self) -> usize {
+        let common = usize::from(!self.common.tx.is_closed());
+        let shadows = self.shadow_txs.iter().filter(|tx| !tx.is_closed()).count();
+        let request_wise = self
+            .tx_router
+            .values()
+            .filter(|channel| !channel.tx.tx.is_closed())
+            .count();
+        common + shadows + request_wise
+    }
 }
 
 #[derive(Debug, Error)]
@@ -372,6 +466,8 @@ enum OutboundChannel {
 #[non_exhaustive]
 pub struct StreamableHttpMessageReceiver {
     pub http_request_id: Option<HttpRequestId>,
+    /// Whether this is a shadow of the still-active common channel.
+    pub is_shadow: bool,
     pub inner: Receiver<ServerSseMessage>,
 }
 
@@ -417,6 +513,13 @@ impl LocalSessionWorker {
             ResourceKey::McpRequestId(request.id.clone()),
             http_request_id,
         );
+        let json_response = self
+            .tx_router
+            .get(&http_request_id)
+            .is_some_and(|channel| channel.json_response);
+        if json_response {
+            return;
+        }
         if let Some(progress_token) = request.request.get_meta().get_progress_token() {
             self.register_resource(
                 ResourceKey::ProgressToken(progress_token.clone()),
@@ -447,6 +550,7 @@ impl LocalSessionWorker {
     }
     async fn establish_request_wise_channel(
         &mut self,
+        json_response: bool,
     ) -> Result<StreamableHttpMessageReceiver, SessionError> {
         let http_request_id = self.next_http_request_id();
         let (tx, rx) = tokio::sync::mpsc::channel(self.session_config.channel_capacity);
@@ -457,11 +561,13 @@ impl LocalSessionWorker {
                 resources: Default::default(),
                 tx: CachedTx::new(tx, Some(http_request_id), starting_index),
                 completed_at: None,
+                json_response,
             },
         );
         tracing::debug!(http_request_id, "establish new request wise channel");
         Ok(StreamableHttpMessageReceiver {
             http_request_id: Some(http_request_id),
+            is_shadow: false,
             inner: rx,
         })
     }
@@ -590,6 +696,7 @@ impl LocalSessionWorker {
                 }
                 Ok(StreamableHttpMessageReceiver {
                     http_request_id: Some(http_request_id),
+                    is_shadow: false,
                     inner: rx,
                 })
             }
@@ -649,6 +756,7 @@ impl LocalSessionWorker {
         }
         Ok(StreamableHttpMessageReceiver {
             http_request_id: None,
+            is_shadow: !is_replacing_dead_primary,
             inner: rx,
         })
     }
@@ -710,6 +818,7 @@ pub enum SessionEvent {
         http_request_id: Option<HttpRequestId>,
     },
     EstablishRequestWiseChannel {
+        json_response: bool,
         responder: oneshot::Sender<Result<StreamableHttpMessageReceiver, SessionError>>,
     },
     CloseRequestWiseChannel {
@@ -725,6 +834,9 @@ pub enum SessionEvent {
         responder: oneshot::Sender<Result<ServerJsonRpcMessage, SessionError>>,
     },
     Close,
+    ActiveStreamCount {
+        responder: oneshot::Sender<usize>,
+    },
     CloseSseStream {
         /// The HTTP request ID to close. If `None`, closes the standalone (common) stream.
         http_request_id: Option<HttpRequestId>,
@@ -766,6 +878,17 @@ impl LocalSessionHandle {
         Ok(())
     }
 
+    /// Count the SSE streams currently open on this session: the common
+    /// channel, its shadows and any request-wise streams.
+    pub async fn active_stream_count(&self) -> Result<usize, SessionError> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.event_tx
+            .send(SessionEvent::ActiveStreamCount { responder: tx })
+            .await
+            .map_err(|_| SessionError::SessionServiceTerminated)?;
+        rx.await.map_err(|_| SessionError::SessionServiceTerminated)
+    }
+
     /// Send a message to the session
     pub async fn push_message(
         &self,
@@ -787,10 +910,28 @@ impl LocalSessionHandle {
     /// or you can close it manually by calling [`LocalSessionHandle::close_request_wise_channel`].
     pub async fn establish_request_wise_channel(
         &self,
+    ) -> Result<StreamableHttpMessageReceiver, SessionError> {
+        self.request_wise_channel(false).await
+    }
+
+    /// Like [`LocalSessionHandle::establish_request_wise_channel`], for a request answered
+    /// with a single JSON body. Its progress notifications go to the common channel.
+    pub async fn establish_json_request_wise_channel(
+        &self,
+    ) -> Result<StreamableHttpMessageReceiver, SessionError> {
+        self.request_wise_channel(true).await
+    }
+
+    async fn request_wise_channel(
+        &self,
+        json_response: bool,
     ) -> Result<StreamableHttpMessageReceiver, SessionError> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.event_tx
-            .send(SessionEvent::EstablishRequestWiseChannel { responder: tx })
+            .send(SessionEvent::EstablishRequestWiseChannel {
+                json_response,
+                responder: tx,
+            })
             .await
             .map_err(|_| SessionError::SessionServiceTerminated)?;
         rx.await
@@ -973,29 +1114,42 @@ impl Worker for LocalSessionWorker {
             FromHandler(WorkerSendRequest<LocalSessionWorker>),
         }
         let init_timeout = self.session_config.init_timeout.unwrap_or(Duration::MAX);
-        let evt = tokio::select! {
-            evt = self.event_rx.recv() => evt.ok_or_else(|| {
-                WorkerQuitReason::fatal(
-                    LocalSessionWorkerError::TransportTerminated,
-                    "get initialize request",
-                )
-            })?,
-            _ = context.cancellation_token.cancelled() => {
-                return Err(WorkerQuitReason::Cancelled);
-            }
-            _ = tokio::time::sleep(init_timeout) => {
-                return Err(WorkerQuitReason::fatal(
-                    LocalSessionWorkerError::InitTimeout(init_timeout),
-                    "waiting for initialize request",
-                ));
+        let init_deadline = tokio::time::sleep(init_timeout);
+        tokio::pin!(init_deadline);
+        let (request, responder) = loop {
+            let evt = tokio::select! {
+                evt = self.event_rx.recv() => evt.ok_or_else(|| {
+                    WorkerQuitReason::fatal(
+                        LocalSessionWorkerError::TransportTerminated,
+                        "get initialize request",
+                    )
+                })?,
+                _ = context.cancellation_token.cancelled() => {
+                    return Err(WorkerQuitReason::Cancelled);
+                }
+                _ = &mut init_deadline => {
+                    return Err(WorkerQuitReason::fatal(
+                        LocalSessionWorkerError::InitTimeout(init_timeout),
+                        "waiting for initialize request",
+                    ));
+                }
+            };
+            match evt {
+                SessionEvent::InitializeRequest { request, responder } => {
+                    break (request, responder);
+                }
+                // Introspection must not tear down a session that is still initializing.
+                SessionEvent::ActiveStreamCount { responder } => {
+                    let _ = responder.send(self.active_stream_count());
+                }
+                evt => {
+                    return Err(WorkerQuitReason::fatal(
+                        LocalSessionWorkerError::UnexpectedEvent(evt),
+                        "get initialize request",
+                    ));
+                }
             }
         };
-        let SessionEvent::InitializeRequest { request, responder } = evt else {
-            return Err(WorkerQuitReason::fatal(
-                LocalSessionWorkerError::UnexpectedEvent(evt),
-                "get initialize request",
-            ));
-        };
         context.send_to_handler(request).await?;
         let send_initialize_response = context.recv_from_handler().await?;
         responder
@@ -1080,9 +1234,10 @@ impl Worker for LocalSessionWorker {
                     context.send_to_handler(json_rpc_message).await?;
                 }
                 InnerEvent::FromHttpService(SessionEvent::EstablishRequestWiseChannel {
+                    json_response,
                     responder,
                 }) => {
-                    let handle_result = self.establish_request_wise_channel().await;
+                    let handle_result = self.establish_request_wise_channel(json_response).await;
                     let _ = responder.send(handle_result);
                 }
                 InnerEvent::FromHttpService(SessionEvent::CloseRequestWiseChannel {
@@ -1099,6 +1254,9 @@ impl Worker for LocalSessionWorker {
                     let handle_result = self.resume(last_event_id).await;
                     let _ = responder.send(handle_result);
                 }
+                InnerEvent::FromHttpService(SessionEvent::ActiveStreamCount { responder }) => {
+                    let _ = responder.send(self.active_stream_count());
+                }
                 InnerEvent::FromHttpService(SessionEvent::Close) => {
                     return Err(WorkerQuitReason::TransportClosed);
                 }
@@ -1149,6 +1307,15 @@ pub struct SessionConfig {
     /// creation. If not received within this window, the session is
     /// terminated. Default is 60 seconds. Set to `None` to disable.
     pub init_timeout: Option<Duration>,
+    /// Close a shadow SSE stream after this long without sending an event.
+    ///
+    /// A shadow is a standalone GET stream opened while the session's primary
+    /// common channel is still active; it only carries keep-alive pings, so
+    /// abandoned shadows would otherwise linger until the session ends. When
+    /// the timeout fires the shadow is closed with a final priming event
+    /// (if `sse_retry` is set) so the client knows when to reconnect. The
+    /// primary common channel is never affected. Default is `None` (disabled).
+    pub shadow_idle_timeout: Option<Duration>,
 }
 
 impl SessionConfig {
@@ -1167,6 +1334,7 @@ impl Default for SessionConfig {
             sse_retry: Some(Self::DEFAULT_SSE_RETRY),
             completed_cache_ttl: Self::DEFAULT_COMPLETED_CACHE_TTL,
             init_timeout: Some(Self::DEFAULT_INIT_TIMEOUT),
+            shadow_idle_timeout: None,
         }
     }
 }
@@ -10,11 +10,23 @@ use crate::model::InitializeRequestParams;
 pub struct SessionState {
     /// Parameters from the client's original `initialize` request.
     pub initialize_params: InitializeRequestParams,
+    /// The [`AuthContext::subject`](crate::transport::streamable_http_server::AuthContext::subject)
+    /// the session belongs to, when an authorizer is configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
 }
 
 impl SessionState {
     pub fn new(initialize_params: InitializeRequestParams) -> Self {
-        Self { initialize_params }
+        Self {
+            initialize_params,
+            subject: None,
+        }
+    }
+
+    pub fn with_subject(mut self, subject: Option<String>) -> Self {
+        self.subject = subject;
+        self
     }
 }
 
@@ -6,22 +6,26 @@ use bytes::Bytes;
 use futures::{StreamExt, future::BoxFuture};
 use http::{HeaderMap, Method, Request, Response, header::ALLOW};
 use http_body::Body;
-use http_body_util::{BodyExt, Full, combinators::BoxBody};
+use http_body_util::{BodyExt, Empty, Full, combinators::BoxBody};
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::sync::CancellationToken;
 
-use super::session::{
-    RestoreOutcome, SessionId, SessionManager, SessionRestoreMarker, SessionState, SessionStore,
+use super::{
+    download::BinaryDownloads,
+    session::{
+        RestoreOutcome, SessionId, SessionManager, SessionRestoreMarker, SessionState,
+        SessionStore, ValidatedSession,
+    },
 };
 use crate::{
     RoleServer,
     model::{
-        ClientCapabilities, ClientJsonRpcMessage, ClientNotification, ClientRequest, ErrorData,
-        GetExtensions, Implementation, InitializeRequest, InitializeRequestParams,
+        ClientCapabilities, ClientJsonRpcMessage, ClientNotification, ClientRequest, ErrorCode,
+        ErrorData, GetExtensions, Implementation, InitializeRequest, InitializeRequestParams,
         InitializedNotification, JsonRpcError, ProtocolVersion, RequestId,
     },
     serve_server,
-    service::serve_directly,
+    service::{LoggingThrottleConfig, serve_directly, serve_server_with_logging_throttle},
     transport::{
         OneshotTransport, TransportAdapterIdentity,
         common::{
@@ -31,7 +35,8 @@ use crate::{
             },
             server_side_http::{
                 BoxResponse, ServerSseMessage, accepted_response, expect_json,
-                internal_error_response, sse_stream_response, unexpected_message_response,
+                internal_error_response, json_stream_response, sse_stream_response,
+                unexpected_message_response,
             },
         },
     },
@@ -51,6 +56,10 @@ pub struct StreamableHttpServerConfig {
     /// `Content-Type: application/json` directly instead of `text/event-stream`.
     /// This eliminates SSE framing overhead for simple request-response tools,
     /// allowed by the MCP Streamable HTTP spec (2025-06-18).
+    ///
+    /// This only breaks the tie when the client's `Accept` header allows both
+    /// formats. A client accepting just one of them always gets that one, in
+    /// either mode, and a client accepting neither gets `406 Not Acceptable`.
     pub json_response: bool,
     /// Cancellation token for the Streamable HTTP server.
     ///
@@ -98,6 +107,42 @@ pub struct StreamableHttpServerConfig {
     /// };
     /// ```
     pub session_store: Option<Arc<dyn SessionStore>>,
+    /// Metrics hooks invoked for every request handled by a session.
+    ///
+    /// Defaults to [`NoopMetrics`](crate::service::NoopMetrics).
+    #[cfg(feature = "metrics")]
+    pub metrics: Arc<dyn crate::service::ServerMetrics>,
+    /// Gzip `application/json` responses of at least this many bytes when the
+    /// client sends `Accept-Encoding: gzip`; `None` disables it. SSE responses
+    /// are never compressed.
+    ///
+    /// Requests sent with `Content-Encoding: gzip` or `deflate` are always
+    /// decompressed when the `compression` feature is enabled.
+    #[cfg(feature = "compression")]
+    pub response_compression_threshold: Option<usize>,
+    /// Maximum number of requests a single session may have in flight.
+    ///
+    /// Further requests are rejected with HTTP 429 and a JSON-RPC `-32000`
+    /// error until earlier ones complete. `None` (the default) means no limit.
+    /// Only applies in stateful mode.
+    pub max_in_flight_requests_per_session: Option<usize>,
+    /// Path of a liveness route answered with `200 OK` on `GET`, e.g. `"/healthz"`.
+    ///
+    /// The route is served before Host/Origin validation and never touches the
+    /// session manager, so load balancer probes don't create MCP sessions. The
+    /// path is matched against the request URI as seen by this service (after
+    /// any router prefix has been stripped). `None` (the default) disables it.
+    pub health_path: Option<String>,
+    /// Route for delivering binary resource contents as raw HTTP bodies
+    /// instead of inline base64; see [`BinaryDownloads`]. `None` (the
+    /// default) disables it.
+    pub binary_downloads: Option<BinaryDownloads>,
+    /// Route each session's log notifications through a
+    /// [`LoggingThrottle`](crate::service::LoggingThrottle) built from this
+    /// config; see [`serve_server_with_logging_throttle`]. An invalid config
+    /// makes sessions fail to start. Only applies in stateful mode. `None`
+    /// (the default) sends log messages unthrottled.
+    pub logging_throttle: Option<LoggingThrottleConfig>,
 }
 
 impl std::fmt::Debug for dyn SessionStore {
@@ -117,6 +162,14 @@ impl Default for StreamableHttpServerConfig {
             allowed_hosts: vec!["localhost".into(), "127.0.0.1".into(), "::1".into()],
             allowed_origins: vec![],
             session_store: None,
+            #[cfg(feature = "metrics")]
+            metrics: Arc::new(crate::service::NoopMetrics),
+            #[cfg(feature = "compression")]
+            response_compression_threshold: Some(1024),
+            max_in_flight_requests_per_session: None,
+            health_path: None,
+            binary_downloads: None,
+            logging_throttle: None,
         }
     }
 }
@@ -170,8 +223,46 @@ impl StreamableHttpServerConfig {
         self.cancellation_token = token;
         self
     }
+
+    #[cfg(feature = "metrics")]
+    pub fn with_metrics(mut self, metrics: Arc<dyn crate::service::ServerMetrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    pub fn with_max_in_flight_requests_per_session(mut self, limit: Option<usize>) -> Self {
+        self.max_in_flight_requests_per_session = limit;
+        self
+    }
+
+    #[cfg(feature = "compression")]
+    pub fn with_response_compression_threshold(mut self, threshold: Option<usize>) -> Self {
+        self.response_compression_threshold = threshold;
+        self
+    }
+
+    pub fn with_binary_downloads(mut self, downloads: BinaryDownloads) -> Self {
+        self.binary_downloads = Some(downloads);
+        self
+    }
+
+    pub fn with_health_path(mut self, path: impl Into<String>) -> Self {
+        self.health_path = Some(path.into());
+        self
+    }
+
+    pub fn with_logging_throttle(mut self, config: LoggingThrottleConfig) -> Self {
+        self.logging_throttle = Some(config);
+        self
+    }
 }
 
+/// The service actually served for each session or stateless request.
+#[cfg(feature = "metrics")]
+type ServedService<S> = crate::service::MeteredService<S>;
+#[cfg(not(feature = "metrics"))]
+type ServedService<S> = S;
+
 #[expect(
     clippy::result_large_err,
     reason = "BoxResponse is intentionally large; matches other handlers in this file"
@@ -379,6 +470,10 @@ fn bad_request_response(message: &str) -> BoxResponse {
         .expect("failed to build bad request response")
 }
 
+#[expect(
+    clippy::result_large_err,
+    reason = "BoxResponse is intentionally large; matches other handlers in this file"
+)]
 fn parse_host_header(
     uri: &http::Uri,
     headers: &HeaderMap,
@@ -409,6 +504,10 @@ fn parse_host_header(
     Ok(normalize_authority(authority.host(), authority.port_u16()))
 }
 
+#[expect(
+    clippy::result_large_err,
+    reason = "BoxResponse is intentionally large; matches other handlers in this file"
+)]
 fn validate_dns_rebinding_headers(
     uri: &http::Uri,
     headers: &HeaderMap,
@@ -426,6 +525,10 @@ fn validate_dns_rebinding_headers(
     Ok(())
 }
 
+#[expect(
+    clippy::result_large_err,
+    reason = "BoxResponse is intentionally large; matches other handlers in this file"
+)]
 fn validate_origin_header(
     headers: &HeaderMap,
     allowed_origins: &[String],
@@ -461,6 +564,178 @@ fn validate_origin_header(
     Ok(())
 }
 
+/// The identity established by a [`StreamableHttpService::with_authorizer`] hook.
+///
+/// It is computed for every request. In stateful mode the session is bound to
+/// the `subject` of its `initialize` request, and later requests carrying a
+/// different subject are answered as if the session did not exist. It is
+/// inserted into the request [`Extensions`](crate::model::Extensions), so
+/// handlers can read it through
+/// [`Extension<AuthContext>`](crate::handler::server::tool::Extension) or
+/// `context.extensions.get::<AuthContext>()`.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct AuthContext {
+    /// The authenticated principal, e.g. the `sub` claim of a token.
+    pub subject: Option<String>,
+    /// Scopes granted to the session.
+    pub scopes: Vec<String>,
+    /// Any additional claims the authorizer wants to expose to handlers.
+    pub claims: crate::model::JsonObject,
+}
+
+impl AuthContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_subject(mut self, subject: impl Into<String>) -> Self {
+        self.subject = Some(subject.into());
+        self
+    }
+
+    pub fn with_scopes(mut self, scopes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.scopes = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_claims(mut self, claims: crate::model::JsonObject) -> Self {
+        self.claims = claims;
+        self
+    }
+
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+}
+
+type Authorizer =
+    Arc<dyn Fn(&http::HeaderMap) -> Result<AuthContext, http::StatusCode> + Send + Sync>;
+
+/// The principal ([`AuthContext::subject`]) each live session belongs to,
+/// keyed by session ID. Only populated when an authorizer is configured.
+type SessionAuth = Arc<std::sync::RwLock<HashMap<SessionId, Option<String>>>>;
+
+/// Number of requests each session has in flight. Sessions without any are
+/// not in the map.
+type InFlightRequests = Arc<std::sync::Mutex<HashMap<SessionId, usize>>>;
+
+/// Counts one request against its session's in-flight limit until dropped.
+struct InFlightGuard {
+    in_flight: InFlightRequests,
+    session_id: SessionId,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        let mut in_flight = self.in_flight.lock().expect("in-flight lock poisoned");
+        if let Some(count) = in_flight.get_mut(&self.session_id) {
+            *count -= 1;
+            if *count == 0 {
+                in_flight.remove(&self.session_id);
+            }
+        }
+    }
+}
+
+fn too_many_requests_response(id: RequestId) -> BoxResponse {
+    // -32000 is the first code of the JSON-RPC implementation-defined server error range.
+    let err = JsonRpcError::new(
+        Some(id),
+        ErrorData::new(
+            ErrorCode(-32000),
+            "Too many in-flight requests for this session",
+            None,
+        ),
+    );
+    let body = serde_json::to_vec(&err).expect("serialize JsonRpcError");
+    Response::builder()
+        .status(http::StatusCode::TOO_MANY_REQUESTS)
+        .header(http::header::CONTENT_TYPE, JSON_MIME_TYPE)
+        .body(Full::new(Bytes::from(body)).boxed())
+        .expect("valid response")
+}
+
+fn session_not_found_response() -> BoxResponse {
+    // MCP spec: server MUST respond with 404 Not Found for terminated/unknown sessions
+    Response::builder()
+        .status(http::StatusCode::NOT_FOUND)
+        .body(Full::new(Bytes::from("Not Found: Session not found")).boxed())
+        .expect("valid response")
+}
+
+fn authorization_error_response(status: http::StatusCode) -> BoxResponse {
+    let mut builder = Response::builder().status(status);
+    if status == http::StatusCode::UNAUTHORIZED {
+        builder = builder.header(http::header::WWW_AUTHENTICATE, "Bearer");
+    }
+    builder
+        .body(
+            Full::new(Bytes::from(
+                status.canonical_reason().unwrap_or("Unauthorized"),
+            ))
+            .boxed(),
+        )
+        .expect("valid response")
+}
+
+/// Body format of a POST response, picked from the request's `Accept` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PostResponseFormat {
+    Json,
+    Sse,
+}
+
+/// Negotiate the POST response format from the `Accept` header.
+///
+/// Media ranges (`*/*`, `application/*`, `text/*`) are honoured. As in
+/// RFC 9110, each format takes its quality from the most specific range that
+/// matches it, and `q=0` refuses it: `application/json;q=0, */*` accepts only
+/// SSE. When both formats are acceptable, `prefer_json` breaks the tie.
+/// Returns `None` when neither is acceptable, including when the header is
+/// missing.
+fn negotiate_post_response(headers: &HeaderMap, prefer_json: bool) -> Option<PostResponseFormat> {
+    // (specificity, quality) of the best range seen for each format; 2 is an
+    // exact type, 1 a `type/*` range and 0 `*/*`.
+    let mut json: Option<(u8, f32)> = None;
+    let mut sse: Option<(u8, f32)> = None;
+    let ranges = headers
+        .get_all(http::header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','));
+    for range in ranges {
+        let mut parts = range.split(';').map(str::trim);
+        let media_type = parts.next().unwrap_or_default().to_ascii_lowercase();
+        let quality = parts
+            .find_map(|param| param.strip_prefix("q="))
+            .map_or(1.0, |q| q.parse::<f32>().unwrap_or(0.0));
+        let (json_match, sse_match) = match media_type.as_str() {
+            "*/*" => (Some(0), Some(0)),
+            "application/*" => (Some(1), None),
+            JSON_MIME_TYPE => (Some(2), None),
+            "text/*" => (None, Some(1)),
+            EVENT_STREAM_MIME_TYPE => (None, Some(2)),
+            _ => (None, None),
+        };
+        for (best, specificity) in [(&mut json, json_match), (&mut sse, sse_match)] {
+            let Some(specificity) = specificity else {
+                continue;
+            };
+            if best.is_none_or(|(seen, _)| specificity > seen) {
+                *best = Some((specificity, quality));
+            }
+        }
+    }
+    let acceptable = |best: Option<(u8, f32)>| best.is_some_and(|(_, quality)| quality > 0.0);
+    match (acceptable(json), acceptable(sse)) {
+        (true, true) if prefer_json => Some(PostResponseFormat::Json),
+        (_, true) => Some(PostResponseFormat::Sse),
+        (true, false) => Some(PostResponseFormat::Json),
+        (false, false) => None,
+    }
+}
+
 /// # Streamable HTTP server
 ///
 /// An HTTP service that implements the
@@ -485,6 +760,46 @@ fn validate_origin_header(
 /// * [`NeverSessionManager`](super::session::never::NeverSessionManager) —
 ///   disables sessions entirely (stateless mode).
 ///
+/// ## Mounting into an existing axum app
+///
+/// The service does not own a listener, so it can be nested into an existing
+/// `axum::Router` next to other handlers and share its middleware:
+///
+/// ```rust,ignore
+/// let mcp = StreamableHttpService::new(
+///     || Ok(Counter::new()),
+///     LocalSessionManager::default().into(),
+///     StreamableHttpServerConfig::default(),
+/// );
+/// let app = axum::Router::new()
+///     .route("/health", axum::routing::get(|| async { "ok" }))
+///     .nest_service("/mcp", mcp)
+///     .layer(tower_http::trace::TraceLayer::new_for_http());
+/// axum::serve(listener, app).await?;
+/// ```
+///
+/// ## Browser clients and CORS
+///
+/// The service does not emit CORS headers itself. Because it is a plain tower
+/// service, browser-based clients are supported by wrapping it in a CORS layer
+/// such as `tower_http::cors::CorsLayer`. The layer must expose the
+/// `Mcp-Session-Id` response header, otherwise browsers hide the session ID
+/// from the client:
+///
+/// ```rust,ignore
+/// use tower_http::cors::{Any, CorsLayer};
+///
+/// let cors = CorsLayer::new()
+///     .allow_origin(["https://app.example.com".parse().unwrap()])
+///     .allow_methods([http::Method::GET, http::Method::POST, http::Method::DELETE])
+///     .allow_headers(Any)
+///     .expose_headers([http::HeaderName::from_static("mcp-session-id")]);
+/// let router = axum::Router::new().nest_service("/mcp", service).layer(cors);
+/// ```
+///
+/// CORS only relaxes what browsers allow; origin checks against DNS rebinding
+/// are configured separately through [`StreamableHttpServerConfig::allowed_origins`].
+///
 /// ## Accessing HTTP request data from tool handlers
 ///
 /// The service consumes the request body but injects the remaining
@@ -552,11 +867,16 @@ pub struct StreamableHttpService<S, M> {
     /// same unknown session ID wait for the first restore to complete rather
     /// than racing to replay the initialize handshake. `None` when no external
     /// session store is configured (avoids allocating the map).
-    pending_restores: Option<
-        Arc<tokio::sync::RwLock<HashMap<SessionId, tokio::sync::watch::Sender<Option<bool>>>>>,
-    >,
+    pending_restores: Option<PendingRestores>,
+    authorizer: Option<Authorizer>,
+    session_auth: SessionAuth,
+    in_flight: InFlightRequests,
 }
 
+/// In-progress session restores, keyed by session ID.
+type PendingRestores =
+    Arc<tokio::sync::RwLock<HashMap<SessionId, tokio::sync::watch::Sender<Option<bool>>>>>;
+
 impl<S, M> Clone for StreamableHttpService<S, M> {
     fn clone(&self) -> Self {
         Self {
@@ -564,6 +884,9 @@ impl<S, M> Clone for StreamableHttpService<S, M> {
             session_manager: self.session_manager.clone(),
             service_factory: self.service_factory.clone(),
             pending_restores: self.pending_restores.clone(),
+            authorizer: self.authorizer.clone(),
+            session_auth: self.session_auth.clone(),
+            in_flight: self.in_flight.clone(),
         }
     }
 }
@@ -602,8 +925,7 @@ where
 /// `result` defaults to `false` (failure / cancellation). Only the success path
 /// needs to set it to `true` before returning.
 struct PendingRestoreGuard {
-    pending_restores:
-        Arc<tokio::sync::RwLock<HashMap<SessionId, tokio::sync::watch::Sender<Option<bool>>>>>,
+    pending_restores: PendingRestores,
     session_id: SessionId,
     watch_tx: tokio::sync::watch::Sender<Option<bool>>,
     /// The value that will be broadcast to waiting tasks on drop.
@@ -644,10 +966,124 @@ where
             session_manager,
             service_factory: Arc::new(service_factory),
             pending_restores,
+            authorizer: None,
+            session_auth: Default::default(),
+            in_flight: Default::default(),
         }
     }
-    fn get_service(&self) -> Result<S, std::io::Error> {
-        (self.service_factory)()
+
+    /// Authorize clients from the headers of their requests.
+    ///
+    /// `authorizer` runs on every request; for `initialize` it runs before a
+    /// session is created. Returning an error status rejects the request with
+    /// that status and, for `initialize`, no session is created. In stateful
+    /// mode a session belongs to the subject that initialized it: requests for
+    /// the session, including `DELETE` and restores from the session store, are
+    /// answered with `404 Not Found` when they authorize as anyone else. The
+    /// returned [`AuthContext`] is available to handlers through the request
+    /// extensions.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let service = StreamableHttpService::new(factory, session_manager, config)
+    ///     .with_authorizer(|headers| {
+    ///         let token = headers
+    ///             .get(http::header::AUTHORIZATION)
+    ///             .and_then(|v| v.to_str().ok())
+    ///             .and_then(|v| v.strip_prefix("Bearer "))
+    ///             .ok_or(http::StatusCode::UNAUTHORIZED)?;
+    ///         let subject = verify(token).map_err(|_| http::StatusCode::UNAUTHORIZED)?;
+    ///         Ok(AuthContext::new().with_subject(subject))
+    ///     });
+    /// ```
+    pub fn with_authorizer(
+        mut self,
+        authorizer: impl Fn(&http::HeaderMap) -> Result<AuthContext, http::StatusCode>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        self.authorizer = Some(Arc::new(authorizer));
+        self
+    }
+
+    #[expect(
+        clippy::result_large_err,
+        reason = "BoxResponse is intentionally large; matches other handlers in this file"
+    )]
+    fn authorize(&self, headers: &http::HeaderMap) -> Result<Option<AuthContext>, BoxResponse> {
+        match &self.authorizer {
+            Some(authorizer) => authorizer(headers)
+                .map(Some)
+                .map_err(authorization_error_response),
+            None => Ok(None),
+        }
+    }
+
+    /// Authorize a request for an existing session.
+    ///
+    /// The session ID alone grants nothing: the request is authorized from its
+    /// own headers, and its subject must be the one the session belongs to.
+    #[expect(
+        clippy::result_large_err,
+        reason = "BoxResponse is intentionally large; matches other handlers in this file"
+    )]
+    fn session_auth_context(
+        &self,
+        session_id: &SessionId,
+        headers: &http::HeaderMap,
+    ) -> Result<Option<AuthContext>, BoxResponse> {
+        let Some(context) = self.authorize(headers)? else {
+            return Ok(None);
+        };
+        let owner = self
+            .session_auth
+            .read()
+            .expect("session auth lock poisoned")
+            .get(session_id)
+            .cloned();
+        match owner {
+            Some(subject) if subject == context.subject => Ok(Some(context)),
+            _ => {
+                tracing::warn!(
+                    session_id = session_id.as_ref(),
+                    "rejected request for a session owned by another principal"
+                );
+                Err(session_not_found_response())
+            }
+        }
+    }
+
+    /// Count a request against the session's in-flight limit, or reject it
+    /// if the limit is reached.
+    #[expect(
+        clippy::result_large_err,
+        reason = "BoxResponse is intentionally large; matches other handlers in this file"
+    )]
+    fn acquire_in_flight(
+        &self,
+        session_id: &SessionId,
+        request_id: &RequestId,
+    ) -> Result<Option<InFlightGuard>, BoxResponse> {
+        let Some(limit) = self.config.max_in_flight_requests_per_session else {
+            return Ok(None);
+        };
+        let mut in_flight = self.in_flight.lock().expect("in-flight lock poisoned");
+        let count = in_flight.get(session_id).copied().unwrap_or(0);
+        if count >= limit {
+            return Err(too_many_requests_response(request_id.clone()));
+        }
+        in_flight.insert(session_id.clone(), count + 1);
+        Ok(Some(InFlightGuard {
+            in_flight: self.in_flight.clone(),
+            session_id: session_id.clone(),
+        }))
+    }
+    fn get_service(&self) -> Result<ServedService<S>, std::io::Error> {
+        let service = (self.service_factory)()?;
+        #[cfg(feature = "metrics")]
+        let service = crate::service::MeteredService::new(service, self.config.metrics.clone());
+        Ok(service)
     }
 
     /// Spawn a task that runs `serve_server` for the given session, waits for
@@ -659,18 +1095,34 @@ where
     /// restore `initialize` replay; `handle_post` passes `None`.
     fn spawn_session_worker(
         session_manager: Arc<M>,
+        session_auth: SessionAuth,
         session_id: SessionId,
-        service: S,
+        service: ServedService<S>,
         transport: M::Transport,
+        logging_throttle: Option<LoggingThrottleConfig>,
         init_done_tx: Option<tokio::sync::oneshot::Sender<()>>,
     ) where
         S: crate::Service<RoleServer> + Send + 'static,
         M: SessionManager,
     {
         tokio::spawn(async move {
-            let svc =
-                serve_server::<S, M::Transport, _, TransportAdapterIdentity>(service, transport)
-                    .await;
+            let svc = match logging_throttle {
+                Some(config) => {
+                    serve_server_with_logging_throttle::<
+                        ServedService<S>,
+                        M::Transport,
+                        _,
+                        TransportAdapterIdentity,
+                    >(service, transport, config, CancellationToken::new())
+                    .await
+                }
+                None => {
+                    serve_server::<ServedService<S>, M::Transport, _, TransportAdapterIdentity>(
+                        service, transport,
+                    )
+                    .await
+                }
+            };
             match svc {
                 Ok(svc) => {
                     if let Some(tx) = init_done_tx {
@@ -689,6 +1141,10 @@ where
                 .inspect_err(|e| {
                     tracing::error!("Failed to close session {session_id}: {e}");
                 });
+            session_auth
+                .write()
+                .expect("session auth lock poisoned")
+                .remove(&session_id);
         });
     }
 
@@ -705,6 +1161,7 @@ where
         &self,
         session_id: &SessionId,
         parts: &http::request::Parts,
+        auth_context: Option<&AuthContext>,
     ) -> Result<bool, std::io::Error>
     where
         S: crate::Service<RoleServer> + Send + 'static,
@@ -761,6 +1218,20 @@ where
                 return Err(std::io::Error::other(e));
             }
         };
+        // Only the principal that created the session may restore it.
+        if let Some(context) = auth_context {
+            if context.subject != state.subject {
+                tracing::warn!(
+                    session_id = session_id.as_ref(),
+                    "refused to restore a session owned by another principal"
+                );
+                return Ok(false);
+            }
+            self.session_auth
+                .write()
+                .expect("session auth lock poisoned")
+                .insert(session_id.clone(), state.subject.clone());
+        }
 
         // --- Step 4: ask the session manager to allocate an in-memory worker ---
         let transport = match self
@@ -821,29 +1292,23 @@ where
 
         Self::spawn_session_worker(
             self.session_manager.clone(),
+            self.session_auth.clone(),
             session_id.clone(),
             service,
             transport,
+            self.config.logging_throttle,
             Some(init_done_tx),
         );
 
-        if let Err(e) = self
-            .session_manager
+        self.session_manager
             .initialize_session(session_id, restore_init)
             .await
-            .map_err(|e| std::io::Error::other(e.to_string()))
-        {
-            return Err(e);
-        }
+            .map_err(|e| std::io::Error::other(e.to_string()))?;
 
-        if let Err(e) = self
-            .session_manager
+        self.session_manager
             .accept_message(session_id, restore_initialized)
             .await
-            .map_err(|e| std::io::Error::other(e.to_string()))
-        {
-            return Err(e);
-        }
+            .map_err(|e| std::io::Error::other(e.to_string()))?;
 
         if init_done_rx.await.is_err() {
             return Err(std::io::Error::other(
@@ -860,22 +1325,46 @@ where
         );
         Ok(true)
     }
-    pub async fn handle<B>(&self, request: Request<B>) -> Response<BoxBody<Bytes, Infallible>>
+    pub async fn handle<B>(&self, mut request: Request<B>) -> Response<BoxBody<Bytes, Infallible>>
     where
         B: Body + Send + 'static,
         B::Error: Display,
     {
+        if request.method() == Method::GET
+            && self
+                .config
+                .health_path
+                .as_deref()
+                .is_some_and(|path| path == request.uri().path())
+        {
+            return Response::builder()
+                .status(http::StatusCode::OK)
+                .header(http::header::CONTENT_TYPE, "text/plain; charset=utf-8")
+                .body(Full::new(Bytes::from("ok")).boxed())
+                .expect("valid response");
+        }
         if let Err(response) =
             validate_dns_rebinding_headers(request.uri(), request.headers(), &self.config)
         {
             return response;
         }
+        if let Some(downloads) = &self.config.binary_downloads {
+            if request.method() == Method::GET {
+                if let Some(response) = downloads.serve(request.uri().path()) {
+                    return response;
+                }
+            }
+            request.extensions_mut().insert(downloads.clone());
+        }
         let method = request.method().clone();
         let allowed_methods = match self.config.stateful_mode {
             true => "GET, POST, DELETE",
             false => "POST",
         };
         let result = match (method, self.config.stateful_mode) {
+            #[cfg(feature = "compression")]
+            (Method::POST, _) => self.handle_encoded_post(request).await,
+            #[cfg(not(feature = "compression"))]
             (Method::POST, _) => self.handle_post(request).await,
             // if we're not in stateful mode, we don't support GET or DELETE because there is no session
             (Method::GET, true) => self.handle_get(request).await,
@@ -938,9 +1427,10 @@ where
             .map_err(internal_error_response("check session"))?;
         let (parts, _) = request.into_parts();
         if !has_session {
+            let auth_context = self.authorize(&parts.headers)?;
             // Attempt transparent cross-instance restore from external store.
             let restored = self
-                .try_restore_from_store(&session_id, &parts)
+                .try_restore_from_store(&session_id, &parts, auth_context.as_ref())
                 .await
                 .map_err(internal_error_response("restore session"))?;
             if !restored {
@@ -953,6 +1443,7 @@ where
         }
         // Validate MCP-Protocol-Version header (per 2025-06-18 spec)
         validate_protocol_version_header(&parts.headers)?;
+        self.session_auth_context(&session_id, &parts.headers)?;
         // check if last event id is provided
         let last_event_id = parts
             .headers
@@ -1008,25 +1499,57 @@ where
         ))
     }
 
-    async fn handle_post<B>(&self, request: Request<B>) -> Result<BoxResponse, BoxResponse>
+    /// Decode a compressed request body, and compress the response if the
+    /// client accepts it, around [`Self::handle_post`].
+    #[cfg(feature = "compression")]
+    async fn handle_encoded_post<B>(&self, request: Request<B>) -> Result<BoxResponse, BoxResponse>
     where
         B: Body + Send + 'static,
         B::Error: Display,
     {
-        // check accept header
-        if !request
+        use crate::transport::common::{
+            compression::accepts_gzip,
+            server_side_http::{compress_json_response, decode_request_body},
+        };
+
+        let accepts_gzip = request
             .headers()
-            .get(http::header::ACCEPT)
-            .and_then(|header| header.to_str().ok())
-            .is_some_and(|header| {
-                header.contains(JSON_MIME_TYPE) && header.contains(EVENT_STREAM_MIME_TYPE)
-            })
+            .get(http::header::ACCEPT_ENCODING)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(accepts_gzip);
+        let response = if request
+            .headers()
+            .contains_key(http::header::CONTENT_ENCODING)
         {
+            self.handle_post(decode_request_body(request).await?)
+                .await?
+        } else {
+            self.handle_post(request).await?
+        };
+        Ok(match self.config.response_compression_threshold {
+            Some(threshold) if accepts_gzip => compress_json_response(response, threshold).await,
+            _ => response,
+        })
+    }
+
+    async fn handle_post<B>(&self, request: Request<B>) -> Result<BoxResponse, BoxResponse>
+    where
+        B: Body + Send + 'static,
+        B::Error: Display,
+    {
+        // check accept header
+        let prefer_json = self.config.json_response && !self.config.stateful_mode;
+        let Some(response_format) = negotiate_post_response(request.headers(), prefer_json) else {
             return Ok(Response::builder()
                 .status(http::StatusCode::NOT_ACCEPTABLE)
-                .body(Full::new(Bytes::from("Not Acceptable: Client must accept both application/json and text/event-stream")).boxed())
+                .body(
+                    Full::new(Bytes::from(
+                        "Not Acceptable: Client must accept application/json or text/event-stream",
+                    ))
+                    .boxed(),
+                )
                 .expect("valid response"));
-        }
+        };
 
         // check content type
         if !request
@@ -1067,9 +1590,10 @@ where
                     .await
                     .map_err(internal_error_response("check session"))?;
                 if !has_session {
+                    let auth_context = self.authorize(&part.headers)?;
                     // Attempt transparent cross-instance restore from external store.
                     let restored = self
-                        .try_restore_from_store(&session_id, &part)
+                        .try_restore_from_store(&session_id, &part, auth_context.as_ref())
                         .await
                         .map_err(internal_error_response("restore session"))?;
                     if !restored {
@@ -1083,13 +1607,25 @@ where
 
                 // Validate MCP-Protocol-Version header (per 2025-06-18 spec)
                 validate_protocol_version_header(&part.headers)?;
+                let auth_context = self.session_auth_context(&session_id, &part.headers)?;
+                let validated = ValidatedSession {
+                    id: session_id.clone(),
+                };
 
                 // inject request part to extensions
                 match &mut message {
                     ClientJsonRpcMessage::Request(req) => {
+                        if let Some(context) = auth_context {
+                            req.request.extensions_mut().insert(context);
+                        }
+                        req.request.extensions_mut().insert(validated);
                         req.request.extensions_mut().insert(part);
                     }
                     ClientJsonRpcMessage::Notification(not) => {
+                        if let Some(context) = auth_context {
+                            not.notification.extensions_mut().insert(context);
+                        }
+                        not.notification.extensions_mut().insert(validated);
                         not.notification.extensions_mut().insert(part);
                     }
                     _ => {
@@ -1098,15 +1634,40 @@ where
                 }
 
                 match message {
-                    ClientJsonRpcMessage::Request(_) => {
+                    ClientJsonRpcMessage::Request(ref request) => {
+                        let in_flight = self.acquire_in_flight(&session_id, &request.id)?;
                         // Priming for request-wise streams is handled by the
                         // session layer (SessionManager::create_stream) which
                         // has access to the http_request_id for correct event IDs.
+                        if response_format == PostResponseFormat::Json {
+                            // Server requests and notifications tied to this
+                            // call travel on the standalone GET stream.
+                            let stream = self
+                                .session_manager
+                                .create_json_stream(&session_id, message)
+                                .await
+                                .map_err(internal_error_response("get session"))?;
+                            let messages = stream.filter_map(move |message| {
+                                let _ = &in_flight;
+                                std::future::ready(
+                                    message.message.map(|message| message.as_ref().clone()),
+                                )
+                            });
+                            return json_stream_response(
+                                messages,
+                                self.config.cancellation_token.child_token(),
+                            )
+                            .await;
+                        }
                         let stream = self
                             .session_manager
                             .create_stream(&session_id, message)
                             .await
                             .map_err(internal_error_response("get session"))?;
+                        // The request stays in flight until its stream ends.
+                        let stream = stream.inspect(move |_| {
+                            let _ = &in_flight;
+                        });
                         Ok(sse_stream_response(
                             stream,
                             self.config.sse_keep_alive,
@@ -1127,7 +1688,7 @@ where
             } else {
                 // Capture init params for external store persistence before
                 // extensions are injected (which would require Clone).
-                let stored_init_params = match &mut message {
+                let (stored_init_params, auth_context) = match &mut message {
                     ClientJsonRpcMessage::Request(req) => {
                         let ClientRequest::InitializeRequest(init_req) = &req.request else {
                             return Err(unexpected_message_response("initialize request"));
@@ -1138,14 +1699,19 @@ where
                             init_req.params.protocol_version.as_str(),
                             Some(req.id.clone()),
                         )?;
+                        // Authorize before any session state is created.
+                        let auth_context = self.authorize(&part.headers)?;
                         let stored_init_params = self
                             .config
                             .session_store
                             .as_ref()
                             .map(|_| init_req.params.clone());
                         // inject request part to extensions
+                        if let Some(context) = &auth_context {
+                            req.request.extensions_mut().insert(context.clone());
+                        }
                         req.request.extensions_mut().insert(part);
-                        stored_init_params
+                        (stored_init_params, auth_context)
                     }
                     _ => {
                         return Err(unexpected_message_response("initialize request"));
@@ -1160,11 +1726,20 @@ where
                     .await
                     .map_err(internal_error_response("create session"))?;
                 // spawn a task to serve the session
+                let subject = auth_context.map(|context| context.subject);
+                if let Some(subject) = &subject {
+                    self.session_auth
+                        .write()
+                        .expect("session auth lock poisoned")
+                        .insert(session_id.clone(), subject.clone());
+                }
                 Self::spawn_session_worker(
                     self.session_manager.clone(),
+                    self.session_auth.clone(),
                     session_id.clone(),
                     service,
                     transport,
+                    self.config.logging_throttle,
                     None,
                 );
                 // get initialize response
@@ -1177,9 +1752,7 @@ where
                 if let (Some(store), Some(params)) =
                     (&self.config.session_store, stored_init_params)
                 {
-                    let state = SessionState {
-                        initialize_params: params,
-                    };
+                    let state = SessionState::new(params).with_subject(subject.flatten());
                     let _ = store
                         .store(session_id.as_ref(), &state)
                         .await
@@ -1190,22 +1763,34 @@ where
                             );
                         });
                 }
-                let stream =
-                    futures::stream::once(async move { ServerSseMessage::from_message(response) });
-                // Prepend priming event if sse_retry configured
-                let stream = if let Some(retry) = self.config.sse_retry {
-                    let priming = ServerSseMessage::priming("0", retry);
-                    futures::stream::once(async move { priming })
-                        .chain(stream)
-                        .left_stream()
-                } else {
-                    stream.right_stream()
+                let mut response = match response_format {
+                    PostResponseFormat::Json => {
+                        json_stream_response(
+                            futures::stream::once(async move { response }),
+                            self.config.cancellation_token.child_token(),
+                        )
+                        .await?
+                    }
+                    PostResponseFormat::Sse => {
+                        let stream = futures::stream::once(async move {
+                            ServerSseMessage::from_message(response)
+                        });
+                        // Prepend priming event if sse_retry configured
+                        let stream = if let Some(retry) = self.config.sse_retry {
+                            let priming = ServerSseMessage::priming("0", retry);
+                            futures::stream::once(async move { priming })
+                                .chain(stream)
+                                .left_stream()
+                        } else {
+                            stream.right_stream()
+                        };
+                        sse_stream_response(
+                            stream,
+                            self.config.sse_keep_alive,
+                            self.config.cancellation_token.child_token(),
+                        )
+                    }
                 };
-                let mut response = sse_stream_response(
-                    stream,
-                    self.config.sse_keep_alive,
-                    self.config.cancellation_token.child_token(),
-                );
 
                 response.headers_mut().insert(
                     HEADER_SESSION_ID,
@@ -1235,6 +1820,7 @@ where
                     validate_protocol_version_header(&part.headers)?;
                 }
             }
+            let auth_context = self.authorize(&part.headers)?;
             let service = self
                 .get_service()
                 .map_err(internal_error_response("get service"))?;
@@ -1247,41 +1833,26 @@ where
                     // - all other requests: version comes from the MCP-Protocol-Version header
                     //   (already validated above; absent header defaults to 2025-03-26)
                     let peer_info = Self::peer_info_for_stateless_request(&request, &part.headers);
+                    if let Some(context) = auth_context {
+                        request.request.extensions_mut().insert(context);
+                    }
                     request.request.extensions_mut().insert(part);
-                    let (transport, mut receiver) =
+                    let (transport, receiver) =
                         OneshotTransport::<RoleServer>::new(ClientJsonRpcMessage::Request(request));
                     let service = serve_directly(service, transport, peer_info);
                     tokio::spawn(async move {
                         // on service created
                         let _ = service.waiting().await;
                     });
-                    if self.config.json_response {
+                    if response_format == PostResponseFormat::Json {
                         // JSON-direct mode: await the single response and return as
                         // application/json, eliminating SSE framing overhead.
                         // Allowed by MCP Streamable HTTP spec (2025-06-18).
-                        let cancel = self.config.cancellation_token.child_token();
-                        match tokio::select! {
-                            res = receiver.recv() => res,
-                            _ = cancel.cancelled() => None,
-                        } {
-                            Some(message) => {
-                                tracing::trace!(?message);
-                                let body = serde_json::to_vec(&message).map_err(|e| {
-                                    internal_error_response("serialize json response")(e)
-                                })?;
-                                Ok(Response::builder()
-                                    .status(http::StatusCode::OK)
-                                    .header(http::header::CONTENT_TYPE, JSON_MIME_TYPE)
-                                    .body(Full::new(Bytes::from(body)).boxed())
-                                    .expect("valid response"))
-                            }
-                            None => Err(internal_error_response("empty response")(
-                                std::io::Error::new(
-                                    std::io::ErrorKind::UnexpectedEof,
-                                    "no response message received from handler",
-                                ),
-                            )),
-                        }
+                        json_stream_response(
+                            ReceiverStream::new(receiver),
+                            self.config.cancellation_token.child_token(),
+                        )
+                        .await
                     } else {
                         // SSE mode (default): original behaviour preserved unchanged
                         let stream = ReceiverStream::new(receiver).map(|message| {
@@ -1325,11 +1896,45 @@ where
         };
         // Validate MCP-Protocol-Version header (per 2025-06-18 spec)
         validate_protocol_version_header(request.headers())?;
-        // close session
+        let has_session = self
+            .session_manager
+            .has_session(&session_id)
+            .await
+            .map_err(internal_error_response("check session"))?;
+        if has_session {
+            self.session_auth_context(&session_id, request.headers())?;
+        } else {
+            // A session living only in the external store (e.g. created on another
+            // instance) is still known and can be terminated from here.
+            let stored = match &self.config.session_store {
+                Some(store) => store
+                    .load(session_id.as_ref())
+                    .await
+                    .map_err(internal_error_response("load session"))?,
+                None => None,
+            };
+            let Some(state) = stored else {
+                return Ok(session_not_found_response());
+            };
+            if let Some(context) = self.authorize(request.headers())? {
+                if context.subject != state.subject {
+                    tracing::warn!(
+                        session_id = session_id.as_ref(),
+                        "refused to delete a session owned by another principal"
+                    );
+                    return Err(session_not_found_response());
+                }
+            }
+        }
+        // close session, ending all of its SSE streams
         self.session_manager
             .close_session(&session_id)
             .await
             .map_err(internal_error_response("close session"))?;
+        self.session_auth
+            .write()
+            .expect("session auth lock poisoned")
+            .remove(&session_id);
         // Remove from external store: a DELETE means the client intentionally
         // ends the session, so the store entry is no longer needed.
         if let Some(store) = &self.config.session_store {
@@ -1337,7 +1942,10 @@ where
                 tracing::warn!("Failed to delete session {} from store: {e}", session_id);
             });
         }
-        Ok(accepted_response())
+        Ok(Response::builder()
+            .status(http::StatusCode::OK)
+            .body(Empty::new().boxed())
+            .expect("valid response"))
     }
 
     /// Build a `ClientInfo` (peer_info) for a stateless request so that
@@ -0,0 +1,112 @@
+//! # Unix domain socket transport
+//!
+//! Serves and connects to local MCP servers over a Unix domain socket, using
+//! the same newline-delimited JSON framing as stdio.
+//!
+//! ## Socket permissions
+//!
+//! Anyone who can connect to the socket can talk to the server, and connecting
+//! requires write permission on the socket file. Access control therefore comes
+//! from the filesystem:
+//!
+//! - Put the socket in a directory only the intended users can traverse, such as
+//!   `$XDG_RUNTIME_DIR` (mode `0700`), rather than a world-writable one like `/tmp`.
+//! - Restrict the socket itself with
+//!   [`UnixSocketServerTransport::with_permissions`], e.g. `0o600` for the
+//!   owner only or `0o660` to also admit the socket's group.
+//! - The socket is created with the server process's owner and umask, so
+//!   adjust ownership separately (e.g. `chown`) if another user must connect.
+//!
+//! The socket file is removed when the [`UnixSocketServerTransport`] is dropped.
+//! Binding fails if the path already exists; remove a stale socket left by a
+//! crashed server before binding again.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use rmcp::{ServiceExt, transport::{UnixSocketClientTransport, UnixSocketServerTransport}};
+//!
+//! // server
+//! let listener = UnixSocketServerTransport::bind("/run/user/1000/mcp.sock")?.with_permissions(0o600)?;
+//! loop {
+//!     let transport = listener.accept().await?;
+//!     tokio::spawn(async move { MyServer::new().serve(transport).await?.waiting().await });
+//! }
+//!
+//! // client
+//! let transport = UnixSocketClientTransport::connect("/run/user/1000/mcp.sock").await?;
+//! let client = ().serve(transport).await?;
+//! ```
+use std::{
+    io,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+};
+
+use tokio::net::{
+    UnixListener, UnixStream,
+    unix::{OwnedReadHalf, OwnedWriteHalf},
+};
+
+use super::async_rw::AsyncRwTransport;
+
+/// One connection over a Unix domain socket.
+pub type UnixSocketTransport<Role> = AsyncRwTransport<Role, OwnedReadHalf, OwnedWriteHalf>;
+
+/// Client side of a Unix domain socket connection.
+#[cfg(feature = "client")]
+pub type UnixSocketClientTransport = UnixSocketTransport<crate::RoleClient>;
+
+#[cfg(feature = "client")]
+impl UnixSocketClientTransport {
+    /// Connect to a server listening on `path`.
+    pub async fn connect(path: impl AsRef<Path>) -> io::Result<Self> {
+        let (read, write) = UnixStream::connect(path).await?.into_split();
+        Ok(Self::new(read, write))
+    }
+}
+
+/// Listens for MCP clients on a Unix domain socket.
+///
+/// See the [module documentation](self) for permission considerations.
+#[derive(Debug)]
+pub struct UnixSocketServerTransport {
+    listener: UnixListener,
+    path: PathBuf,
+}
+
+impl UnixSocketServerTransport {
+    /// Create the socket at `path` and start listening.
+    pub fn bind(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let listener = UnixListener::bind(&path)?;
+        Ok(Self { listener, path })
+    }
+
+    /// Set the permission bits of the socket file, e.g. `0o600`.
+    pub fn with_permissions(self, mode: u32) -> io::Result<Self> {
+        std::fs::set_permissions(&self.path, std::fs::Permissions::from_mode(mode))?;
+        Ok(self)
+    }
+
+    /// The path of the socket file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Wait for the next client and return a transport for serving it.
+    #[cfg(feature = "server")]
+    pub async fn accept(&self) -> io::Result<UnixSocketTransport<crate::RoleServer>> {
+        let (stream, _addr) = self.listener.accept().await?;
+        let (read, write) = stream.into_split();
+        Ok(AsyncRwTransport::new(read, write))
+    }
+}
+
+impl Drop for UnixSocketServerTransport {
+    fn drop(&mut self) {
+        if let Err(error) = std::fs::remove_file(&self.path) {
+            tracing::debug!(%error, path = %self.path.display(), "failed to remove unix socket");
+        }
+    }
+}
@@ -0,0 +1,111 @@
+#![cfg(all(feature = "client", feature = "server", not(feature = "local")))]
+// cargo test --test test_aggregate_client --features "client server"
+
+use rmcp::{
+    RoleClient, ServerHandler, ServiceError, ServiceExt,
+    handler::server::router::tool::ToolRouter,
+    model::{CallToolRequestParams, ErrorCode},
+    service::{AggregateClient, AggregateClientError, RunningService},
+    tool, tool_handler, tool_router,
+};
+
+#[derive(Debug, Clone)]
+struct LabelServer {
+    label: &'static str,
+    tool_router: ToolRouter<Self>,
+}
+
+#[tool_router]
+impl LabelServer {
+    fn new(label: &'static str) -> Self {
+        Self {
+            label,
+            tool_router: Self::tool_router(),
+        }
+    }
+
+    #[tool(description = "Say which server answered")]
+    fn whoami(&self) -> String {
+        self.label.to_string()
+    }
+}
+
+#[tool_handler(router = self.tool_router)]
+impl ServerHandler for LabelServer {}
+
+/// A server without the tools capability.
+#[derive(Debug, Clone)]
+struct NoToolsServer;
+
+impl ServerHandler for NoToolsServer {}
+
+async fn connect<S: ServerHandler>(server: S) -> anyhow::Result<RunningService<RoleClient, ()>> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    tokio::spawn(async move {
+        let running = server.serve(server_transport).await?;
+        anyhow::Ok(running.waiting().await?)
+    });
+    Ok(().serve(client_transport).await?)
+}
+
+#[tokio::test]
+async fn test_aggregate_client_namespaces_and_routes_tools() -> anyhow::Result<()> {
+    let alpha = connect(LabelServer::new("alpha")).await?;
+    let beta = connect(LabelServer::new("beta")).await?;
+    let bare = connect(NoToolsServer).await?;
+
+    let mut aggregate = AggregateClient::new();
+    aggregate.add_server("alpha", alpha.peer().clone())?;
+    aggregate.add_server("beta", beta.peer().clone())?;
+    aggregate.add_server("bare", bare.peer().clone())?;
+
+    let mut names: Vec<_> = aggregate
+        .list_all_tools()
+        .await
+        .into_iter()
+        .map(|tool| tool.name.into_owned())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["alpha__whoami", "beta__whoami"]);
+
+    for server in ["alpha", "beta"] {
+        let result = aggregate
+            .call_tool(CallToolRequestParams::new(format!("{server}__whoami")))
+            .await?;
+        assert_eq!(result.content[0].as_text().unwrap().text, server);
+    }
+
+    let error = aggregate
+        .call_tool(CallToolRequestParams::new("gamma__whoami"))
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), Some(ErrorCode::INVALID_PARAMS));
+    let error = aggregate
+        .call_tool(CallToolRequestParams::new("bare__whoami"))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&error, ServiceError::McpError(e) if e.code == ErrorCode::METHOD_NOT_FOUND),
+        "got {error:?}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_aggregate_client_rejects_ambiguous_server_names() -> anyhow::Result<()> {
+    let client = connect(LabelServer::new("alpha")).await?;
+    let mut aggregate = AggregateClient::new().with_separator(".");
+
+    aggregate.add_server("alpha", client.peer().clone())?;
+    assert!(matches!(
+        aggregate.add_server("alpha", client.peer().clone()),
+        Err(AggregateClientError::DuplicateServer(_))
+    ));
+    assert!(matches!(
+        aggregate.add_server("alpha.v2", client.peer().clone()),
+        Err(AggregateClientError::InvalidServerName { .. })
+    ));
+    assert_eq!(aggregate.resolve("alpha.whoami"), Some(("alpha", "whoami")));
+    assert_eq!(aggregate.resolve("alpha__whoami"), None);
+    Ok(())
+}
@@ -0,0 +1,91 @@
+#![cfg(not(feature = "local"))]
+use futures::future::BoxFuture;
+use rmcp::{
+    ErrorData, ServerHandler, ServiceExt,
+    handler::server::{
+        common::AsyncFromContextPart, router::tool::ToolRouter, tool::ToolCallContext,
+    },
+    model::{CallToolRequestParams, JsonObject, Meta},
+    tool, tool_handler, tool_router,
+};
+use serde_json::json;
+
+/// Extractor that "loads" a session from the `sessionId` in the request `_meta`
+struct Session {
+    user: String,
+}
+
+impl<S> AsyncFromContextPart<ToolCallContext<'_, S>> for Session {
+    type Future = BoxFuture<'static, Result<Self, ErrorData>>;
+
+    fn from_context_part_async(context: &mut ToolCallContext<'_, S>) -> Self::Future {
+        let id = context
+            .request_meta()
+            .get("sessionId")
+            .and_then(|id| id.as_str())
+            .map(str::to_owned);
+        Box::pin(async move {
+            let id = id.ok_or_else(|| ErrorData::invalid_params("missing sessionId", None))?;
+            // stand-in for a database lookup
+            tokio::task::yield_now().await;
+            Ok(Session {
+                user: format!("user-of-{id}"),
+            })
+        })
+    }
+}
+
+#[derive(Clone)]
+struct SessionServer {
+    tool_router: ToolRouter<Self>,
+}
+
+#[tool_router]
+impl SessionServer {
+    fn new() -> Self {
+        Self {
+            tool_router: Self::tool_router(),
+        }
+    }
+
+    #[tool(description = "Return the current session's user")]
+    async fn whoami(&self, session: Session, _args: JsonObject) -> String {
+        session.user
+    }
+}
+
+#[tool_handler(router = self.tool_router)]
+impl ServerHandler for SessionServer {}
+
+#[tokio::test]
+async fn test_async_extractor_is_awaited_before_tool() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let server_handle = tokio::spawn(async move {
+        SessionServer::new()
+            .serve(server_transport)
+            .await?
+            .waiting()
+            .await?;
+        anyhow::Ok(())
+    });
+    let client = ().serve(client_transport).await?;
+
+    let mut meta = JsonObject::new();
+    meta.insert("sessionId".into(), json!("42"));
+    let result = client
+        .call_tool_with_meta(CallToolRequestParams::new("whoami"), meta)
+        .await?;
+    assert_eq!(
+        result.content[0].as_text().map(|t| t.text.as_str()),
+        Some("user-of-42")
+    );
+
+    let mut params = CallToolRequestParams::new("whoami");
+    params.meta = Some(Meta::new());
+    let error = client.call_tool(params).await.unwrap_err();
+    assert!(error.to_string().contains("missing sessionId"), "{error}");
+
+    client.cancel().await?;
+    server_handle.await??;
+    Ok(())
+}
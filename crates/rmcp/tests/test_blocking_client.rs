@@ -0,0 +1,103 @@
+#![cfg(not(feature = "local"))]
+use rmcp::{
+    ServerHandler, ServiceExt,
+    blocking::BlockingClient,
+    handler::server::{router::tool::ToolRouter, wrapper::Parameters},
+    model::CallToolRequestParams,
+    schemars, tool, tool_handler, tool_router,
+};
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct Operands {
+    a: i32,
+    b: i32,
+}
+
+#[derive(Debug, Clone)]
+struct Calculator {
+    #[expect(dead_code, reason = "tool_handler macro accesses this router field")]
+    tool_router: ToolRouter<Self>,
+}
+
+#[tool_router]
+impl Calculator {
+    fn new() -> Self {
+        Self {
+            tool_router: Self::tool_router(),
+        }
+    }
+
+    #[tool(description = "Add two numbers")]
+    fn sum(&self, Parameters(Operands { a, b }): Parameters<Operands>) -> String {
+        (a + b).to_string()
+    }
+
+    #[tool(description = "Subtract two numbers")]
+    fn sub(&self, Parameters(Operands { a, b }): Parameters<Operands>) -> String {
+        (a - b).to_string()
+    }
+}
+
+#[tool_handler]
+impl ServerHandler for Calculator {}
+
+/// Run the calculator server on a runtime of its own, as a separate process would.
+fn spawn_server() -> tokio::io::DuplexStream {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        runtime.block_on(async move {
+            Calculator::new()
+                .serve(server_transport)
+                .await?
+                .waiting()
+                .await?;
+            anyhow::Ok(())
+        })
+    });
+    client_transport
+}
+
+fn operands(a: i32, b: i32) -> serde_json::Map<String, serde_json::Value> {
+    serde_json::from_value(serde_json::json!({"a": a, "b": b})).unwrap()
+}
+
+#[test]
+fn test_blocking_client_calls_tools() -> anyhow::Result<()> {
+    let transport = spawn_server();
+    let client = BlockingClient::serve((), || transport)?;
+
+    let mut tools: Vec<_> = client
+        .list_all_tools_blocking()?
+        .into_iter()
+        .map(|tool| tool.name)
+        .collect();
+    tools.sort();
+    assert_eq!(tools, ["sub", "sum"]);
+
+    let result = client
+        .call_tool_blocking(CallToolRequestParams::new("sum").with_arguments(operands(1, 2)))?;
+    assert_eq!(result.text().as_deref(), Some("3"));
+
+    client.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_blocking_client_on_shared_runtime() -> anyhow::Result<()> {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .enable_all()
+        .build()?;
+    let transport = spawn_server();
+    let client = BlockingClient::serve_with_handle(runtime.handle().clone(), (), || transport)?;
+
+    let result = client
+        .call_tool_blocking(CallToolRequestParams::new("sub").with_arguments(operands(5, 2)))?;
+    assert_eq!(result.text().as_deref(), Some("3"));
+
+    client.close()?;
+    Ok(())
+}
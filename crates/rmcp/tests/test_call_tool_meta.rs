@@ -0,0 +1,64 @@
+#![cfg(not(feature = "local"))]
+use std::sync::Arc;
+
+use rmcp::{
+    RoleServer, ServerHandler, ServiceExt,
+    handler::server::tool::ToolCallContext,
+    model::{
+        CallToolRequestParams, CallToolResult, JsonObject, Meta, ServerCapabilities, ServerInfo,
+    },
+    service::RequestContext,
+};
+use serde_json::json;
+use tokio::sync::Mutex;
+
+#[derive(Clone, Default)]
+struct MetaEchoServer {
+    seen: Arc<Mutex<Vec<Meta>>>,
+}
+
+impl ServerHandler for MetaEchoServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo::new(ServerCapabilities::builder().enable_tools().build())
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let context = ToolCallContext::new(self, request, context);
+        self.seen.lock().await.push(context.request_meta().clone());
+        Ok(CallToolResult::success(vec![]))
+    }
+}
+
+#[tokio::test]
+async fn test_call_tool_with_meta_reaches_tool_call_context() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let server = MetaEchoServer::default();
+    let seen = server.seen.clone();
+    let server_handle = tokio::spawn(async move {
+        server.serve(server_transport).await?.waiting().await?;
+        anyhow::Ok(())
+    });
+    let client = ().serve(client_transport).await?;
+
+    let mut existing = Meta::new();
+    existing.insert("traceId".into(), json!("abc"));
+    existing.insert("tenant".into(), json!("old"));
+    let mut params = CallToolRequestParams::new("echo");
+    params.meta = Some(existing);
+    let mut meta = JsonObject::new();
+    meta.insert("tenant".into(), json!("acme"));
+    client.call_tool_with_meta(params, meta).await?;
+    client.cancel().await?;
+    server_handle.await??;
+
+    let seen = seen.lock().await;
+    assert_eq!(seen.len(), 1);
+    assert_eq!(seen[0].get("tenant"), Some(&json!("acme")));
+    assert_eq!(seen[0].get("traceId"), Some(&json!("abc")));
+    assert!(seen[0].get_progress_token().is_some());
+    Ok(())
+}
@@ -0,0 +1,104 @@
+#![cfg(not(feature = "local"))]
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use rmcp::{
+    RoleServer, ServerHandler, ServiceExt,
+    model::{ServerCapabilities, ServerInfo},
+    service::{
+        HeartbeatConfig, ReconnectConfig, ReconnectingClient, RequestContext,
+        serve_client_with_heartbeat,
+    },
+};
+use tokio::sync::mpsc;
+
+/// Answers pings until `stalled` is set, then never responds to them.
+#[derive(Clone, Default)]
+struct PingServer {
+    pings: Arc<AtomicUsize>,
+    stalled: Arc<AtomicBool>,
+}
+
+impl ServerHandler for PingServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo::new(ServerCapabilities::builder().enable_tools().build())
+    }
+
+    async fn ping(&self, _context: RequestContext<RoleServer>) -> Result<(), rmcp::ErrorData> {
+        self.pings.fetch_add(1, Ordering::SeqCst);
+        if self.stalled.load(Ordering::SeqCst) {
+            std::future::pending::<()>().await;
+        }
+        Ok(())
+    }
+}
+
+fn heartbeat() -> HeartbeatConfig {
+    HeartbeatConfig::new(Duration::from_millis(20)).with_timeout(Duration::from_millis(100))
+}
+
+#[tokio::test]
+async fn test_missed_ping_closes_connection() -> anyhow::Result<()> {
+    let server = PingServer::default();
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    tokio::spawn({
+        let server = server.clone();
+        async move {
+            server.serve(server_transport).await?.waiting().await?;
+            anyhow::Ok(())
+        }
+    });
+    let client =
+        serve_client_with_heartbeat((), client_transport, heartbeat(), Default::default()).await?;
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert!(server.pings.load(Ordering::SeqCst) >= 2);
+    assert!(!client.is_closed());
+
+    server.stalled.store(true, Ordering::SeqCst);
+    tokio::time::timeout(Duration::from_secs(5), client.waiting()).await??;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_missed_ping_triggers_reconnect() -> anyhow::Result<()> {
+    let server = PingServer::default();
+    let (connected_tx, mut connections) = mpsc::unbounded_channel();
+    let factory = {
+        let server = server.clone();
+        move || {
+            let server = server.clone();
+            let connected_tx = connected_tx.clone();
+            async move {
+                let (server_transport, client_transport) = tokio::io::duplex(4096);
+                let _ = connected_tx.send(());
+                tokio::spawn(async move {
+                    server.serve(server_transport).await?.waiting().await?;
+                    anyhow::Ok(())
+                });
+                Ok::<_, std::convert::Infallible>(client_transport)
+            }
+        }
+    };
+    let config = ReconnectConfig::default()
+        .with_backoff(Duration::from_millis(10), Duration::from_millis(50))
+        .with_heartbeat(heartbeat());
+    let client = ReconnectingClient::connect((), factory, config).await?;
+    connections.recv().await.expect("first connection");
+
+    server.stalled.store(true, Ordering::SeqCst);
+    tokio::time::timeout(Duration::from_secs(5), connections.recv())
+        .await?
+        .expect("reconnect after missed ping");
+    server.stalled.store(false, Ordering::SeqCst);
+
+    let tools = tokio::time::timeout(Duration::from_secs(5), client.list_all_tools()).await??;
+    assert!(tools.is_empty());
+    client.close().await?;
+    Ok(())
+}
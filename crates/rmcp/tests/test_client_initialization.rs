@@ -3,14 +3,16 @@
 
 mod common;
 
-use std::borrow::Cow;
+use std::{borrow::Cow, time::Duration};
 
 use common::handlers::TestClientHandler;
 use rmcp::{
     ServiceExt,
     model::{
-        ErrorCode, ErrorData, JsonRpcError, JsonRpcVersion2_0, RequestId, ServerJsonRpcMessage,
+        CustomResult, ErrorCode, ErrorData, JsonRpcError, JsonRpcResponse, JsonRpcVersion2_0,
+        RequestId, ServerJsonRpcMessage, ServerResult,
     },
+    service::{ClientInitializeError, serve_client_with_init_timeout},
     transport::{IntoTransport, Transport},
 };
 
@@ -51,3 +53,64 @@ async fn test_client_init_handles_jsonrpc_error() {
         _ => panic!("Expected ClientInitializeError::JsonRpcError"),
     }
 }
+
+#[tokio::test]
+async fn test_client_init_times_out_without_response() {
+    let (server_transport, client_transport) = tokio::io::duplex(1024);
+    let mut server = IntoTransport::<rmcp::RoleServer, _, _>::into_transport(server_transport);
+
+    // Keep the server side open but never answer.
+    let server_handle = tokio::spawn(async move {
+        let _init_request = server.receive().await;
+        std::future::pending::<()>().await;
+    });
+
+    let result = serve_client_with_init_timeout(
+        TestClientHandler::new(true, true),
+        client_transport,
+        Some(Duration::from_millis(100)),
+        Default::default(),
+    )
+    .await;
+
+    match result {
+        Err(ClientInitializeError::Timeout(timeout)) => {
+            assert_eq!(timeout, Duration::from_millis(100));
+        }
+        _ => panic!("Expected ClientInitializeError::Timeout"),
+    }
+    server_handle.abort();
+}
+
+#[tokio::test]
+async fn test_client_init_reports_invalid_initialize_result() {
+    let (server_transport, client_transport) = tokio::io::duplex(1024);
+    let mut server = IntoTransport::<rmcp::RoleServer, _, _>::into_transport(server_transport);
+
+    let client_handle = tokio::spawn(async move {
+        TestClientHandler::new(true, true)
+            .serve(client_transport)
+            .await
+    });
+
+    tokio::spawn(async move {
+        let _init_request = server.receive().await;
+
+        let response = ServerJsonRpcMessage::Response(JsonRpcResponse {
+            jsonrpc: JsonRpcVersion2_0,
+            id: RequestId::Number(0),
+            result: ServerResult::CustomResult(CustomResult::new(serde_json::json!({
+                "capabilities": {},
+                "serverInfo": { "name": "broken", "version": "1.0.0" }
+            }))),
+        });
+        let _: Result<(), _> = server.send(response).await;
+    });
+
+    match client_handle.await.unwrap() {
+        Err(ClientInitializeError::InvalidInitResult(message)) => {
+            assert!(message.contains("protocolVersion"), "{message}");
+        }
+        _ => panic!("Expected ClientInitializeError::InvalidInitResult"),
+    }
+}
@@ -533,6 +533,117 @@ async fn test_mcp_custom_headers_sent_to_server() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Integration test: a caller-supplied reqwest client keeps its default headers, but
+/// the transport's own headers win and reserved custom headers are still rejected
+#[tokio::test]
+#[cfg(all(
+    feature = "transport-streamable-http-client",
+    feature = "transport-streamable-http-client-reqwest"
+))]
+async fn test_shared_reqwest_client() -> anyhow::Result<()> {
+    use std::sync::Arc;
+
+    use axum::{Router, extract::State, http::StatusCode, response::IntoResponse, routing::post};
+    use rmcp::{
+        ServiceExt,
+        model::{ClientJsonRpcMessage, ClientRequest, PingRequest, RequestId},
+        transport::{
+            StreamableHttpClientTransport,
+            streamable_http_client::{
+                StreamableHttpClient, StreamableHttpClientTransportConfig, StreamableHttpError,
+            },
+        },
+    };
+    use serde_json::json;
+    use tokio::sync::Mutex;
+
+    type Received = Arc<Mutex<Option<http::HeaderMap>>>;
+
+    async fn mcp_handler(
+        State(received): State<Received>,
+        headers: http::HeaderMap,
+        body: axum::body::Bytes,
+    ) -> impl IntoResponse {
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap_or_default();
+        if body["method"] != "initialize" {
+            return (StatusCode::ACCEPTED, String::new()).into_response();
+        }
+        received.lock().await.replace(headers);
+        let response = json!({
+            "jsonrpc": "2.0",
+            "id": body["id"],
+            "result": {
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "serverInfo": { "name": "test-server", "version": "1.0.0" }
+            }
+        });
+        (
+            StatusCode::OK,
+            [
+                (http::header::CONTENT_TYPE, "application/json"),
+                (
+                    http::HeaderName::from_static("mcp-session-id"),
+                    "test-session-123",
+                ),
+            ],
+            response.to_string(),
+        )
+            .into_response()
+    }
+
+    let received = Received::default();
+    let app = Router::new()
+        .route("/mcp", post(mcp_handler))
+        .with_state(received.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let uri = format!("http://{}/mcp", listener.local_addr()?);
+    let server_handle = tokio::spawn(async move { axum::serve(listener, app).await });
+
+    let mut default_headers = http::HeaderMap::new();
+    default_headers.insert("x-shared-client", HeaderValue::from_static("yes"));
+    default_headers.insert(http::header::ACCEPT, HeaderValue::from_static("text/html"));
+    let shared = reqwest::Client::builder()
+        .default_headers(default_headers)
+        .build()?;
+
+    let transport = StreamableHttpClientTransport::with_client(
+        shared.clone(),
+        StreamableHttpClientTransportConfig::with_uri(uri.as_str()),
+    );
+    let client = ().serve(transport).await?;
+
+    let headers = received.lock().await.take().expect("initialize received");
+    assert_eq!(headers["x-shared-client"], "yes");
+    assert_eq!(headers["accept"], "text/event-stream, application/json");
+
+    let mut custom_headers = HashMap::new();
+    custom_headers.insert(
+        HeaderName::from_static("mcp-session-id"),
+        HeaderValue::from_static("forged"),
+    );
+    let result = shared
+        .post_message(
+            Arc::from(uri.as_str()),
+            ClientJsonRpcMessage::request(
+                ClientRequest::PingRequest(PingRequest::default()),
+                RequestId::Number(1),
+            ),
+            None,
+            None,
+            custom_headers,
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(StreamableHttpError::ReservedHeaderConflict(_))
+    ));
+
+    drop(client);
+    server_handle.abort();
+    Ok(())
+}
+
 /// Integration test: Verify that MCP-Protocol-Version header is sent on post-init requests
 #[tokio::test]
 #[cfg(all(
@@ -4,7 +4,8 @@ use std::sync::Arc;
 use rmcp::{
     ClientHandler, ServerHandler, ServiceExt,
     model::{
-        ClientRequest, ClientResult, CustomRequest, CustomResult, ServerRequest, ServerResult,
+        ClientRequest, ClientResult, CustomRequest, CustomResult, ExperimentalCapabilities,
+        ServerCapabilities, ServerInfo, ServerRequest, ServerResult,
     },
 };
 use serde_json::json;
@@ -188,3 +189,77 @@ async fn test_custom_server_request_reaches_client() -> anyhow::Result<()> {
     client.cancel().await?;
     Ok(())
 }
+
+struct ExperimentalServer;
+
+impl ServerHandler for ExperimentalServer {
+    fn get_info(&self) -> ServerInfo {
+        let mut experimental = ExperimentalCapabilities::new();
+        experimental.insert(
+            "com.example/echo".to_string(),
+            json!({ "version": 1 }).as_object().cloned().unwrap(),
+        );
+        ServerInfo::new(
+            ServerCapabilities::builder()
+                .enable_experimental_with(experimental)
+                .build(),
+        )
+    }
+
+    async fn on_custom_request(
+        &self,
+        request: CustomRequest,
+        _context: rmcp::service::RequestContext<rmcp::RoleServer>,
+    ) -> Result<CustomResult, rmcp::ErrorData> {
+        match request.method.as_str() {
+            "com.example/echo" => Ok(CustomResult::new(request.params.unwrap_or_default())),
+            _ => Err(rmcp::ErrorData::method_not_found_for(request.method)),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_experimental_method_is_advertised_and_served() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    tokio::spawn(async move {
+        ExperimentalServer
+            .serve(server_transport)
+            .await?
+            .waiting()
+            .await?;
+        anyhow::Ok(())
+    });
+    let client = ().serve(client_transport).await?;
+
+    let experimental = client
+        .peer_info()
+        .and_then(|info| info.capabilities.experimental.clone())
+        .expect("experimental capabilities advertised");
+    assert!(experimental.contains_key("com.example/echo"));
+
+    let response = client
+        .send_request(ClientRequest::CustomRequest(CustomRequest::new(
+            "com.example/echo",
+            Some(json!({ "hello": "world" })),
+        )))
+        .await?;
+    let ServerResult::CustomResult(result) = response else {
+        panic!("Expected custom result, got: {response:?}");
+    };
+    assert_eq!(result.0, json!({ "hello": "world" }));
+
+    let error = client
+        .send_request(ClientRequest::CustomRequest(CustomRequest::new(
+            "com.example/other",
+            None,
+        )))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        rmcp::ServiceError::McpError(ref data) if data.code == rmcp::model::ErrorCode::METHOD_NOT_FOUND
+    ));
+
+    client.cancel().await?;
+    Ok(())
+}
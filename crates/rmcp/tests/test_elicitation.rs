@@ -2168,3 +2168,86 @@ async fn test_elicitation_method_constants() {
         "notifications/elicitation/complete"
     );
 }
+
+#[cfg(all(feature = "schemars", not(feature = "local")))]
+struct ElicitingServer;
+
+#[cfg(all(feature = "schemars", not(feature = "local")))]
+impl rmcp::ServerHandler for ElicitingServer {}
+
+/// A client that never answers elicitation requests and reports cancellations.
+#[cfg(all(feature = "schemars", not(feature = "local")))]
+struct UnresponsiveClient {
+    cancelled: tokio::sync::mpsc::UnboundedSender<CancelledNotificationParam>,
+}
+
+#[cfg(all(feature = "schemars", not(feature = "local")))]
+impl rmcp::ClientHandler for UnresponsiveClient {
+    async fn create_elicitation(
+        &self,
+        _request: ElicitRequestParams,
+        context: RequestContext<RoleClient>,
+    ) -> Result<ElicitResult, rmcp::ErrorData> {
+        context.ct.cancelled().await;
+        Ok(ElicitResult::new(ElicitationAction::Cancel))
+    }
+
+    async fn on_cancelled(
+        &self,
+        params: CancelledNotificationParam,
+        _context: NotificationContext<RoleClient>,
+    ) {
+        let _ = self.cancelled.send(params);
+    }
+
+    fn get_info(&self) -> ClientInfo {
+        ClientInfo::new(
+            ClientCapabilities::builder().enable_elicitation().build(),
+            Implementation::from_build_env(),
+        )
+    }
+}
+
+/// Test that an expired elicitation is cancelled and reported as `TimedOut`
+#[cfg(all(feature = "schemars", not(feature = "local")))]
+#[tokio::test]
+async fn test_elicit_with_timeout_expires() -> anyhow::Result<()> {
+    use std::time::Duration;
+
+    #[derive(Debug, Serialize, Deserialize, JsonSchema)]
+    struct Answer {
+        answer: String,
+    }
+    rmcp::elicit_safe!(Answer);
+
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let (cancelled_tx, mut cancelled_rx) = tokio::sync::mpsc::unbounded_channel();
+    let client_handle = tokio::spawn(async move {
+        let client = UnresponsiveClient {
+            cancelled: cancelled_tx,
+        }
+        .serve(client_transport)
+        .await?;
+        client.waiting().await?;
+        anyhow::Ok(())
+    });
+    let server = ElicitingServer.serve(server_transport).await?;
+
+    let timeout = Duration::from_millis(50);
+    let error = server
+        .peer()
+        .elicit_with_timeout::<Answer>("Still there?", Some(timeout))
+        .await
+        .expect_err("elicitation should expire");
+    assert!(matches!(error, ElicitationError::TimedOut { timeout: t } if t == timeout));
+    assert!(error.to_string().contains("did not respond"));
+
+    let cancelled = tokio::time::timeout(Duration::from_secs(5), cancelled_rx.recv())
+        .await?
+        .expect("cancellation notification");
+    assert!(cancelled.request_id.is_some());
+
+    server.cancel().await?;
+    client_handle.await??;
+    Ok(())
+}
@@ -0,0 +1,132 @@
+#![cfg(all(feature = "client", feature = "server", not(feature = "local")))]
+// cargo test --test test_graceful_shutdown --features "client server"
+
+use std::time::Duration;
+
+use rmcp::{
+    ServerHandler, ServiceError, ServiceExt,
+    handler::server::{router::tool::ToolRouter, wrapper::Parameters},
+    model::{CallToolRequestParams, ServerCapabilities, ServerInfo},
+    service::QuitReason,
+    tool, tool_handler, tool_router,
+};
+
+#[derive(Debug, Clone)]
+struct SlowToolServer {
+    #[expect(dead_code, reason = "tool_handler macro accesses this router field")]
+    tool_router: ToolRouter<Self>,
+}
+
+impl SlowToolServer {
+    fn new() -> Self {
+        Self {
+            tool_router: Self::tool_router(),
+        }
+    }
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct SlowToolRequest {
+    sleep_ms: u64,
+}
+
+#[tool_router]
+impl SlowToolServer {
+    #[tool(description = "A tool that sleeps then returns")]
+    async fn slow_tool(
+        &self,
+        Parameters(SlowToolRequest { sleep_ms }): Parameters<SlowToolRequest>,
+    ) -> String {
+        tokio::time::sleep(Duration::from_millis(sleep_ms)).await;
+        format!("done after {}ms", sleep_ms)
+    }
+}
+
+#[tool_handler]
+impl ServerHandler for SlowToolServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo::new(ServerCapabilities::builder().enable_tools().build())
+    }
+}
+
+fn slow_call(sleep_ms: u64) -> CallToolRequestParams {
+    CallToolRequestParams::new("slow_tool").with_arguments(
+        serde_json::json!({ "sleep_ms": sleep_ms })
+            .as_object()
+            .unwrap()
+            .clone(),
+    )
+}
+
+/// `shutdown` waits for the reply to a request that is still in flight,
+/// then refuses new work and closes the transport.
+#[tokio::test]
+async fn test_shutdown_awaits_outstanding_request() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let server = tokio::spawn(async move {
+        let running = SlowToolServer::new().serve(server_transport).await?;
+        anyhow::Ok(running.waiting().await?)
+    });
+    let mut client = ().serve(client_transport).await?;
+
+    let peer = client.peer().clone();
+    let call = tokio::spawn(async move { peer.call_tool(slow_call(300)).await });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let reason = client.shutdown(Duration::from_secs(5)).await?;
+    assert!(matches!(reason, QuitReason::Cancelled), "got {reason:?}");
+
+    let result = call.await??;
+    let text = result.content[0].as_text().expect("text content");
+    assert_eq!(text.text, "done after 300ms");
+
+    let late = client.peer().call_tool(slow_call(0)).await;
+    assert!(
+        matches!(late, Err(ServiceError::TransportClosed)),
+        "got {late:?}"
+    );
+    assert!(matches!(server.await??, QuitReason::Closed));
+    Ok(())
+}
+
+/// `cancel` stays a hard stop: the in-flight request is dropped.
+#[tokio::test]
+async fn test_cancel_drops_outstanding_request() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    tokio::spawn(async move {
+        let running = SlowToolServer::new().serve(server_transport).await?;
+        anyhow::Ok(running.waiting().await?)
+    });
+    let client = ().serve(client_transport).await?;
+
+    let peer = client.peer().clone();
+    let call = tokio::spawn(async move { peer.call_tool(slow_call(300)).await });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    client.cancel().await?;
+    assert!(call.await?.is_err());
+    Ok(())
+}
+
+/// When outstanding requests outlast the timeout, `shutdown` falls back to
+/// cancelling the service.
+#[tokio::test]
+async fn test_shutdown_times_out_to_cancel() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    tokio::spawn(async move {
+        let running = SlowToolServer::new().serve(server_transport).await?;
+        anyhow::Ok(running.waiting().await?)
+    });
+    let mut client = ().serve(client_transport).await?;
+
+    let peer = client.peer().clone();
+    let call = tokio::spawn(async move { peer.call_tool(slow_call(10_000)).await });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let started = std::time::Instant::now();
+    client.shutdown(Duration::from_millis(200)).await?;
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert!(call.await?.is_err());
+    assert!(client.is_closed());
+    Ok(())
+}
@@ -0,0 +1,246 @@
+#![cfg(all(feature = "client", feature = "server", not(feature = "local")))]
+#![allow(deprecated)]
+// cargo test --test test_logging_throttle --features "client server"
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use rmcp::{
+    ClientHandler, ErrorData, RoleClient, RoleServer, ServerHandler, ServiceExt,
+    model::{LoggingLevel, LoggingMessageNotificationParam, SetLevelRequestParams},
+    service::{
+        LogDisposition, LoggingThrottle, LoggingThrottleConfig, LoggingThrottleConfigError,
+        NotificationContext, RequestContext, serve_server_with_logging_throttle,
+    },
+};
+use serde_json::json;
+use tokio_util::sync::CancellationToken;
+
+#[derive(Clone)]
+struct ThrottledServer {
+    logs: Arc<LoggingThrottle>,
+}
+
+impl ServerHandler for ThrottledServer {
+    async fn set_level(
+        &self,
+        request: SetLevelRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), ErrorData> {
+        self.logs.set_level(request.level);
+        Ok(())
+    }
+}
+
+/// Leaves `logging/setLevel` to the throttle the server was started with.
+struct PlainServer;
+
+impl ServerHandler for PlainServer {}
+
+#[derive(Clone, Default)]
+struct CollectingClient {
+    received: Arc<Mutex<Vec<LoggingMessageNotificationParam>>>,
+}
+
+impl ClientHandler for CollectingClient {
+    async fn on_logging_message(
+        &self,
+        params: LoggingMessageNotificationParam,
+        _context: NotificationContext<RoleClient>,
+    ) {
+        self.received.lock().unwrap().push(params);
+    }
+}
+
+fn message(level: LoggingLevel, text: &str) -> LoggingMessageNotificationParam {
+    LoggingMessageNotificationParam::new(level, json!(text)).with_logger("disk")
+}
+
+impl CollectingClient {
+    async fn wait_for(&self, count: usize) -> anyhow::Result<Vec<LoggingMessageNotificationParam>> {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while self.received.lock().unwrap().len() < count {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await?;
+        Ok(self.received.lock().unwrap().clone())
+    }
+}
+
+#[test]
+fn test_logging_throttle_config_validation() {
+    assert_eq!(
+        LoggingThrottleConfig::new(0).validate(),
+        Err(LoggingThrottleConfigError::ZeroRate)
+    );
+    assert_eq!(
+        LoggingThrottle::new(
+            LoggingThrottleConfig::new(5).with_coalesce_window(Some(Duration::ZERO))
+        )
+        .unwrap_err(),
+        LoggingThrottleConfigError::ZeroCoalesceWindow
+    );
+    assert!(
+        LoggingThrottleConfig::new(5)
+            .with_coalesce_window(None)
+            .validate()
+            .is_ok()
+    );
+}
+
+#[tokio::test]
+async fn test_logging_throttle_filters_coalesces_and_drops() -> anyhow::Result<()> {
+    let logs = Arc::new(LoggingThrottle::new(LoggingThrottleConfig::new(5))?);
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let client_handler = CollectingClient::default();
+    let (server, client) = tokio::join!(
+        ThrottledServer { logs: logs.clone() }.serve(server_transport),
+        client_handler.clone().serve(client_transport)
+    );
+    let (server, client) = (server?, client?);
+    let peer = server.peer().clone();
+
+    client
+        .peer()
+        .set_level(SetLevelRequestParams::new(LoggingLevel::Warning))
+        .await?;
+    assert_eq!(logs.level(), LoggingLevel::Warning);
+
+    let info = logs.log(&peer, message(LoggingLevel::Info, "tick")).await?;
+    assert_eq!(info, LogDisposition::BelowLevel);
+
+    let mut dispositions = Vec::new();
+    for _ in 0..3 {
+        dispositions.push(
+            logs.log(&peer, message(LoggingLevel::Warning, "disk low"))
+                .await?,
+        );
+    }
+    assert_eq!(
+        dispositions,
+        [
+            LogDisposition::Sent,
+            LogDisposition::Coalesced,
+            LogDisposition::Coalesced
+        ]
+    );
+
+    let mut sent = 0;
+    for i in 0..10 {
+        match logs
+            .log(
+                &peer,
+                message(LoggingLevel::Error, &format!("write {i} failed")),
+            )
+            .await?
+        {
+            LogDisposition::Sent => sent += 1,
+            LogDisposition::Dropped => {}
+            other => panic!("unexpected disposition {other:?}"),
+        }
+    }
+    // One of the five messages allowed per second went to "disk low".
+    assert_eq!(sent, 4);
+    assert_eq!(logs.dropped(), 6);
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while client_handler.received.lock().unwrap().len() < 6 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await?;
+    let received = client_handler.received.lock().unwrap().clone();
+    assert_eq!(received.len(), 6);
+    assert_eq!(received[0].data, json!("disk low"));
+    assert_eq!(received[1].level, LoggingLevel::Warning);
+    assert_eq!(
+        received[1].data,
+        json!({ "repeated": 2, "data": "disk low" })
+    );
+    assert_eq!(received[2].data, json!("write 0 failed"));
+
+    // The allowance refills over time.
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    let later = logs
+        .log(&peer, message(LoggingLevel::Error, "recovered"))
+        .await?;
+    assert_eq!(later, LogDisposition::Sent);
+
+    client.cancel().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_server_owned_logging_throttle_reports_trailing_repeats() -> anyhow::Result<()> {
+    let config =
+        LoggingThrottleConfig::new(100).with_coalesce_window(Some(Duration::from_millis(300)));
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let client_handler = CollectingClient::default();
+    let (server, client) = tokio::join!(
+        serve_server_with_logging_throttle(
+            PlainServer,
+            server_transport,
+            config,
+            CancellationToken::new()
+        ),
+        client_handler.clone().serve(client_transport)
+    );
+    let (server, client) = (server?, client?);
+    let peer = server.peer().clone();
+
+    client
+        .peer()
+        .set_level(SetLevelRequestParams::new(LoggingLevel::Warning))
+        .await?;
+    assert_eq!(
+        peer.logging_throttle().map(LoggingThrottle::level),
+        Some(LoggingLevel::Warning)
+    );
+
+    peer.notify_logging_message(message(LoggingLevel::Info, "tick"))
+        .await?;
+    for _ in 0..3 {
+        peer.notify_logging_message(message(LoggingLevel::Warning, "disk low"))
+            .await?;
+    }
+    // Nothing follows the burst; the repeats are reported once the window closes.
+    let received = client_handler.wait_for(2).await?;
+    assert_eq!(received[0].data, json!("disk low"));
+    assert_eq!(
+        received[1].data,
+        json!({ "repeated": 2, "data": "disk low" })
+    );
+
+    // A throttle held by the handler reports its repeats when dropped.
+    let logs = LoggingThrottle::new(config)?;
+    for _ in 0..2 {
+        logs.log(&peer, message(LoggingLevel::Error, "fan stopped"))
+            .await?;
+    }
+    drop(logs);
+    let received = client_handler.wait_for(4).await?;
+    assert_eq!(received[2].data, json!("fan stopped"));
+    assert_eq!(
+        received[3].data,
+        json!({ "repeated": 1, "data": "fan stopped" })
+    );
+
+    // Repeats still held back at shutdown go out before the transport closes.
+    for _ in 0..2 {
+        peer.notify_logging_message(message(LoggingLevel::Warning, "disk full"))
+            .await?;
+    }
+    server.cancel().await?;
+    let received = client_handler.wait_for(6).await?;
+    assert_eq!(received[4].data, json!("disk full"));
+    assert_eq!(
+        received[5].data,
+        json!({ "repeated": 1, "data": "disk full" })
+    );
+
+    client.cancel().await?;
+    Ok(())
+}
@@ -0,0 +1,112 @@
+#![cfg(not(feature = "local"))]
+use rmcp::{
+    ServerHandler, ServiceError, ServiceExt,
+    model::{
+        ClientRequest, CustomRequest, ErrorCode, GetPromptRequestParams, ServerCapabilities,
+        ServerInfo,
+    },
+    service::{RoleClient, RunningService},
+    transport::{
+        StreamableHttpClientTransport,
+        streamable_http_client::StreamableHttpClientTransportConfig,
+        streamable_http_server::{
+            StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
+        },
+    },
+};
+use serde_json::json;
+use tokio_util::sync::CancellationToken;
+
+#[derive(Debug, Clone, Default)]
+struct ToolsOnlyServer;
+
+impl ServerHandler for ToolsOnlyServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo::new(ServerCapabilities::builder().enable_tools().build())
+    }
+}
+
+fn method_not_found_error(
+    result: Result<impl std::fmt::Debug, ServiceError>,
+    method: &str,
+) -> rmcp::ErrorData {
+    let Err(ServiceError::McpError(error)) = result else {
+        panic!("expected a method-not-found error for {method}, got {result:?}");
+    };
+    assert_eq!(error.code, ErrorCode::METHOD_NOT_FOUND);
+    error
+}
+
+async fn assert_unknown_methods_rejected(client: &RunningService<RoleClient, ()>) {
+    let unknown = client
+        .send_request(ClientRequest::CustomRequest(CustomRequest::new(
+            "foo/bar",
+            Some(json!({ "x": 1 })),
+        )))
+        .await;
+    let error = method_not_found_error(unknown, "foo/bar");
+    assert_eq!(error.message, "method not found: foo/bar");
+    assert_eq!(error.data, Some(json!({ "method": "foo/bar" })));
+
+    let unsupported = client
+        .get_prompt(GetPromptRequestParams::new("greeting"))
+        .await;
+    // Known methods the handler does not implement keep the bare method name.
+    let error = method_not_found_error(unsupported, "prompts/get");
+    assert_eq!(error.message, "prompts/get");
+    assert_eq!(error.data, None);
+}
+
+#[tokio::test]
+async fn test_unknown_method_over_stream_transport() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    tokio::spawn(async move {
+        ToolsOnlyServer
+            .serve(server_transport)
+            .await?
+            .waiting()
+            .await?;
+        anyhow::Ok(())
+    });
+    let client = ().serve(client_transport).await?;
+
+    assert_unknown_methods_rejected(&client).await;
+
+    client.cancel().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unknown_method_over_streamable_http() -> anyhow::Result<()> {
+    let ct = CancellationToken::new();
+    let service: StreamableHttpService<ToolsOnlyServer, LocalSessionManager> =
+        StreamableHttpService::new(
+            || Ok(ToolsOnlyServer),
+            Default::default(),
+            StreamableHttpServerConfig::default()
+                .with_sse_keep_alive(None)
+                .with_cancellation_token(ct.child_token()),
+        );
+    let router = axum::Router::new().nest_service("/mcp", service);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn({
+        let ct = ct.clone();
+        async move {
+            let _ = axum::serve(listener, router)
+                .with_graceful_shutdown(async move { ct.cancelled_owned().await })
+                .await;
+        }
+    });
+
+    let transport = StreamableHttpClientTransport::from_config(
+        StreamableHttpClientTransportConfig::with_uri(format!("http://{addr}/mcp")),
+    );
+    let client = ().serve(transport).await?;
+
+    assert_unknown_methods_rejected(&client).await;
+
+    client.cancel().await?;
+    ct.cancel();
+    Ok(())
+}
@@ -0,0 +1,215 @@
+#![cfg(not(feature = "local"))]
+#![allow(deprecated)]
+use rmcp::{
+    RoleClient, ServiceError,
+    model::*,
+    service::{Peer, ServerCapability},
+    test_util::{MockClient, MockServer, connect},
+};
+
+/// The orchestration logic under test: pick the first tool and summarize its output.
+async fn run_first_tool(peer: &Peer<RoleClient>) -> Result<String, ServiceError> {
+    let tools = peer.list_all_tools().await?;
+    let Some(tool) = tools.first() else {
+        return Ok("no tools".to_string());
+    };
+    let result = peer
+        .call_tool(CallToolRequestParams::new(tool.name.clone()))
+        .await?;
+    Ok(format!(
+        "{}: {}",
+        tool.name,
+        result.content[0]
+            .as_text()
+            .map(|t| t.text.as_str())
+            .unwrap_or("")
+    ))
+}
+
+#[tokio::test]
+async fn test_mock_server_drives_client_logic() -> anyhow::Result<()> {
+    let server = MockServer::new()
+        .with_tool(
+            Tool::new("weather", "Current weather", JsonObject::new()),
+            CallToolResult::success(vec![ContentBlock::text("sunny")]),
+        )
+        .with_tool_error(
+            Tool::new("broken", "Always fails", JsonObject::new()),
+            ErrorData::internal("backend down"),
+        )
+        .with_resource(
+            Resource::new("mem://notes", "notes"),
+            vec![ResourceContents::text("remember the milk", "mem://notes")],
+        )
+        .with_prompt(
+            Prompt::new("greet", Some("Greet someone"), None),
+            GetPromptResult::new(vec![PromptMessage::new_text(Role::User, "Say hello")]),
+        );
+    let (_server, client) = connect(server.clone(), MockClient::new()).await?;
+
+    assert_eq!(run_first_tool(client.peer()).await?, "weather: sunny");
+    assert_eq!(server.tool_calls().len(), 1);
+
+    let error = client
+        .call_tool(CallToolRequestParams::new("broken"))
+        .await
+        .expect_err("canned error");
+    assert!(matches!(error, ServiceError::McpError(e) if e.message == "backend down"));
+    let error = client
+        .call_tool(CallToolRequestParams::new("missing"))
+        .await
+        .expect_err("unknown tool");
+    assert!(matches!(error, ServiceError::McpError(e) if e.code == ErrorCode::INVALID_PARAMS));
+    assert_eq!(
+        server
+            .tool_calls()
+            .iter()
+            .map(|c| c.name.as_ref())
+            .collect::<Vec<_>>(),
+        ["weather", "broken", "missing"]
+    );
+
+    let read = client
+        .read_resource(ReadResourceRequestParams::new("mem://notes"))
+        .await?;
+    assert_eq!(
+        read.contents,
+        vec![ResourceContents::text("remember the milk", "mem://notes")]
+    );
+    let prompt = client
+        .get_prompt(GetPromptRequestParams::new("greet"))
+        .await?;
+    assert_eq!(prompt.messages.len(), 1);
+
+    client.cancel().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mock_client_answers_sampling_and_elicitation() -> anyhow::Result<()> {
+    let client = MockClient::new()
+        .with_sampling_response(CreateMessageResult::new(
+            SamplingMessage::assistant_text("42"),
+            "mock-model".to_string(),
+        ))
+        .with_elicitation_response(ElicitResult::new(ElicitationAction::Cancel));
+    let (server, _client) = connect(MockServer::new(), client.clone()).await?;
+
+    let result = server
+        .peer()
+        .create_message(CreateMessageRequestParams::new(
+            vec![SamplingMessage::user_text("What is the answer?")],
+            16,
+        ))
+        .await?;
+    assert_eq!(result.model, "mock-model");
+    assert_eq!(client.sampling_requests().len(), 1);
+
+    let result = server
+        .peer()
+        .create_elicitation(ElicitRequestParams::FormElicitationParams {
+            meta: None,
+            message: "Your name?".to_string(),
+            requested_schema: ElicitationSchema::builder()
+                .required_string("name")
+                .build()
+                .unwrap(),
+        })
+        .await?;
+    assert_eq!(result.action, ElicitationAction::Cancel);
+    assert_eq!(client.elicitation_requests().len(), 1);
+
+    server.cancel().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_service_error_exposes_code_and_data() -> anyhow::Result<()> {
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct RetryHint {
+        retry_after_ms: u64,
+    }
+
+    let server = MockServer::new().with_tool_error(
+        Tool::new("flaky", "Rate limited", JsonObject::new()),
+        ErrorData::new(
+            ErrorCode(-32050),
+            "rate limited",
+            Some(serde_json::json!({ "retry_after_ms": 250 })),
+        ),
+    );
+    let (_server, client) = connect(server, MockClient::new()).await?;
+
+    let error = client
+        .call_tool(CallToolRequestParams::new("flaky"))
+        .await
+        .expect_err("canned error");
+    assert_eq!(error.code(), Some(ErrorCode(-32050)));
+    assert_eq!(
+        error.data::<RetryHint>(),
+        Some(RetryHint {
+            retry_after_ms: 250
+        })
+    );
+    assert_eq!(error.data::<Vec<String>>(), None);
+    assert_eq!(error.as_error_data().unwrap().message, "rate limited");
+
+    client.cancel().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_client_reads_negotiated_server_capabilities() -> anyhow::Result<()> {
+    let server = MockServer::new().with_tool(
+        Tool::new("noop", "Does nothing", JsonObject::new()),
+        CallToolResult::success(vec![]),
+    );
+    let (_server, client) = connect(server, MockClient::new()).await?;
+
+    let capabilities = client.peer().server_capabilities().expect("initialized");
+    assert!(capabilities.tools.is_some());
+    assert!(client.peer().supports_tools());
+    assert!(!client.peer().supports_tool_list_changed());
+    assert!(!client.peer().supports_resources());
+    assert!(!client.peer().supports_resource_subscription());
+    assert!(!client.peer().supports_prompts());
+
+    client.cancel().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_check_required_capabilities_names_missing_ones() -> anyhow::Result<()> {
+    let server = MockServer::new().with_tool(
+        Tool::new("noop", "Does nothing", JsonObject::new()),
+        CallToolResult::success(vec![]),
+    );
+    let (_server, client) = connect(server, MockClient::new()).await?;
+
+    client
+        .peer()
+        .check_required_capabilities(&[ServerCapability::Tools])?;
+    let error = client
+        .peer()
+        .check_required_capabilities(&[
+            ServerCapability::Tools,
+            ServerCapability::Resources,
+            ServerCapability::PromptListChanged,
+        ])
+        .expect_err("resources and prompts are not declared");
+    assert_eq!(
+        error.missing,
+        [
+            ServerCapability::Resources,
+            ServerCapability::PromptListChanged
+        ]
+    );
+    let message = error.to_string();
+    assert!(
+        message.ends_with("required capabilities: resources, prompts.listChanged"),
+        "{message}"
+    );
+
+    client.cancel().await?;
+    Ok(())
+}
@@ -0,0 +1,34 @@
+#![cfg(all(windows, not(feature = "local")))]
+use rmcp::{
+    ServerHandler, ServiceExt,
+    model::{ServerCapabilities, ServerInfo},
+    transport::{NamedPipeClientTransport, NamedPipeServerTransport},
+};
+
+#[derive(Clone)]
+struct EmptyServer;
+
+impl ServerHandler for EmptyServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo::new(ServerCapabilities::builder().enable_tools().build())
+    }
+}
+
+#[tokio::test]
+async fn test_named_pipe_round_trip() -> anyhow::Result<()> {
+    let name = format!(r"\\.\pipe\rmcp-test-{}", std::process::id());
+    let mut listener = NamedPipeServerTransport::bind(&name)?;
+
+    let server_handle = tokio::spawn(async move {
+        let transport = listener.accept().await?;
+        EmptyServer.serve(transport).await?.waiting().await?;
+        anyhow::Ok(())
+    });
+
+    let client = ().serve(NamedPipeClientTransport::connect(&name).await?).await?;
+    let tools = client.list_all_tools().await?;
+    assert!(tools.is_empty());
+    client.cancel().await?;
+    server_handle.await??;
+    Ok(())
+}
@@ -0,0 +1,80 @@
+#![cfg(not(feature = "local"))]
+use rmcp::{
+    ServerHandler, ServiceExt,
+    handler::server::{router::tool::ToolRouter, wrapper::Parameters},
+    oneshot, schemars, tool, tool_handler, tool_router,
+};
+use tokio::io::DuplexStream;
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct EchoRequest {
+    message: String,
+}
+
+#[derive(Debug, Clone)]
+struct EchoServer {
+    #[expect(dead_code, reason = "tool_handler macro accesses this router field")]
+    tool_router: ToolRouter<Self>,
+}
+
+#[tool_router]
+impl EchoServer {
+    fn new() -> Self {
+        Self {
+            tool_router: Self::tool_router(),
+        }
+    }
+
+    #[tool(description = "Echo a message back")]
+    fn echo(&self, Parameters(EchoRequest { message }): Parameters<EchoRequest>) -> String {
+        message
+    }
+}
+
+#[tool_handler]
+impl ServerHandler for EchoServer {}
+
+/// Start a server and return the client end plus a handle that resolves once
+/// the server sees the connection close.
+fn spawn_server() -> (DuplexStream, tokio::task::JoinHandle<anyhow::Result<()>>) {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let server = tokio::spawn(async move {
+        EchoServer::new()
+            .serve(server_transport)
+            .await?
+            .waiting()
+            .await?;
+        anyhow::Ok(())
+    });
+    (client_transport, server)
+}
+
+#[tokio::test]
+async fn test_oneshot_call_tool_closes_connection() -> anyhow::Result<()> {
+    let (transport, server) = spawn_server();
+    let arguments = serde_json::json!({"message": "hello"}).as_object().cloned();
+    let result = oneshot::call_tool(transport, "echo", arguments).await?;
+    assert_eq!(result.text().as_deref(), Some("hello"));
+
+    tokio::time::timeout(std::time::Duration::from_secs(5), server).await???;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_oneshot_list_tools() -> anyhow::Result<()> {
+    let (transport, _server) = spawn_server();
+    let tools = oneshot::list_tools(transport).await?;
+    assert_eq!(tools.len(), 1);
+    assert_eq!(tools[0].name, "echo");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_oneshot_reports_request_error() -> anyhow::Result<()> {
+    let (transport, _server) = spawn_server();
+    let error = oneshot::read_resource(transport, "file:///missing")
+        .await
+        .unwrap_err();
+    assert!(matches!(error, rmcp::RmcpError::Service(_)), "{error}");
+    Ok(())
+}
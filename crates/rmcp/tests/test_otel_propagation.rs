@@ -0,0 +1,170 @@
+#![cfg(not(feature = "local"))]
+//! The client injects its OpenTelemetry context into `_meta`, and the server parents its
+//! request span to it.
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+
+use opentelemetry::{
+    Context,
+    propagation::{Extractor, Injector, TextMapPropagator, text_map_propagator::FieldIter},
+    testing::trace::TestSpan,
+    trace::{
+        SpanBuilder, SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState, Tracer,
+    },
+};
+use rmcp::{
+    RoleServer, ServerHandler, ServiceExt,
+    model::{ClientRequest, CustomRequest, CustomResult, Meta},
+    service::RequestContext,
+};
+use serde_json::json;
+use tokio::sync::Mutex;
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::prelude::*;
+
+/// Tracer that hands out sequential ids and keeps the parent's trace id, so
+/// the test needs no SDK.
+#[derive(Debug, Default)]
+struct CountingTracer {
+    next_id: AtomicU64,
+}
+
+impl Tracer for CountingTracer {
+    type Span = TestSpan;
+
+    fn build_with_context(&self, _builder: SpanBuilder, parent_cx: &Context) -> TestSpan {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let parent = parent_cx.span().span_context().clone();
+        let trace_id = if parent.is_valid() {
+            parent.trace_id()
+        } else {
+            TraceId::from(u128::from(id))
+        };
+        TestSpan(SpanContext::new(
+            trace_id,
+            SpanId::from(id),
+            TraceFlags::SAMPLED,
+            false,
+            TraceState::NONE,
+        ))
+    }
+}
+
+/// Minimal W3C `traceparent` propagator.
+#[derive(Debug)]
+struct TraceparentPropagator {
+    fields: [String; 1],
+}
+
+impl TraceparentPropagator {
+    fn new() -> Self {
+        Self {
+            fields: ["traceparent".to_owned()],
+        }
+    }
+}
+
+impl TextMapPropagator for TraceparentPropagator {
+    fn inject_context(&self, cx: &Context, injector: &mut dyn Injector) {
+        let span = cx.span();
+        let span_context = span.span_context();
+        if span_context.is_valid() {
+            injector.set(
+                "traceparent",
+                format!(
+                    "00-{:032x}-{:016x}-{:02x}",
+                    span_context.trace_id(),
+                    span_context.span_id(),
+                    span_context.trace_flags()
+                ),
+            );
+        }
+    }
+
+    fn extract_with_context(&self, cx: &Context, extractor: &dyn Extractor) -> Context {
+        let parts: Option<Vec<&str>> = extractor
+            .get("traceparent")
+            .map(|value| value.split('-').collect());
+        let Some(["00", trace_id, span_id, flags]) = parts.as_deref() else {
+            return cx.clone();
+        };
+        let (Ok(trace_id), Ok(span_id), Ok(flags)) = (
+            TraceId::from_hex(trace_id),
+            SpanId::from_hex(span_id),
+            u8::from_str_radix(flags, 16),
+        ) else {
+            return cx.clone();
+        };
+        cx.with_remote_span_context(SpanContext::new(
+            trace_id,
+            span_id,
+            TraceFlags::new(flags),
+            true,
+            TraceState::NONE,
+        ))
+    }
+
+    fn fields(&self) -> FieldIter<'_> {
+        FieldIter::new(&self.fields)
+    }
+}
+
+struct TraceCapturingServer {
+    seen: Arc<Mutex<Option<(Meta, TraceId)>>>,
+}
+
+impl ServerHandler for TraceCapturingServer {
+    async fn on_custom_request(
+        &self,
+        _request: CustomRequest,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CustomResult, rmcp::ErrorData> {
+        let trace_id = tracing::Span::current()
+            .context()
+            .span()
+            .span_context()
+            .trace_id();
+        *self.seen.lock().await = Some((context.meta, trace_id));
+        Ok(CustomResult::new(json!({ "status": "ok" })))
+    }
+}
+
+#[tokio::test]
+async fn test_trace_context_propagates_through_meta() -> anyhow::Result<()> {
+    opentelemetry::global::set_text_map_propagator(TraceparentPropagator::new());
+    tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(CountingTracer::default()))
+        .try_init()?;
+
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let seen = Arc::new(Mutex::new(None));
+    let server = TraceCapturingServer { seen: seen.clone() };
+    let server_handle = tokio::spawn(async move {
+        let server = server.serve(server_transport).await?;
+        server.waiting().await?;
+        anyhow::Ok(())
+    });
+
+    let client = ().serve(client_transport).await?;
+    let client_span = tracing::info_span!("client.call");
+    let client_trace_id = client_span.context().span().span_context().trace_id();
+    assert_ne!(client_trace_id, TraceId::INVALID);
+    client
+        .send_request(ClientRequest::CustomRequest(CustomRequest::new(
+            "requests/trace",
+            None,
+        )))
+        .instrument(client_span)
+        .await?;
+    client.cancel().await?;
+    server_handle.await??;
+
+    let (meta, server_trace_id) = seen.lock().await.take().expect("request handled");
+    let traceparent = meta.get_traceparent().expect("traceparent injected");
+    assert!(traceparent.contains(&client_trace_id.to_string()));
+    assert_eq!(server_trace_id, client_trace_id);
+    Ok(())
+}
@@ -0,0 +1,86 @@
+#![cfg(not(feature = "local"))]
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use rmcp::{
+    RoleClient, RoleServer, ServerHandler, ServiceExt,
+    model::{ClientRequest, CustomRequest, CustomResult, GetMeta, Meta, ServerResult},
+    service::{PeerInterceptor, RequestContext, serve_client_with_interceptors},
+};
+use serde_json::json;
+use tokio::sync::Mutex;
+
+struct MetaCapturingServer {
+    seen: Arc<Mutex<Vec<Meta>>>,
+}
+
+impl ServerHandler for MetaCapturingServer {
+    async fn on_custom_request(
+        &self,
+        _request: CustomRequest,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CustomResult, rmcp::ErrorData> {
+        self.seen.lock().await.push(context.meta);
+        Ok(CustomResult::new(json!({ "status": "ok" })))
+    }
+}
+
+#[derive(Default)]
+struct CorrelationId {
+    next: AtomicUsize,
+    responses: AtomicUsize,
+}
+
+impl PeerInterceptor<RoleClient> for CorrelationId {
+    fn before_request(&self, request: &mut ClientRequest) {
+        let id = self.next.fetch_add(1, Ordering::SeqCst);
+        request
+            .get_meta_mut()
+            .insert("correlationId".into(), json!(format!("call-{id}")));
+    }
+
+    fn after_response(&self, response: &ServerResult) {
+        if matches!(response, ServerResult::CustomResult(_)) {
+            self.responses.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_interceptor_stamps_requests_and_sees_responses() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let server = MetaCapturingServer { seen: seen.clone() };
+    let server_handle = tokio::spawn(async move {
+        let server = server.serve(server_transport).await?;
+        server.waiting().await?;
+        anyhow::Ok(())
+    });
+
+    let interceptor = Arc::new(CorrelationId::default());
+    let client = serve_client_with_interceptors(
+        (),
+        client_transport,
+        vec![interceptor.clone() as Arc<dyn PeerInterceptor<RoleClient>>],
+        Default::default(),
+    )
+    .await?;
+    for _ in 0..2 {
+        client
+            .send_request(ClientRequest::CustomRequest(CustomRequest::new(
+                "requests/custom",
+                None,
+            )))
+            .await?;
+    }
+    client.cancel().await?;
+    server_handle.await??;
+
+    let seen = seen.lock().await;
+    let ids: Vec<_> = seen.iter().map(|meta| meta.get("correlationId")).collect();
+    assert_eq!(ids, [Some(&json!("call-0")), Some(&json!("call-1"))]);
+    assert_eq!(interceptor.responses.load(Ordering::SeqCst), 2);
+    Ok(())
+}
@@ -0,0 +1,80 @@
+#![cfg(all(feature = "client", feature = "server", not(feature = "local")))]
+// cargo test --test test_ping --features "client server"
+
+use std::time::Duration;
+
+use rmcp::{
+    RoleServer, ServerHandler, ServiceExt,
+    model::{ClientJsonRpcMessage, ClientRequest, ClientResult, ServerJsonRpcMessage},
+    transport::{IntoTransport, Transport},
+};
+
+/// A server that leaves `ping` to the framework.
+#[derive(Debug, Clone)]
+struct QuietServer;
+
+impl ServerHandler for QuietServer {}
+
+#[tokio::test]
+async fn test_ping_measures_round_trip_after_initialize() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    tokio::spawn(async move {
+        let running = QuietServer.serve(server_transport).await?;
+        anyhow::Ok(running.waiting().await?)
+    });
+    let client = ().serve(client_transport).await?;
+
+    for _ in 0..3 {
+        let latency = client.ping().await?;
+        assert!(latency < Duration::from_secs(5), "{latency:?}");
+    }
+    client.cancel().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_client_answers_ping_before_initialize_response() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let client = tokio::spawn(async move { ().serve(client_transport).await });
+    let mut server = IntoTransport::<RoleServer, _, _>::into_transport(server_transport);
+
+    let Some(ClientJsonRpcMessage::Request(initialize)) = server.receive().await else {
+        panic!("expected initialize request");
+    };
+    assert!(matches!(
+        initialize.request,
+        ClientRequest::InitializeRequest(_)
+    ));
+
+    let ping: ServerJsonRpcMessage =
+        serde_json::from_str(r#"{ "jsonrpc": "2.0", "id": 100, "method": "ping" }"#)?;
+    server.send(ping).await?;
+    let pong = server.receive().await;
+    assert!(
+        matches!(
+            &pong,
+            Some(ClientJsonRpcMessage::Response(response))
+                if matches!(response.result, ClientResult::EmptyResult(_))
+        ),
+        "expected EmptyResult for pre-initialize ping, got: {pong:?}"
+    );
+
+    let initialize_result: ServerJsonRpcMessage = serde_json::from_value(serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": initialize.id,
+        "result": {
+            "protocolVersion": "2025-11-25",
+            "capabilities": {},
+            "serverInfo": { "name": "raw-server", "version": "0.0.1" }
+        }
+    }))?;
+    server.send(initialize_result).await?;
+
+    let client = client.await??;
+    assert_eq!(client.peer_info().unwrap().server_info.name, "raw-server");
+    assert!(matches!(
+        server.receive().await,
+        Some(ClientJsonRpcMessage::Notification(_))
+    ));
+    Ok(())
+}
@@ -0,0 +1,165 @@
+#![cfg(not(feature = "local"))]
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use rmcp::{
+    RoleClient, RoleServer, ServerHandler, ServiceError, ServiceExt,
+    model::{ClientRequest, ServerCapabilities, ServerInfo, SubscribeRequestParams},
+    service::{
+        PeerInterceptor, PendingCallPolicy, ReconnectConfig, ReconnectingClient, RequestContext,
+    },
+};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+#[derive(Clone, Default)]
+struct SubscriptionServer {
+    subscribed: Arc<Mutex<Vec<String>>>,
+}
+
+impl ServerHandler for SubscriptionServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo::new(
+            ServerCapabilities::builder()
+                .enable_resources()
+                .enable_resources_subscribe()
+                .build(),
+        )
+    }
+
+    async fn subscribe(
+        &self,
+        request: SubscribeRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), rmcp::ErrorData> {
+        self.subscribed.lock().unwrap().push(request.uri);
+        Ok(())
+    }
+}
+
+/// Each connection gets a fresh duplex pair; the server side's cancellation token is
+/// handed back to the test so it can drop the connection.
+fn transport_factory(
+    server: SubscriptionServer,
+) -> (
+    impl Fn() -> futures::future::BoxFuture<
+        'static,
+        Result<tokio::io::DuplexStream, std::convert::Infallible>,
+    > + Send
+    + Sync
+    + 'static,
+    mpsc::UnboundedReceiver<CancellationToken>,
+) {
+    let (ct_tx, ct_rx) = mpsc::unbounded_channel();
+    let factory = move || {
+        let server = server.clone();
+        let ct_tx = ct_tx.clone();
+        Box::pin(async move {
+            let (server_transport, client_transport) = tokio::io::duplex(4096);
+            let ct = CancellationToken::new();
+            let _ = ct_tx.send(ct.clone());
+            tokio::spawn(async move {
+                let server = server.serve_with_ct(server_transport, ct).await?;
+                server.waiting().await?;
+                anyhow::Ok(())
+            });
+            Ok(client_transport)
+        }) as futures::future::BoxFuture<'static, _>
+    };
+    (factory, ct_rx)
+}
+
+#[tokio::test]
+async fn test_reconnects_and_replays_subscriptions() -> anyhow::Result<()> {
+    let server = SubscriptionServer::default();
+    let (factory, mut connections) = transport_factory(server.clone());
+    let config = ReconnectConfig::default()
+        .with_backoff(Duration::from_millis(10), Duration::from_millis(50));
+    let client = ReconnectingClient::connect((), factory, config).await?;
+
+    client
+        .subscribe(SubscribeRequestParams::new("memo://a"))
+        .await?;
+    assert!(client.is_connected());
+
+    let first = connections.recv().await.expect("first connection");
+    first.cancel();
+
+    // Once a new transport is being set up, calls queue until the client has re-initialized.
+    assert!(connections.recv().await.is_some());
+    let tools = tokio::time::timeout(Duration::from_secs(5), client.list_all_tools()).await??;
+    assert!(tools.is_empty());
+    assert_eq!(
+        *server.subscribed.lock().unwrap(),
+        ["memo://a".to_owned(), "memo://a".to_owned()]
+    );
+
+    client.close().await?;
+    Ok(())
+}
+
+#[derive(Default)]
+struct MethodRecorder {
+    methods: Mutex<Vec<String>>,
+}
+
+impl PeerInterceptor<RoleClient> for MethodRecorder {
+    fn before_request(&self, request: &mut ClientRequest) {
+        self.methods
+            .lock()
+            .unwrap()
+            .push(request.method().to_owned());
+    }
+}
+
+#[tokio::test]
+async fn test_interceptors_survive_reconnect() -> anyhow::Result<()> {
+    let server = SubscriptionServer::default();
+    let (factory, mut connections) = transport_factory(server);
+    let recorder = Arc::new(MethodRecorder::default());
+    let config = ReconnectConfig::default()
+        .with_backoff(Duration::from_millis(10), Duration::from_millis(50))
+        .with_interceptor(recorder.clone());
+    let client = ReconnectingClient::connect((), factory, config).await?;
+
+    client
+        .subscribe(SubscribeRequestParams::new("memo://a"))
+        .await?;
+    connections.recv().await.expect("first connection").cancel();
+    assert!(connections.recv().await.is_some());
+    tokio::time::timeout(Duration::from_secs(5), client.list_tools(None)).await??;
+
+    assert_eq!(
+        *recorder.methods.lock().unwrap(),
+        ["resources/subscribe", "resources/subscribe", "tools/list"]
+    );
+
+    client.close().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fail_fast_after_giving_up() -> anyhow::Result<()> {
+    let server = SubscriptionServer::default();
+    let (factory, mut connections) = transport_factory(server);
+    let config = ReconnectConfig::default()
+        .with_max_attempts(Some(0))
+        .with_pending_calls(PendingCallPolicy::FailFast);
+    let client = ReconnectingClient::connect((), factory, config).await?;
+
+    connections.recv().await.expect("first connection").cancel();
+    let error = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let Err(error) = client.list_tools(None).await {
+                break error;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await?;
+    assert!(matches!(error, ServiceError::TransportClosed));
+    assert!(!client.is_connected());
+    Ok(())
+}
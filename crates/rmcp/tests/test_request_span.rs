@@ -0,0 +1,92 @@
+#![cfg(not(feature = "local"))]
+//! Inbound requests and notifications are handled inside `mcp.request` / `mcp.notification` spans.
+use std::sync::{Arc, Mutex};
+
+use rmcp::{ServerHandler, ServiceExt, model::ClientNotification};
+use tracing::{
+    Subscriber,
+    field::{Field, Visit},
+    span,
+};
+use tracing_subscriber::{Layer, layer::Context, prelude::*};
+
+struct EmptyServer;
+
+impl ServerHandler for EmptyServer {}
+
+type RecordedSpans = Arc<Mutex<Vec<(String, Vec<(String, String)>)>>>;
+
+#[derive(Clone, Default)]
+struct SpanRecorder {
+    spans: RecordedSpans,
+}
+
+struct FieldVisitor<'a>(&'a mut Vec<(String, String)>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.push((field.name().to_owned(), format!("{value:?}")));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name().to_owned(), value.to_owned()));
+    }
+}
+
+impl<S: Subscriber> Layer<S> for SpanRecorder {
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, _id: &span::Id, _ctx: Context<'_, S>) {
+        let name = attrs.metadata().name();
+        if !name.starts_with("mcp.") {
+            return;
+        }
+        let mut fields = Vec::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+        self.spans.lock().unwrap().push((name.to_owned(), fields));
+    }
+}
+
+fn field<'a>(fields: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    fields
+        .iter()
+        .find(|(field, _)| field == name)
+        .map(|(_, value)| value.as_str())
+}
+
+#[tokio::test]
+async fn test_request_and_notification_spans() -> anyhow::Result<()> {
+    let recorder = SpanRecorder::default();
+    tracing_subscriber::registry()
+        .with(recorder.clone())
+        .try_init()?;
+
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let server_handle = tokio::spawn(async move {
+        let server = EmptyServer.serve(server_transport).await?;
+        server.waiting().await?;
+        anyhow::Ok(())
+    });
+
+    let client = ().serve(client_transport).await?;
+    client.list_tools(None).await?;
+    client
+        .send_notification(ClientNotification::RootsListChangedNotification(
+            Default::default(),
+        ))
+        .await?;
+    client.cancel().await?;
+    server_handle.await??;
+
+    let spans = recorder.spans.lock().unwrap().clone();
+    let (_, list_tools) = spans
+        .iter()
+        .find(|(name, fields)| {
+            name == "mcp.request" && field(fields, "method") == Some("tools/list")
+        })
+        .expect("tools/list request span");
+    assert!(field(list_tools, "id").is_some());
+    assert!(spans.iter().any(|(name, fields)| {
+        name == "mcp.notification"
+            && field(fields, "method") == Some("notifications/roots/list_changed")
+    }));
+    Ok(())
+}
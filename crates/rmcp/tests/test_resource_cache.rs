@@ -0,0 +1,171 @@
+#![cfg(not(feature = "local"))]
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use chrono::{TimeZone, Utc};
+use rmcp::{
+    ClientHandler, RoleClient, RoleServer, ServerHandler, ServiceExt,
+    handler::client::resource_cache::ResourceCache,
+    model::{
+        Annotations, ReadResourceRequestParams, ReadResourceResult, Resource, ResourceContents,
+        ResourceUpdatedNotificationParam, ServerCapabilities, ServerInfo, SubscribeRequestParams,
+    },
+    service::{NotificationContext, RequestContext},
+};
+use tokio::sync::mpsc;
+
+#[derive(Clone, Default)]
+struct CountingServer {
+    reads: Arc<AtomicUsize>,
+}
+
+impl ServerHandler for CountingServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo::new(
+            ServerCapabilities::builder()
+                .enable_resources()
+                .enable_resources_subscribe()
+                .build(),
+        )
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, rmcp::ErrorData> {
+        let n = self.reads.fetch_add(1, Ordering::SeqCst);
+        Ok(ReadResourceResult::new(vec![ResourceContents::text(
+            format!("version {n}"),
+            request.uri,
+        )]))
+    }
+
+    async fn subscribe(
+        &self,
+        request: SubscribeRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<(), rmcp::ErrorData> {
+        let peer = context.peer.clone();
+        tokio::spawn(async move {
+            let _ = peer
+                .notify_resource_updated(ResourceUpdatedNotificationParam::new(request.uri))
+                .await;
+        });
+        Ok(())
+    }
+}
+
+struct CachingClient {
+    cache: ResourceCache,
+    updates: mpsc::UnboundedSender<String>,
+}
+
+impl ClientHandler for CachingClient {
+    fn resource_cache(&self) -> Option<&ResourceCache> {
+        Some(&self.cache)
+    }
+
+    async fn on_resource_updated(
+        &self,
+        params: ResourceUpdatedNotificationParam,
+        _context: NotificationContext<RoleClient>,
+    ) {
+        let _ = self.updates.send(params.uri);
+    }
+}
+
+fn text_of(result: &ReadResourceResult) -> &str {
+    match &result.contents[0] {
+        ResourceContents::TextResourceContents { text, .. } => text,
+        _ => panic!("expected text contents"),
+    }
+}
+
+#[tokio::test]
+async fn test_cached_reads_skip_round_trip_until_updated() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let server = CountingServer::default();
+    let reads = server.reads.clone();
+    let server_handle = tokio::spawn(async move {
+        server.serve(server_transport).await?.waiting().await?;
+        anyhow::Ok(())
+    });
+
+    let cache = ResourceCache::new();
+    let (updates, mut updated) = mpsc::unbounded_channel();
+    let client = CachingClient {
+        cache: cache.clone(),
+        updates,
+    }
+    .serve(client_transport)
+    .await?;
+
+    let uri = "file:///big.txt";
+    let first = cache
+        .read_resource(client.peer(), ReadResourceRequestParams::new(uri))
+        .await?;
+    let second = cache
+        .read_resource(client.peer(), ReadResourceRequestParams::new(uri))
+        .await?;
+    assert_eq!(text_of(&first), "version 0");
+    assert_eq!(text_of(&second), "version 0");
+    assert_eq!(reads.load(Ordering::SeqCst), 1);
+
+    // The test server announces an update as soon as a resource is subscribed.
+    client.subscribe(SubscribeRequestParams::new(uri)).await?;
+    assert_eq!(updated.recv().await.as_deref(), Some(uri));
+    assert!(cache.get(uri).is_none());
+
+    let third = cache
+        .read_resource(client.peer(), ReadResourceRequestParams::new(uri))
+        .await?;
+    assert_eq!(text_of(&third), "version 1");
+    assert_eq!(reads.load(Ordering::SeqCst), 2);
+
+    client.cancel().await?;
+    server_handle.await??;
+    Ok(())
+}
+
+#[test]
+fn test_least_recently_used_entry_is_evicted() {
+    let cache = ResourceCache::with_capacity(2);
+    let result = |uri: &str| ReadResourceResult::new(vec![ResourceContents::text("", uri)]);
+    cache.insert("a", result("a"));
+    cache.insert("b", result("b"));
+    assert!(cache.get("a").is_some());
+    cache.insert("c", result("c"));
+
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get("a").is_some());
+    assert!(cache.get("b").is_none());
+    assert!(cache.get("c").is_some());
+
+    let disabled = ResourceCache::with_capacity(0);
+    disabled.insert("a", result("a"));
+    assert!(disabled.is_empty());
+}
+
+#[test]
+fn test_changed_last_modified_invalidates_entry() {
+    let cache = ResourceCache::new();
+    cache.insert(
+        "file:///a",
+        ReadResourceResult::new(vec![ResourceContents::text("a", "file:///a")]),
+    );
+    let listed = |day| {
+        Resource::new("file:///a", "a").with_annotations(Annotations::for_resource(
+            0.5,
+            Utc.with_ymd_and_hms(2025, 1, day, 0, 0, 0).unwrap(),
+        ))
+    };
+
+    assert_eq!(cache.observe_resources([&listed(1)]), 0);
+    assert_eq!(cache.observe_resources([&listed(1)]), 0);
+    assert!(cache.get("file:///a").is_some());
+    assert_eq!(cache.observe_resources([&listed(2)]), 1);
+    assert!(cache.get("file:///a").is_none());
+}
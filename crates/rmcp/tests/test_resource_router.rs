@@ -0,0 +1,234 @@
+#![cfg(all(feature = "client", feature = "server", not(feature = "local")))]
+// cargo test --test test_resource_router --features "client server macros"
+
+use rmcp::{
+    ErrorData, RoleServer, ServerHandler, ServiceExt,
+    handler::server::router::{
+        Router,
+        resource::{ResourceRoute, ResourceRouter},
+    },
+    model::{
+        ClientJsonRpcMessage, ClientRequest, ErrorCode, ListResourceTemplatesRequest,
+        ListResourcesResult, PaginatedRequestParams, ReadResourceRequestParams, ReadResourceResult,
+        Resource, ResourceContents, ResourceTemplate, ServerCapabilities, ServerInfo,
+    },
+    service::RequestContext,
+};
+
+struct WeatherServer;
+
+impl ServerHandler for WeatherServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo::new(ServerCapabilities::builder().enable_resources().build())
+    }
+
+    // Reads the router has no route for fall through to the handler.
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, ErrorData> {
+        Err(ErrorData::resource_not_found_for(request.uri))
+    }
+}
+
+fn weather_router() -> ResourceRouter<WeatherServer> {
+    ResourceRouter::new()
+        .with_route(ResourceRoute::template_dyn(
+            ResourceTemplate::new("weather://{city}/current", "current-weather")
+                .with_description("Current weather for a city")
+                .with_mime_type("text/plain"),
+            |ctx| {
+                Box::pin(async move {
+                    let city = ctx.variable("city").unwrap_or_default().to_string();
+                    Ok(ReadResourceResult::new(vec![ResourceContents::text(
+                        format!("sunny in {city}"),
+                        ctx.uri,
+                    )]))
+                })
+            },
+        ))
+        .with_route(ResourceRoute::new_dyn(
+            Resource::new("weather://stations", "stations"),
+            |ctx| {
+                Box::pin(async move {
+                    Ok(ReadResourceResult::new(vec![ResourceContents::text(
+                        "paris,oslo",
+                        ctx.uri,
+                    )]))
+                })
+            },
+        ))
+}
+
+#[test]
+fn test_list_resource_templates_request_serialization() {
+    let request = ClientRequest::ListResourceTemplatesRequest(ListResourceTemplatesRequest {
+        method: Default::default(),
+        params: None,
+        extensions: Default::default(),
+    });
+    let message = ClientJsonRpcMessage::request(request, rmcp::model::NumberOrString::Number(1));
+    let value = serde_json::to_value(&message).unwrap();
+    assert_eq!(value["method"], "resources/templates/list");
+
+    let template = ResourceTemplate::new("weather://{city}/current", "current-weather")
+        .with_mime_type("text/plain");
+    let value = serde_json::to_value(&template).unwrap();
+    assert_eq!(value["uriTemplate"], "weather://{city}/current");
+    assert_eq!(value["mimeType"], "text/plain");
+    let back: ResourceTemplate = serde_json::from_value(value).unwrap();
+    assert_eq!(back, template);
+}
+
+#[tokio::test]
+async fn test_resource_router_round_trip() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let mut router = Router::new(WeatherServer);
+    router.resource_router = weather_router();
+    let server = tokio::spawn(async move {
+        router.serve(server_transport).await?.waiting().await?;
+        anyhow::Ok(())
+    });
+    let client = ().serve(client_transport).await?;
+
+    let capabilities = client.peer_info().unwrap().capabilities.clone();
+    assert!(capabilities.resources.is_some());
+
+    let templates = client.list_all_resource_templates().await?;
+    assert_eq!(templates.len(), 1);
+    assert_eq!(templates[0].uri_template, "weather://{city}/current");
+    assert_eq!(templates[0].name, "current-weather");
+
+    let resources = client.list_all_resources().await?;
+    assert_eq!(resources.len(), 1);
+    assert_eq!(resources[0].uri, "weather://stations");
+
+    let result = client
+        .read_resource(ReadResourceRequestParams::new("weather://oslo/current"))
+        .await?;
+    let ResourceContents::TextResourceContents { uri, text, .. } = &result.contents[0] else {
+        panic!("expected text contents");
+    };
+    assert_eq!(uri, "weather://oslo/current");
+    assert_eq!(text, "sunny in oslo");
+
+    let error = client
+        .read_resource(ReadResourceRequestParams::new("weather://oslo/daily"))
+        .await
+        .unwrap_err();
+    let rmcp::ServiceError::McpError(error) = error else {
+        panic!("expected MCP error, got {error:?}");
+    };
+    assert_eq!(error.code, ErrorCode::RESOURCE_NOT_FOUND);
+
+    client.cancel().await?;
+    server.await??;
+    Ok(())
+}
+
+/// Lists its resources by hand and advertises no capabilities of its own.
+struct StationsServer;
+
+impl ServerHandler for StationsServer {
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, ErrorData> {
+        Ok(ListResourcesResult::with_all_items(vec![Resource::new(
+            "weather://stations",
+            "stations",
+        )]))
+    }
+}
+
+#[tokio::test]
+async fn test_router_with_only_templates_keeps_handler_resources() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let mut router = Router::new(StationsServer);
+    router.resource_router = ResourceRouter::new().with_route(ResourceRoute::template_dyn(
+        ResourceTemplate::new("weather://{city}/current", "current-weather"),
+        |ctx| {
+            Box::pin(async move {
+                Ok(ReadResourceResult::new(vec![ResourceContents::text(
+                    "sunny", ctx.uri,
+                )]))
+            })
+        },
+    ));
+    let server = tokio::spawn(async move {
+        router.serve(server_transport).await?.waiting().await?;
+        anyhow::Ok(())
+    });
+    let client = ().serve(client_transport).await?;
+
+    let capabilities = client.peer_info().unwrap().capabilities.clone();
+    assert!(capabilities.resources.is_some());
+
+    let resources = client.list_all_resources().await?;
+    assert_eq!(resources.len(), 1);
+    assert_eq!(resources[0].uri, "weather://stations");
+    let templates = client.list_all_resource_templates().await?;
+    assert_eq!(templates.len(), 1);
+
+    client.cancel().await?;
+    server.await??;
+    Ok(())
+}
+
+#[cfg(feature = "macros")]
+mod server_handler_macro {
+    use rmcp::server_handler;
+
+    use super::*;
+
+    struct MacroWeatherServer {
+        resource_router: ResourceRouter<Self>,
+    }
+
+    #[server_handler(resources)]
+    impl ServerHandler for MacroWeatherServer {}
+
+    #[tokio::test]
+    async fn test_server_handler_routes_resources() -> anyhow::Result<()> {
+        let (server_transport, client_transport) = tokio::io::duplex(4096);
+        let server = MacroWeatherServer {
+            resource_router: ResourceRouter::new().with_route(ResourceRoute::template_dyn(
+                ResourceTemplate::new("weather://{city}/current", "current-weather"),
+                |ctx| {
+                    Box::pin(async move {
+                        let city = ctx.variable("city").unwrap_or_default().to_string();
+                        Ok(ReadResourceResult::new(vec![ResourceContents::text(
+                            format!("sunny in {city}"),
+                            ctx.uri,
+                        )]))
+                    })
+                },
+            )),
+        };
+        let server = tokio::spawn(async move {
+            server.serve(server_transport).await?.waiting().await?;
+            anyhow::Ok(())
+        });
+        let client = ().serve(client_transport).await?;
+
+        let capabilities = client.peer_info().unwrap().capabilities.clone();
+        assert!(capabilities.resources.is_some());
+
+        let templates = client.list_all_resource_templates().await?;
+        assert_eq!(templates.len(), 1);
+        assert!(client.list_all_resources().await?.is_empty());
+        let result = client
+            .read_resource(ReadResourceRequestParams::new("weather://oslo/current"))
+            .await?;
+        let ResourceContents::TextResourceContents { text, .. } = &result.contents[0] else {
+            panic!("expected text contents");
+        };
+        assert_eq!(text, "sunny in oslo");
+
+        client.cancel().await?;
+        server.await??;
+        Ok(())
+    }
+}
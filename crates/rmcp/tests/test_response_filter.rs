@@ -0,0 +1,122 @@
+#![cfg(all(feature = "client", feature = "server", not(feature = "local")))]
+// cargo test --test test_response_filter --features "client server"
+
+use rmcp::{
+    ServerHandler, ServiceExt,
+    handler::server::{
+        response_filter::ResponseFilter,
+        router::{Router, tool::ToolRouter},
+    },
+    model::{CallToolRequestParams, CallToolResult, ContentBlock},
+    tool, tool_handler, tool_router,
+};
+
+/// Replaces anything that looks like an API key with `[REDACTED]`.
+struct RedactApiKeys;
+
+fn redact(text: &str) -> String {
+    text.split(' ')
+        .map(|word| {
+            if word.starts_with("sk-") {
+                "[REDACTED]"
+            } else {
+                word
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl ResponseFilter for RedactApiKeys {
+    fn filter_content(&self, result: &mut CallToolResult) {
+        for content in &mut result.content {
+            if let ContentBlock::Text(text) = content {
+                text.text = redact(&text.text);
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+struct LeakyServer {
+    tool_router: ToolRouter<Self>,
+    redact: bool,
+}
+
+#[tool_router]
+impl LeakyServer {
+    fn new(redact: bool) -> Self {
+        Self {
+            tool_router: Self::tool_router(),
+            redact,
+        }
+    }
+
+    #[tool(description = "Report the configured credentials")]
+    fn credentials(&self) -> String {
+        "using key sk-live-1234 for user alice".to_string()
+    }
+}
+
+#[tool_handler(router = self.tool_router)]
+impl ServerHandler for LeakyServer {
+    fn response_filter(&self) -> Option<&dyn ResponseFilter> {
+        self.redact.then_some(&RedactApiKeys)
+    }
+}
+
+/// Served through [`Router`], which calls routed tools itself.
+struct RoutedServer;
+
+#[tool_router(router = routes)]
+impl RoutedServer {
+    #[tool(description = "Report the configured credentials")]
+    fn credentials(&self) -> String {
+        "using key sk-live-1234 for user alice".to_string()
+    }
+}
+
+impl ServerHandler for RoutedServer {
+    fn response_filter(&self) -> Option<&dyn ResponseFilter> {
+        Some(&RedactApiKeys)
+    }
+}
+
+async fn call_credentials<S: ServiceExt<rmcp::RoleServer> + Send + 'static>(
+    server: S,
+) -> anyhow::Result<String> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    tokio::spawn(async move {
+        let running = server.serve(server_transport).await?;
+        anyhow::Ok(running.waiting().await?)
+    });
+    let client = ().serve(client_transport).await?;
+    let result = client
+        .call_tool(CallToolRequestParams::new("credentials"))
+        .await?;
+    client.cancel().await?;
+    Ok(result.content[0].as_text().unwrap().text.clone())
+}
+
+#[tokio::test]
+async fn test_response_filter_redacts_tool_result() -> anyhow::Result<()> {
+    assert_eq!(
+        call_credentials(LeakyServer::new(true)).await?,
+        "using key [REDACTED] for user alice"
+    );
+    assert_eq!(
+        call_credentials(LeakyServer::new(false)).await?,
+        "using key sk-live-1234 for user alice"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_response_filter_redacts_routed_tool_result() -> anyhow::Result<()> {
+    let router = Router::new(RoutedServer).with_tools(RoutedServer::routes());
+    assert_eq!(
+        call_credentials(router).await?,
+        "using key [REDACTED] for user alice"
+    );
+    Ok(())
+}
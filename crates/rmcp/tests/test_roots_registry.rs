@@ -0,0 +1,115 @@
+#![cfg(not(feature = "local"))]
+#![allow(deprecated)]
+use rmcp::{
+    ClientHandler, RoleClient, RoleServer, ServerHandler, ServiceExt,
+    handler::client::roots::RootsRegistry,
+    model::{ClientCapabilities, ClientInfo, ErrorData, ListRootsResult, Root},
+    service::{NotificationContext, RequestContext},
+};
+use tokio::sync::mpsc;
+
+#[derive(Clone)]
+struct RootsClient {
+    registry: RootsRegistry,
+}
+
+impl ClientHandler for RootsClient {
+    fn get_info(&self) -> ClientInfo {
+        ClientInfo::new(
+            ClientCapabilities::builder()
+                .enable_roots()
+                .enable_roots_list_changed()
+                .build(),
+            Default::default(),
+        )
+    }
+
+    async fn list_roots(
+        &self,
+        _context: RequestContext<RoleClient>,
+    ) -> Result<ListRootsResult, ErrorData> {
+        Ok(self.registry.list_roots_result().await)
+    }
+}
+
+/// Re-fetches the client's roots whenever they change.
+#[derive(Clone)]
+struct RootsServer {
+    seen: mpsc::UnboundedSender<Vec<String>>,
+}
+
+impl ServerHandler for RootsServer {
+    async fn on_roots_list_changed(&self, context: NotificationContext<RoleServer>) {
+        let roots = context.peer.list_roots().await.expect("list roots");
+        let _ = self
+            .seen
+            .send(roots.roots.into_iter().map(|r| r.uri).collect());
+    }
+}
+
+#[tokio::test]
+async fn test_roots_registry_notifies_server_on_change() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let (seen_tx, mut seen_rx) = mpsc::unbounded_channel();
+
+    let server_handle = tokio::spawn(async move {
+        let server = RootsServer { seen: seen_tx }
+            .serve(server_transport)
+            .await?;
+        server.waiting().await?;
+        anyhow::Ok(())
+    });
+
+    let registry = RootsRegistry::new().with_roots([Root::new("file:///a")]);
+    let client = RootsClient {
+        registry: registry.clone(),
+    }
+    .serve(client_transport)
+    .await?;
+    assert!(registry.attach(client.peer().clone()));
+
+    assert!(registry.add(Root::new("file:///b")).await?);
+    assert_eq!(
+        seen_rx.recv().await,
+        Some(vec!["file:///a".to_string(), "file:///b".to_string()])
+    );
+
+    // Re-adding an identical root is not a change and is not announced.
+    assert!(!registry.add(Root::new("file:///b")).await?);
+    assert!(registry.remove("file:///a").await?);
+    assert_eq!(seen_rx.recv().await, Some(vec!["file:///b".to_string()]));
+
+    client.cancel().await?;
+    server_handle.await??;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_roots_registry_without_list_changed_stays_silent() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let (seen_tx, mut seen_rx) = mpsc::unbounded_channel();
+
+    let server_handle = tokio::spawn(async move {
+        let server = RootsServer { seen: seen_tx }
+            .serve(server_transport)
+            .await?;
+        server.waiting().await?;
+        anyhow::Ok(())
+    });
+
+    let registry = RootsRegistry::new().with_list_changed(false);
+    let client = RootsClient {
+        registry: registry.clone(),
+    }
+    .serve(client_transport)
+    .await?;
+    registry.attach(client.peer().clone());
+
+    assert!(registry.set([Root::new("file:///c")]).await?);
+    assert_eq!(registry.roots().await, vec![Root::new("file:///c")]);
+
+    client.cancel().await?;
+    server_handle.await??;
+    assert!(seen_rx.recv().await.is_none());
+    Ok(())
+}
@@ -708,3 +708,118 @@ async fn test_create_message_result_validate_accepts_assistant_role() {
 
     assert!(result.validate().is_ok());
 }
+
+#[tokio::test]
+async fn test_default_client_handler_rejects_sampling() -> Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+
+    let client_handle = tokio::spawn(async move {
+        // `()` relies on every `ClientHandler` default.
+        let client = ().serve(client_transport).await?;
+        client.waiting().await?;
+        anyhow::Ok(())
+    });
+    let server = TestServer::new().serve(server_transport).await?;
+
+    let error = server
+        .peer()
+        .create_message(CreateMessageRequestParams::new(
+            vec![SamplingMessage::user_text("Hello")],
+            10,
+        ))
+        .await
+        .expect_err("default client should reject sampling");
+    let rmcp::ServiceError::McpError(error) = error else {
+        panic!("expected an MCP error, got {error:?}");
+    };
+    assert_eq!(error.code, ErrorCode::METHOD_NOT_FOUND);
+    assert!(error.message.contains("does not support sampling"));
+
+    let roots = server.peer().list_roots().await?;
+    assert!(roots.roots.is_empty());
+
+    server.cancel().await?;
+    client_handle.await??;
+    Ok(())
+}
+
+#[test]
+fn test_create_message_params_builder_keeps_history() {
+    let params = CreateMessageRequestParams::builder()
+        .add_user_text("Hi")
+        .add_assistant_text("Hello! How can I help?")
+        .add_user_text("Summarize our chat")
+        .system_prompt("Be brief")
+        .temperature(0.2)
+        .max_tokens(50)
+        .build()
+        .unwrap();
+
+    let expected = CreateMessageRequestParams::new(
+        vec![
+            SamplingMessage::user_text("Hi"),
+            SamplingMessage::assistant_text("Hello! How can I help?"),
+            SamplingMessage::user_text("Summarize our chat"),
+        ],
+        50,
+    )
+    .with_system_prompt("Be brief")
+    .with_temperature(0.2);
+    assert_eq!(params, expected);
+
+    assert!(
+        CreateMessageRequestParams::builder()
+            .add_user_text("Hi")
+            .build()
+            .is_err(),
+        "max_tokens is required"
+    );
+    assert!(
+        CreateMessageRequestParams::builder()
+            .max_tokens(10)
+            .build()
+            .is_err(),
+        "a request needs at least one message"
+    );
+}
+
+#[test]
+fn test_model_preferences_hints_and_priority_range() {
+    let preferences = ModelPreferences::new()
+        .with_hint("claude-3-sonnet")
+        .with_hint("claude")
+        .with_cost_priority(0.3)
+        .with_intelligence_priority(1.0);
+    assert!(preferences.validate().is_ok());
+    assert_eq!(
+        preferences.hints,
+        Some(vec![
+            ModelHint::new("claude-3-sonnet"),
+            ModelHint::new("claude")
+        ])
+    );
+
+    let params = CreateMessageRequestParams::builder()
+        .add_user_text("Hi")
+        .model_preferences(preferences)
+        .max_tokens(10)
+        .build()
+        .unwrap();
+    assert_eq!(params.model_preferences.unwrap().cost_priority, Some(0.3));
+
+    let out_of_range = ModelPreferences::new().with_speed_priority(1.5);
+    assert!(out_of_range.validate().is_err());
+    let error = CreateMessageRequestParams::builder()
+        .add_user_text("Hi")
+        .model_preferences(out_of_range)
+        .max_tokens(10)
+        .build()
+        .unwrap_err();
+    assert!(error.contains("speedPriority"), "{error}");
+    assert!(
+        ModelPreferences::new()
+            .with_cost_priority(f32::NAN)
+            .validate()
+            .is_err()
+    );
+}
@@ -0,0 +1,91 @@
+#![cfg(not(feature = "local"))]
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use rmcp::{
+    ErrorData, RoleServer, ServerHandler, ServiceExt,
+    handler::server::router::{prompt::PromptRouter, tool::ToolRouter},
+    model::{CallToolRequestParams, ClientRequest, GetPromptRequestParams, PromptMessage, Role},
+    prompt, prompt_router, server_handler,
+    service::RequestContext,
+    tool, tool_router,
+};
+
+#[derive(Clone)]
+struct Assistant {
+    tool_router: ToolRouter<Self>,
+    prompt_router: PromptRouter<Self>,
+    pings: Arc<AtomicUsize>,
+}
+
+#[tool_router]
+impl Assistant {
+    fn new() -> Self {
+        Self {
+            tool_router: Self::tool_router(),
+            prompt_router: Self::prompt_router(),
+            pings: Default::default(),
+        }
+    }
+
+    #[tool(description = "Say hello")]
+    fn hello(&self) -> String {
+        "hello".to_string()
+    }
+}
+
+#[prompt_router]
+impl Assistant {
+    #[prompt(description = "Greet the user")]
+    fn greeting(&self) -> Vec<PromptMessage> {
+        vec![PromptMessage::new_text(Role::Assistant, "Hi there")]
+    }
+}
+
+#[server_handler(tools, prompts, instructions = "Says hello")]
+impl ServerHandler for Assistant {
+    async fn ping(&self, _context: RequestContext<RoleServer>) -> Result<(), ErrorData> {
+        self.pings.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_server_handler_delegates_to_routers() -> anyhow::Result<()> {
+    let server = Assistant::new();
+    let pings = server.pings.clone();
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    tokio::spawn(async move {
+        server.serve(server_transport).await?.waiting().await?;
+        anyhow::Ok(())
+    });
+    let client = ().serve(client_transport).await?;
+
+    let info = client.peer_info().expect("initialized");
+    assert!(info.capabilities.tools.is_some());
+    assert!(info.capabilities.prompts.is_some());
+    assert_eq!(info.instructions.as_deref(), Some("Says hello"));
+
+    let tools = client.list_all_tools().await?;
+    assert_eq!(tools.len(), 1);
+    assert_eq!(tools[0].name, "hello");
+    let result = client
+        .call_tool(CallToolRequestParams::new("hello"))
+        .await?;
+    assert_eq!(result.text().as_deref(), Some("hello"));
+
+    let prompt = client
+        .get_prompt(GetPromptRequestParams::new("greeting"))
+        .await?;
+    assert_eq!(prompt.messages.len(), 1);
+
+    client
+        .send_request(ClientRequest::PingRequest(Default::default()))
+        .await?;
+    assert_eq!(pings.load(Ordering::SeqCst), 1);
+
+    client.cancel().await?;
+    Ok(())
+}
@@ -0,0 +1,68 @@
+#![cfg(not(feature = "local"))]
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use rmcp::{
+    ServerHandler, ServiceExt,
+    model::{CallToolRequestParams, ErrorCode},
+    service::{MeteredService, RequestOutcome, ServerMetrics},
+};
+
+struct EmptyServer;
+
+impl ServerHandler for EmptyServer {}
+
+#[derive(Default)]
+struct RecordingMetrics {
+    started: Mutex<Vec<String>>,
+    ended: Mutex<Vec<(String, RequestOutcome)>>,
+}
+
+impl ServerMetrics for RecordingMetrics {
+    fn on_request_start(&self, method: &str) {
+        self.started.lock().unwrap().push(method.to_owned());
+    }
+
+    fn on_request_end(&self, method: &str, _duration: Duration, outcome: RequestOutcome) {
+        self.ended
+            .lock()
+            .unwrap()
+            .push((method.to_owned(), outcome));
+    }
+}
+
+#[tokio::test]
+async fn test_metered_service_reports_requests() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let metrics = Arc::new(RecordingMetrics::default());
+
+    let server = MeteredService::new(EmptyServer, metrics.clone());
+    let server_handle = tokio::spawn(async move {
+        let server = server.serve(server_transport).await?;
+        server.waiting().await?;
+        anyhow::Ok(())
+    });
+
+    let client = ().serve(client_transport).await?;
+    client.list_tools(None).await?;
+    let error = client
+        .call_tool(CallToolRequestParams::new("missing"))
+        .await
+        .expect_err("unknown tool should fail");
+    assert!(matches!(error, rmcp::ServiceError::McpError(_)));
+    client.cancel().await?;
+    server_handle.await??;
+
+    let started = metrics.started.lock().unwrap().clone();
+    let ended = metrics.ended.lock().unwrap().clone();
+    assert_eq!(started, ["initialize", "tools/list", "tools/call"]);
+    assert_eq!(ended.len(), 3);
+    assert_eq!(ended[1], ("tools/list".to_owned(), RequestOutcome::Success));
+    assert!(matches!(
+        ended[2],
+        (ref method, RequestOutcome::Error(ErrorCode(_))) if method == "tools/call"
+    ));
+    Ok(())
+}
@@ -0,0 +1,125 @@
+#![cfg(not(feature = "local"))]
+use std::{
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+use rmcp::{
+    RoleServer, ServerHandler, ServiceExt,
+    model::{CallToolRequestParams, CallToolResult, ContentBlock, ServerCapabilities, ServerInfo},
+    service::RequestContext,
+    transport::{
+        async_rw::AsyncRwTransport,
+        observer::ObservedTransport,
+        record::{Direction, ReplayTransport, SessionRecorder, read_recording},
+    },
+};
+
+struct GreetServer;
+
+impl ServerHandler for GreetServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo::new(ServerCapabilities::builder().enable_tools().build())
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        Ok(CallToolResult::success(vec![ContentBlock::text(format!(
+            "hello from {}",
+            request.name
+        ))]))
+    }
+}
+
+#[derive(Clone, Default)]
+struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuf {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+async fn record_session() -> anyhow::Result<Vec<u8>> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let server_handle = tokio::spawn(async move {
+        GreetServer.serve(server_transport).await?.waiting().await?;
+        anyhow::Ok(())
+    });
+
+    let buf = SharedBuf::default();
+    let (read, write) = tokio::io::split(client_transport);
+    let transport = ObservedTransport::new(
+        AsyncRwTransport::new_client(read, write),
+        SessionRecorder::new(buf.clone()),
+    );
+    let client = ().serve(transport).await?;
+    client.call_tool(CallToolRequestParams::new("a")).await?;
+    client.call_tool(CallToolRequestParams::new("b")).await?;
+    client.cancel().await?;
+    server_handle.await??;
+
+    let recording = buf.0.lock().unwrap().clone();
+    Ok(recording)
+}
+
+#[tokio::test]
+async fn test_recorded_session_replays_without_server() -> anyhow::Result<()> {
+    let recording = record_session().await?;
+
+    let messages = read_recording(recording.as_slice())?;
+    let directions: Vec<_> = messages.iter().map(|m| m.direction).collect();
+    assert_eq!(
+        directions,
+        [
+            Direction::ClientToServer,
+            Direction::ServerToClient,
+            Direction::ClientToServer,
+            Direction::ClientToServer,
+            Direction::ServerToClient,
+            Direction::ClientToServer,
+            Direction::ServerToClient,
+        ]
+    );
+    assert!(
+        messages
+            .windows(2)
+            .all(|w| w[0].timestamp <= w[1].timestamp)
+    );
+
+    let client = ().serve(ReplayTransport::from_reader(recording.as_slice())?).await?;
+    let info = client.peer_info().expect("replayed initialize result");
+    assert!(info.capabilities.tools.is_some());
+    let result = client.call_tool(CallToolRequestParams::new("a")).await?;
+    assert_eq!(result.content[0].as_text().unwrap().text, "hello from a");
+    let result = client.call_tool(CallToolRequestParams::new("b")).await?;
+    assert_eq!(result.content[0].as_text().unwrap().text, "hello from b");
+    client.cancel().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_replay_ends_session_on_unexpected_message() -> anyhow::Result<()> {
+    let recording = record_session().await?;
+    let client = ().serve(ReplayTransport::from_reader(recording.as_slice())?).await?;
+    let error = client.list_tools(None).await.unwrap_err();
+    assert!(
+        matches!(error, rmcp::ServiceError::TransportClosed),
+        "{error:?}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_invalid_recording_reports_line() {
+    let recording = b"\n{\"direction\":\"sideways\"}\n";
+    let error = ReplayTransport::from_reader(&recording[..]).err().unwrap();
+    assert!(error.to_string().contains("line 2"), "{error}");
+}
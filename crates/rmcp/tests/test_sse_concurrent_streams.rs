@@ -72,10 +72,18 @@ impl ServerHandler for TestServer {
 // ─── Helpers ────────────────────────────────────────────────────────────────
 
 async fn start_test_server(ct: CancellationToken, trigger: Arc<Notify>) -> String {
+    start_test_server_with(ct, trigger, Arc::new(LocalSessionManager::default())).await
+}
+
+async fn start_test_server_with(
+    ct: CancellationToken,
+    trigger: Arc<Notify>,
+    session_manager: Arc<LocalSessionManager>,
+) -> String {
     let server = TestServer::new(trigger);
     let service = StreamableHttpService::new(
         move || Ok(server.clone()),
-        Arc::new(LocalSessionManager::default()),
+        session_manager,
         StreamableHttpServerConfig::default().with_cancellation_token(ct.child_token()),
     );
 
@@ -773,3 +781,97 @@ async fn shadow_stream_limit_drops_oldest() {
 
     ct.cancel();
 }
+
+// ─── Tests: Shadow idle timeout ──────────────────────────────────────────────
+
+/// A shadow that sends nothing within `shadow_idle_timeout` is closed with a
+/// final priming event, while the primary stays alive and keeps receiving
+/// notifications.
+#[tokio::test]
+async fn idle_shadow_is_closed_without_affecting_primary() {
+    let ct = CancellationToken::new();
+    let trigger = Arc::new(Notify::new());
+    let mut session_manager = LocalSessionManager::default();
+    session_manager.session_config.shadow_idle_timeout = Some(Duration::from_millis(300));
+    let url = start_test_server_with(ct.clone(), trigger.clone(), Arc::new(session_manager)).await;
+    let client = reqwest::Client::new();
+
+    let session_id = initialize_session(&client, &url).await;
+    send_initialized_notification(&client, &url, &session_id).await;
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // First GET — primary
+    let get1 = open_standalone_get(&client, &url, &session_id).await;
+    assert_eq!(get1.status(), 200);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Second GET — shadow, which should end on its own after the timeout
+    let shadow = open_standalone_get(&client, &url, &session_id).await;
+    assert_eq!(shadow.status(), 200);
+    let body = tokio::time::timeout(Duration::from_secs(3), shadow.text())
+        .await
+        .expect("shadow stream should close after the idle timeout")
+        .expect("read shadow stream");
+    assert!(
+        body.contains("retry:"),
+        "shadow should close with a final priming event, got {body:?}"
+    );
+
+    // The primary outlived the timeout and still receives notifications
+    trigger.notify_one();
+
+    assert!(
+        wait_for_sse_event(get1, "tools/list_changed", Duration::from_secs(3)).await,
+        "Primary should not be affected by the shadow idle timeout"
+    );
+
+    ct.cancel();
+}
+
+// ─── Tests: Session stats ────────────────────────────────────────────────────
+
+/// `LocalSessionManager::stats` counts sessions and the primary and shadow
+/// streams opened on them.
+#[tokio::test]
+async fn stats_report_sessions_and_streams() {
+    let ct = CancellationToken::new();
+    let trigger = Arc::new(Notify::new());
+    let session_manager = Arc::new(LocalSessionManager::default());
+    let url = start_test_server_with(ct.clone(), trigger, session_manager.clone()).await;
+    let client = reqwest::Client::new();
+
+    let stats = session_manager.stats().await;
+    assert_eq!((stats.session_count, stats.total_streams), (0, 0));
+
+    let session_id = initialize_session(&client, &url).await;
+    send_initialized_notification(&client, &url, &session_id).await;
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let stats = session_manager.stats().await;
+    assert_eq!((stats.session_count, stats.total_streams), (1, 0));
+
+    // Primary and shadow
+    let get1 = open_standalone_get(&client, &url, &session_id).await;
+    assert_eq!(get1.status(), 200);
+    let shadow = open_standalone_get(&client, &url, &session_id).await;
+    assert_eq!(shadow.status(), 200);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let stats = session_manager.stats().await;
+    assert_eq!((stats.session_count, stats.total_streams), (1, 2));
+    let handle = session_manager
+        .sessions
+        .read()
+        .await
+        .get(session_id.as_str())
+        .cloned()
+        .expect("session handle");
+    assert_eq!(handle.active_stream_count().await.unwrap(), 2);
+
+    drop(shadow);
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(handle.active_stream_count().await.unwrap(), 1);
+
+    drop(get1);
+    ct.cancel();
+}
@@ -0,0 +1,318 @@
+#![cfg(all(
+    feature = "transport-streamable-http-server",
+    feature = "transport-streamable-http-client-reqwest",
+    not(feature = "local")
+))]
+
+use std::time::Duration;
+
+use futures::StreamExt;
+use rmcp::{
+    ErrorData, RoleServer, ServerHandler,
+    model::{
+        CallToolRequestParams, CallToolResult, ClientResult, ContentBlock, ElicitRequest,
+        ElicitRequestParams, ElicitationSchema, ProgressNotificationParam, ServerCapabilities,
+        ServerInfo, ServerRequest,
+    },
+    service::RequestContext,
+    transport::streamable_http_server::{
+        StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
+    },
+};
+use tokio_util::sync::CancellationToken;
+
+mod common;
+use common::calculator::Calculator;
+
+const INIT_BODY: &str = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-03-26","capabilities":{},"clientInfo":{"name":"test","version":"1.0"}}}"#;
+
+async fn spawn_server(config: StreamableHttpServerConfig, ct: &CancellationToken) -> String {
+    spawn_server_with(Calculator::new, config, ct).await
+}
+
+async fn spawn_server_with<S: ServerHandler>(
+    handler: fn() -> S,
+    config: StreamableHttpServerConfig,
+    ct: &CancellationToken,
+) -> String {
+    let service: StreamableHttpService<S, LocalSessionManager> = StreamableHttpService::new(
+        move || Ok(handler()),
+        Default::default(),
+        config
+            .with_sse_keep_alive(None)
+            .with_cancellation_token(ct.child_token()),
+    );
+
+    let router = axum::Router::new().nest_service("/mcp", service);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn({
+        let ct = ct.clone();
+        async move {
+            let _ = axum::serve(listener, router)
+                .with_graceful_shutdown(async move { ct.cancelled_owned().await })
+                .await;
+        }
+    });
+    format!("http://{addr}/mcp")
+}
+
+/// POST an initialize request and return the status and `Content-Type`.
+async fn post_initialize(url: &str, accept: &str) -> anyhow::Result<(u16, String)> {
+    let response = reqwest::Client::new()
+        .post(url)
+        .header("Content-Type", "application/json")
+        .header("Accept", accept)
+        .body(INIT_BODY)
+        .send()
+        .await?;
+    let content_type = response
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_owned();
+    Ok((response.status().as_u16(), content_type))
+}
+
+const JSON: &str = "application/json";
+const SSE: &str = "text/event-stream";
+
+#[tokio::test]
+async fn test_stateful_accept_combinations() -> anyhow::Result<()> {
+    let ct = CancellationToken::new();
+    let url = spawn_server(StreamableHttpServerConfig::default(), &ct).await;
+
+    let cases = [
+        ("application/json, text/event-stream", 200, SSE),
+        ("application/json", 200, JSON),
+        ("text/event-stream", 200, SSE),
+        ("*/*", 200, SSE),
+        ("application/*", 200, JSON),
+        ("application/json, text/event-stream;q=0", 200, JSON),
+        ("application/json;q=0, */*", 200, SSE),
+        ("*/*, text/event-stream;q=0", 200, JSON),
+        ("*/*;q=0, application/json", 200, JSON),
+        ("application/*;q=0, application/json", 200, JSON),
+        ("text/html", 406, ""),
+        ("", 406, ""),
+    ];
+    for (accept, status, content_type) in cases {
+        let (actual_status, actual_content_type) = post_initialize(&url, accept).await?;
+        assert_eq!(actual_status, status, "Accept: {accept}");
+        assert!(
+            actual_content_type.starts_with(content_type),
+            "Accept: {accept} got {actual_content_type}"
+        );
+    }
+
+    ct.cancel();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stateless_accept_combinations() -> anyhow::Result<()> {
+    let ct = CancellationToken::new();
+    let url = spawn_server(
+        StreamableHttpServerConfig::default()
+            .with_stateful_mode(false)
+            .with_json_response(true),
+        &ct,
+    )
+    .await;
+
+    let cases = [
+        ("application/json, text/event-stream", 200, JSON),
+        ("application/json", 200, JSON),
+        ("text/event-stream", 200, SSE),
+        ("text/plain", 406, ""),
+    ];
+    for (accept, status, content_type) in cases {
+        let (actual_status, actual_content_type) = post_initialize(&url, accept).await?;
+        assert_eq!(actual_status, status, "Accept: {accept}");
+        assert!(
+            actual_content_type.starts_with(content_type),
+            "Accept: {accept} got {actual_content_type}"
+        );
+    }
+
+    ct.cancel();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stateful_request_answers_json_when_sse_is_not_accepted() -> anyhow::Result<()> {
+    let ct = CancellationToken::new();
+    let url = spawn_server(StreamableHttpServerConfig::default(), &ct).await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post(&url)
+        .header("Content-Type", "application/json")
+        .header("Accept", JSON)
+        .body(INIT_BODY)
+        .send()
+        .await?;
+    let session_id = response.headers()["mcp-session-id"].to_str()?.to_owned();
+    let body: serde_json::Value = response.json().await?;
+    assert_eq!(body["id"], 1);
+
+    client
+        .post(&url)
+        .header("Content-Type", "application/json")
+        .header("Accept", JSON)
+        .header("mcp-session-id", &session_id)
+        .body(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#)
+        .send()
+        .await?;
+
+    let response = client
+        .post(&url)
+        .header("Content-Type", "application/json")
+        .header("Accept", JSON)
+        .header("mcp-session-id", &session_id)
+        .body(r#"{"jsonrpc":"2.0","id":2,"method":"ping"}"#)
+        .send()
+        .await?;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-type"], JSON);
+    let body: serde_json::Value = response.json().await?;
+    assert_eq!(body["id"], 2);
+    assert!(body.get("result").is_some());
+
+    ct.cancel();
+    Ok(())
+}
+
+/// Reports progress, then asks the client to confirm before answering.
+struct Confirm;
+
+impl ServerHandler for Confirm {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo::new(ServerCapabilities::builder().enable_tools().build())
+    }
+
+    async fn call_tool(
+        &self,
+        _request: CallToolRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(progress_token) = context.meta.get_progress_token() {
+            context
+                .peer
+                .notify_progress(ProgressNotificationParam::new(progress_token, 0.5))
+                .await
+                .map_err(|e| ErrorData::internal_error(e.to_string(), None))?;
+        }
+        let request = ServerRequest::ElicitRequest(ElicitRequest::new(
+            ElicitRequestParams::FormElicitationParams {
+                meta: None,
+                message: "Proceed?".into(),
+                requested_schema: ElicitationSchema::builder().build().unwrap(),
+            },
+        ));
+        let response = context
+            .peer
+            .send_request(request)
+            .await
+            .map_err(|e| ErrorData::internal_error(e.to_string(), None))?;
+        let ClientResult::ElicitResult(result) = response else {
+            return Err(ErrorData::internal_error("unexpected response", None));
+        };
+        Ok(CallToolResult::success(vec![ContentBlock::text(
+            serde_json::to_string(&result.action).unwrap(),
+        )]))
+    }
+}
+
+/// Read SSE events from `stream` until one carries a JSON-RPC message.
+async fn next_sse_message<B: AsRef<[u8]>>(
+    stream: &mut (impl futures::Stream<Item = reqwest::Result<B>> + Unpin),
+    buffer: &mut String,
+) -> anyhow::Result<serde_json::Value> {
+    loop {
+        while let Some(end) = buffer.find("\n\n") {
+            let event: String = buffer.drain(..end + 2).collect();
+            let data: String = event
+                .lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .map(str::trim)
+                .collect();
+            if !data.is_empty() {
+                return Ok(serde_json::from_str(&data)?);
+            }
+        }
+        let chunk = stream
+            .next()
+            .await
+            .ok_or_else(|| anyhow::anyhow!("stream ended"))??;
+        buffer.push_str(std::str::from_utf8(chunk.as_ref())?);
+    }
+}
+
+#[tokio::test]
+async fn test_stateful_json_request_can_elicit() -> anyhow::Result<()> {
+    let ct = CancellationToken::new();
+    let url = spawn_server_with(|| Confirm, StreamableHttpServerConfig::default(), &ct).await;
+    let client = reqwest::Client::new();
+    let post = |body: String, session_id: Option<&str>| {
+        let mut request = client
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .header("Accept", JSON);
+        if let Some(session_id) = session_id {
+            request = request.header("mcp-session-id", session_id);
+        }
+        request.body(body).send()
+    };
+
+    let response = post(INIT_BODY.into(), None).await?;
+    let session_id = response.headers()["mcp-session-id"].to_str()?.to_owned();
+    post(
+        r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#.into(),
+        Some(&session_id),
+    )
+    .await?;
+
+    let standalone = client
+        .get(&url)
+        .header("Accept", SSE)
+        .header("mcp-session-id", &session_id)
+        .send()
+        .await?;
+    assert_eq!(standalone.status(), 200);
+    let mut events = standalone.bytes_stream();
+    let mut buffer = String::new();
+
+    let call = tokio::spawn(post(
+        r#"{"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"confirm","_meta":{"progressToken":"call-2"}}}"#.into(),
+        Some(&session_id),
+    ));
+
+    // The progress notification and the elicitation travel on the GET stream.
+    let timeout = Duration::from_secs(5);
+    let progress =
+        tokio::time::timeout(timeout, next_sse_message(&mut events, &mut buffer)).await??;
+    assert_eq!(progress["method"], "notifications/progress");
+    assert_eq!(progress["params"]["progressToken"], "call-2");
+    let elicitation =
+        tokio::time::timeout(timeout, next_sse_message(&mut events, &mut buffer)).await??;
+    assert_eq!(elicitation["method"], "elicitation/create");
+
+    let answer = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": elicitation["id"],
+        "result": { "action": "accept", "content": {} },
+    });
+    let response = post(answer.to_string(), Some(&session_id)).await?;
+    assert_eq!(response.status(), 202);
+
+    let response = tokio::time::timeout(timeout, call).await???;
+    assert_eq!(response.headers()["content-type"], JSON);
+    let body: serde_json::Value = response.json().await?;
+    assert_eq!(body["id"], 2);
+    assert_eq!(body["result"]["content"][0]["text"], "\"accept\"");
+
+    ct.cancel();
+    Ok(())
+}
@@ -0,0 +1,234 @@
+#![cfg(all(
+    feature = "transport-streamable-http-client",
+    feature = "transport-streamable-http-client-reqwest",
+    feature = "transport-streamable-http-server",
+    not(feature = "local")
+))]
+
+use std::sync::{Arc, Mutex};
+
+use rmcp::{
+    RoleServer, ServerHandler, ServiceExt,
+    model::{CallToolRequestParams, CallToolResult, ContentBlock, ServerCapabilities, ServerInfo},
+    service::RequestContext,
+    transport::{
+        StreamableHttpClientTransport,
+        streamable_http_client::StreamableHttpClientTransportConfig,
+        streamable_http_server::{
+            AuthContext, StreamableHttpServerConfig, StreamableHttpService,
+            session::local::LocalSessionManager,
+        },
+    },
+};
+use tokio_util::sync::CancellationToken;
+
+#[derive(Clone, Default)]
+struct WhoAmI {
+    seen: Arc<Mutex<Vec<Option<String>>>>,
+}
+
+impl ServerHandler for WhoAmI {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo::new(ServerCapabilities::builder().enable_tools().build())
+    }
+
+    async fn call_tool(
+        &self,
+        _request: CallToolRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let subject = context
+            .extensions
+            .get::<AuthContext>()
+            .and_then(|auth| auth.subject.clone());
+        self.seen.lock().unwrap().push(subject.clone());
+        Ok(CallToolResult::success(vec![ContentBlock::text(
+            subject.unwrap_or_default(),
+        )]))
+    }
+}
+
+#[tokio::test]
+async fn test_authorizer_gates_session_creation() -> anyhow::Result<()> {
+    let ct = CancellationToken::new();
+    let handler = WhoAmI::default();
+    let service: StreamableHttpService<WhoAmI, LocalSessionManager> = StreamableHttpService::new(
+        {
+            let handler = handler.clone();
+            move || Ok(handler.clone())
+        },
+        Default::default(),
+        StreamableHttpServerConfig::default()
+            .with_sse_keep_alive(None)
+            .with_cancellation_token(ct.child_token()),
+    )
+    .with_authorizer(|headers| {
+        match headers
+            .get(http::header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+        {
+            Some("Bearer good") => Ok(AuthContext::new()
+                .with_subject("alice")
+                .with_scopes(["tools:call"])),
+            Some(_) => Err(http::StatusCode::FORBIDDEN),
+            None => Err(http::StatusCode::UNAUTHORIZED),
+        }
+    });
+
+    let router = axum::Router::new().nest_service("/mcp", service);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let handle = tokio::spawn({
+        let ct = ct.clone();
+        async move {
+            let _ = axum::serve(listener, router)
+                .with_graceful_shutdown(async move { ct.cancelled_owned().await })
+                .await;
+        }
+    });
+    let uri = format!("http://{addr}/mcp");
+
+    let initialize = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-06-18","capabilities":{},"clientInfo":{"name":"test","version":"0.0.0"}}}"#;
+    let unauthorized = reqwest::Client::new()
+        .post(&uri)
+        .header("accept", "application/json, text/event-stream")
+        .header("content-type", "application/json")
+        .body(initialize)
+        .send()
+        .await?;
+    assert_eq!(unauthorized.status(), reqwest::StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        unauthorized
+            .headers()
+            .get(reqwest::header::WWW_AUTHENTICATE)
+            .map(|v| v.as_bytes()),
+        Some(&b"Bearer"[..])
+    );
+    assert!(unauthorized.headers().get("mcp-session-id").is_none());
+
+    let forbidden = reqwest::Client::new()
+        .post(&uri)
+        .header("accept", "application/json, text/event-stream")
+        .header("content-type", "application/json")
+        .bearer_auth("bad")
+        .body(initialize)
+        .send()
+        .await?;
+    assert_eq!(forbidden.status(), reqwest::StatusCode::FORBIDDEN);
+
+    let transport = StreamableHttpClientTransport::from_config(
+        StreamableHttpClientTransportConfig::with_uri(uri.as_str()).auth_header("good"),
+    );
+    let client = ().serve(transport).await?;
+    let result = client
+        .call_tool(CallToolRequestParams::new("whoami"))
+        .await?;
+    assert_eq!(
+        result.content[0].as_text().map(|t| t.text.as_str()),
+        Some("alice")
+    );
+    assert_eq!(
+        handler.seen.lock().unwrap().as_slice(),
+        &[Some("alice".to_string())]
+    );
+
+    client.cancel().await?;
+    ct.cancel();
+    handle.await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_session_id_alone_does_not_authorize() -> anyhow::Result<()> {
+    let ct = CancellationToken::new();
+    let service: StreamableHttpService<WhoAmI, LocalSessionManager> = StreamableHttpService::new(
+        || Ok(WhoAmI::default()),
+        Default::default(),
+        StreamableHttpServerConfig::default()
+            .with_sse_keep_alive(None)
+            .with_json_response(true)
+            .with_cancellation_token(ct.child_token()),
+    )
+    .with_authorizer(|headers| {
+        match headers
+            .get(http::header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+        {
+            Some("Bearer alice") => Ok(AuthContext::new().with_subject("alice")),
+            Some("Bearer mallory") => Ok(AuthContext::new().with_subject("mallory")),
+            _ => Err(http::StatusCode::UNAUTHORIZED),
+        }
+    });
+
+    let router = axum::Router::new().nest_service("/mcp", service);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let handle = tokio::spawn({
+        let ct = ct.clone();
+        async move {
+            let _ = axum::serve(listener, router)
+                .with_graceful_shutdown(async move { ct.cancelled_owned().await })
+                .await;
+        }
+    });
+    let uri = format!("http://{addr}/mcp");
+    let http = reqwest::Client::new();
+    let post = |body: &'static str| {
+        http.post(&uri)
+            .header("accept", "application/json, text/event-stream")
+            .header("content-type", "application/json")
+            .header("mcp-protocol-version", "2025-06-18")
+            .body(body)
+    };
+
+    let initialize = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-06-18","capabilities":{},"clientInfo":{"name":"test","version":"0.0.0"}}}"#;
+    let initialized = post(initialize).bearer_auth("alice").send().await?;
+    assert_eq!(initialized.status(), reqwest::StatusCode::OK);
+    let session_id = initialized
+        .headers()
+        .get("mcp-session-id")
+        .expect("session id")
+        .to_str()?
+        .to_owned();
+    let notified = post(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#)
+        .bearer_auth("alice")
+        .header("mcp-session-id", &session_id)
+        .send()
+        .await?;
+    assert_eq!(notified.status(), reqwest::StatusCode::ACCEPTED);
+
+    let call = r#"{"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"whoami"}}"#;
+    let anonymous = post(call)
+        .header("mcp-session-id", &session_id)
+        .send()
+        .await?;
+    assert_eq!(anonymous.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    let hijacked = post(call)
+        .bearer_auth("mallory")
+        .header("mcp-session-id", &session_id)
+        .send()
+        .await?;
+    assert_eq!(hijacked.status(), reqwest::StatusCode::NOT_FOUND);
+
+    let deleted = http
+        .delete(&uri)
+        .bearer_auth("mallory")
+        .header("mcp-session-id", &session_id)
+        .header("mcp-protocol-version", "2025-06-18")
+        .send()
+        .await?;
+    assert_eq!(deleted.status(), reqwest::StatusCode::NOT_FOUND);
+
+    let owned = post(call)
+        .bearer_auth("alice")
+        .header("mcp-session-id", &session_id)
+        .send()
+        .await?;
+    assert_eq!(owned.status(), reqwest::StatusCode::OK);
+    assert!(owned.text().await?.contains("alice"));
+
+    ct.cancel();
+    handle.await?;
+    Ok(())
+}
@@ -0,0 +1,177 @@
+#![cfg(all(
+    feature = "transport-streamable-http-client",
+    feature = "transport-streamable-http-client-reqwest",
+    feature = "transport-streamable-http-server",
+    not(feature = "local")
+))]
+
+use base64::{Engine, prelude::BASE64_STANDARD};
+use rmcp::{
+    ErrorData, RoleServer, ServerHandler, ServiceExt,
+    model::{
+        ClientCapabilities, ClientInfo, ExtensionCapabilities, Implementation,
+        ReadResourceRequestParams, ReadResourceResult, ResourceContents, ServerCapabilities,
+        ServerInfo,
+    },
+    service::RequestContext,
+    transport::{
+        StreamableHttpClientTransport,
+        streamable_http_server::{
+            BinaryDownloads, StreamableHttpServerConfig, StreamableHttpService,
+            session::local::LocalSessionManager,
+        },
+    },
+};
+use tokio_util::sync::CancellationToken;
+
+const IMAGE_URI: &str = "file:///image.png";
+
+fn image_bytes() -> Vec<u8> {
+    (0..64 * 1024).map(|i| (i % 251) as u8).collect()
+}
+
+#[derive(Clone)]
+struct ImageServer;
+
+impl ServerHandler for ImageServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo::new(ServerCapabilities::builder().enable_resources().build())
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, ErrorData> {
+        let contents = BinaryDownloads::offer(
+            &context,
+            request.uri,
+            Some("image/png".into()),
+            image_bytes(),
+        );
+        Ok(ReadResourceResult::new(vec![contents]))
+    }
+}
+
+async fn serve(ct: &CancellationToken) -> anyhow::Result<String> {
+    serve_with(ct, BinaryDownloads::new("/downloads")).await
+}
+
+async fn serve_with(ct: &CancellationToken, downloads: BinaryDownloads) -> anyhow::Result<String> {
+    let config = StreamableHttpServerConfig::default()
+        .with_binary_downloads(downloads)
+        .with_cancellation_token(ct.child_token());
+    let service: StreamableHttpService<ImageServer, LocalSessionManager> =
+        StreamableHttpService::new(|| Ok(ImageServer), Default::default(), config);
+    let router = axum::Router::new().nest_service("/mcp", service);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn({
+        let ct = ct.clone();
+        async move {
+            let _ = axum::serve(listener, router)
+                .with_graceful_shutdown(async move { ct.cancelled_owned().await })
+                .await;
+        }
+    });
+    Ok(format!("http://{addr}/mcp"))
+}
+
+fn opted_in_client_info() -> ClientInfo {
+    let mut extensions = ExtensionCapabilities::new();
+    extensions.insert(
+        ResourceContents::BINARY_DOWNLOAD_EXTENSION.to_string(),
+        Default::default(),
+    );
+    ClientInfo::new(
+        ClientCapabilities::builder()
+            .enable_extensions_with(extensions)
+            .build(),
+        Implementation::new("download-client", "1.0"),
+    )
+}
+
+#[tokio::test]
+async fn test_blob_is_downloaded_when_client_opts_in() -> anyhow::Result<()> {
+    let ct = CancellationToken::new();
+    let url = serve(&ct).await?;
+
+    let client = opted_in_client_info()
+        .serve(StreamableHttpClientTransport::from_uri(url.clone()))
+        .await?;
+
+    let result = client
+        .read_resource(ReadResourceRequestParams::new(IMAGE_URI))
+        .await?;
+    let contents = &result.contents[0];
+    let ResourceContents::BlobResourceContents { blob, .. } = contents else {
+        panic!("expected blob contents, got {contents:?}");
+    };
+    assert!(blob.is_empty());
+    let path = contents.download_path().expect("download path");
+
+    let response = reqwest::get(format!("{url}{path}")).await?;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "image/png");
+    assert_eq!(response.bytes().await?.as_ref(), image_bytes().as_slice());
+
+    // Each download can be fetched once.
+    let again = reqwest::get(format!("{url}{path}")).await?;
+    assert_eq!(again.status(), reqwest::StatusCode::NOT_FOUND);
+
+    let missing = reqwest::get(format!("{url}/downloads/unknown")).await?;
+    assert_eq!(missing.status(), reqwest::StatusCode::NOT_FOUND);
+
+    client.cancel().await?;
+    ct.cancel();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_blob_stays_inline_without_opt_in() -> anyhow::Result<()> {
+    let ct = CancellationToken::new();
+    let url = serve(&ct).await?;
+
+    let client = ().serve(StreamableHttpClientTransport::from_uri(url)).await?;
+    let result = client
+        .read_resource(ReadResourceRequestParams::new(IMAGE_URI))
+        .await?;
+    let contents = &result.contents[0];
+    assert_eq!(contents.download_path(), None);
+    let ResourceContents::BlobResourceContents { blob, .. } = contents else {
+        panic!("expected blob contents, got {contents:?}");
+    };
+    assert_eq!(BASE64_STANDARD.decode(blob)?, image_bytes());
+
+    client.cancel().await?;
+    ct.cancel();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_blob_stays_inline_when_downloads_are_full() -> anyhow::Result<()> {
+    let ct = CancellationToken::new();
+    let downloads = BinaryDownloads::new("/downloads").with_max_total_bytes(96 * 1024);
+    let url = serve_with(&ct, downloads).await?;
+
+    let client = opted_in_client_info()
+        .serve(StreamableHttpClientTransport::from_uri(url.clone()))
+        .await?;
+    let read = || client.read_resource(ReadResourceRequestParams::new(IMAGE_URI));
+
+    let first = read().await?;
+    let path = first.contents[0].download_path().expect("download path");
+    // A second image does not fit next to the first one.
+    let second = read().await?;
+    assert_eq!(second.contents[0].download_path(), None);
+
+    // Fetching the first download frees its space.
+    let response = reqwest::get(format!("{url}{path}")).await?;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let third = read().await?;
+    assert!(third.contents[0].download_path().is_some());
+
+    client.cancel().await?;
+    ct.cancel();
+    Ok(())
+}
@@ -0,0 +1,189 @@
+#![cfg(all(
+    feature = "transport-streamable-http-client",
+    feature = "transport-streamable-http-client-reqwest",
+    feature = "transport-streamable-http-server",
+    feature = "compression",
+    not(feature = "local")
+))]
+
+use std::io::{Read, Write};
+
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use rmcp::{
+    RoleServer, ServerHandler, ServiceExt,
+    model::{CallToolRequestParams, CallToolResult, ContentBlock, ServerCapabilities, ServerInfo},
+    service::RequestContext,
+    transport::{
+        StreamableHttpClientTransport,
+        streamable_http_client::{GzipRequestClient, StreamableHttpClientTransportConfig},
+        streamable_http_server::{
+            StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
+        },
+    },
+};
+use serde_json::json;
+use tokio_util::sync::CancellationToken;
+
+#[derive(Clone)]
+struct Echo;
+
+impl ServerHandler for Echo {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo::new(ServerCapabilities::builder().enable_tools().build())
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let text = request
+            .arguments
+            .and_then(|args| args.get("text").and_then(|t| t.as_str()).map(String::from))
+            .unwrap_or_default();
+        Ok(CallToolResult::success(vec![ContentBlock::text(text)]))
+    }
+}
+
+async fn serve(
+    config: StreamableHttpServerConfig,
+    ct: &CancellationToken,
+) -> anyhow::Result<String> {
+    let service: StreamableHttpService<Echo, LocalSessionManager> = StreamableHttpService::new(
+        || Ok(Echo),
+        Default::default(),
+        config
+            .with_sse_keep_alive(None)
+            .with_cancellation_token(ct.child_token()),
+    );
+    let router = axum::Router::new().nest_service("/mcp", service);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let ct = ct.clone();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, router)
+            .with_graceful_shutdown(async move { ct.cancelled_owned().await })
+            .await;
+    });
+    Ok(format!("http://{addr}/mcp"))
+}
+
+fn gzip(bytes: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(bytes).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn initialize_body() -> Vec<u8> {
+    serde_json::to_vec(&json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-06-18",
+            "capabilities": {},
+            "clientInfo": { "name": "raw", "version": "0" }
+        }
+    }))
+    .unwrap()
+}
+
+fn raw_post(client: &reqwest::Client, uri: &str) -> reqwest::RequestBuilder {
+    client
+        .post(uri)
+        .header("accept", "application/json, text/event-stream")
+        .header("content-type", "application/json")
+}
+
+#[tokio::test]
+async fn test_gzip_request_and_json_response() -> anyhow::Result<()> {
+    let ct = CancellationToken::new();
+    let uri = serve(
+        StreamableHttpServerConfig::default()
+            .with_stateful_mode(false)
+            .with_json_response(true)
+            .with_response_compression_threshold(Some(1)),
+        &ct,
+    )
+    .await?;
+    // Disable transparent decompression so the encoding is observable.
+    let client = reqwest::Client::builder().no_gzip().build()?;
+
+    let response = raw_post(&client, &uri)
+        .header("content-encoding", "gzip")
+        .header("accept-encoding", "gzip")
+        .body(gzip(&initialize_body()))
+        .send()
+        .await?;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-encoding"], "gzip");
+    let mut body = String::new();
+    GzDecoder::new(&response.bytes().await?[..]).read_to_string(&mut body)?;
+    let body: serde_json::Value = serde_json::from_str(&body)?;
+    assert_eq!(body["id"], 1);
+    assert!(body["result"]["capabilities"]["tools"].is_object());
+
+    // Without `Accept-Encoding` the response is sent as is.
+    let response = raw_post(&client, &uri)
+        .body(initialize_body())
+        .send()
+        .await?;
+    assert!(response.headers().get("content-encoding").is_none());
+    serde_json::from_slice::<serde_json::Value>(&response.bytes().await?)?;
+
+    let response = raw_post(&client, &uri)
+        .header("content-encoding", "br")
+        .body(initialize_body())
+        .send()
+        .await?;
+    assert_eq!(response.status(), 415);
+
+    ct.cancel();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sse_responses_are_not_compressed() -> anyhow::Result<()> {
+    let ct = CancellationToken::new();
+    let uri = serve(
+        StreamableHttpServerConfig::default().with_response_compression_threshold(Some(1)),
+        &ct,
+    )
+    .await?;
+    let client = reqwest::Client::builder().no_gzip().build()?;
+    let response = raw_post(&client, &uri)
+        .header("accept-encoding", "gzip")
+        .body(initialize_body())
+        .send()
+        .await?;
+    assert_eq!(response.status(), 200);
+    assert!(
+        response.headers()["content-type"]
+            .to_str()?
+            .starts_with("text/event-stream")
+    );
+    assert!(response.headers().get("content-encoding").is_none());
+
+    // A full client round-trip with compressed requests over SSE.
+    let transport = StreamableHttpClientTransport::with_client(
+        GzipRequestClient::new(reqwest::Client::new(), 0),
+        StreamableHttpClientTransportConfig::with_uri(uri),
+    );
+    let client = ().serve(transport).await?;
+    let text = "compress me ".repeat(1000);
+    let result = client
+        .call_tool(
+            CallToolRequestParams::new("echo").with_arguments(
+                json!({ "text": text })
+                    .as_object()
+                    .cloned()
+                    .unwrap_or_default(),
+            ),
+        )
+        .await?;
+    assert_eq!(result.content[0].as_text().unwrap().text, text);
+    client.cancel().await?;
+
+    ct.cancel();
+    Ok(())
+}
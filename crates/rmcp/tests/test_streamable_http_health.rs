@@ -0,0 +1,81 @@
+#![cfg(all(
+    feature = "transport-streamable-http-server",
+    feature = "transport-streamable-http-client-reqwest",
+    not(feature = "local")
+))]
+
+use std::sync::Arc;
+
+use rmcp::{
+    ServerHandler,
+    model::{ServerCapabilities, ServerInfo},
+    transport::streamable_http_server::{
+        StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
+    },
+};
+use tokio_util::sync::CancellationToken;
+
+#[derive(Clone)]
+struct Noop;
+
+impl ServerHandler for Noop {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo::new(ServerCapabilities::builder().build())
+    }
+}
+
+async fn serve(
+    config: StreamableHttpServerConfig,
+    ct: &CancellationToken,
+) -> anyhow::Result<(String, Arc<LocalSessionManager>)> {
+    let session_manager = Arc::new(LocalSessionManager::default());
+    let service: StreamableHttpService<Noop, LocalSessionManager> = StreamableHttpService::new(
+        || Ok(Noop),
+        session_manager.clone(),
+        config.with_cancellation_token(ct.child_token()),
+    );
+
+    let router = axum::Router::new().nest_service("/mcp", service);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn({
+        let ct = ct.clone();
+        async move {
+            let _ = axum::serve(listener, router)
+                .with_graceful_shutdown(async move { ct.cancelled_owned().await })
+                .await;
+        }
+    });
+    Ok((format!("http://{addr}/mcp"), session_manager))
+}
+
+#[tokio::test]
+async fn test_health_route_does_not_create_a_session() -> anyhow::Result<()> {
+    let ct = CancellationToken::new();
+    let (url, session_manager) = serve(
+        StreamableHttpServerConfig::default().with_health_path("/healthz"),
+        &ct,
+    )
+    .await?;
+
+    let response = reqwest::get(format!("{url}/healthz")).await?;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert_eq!(response.text().await?, "ok");
+    assert!(session_manager.sessions.read().await.is_empty());
+
+    ct.cancel();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_health_route_is_disabled_by_default() -> anyhow::Result<()> {
+    let ct = CancellationToken::new();
+    let (url, session_manager) = serve(StreamableHttpServerConfig::default(), &ct).await?;
+
+    let response = reqwest::get(format!("{url}/healthz")).await?;
+    assert_ne!(response.status(), reqwest::StatusCode::OK);
+    assert!(session_manager.sessions.read().await.is_empty());
+
+    ct.cancel();
+    Ok(())
+}
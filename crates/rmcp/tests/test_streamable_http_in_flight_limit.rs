@@ -0,0 +1,142 @@
+#![cfg(all(
+    feature = "transport-streamable-http-server",
+    feature = "transport-streamable-http-client-reqwest",
+    not(feature = "local")
+))]
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use rmcp::{
+    RoleServer, ServerHandler,
+    model::{CallToolRequestParams, CallToolResult, ServerCapabilities, ServerInfo},
+    service::RequestContext,
+    transport::streamable_http_server::{
+        StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
+    },
+};
+use tokio::sync::Semaphore;
+use tokio_util::sync::CancellationToken;
+
+/// A tool that blocks until the test hands out a permit.
+#[derive(Clone)]
+struct Gate {
+    started: Arc<AtomicUsize>,
+    permits: Arc<Semaphore>,
+}
+
+impl ServerHandler for Gate {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo::new(ServerCapabilities::builder().enable_tools().build())
+    }
+
+    async fn call_tool(
+        &self,
+        _request: CallToolRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        self.started.fetch_add(1, Ordering::SeqCst);
+        self.permits.acquire().await.unwrap().forget();
+        Ok(CallToolResult::success(vec![]))
+    }
+}
+
+fn call_tool_body(id: u32) -> String {
+    format!(r#"{{"jsonrpc":"2.0","id":{id},"method":"tools/call","params":{{"name":"wait"}}}}"#)
+}
+
+#[tokio::test]
+async fn test_requests_over_the_limit_are_rejected() -> anyhow::Result<()> {
+    const LIMIT: usize = 2;
+    let ct = CancellationToken::new();
+    let handler = Gate {
+        started: Default::default(),
+        permits: Arc::new(Semaphore::new(0)),
+    };
+    let service: StreamableHttpService<Gate, LocalSessionManager> = StreamableHttpService::new(
+        {
+            let handler = handler.clone();
+            move || Ok(handler.clone())
+        },
+        Default::default(),
+        StreamableHttpServerConfig::default()
+            .with_sse_keep_alive(None)
+            .with_max_in_flight_requests_per_session(Some(LIMIT))
+            .with_cancellation_token(ct.child_token()),
+    );
+
+    let router = axum::Router::new().nest_service("/mcp", service);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn({
+        let ct = ct.clone();
+        async move {
+            let _ = axum::serve(listener, router)
+                .with_graceful_shutdown(async move { ct.cancelled_owned().await })
+                .await;
+        }
+    });
+
+    let client = reqwest::Client::new();
+    let url = format!("http://{addr}/mcp");
+    let response = client
+        .post(&url)
+        .header("Content-Type", "application/json")
+        .header("Accept", "application/json, text/event-stream")
+        .body(r#"{"jsonrpc":"2.0","id":0,"method":"initialize","params":{"protocolVersion":"2025-06-18","capabilities":{},"clientInfo":{"name":"test","version":"1.0"}}}"#)
+        .send()
+        .await?;
+    assert_eq!(response.status(), 200);
+    let session_id = response.headers()["mcp-session-id"].to_str()?.to_owned();
+    let post = |body: String| {
+        client
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .header("Accept", "application/json, text/event-stream")
+            .header("mcp-session-id", &session_id)
+            .header("Mcp-Protocol-Version", "2025-06-18")
+            .body(body)
+            .send()
+    };
+    post(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#.to_owned()).await?;
+
+    let mut in_flight = Vec::new();
+    for id in 1..=LIMIT as u32 {
+        let response = post(call_tool_body(id)).await?;
+        assert_eq!(response.status(), 200);
+        in_flight.push(response);
+    }
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while handler.started.load(Ordering::SeqCst) < LIMIT {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await?;
+
+    let rejected = post(call_tool_body(LIMIT as u32 + 1)).await?;
+    assert_eq!(rejected.status(), 429);
+    let error: serde_json::Value = rejected.json().await?;
+    assert_eq!(error["id"], LIMIT + 1);
+    assert_eq!(error["error"]["code"], -32000);
+    assert_eq!(handler.started.load(Ordering::SeqCst), LIMIT);
+
+    handler.permits.add_permits(LIMIT + 1);
+    for response in in_flight {
+        let body = response.text().await?;
+        assert!(body.contains(r#""result""#), "unexpected body: {body}");
+    }
+
+    // Completed requests free their slots.
+    let response = post(call_tool_body(LIMIT as u32 + 2)).await?;
+    assert_eq!(response.status(), 200);
+    let body = response.text().await?;
+    assert!(body.contains(r#""result""#), "unexpected body: {body}");
+
+    ct.cancel();
+    Ok(())
+}
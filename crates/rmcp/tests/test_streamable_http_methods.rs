@@ -0,0 +1,174 @@
+#![cfg(all(
+    feature = "transport-streamable-http-server",
+    feature = "transport-streamable-http-client-reqwest",
+    not(feature = "local")
+))]
+
+use std::sync::Arc;
+
+use rmcp::transport::streamable_http_server::{
+    StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
+};
+use tokio_util::sync::CancellationToken;
+
+mod common;
+use common::calculator::Calculator;
+
+const INITIALIZE: &str = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-11-25","capabilities":{},"clientInfo":{"name":"test","version":"0"}}}"#;
+
+async fn serve(
+    config: StreamableHttpServerConfig,
+    ct: &CancellationToken,
+) -> anyhow::Result<(String, Arc<LocalSessionManager>)> {
+    let session_manager = Arc::new(LocalSessionManager::default());
+    let service: StreamableHttpService<Calculator, LocalSessionManager> =
+        StreamableHttpService::new(
+            || Ok(Calculator::new()),
+            session_manager.clone(),
+            config
+                .with_sse_keep_alive(None)
+                .with_cancellation_token(ct.child_token()),
+        );
+
+    let router = axum::Router::new().nest_service("/mcp", service);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn({
+        let ct = ct.clone();
+        async move {
+            let _ = axum::serve(listener, router)
+                .with_graceful_shutdown(async move { ct.cancelled_owned().await })
+                .await;
+        }
+    });
+    Ok((format!("http://{addr}/mcp"), session_manager))
+}
+
+#[tokio::test]
+async fn test_delete_terminates_session() -> anyhow::Result<()> {
+    let ct = CancellationToken::new();
+    let (url, session_manager) = serve(StreamableHttpServerConfig::default(), &ct).await?;
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(&url)
+        .header("Content-Type", "application/json")
+        .header("Accept", "application/json, text/event-stream")
+        .body(INITIALIZE)
+        .send()
+        .await?;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let session_id = response
+        .headers()
+        .get("mcp-session-id")
+        .expect("stateful server returns a session id")
+        .to_str()?
+        .to_owned();
+    drop(response);
+    assert_eq!(session_manager.sessions.read().await.len(), 1);
+
+    let response = client
+        .delete(&url)
+        .header("mcp-session-id", &session_id)
+        .send()
+        .await?;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert!(session_manager.sessions.read().await.is_empty());
+
+    ct.cancel();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_delete_closes_sse_streams() -> anyhow::Result<()> {
+    let ct = CancellationToken::new();
+    let (url, session_manager) = serve(StreamableHttpServerConfig::default(), &ct).await?;
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(&url)
+        .header("Content-Type", "application/json")
+        .header("Accept", "application/json, text/event-stream")
+        .body(INITIALIZE)
+        .send()
+        .await?;
+    let session_id = response.headers()["mcp-session-id"].to_str()?.to_owned();
+    drop(response);
+    let response = client
+        .post(&url)
+        .header("Content-Type", "application/json")
+        .header("Accept", "application/json, text/event-stream")
+        .header("mcp-session-id", &session_id)
+        .body(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#)
+        .send()
+        .await?;
+    assert_eq!(response.status(), reqwest::StatusCode::ACCEPTED);
+
+    let stream = client
+        .get(&url)
+        .header("Accept", "text/event-stream")
+        .header("mcp-session-id", &session_id)
+        .send()
+        .await?;
+    assert_eq!(stream.status(), reqwest::StatusCode::OK);
+
+    let response = client
+        .delete(&url)
+        .header("mcp-session-id", &session_id)
+        .send()
+        .await?;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert!(session_manager.sessions.read().await.is_empty());
+
+    // The standalone SSE stream ends once its session is gone.
+    tokio::time::timeout(std::time::Duration::from_secs(5), stream.bytes()).await??;
+
+    ct.cancel();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_delete_unknown_session_returns_404() -> anyhow::Result<()> {
+    let ct = CancellationToken::new();
+    let (url, _) = serve(StreamableHttpServerConfig::default(), &ct).await?;
+
+    let response = reqwest::Client::new()
+        .delete(&url)
+        .header("mcp-session-id", "unknown-session-id")
+        .send()
+        .await?;
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+    ct.cancel();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unsupported_method_gets_405_with_allow() -> anyhow::Result<()> {
+    let ct = CancellationToken::new();
+    let (url, _) = serve(StreamableHttpServerConfig::default(), &ct).await?;
+
+    let response = reqwest::Client::new().put(&url).send().await?;
+    assert_eq!(response.status(), reqwest::StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(response.headers()["allow"], "GET, POST, DELETE");
+
+    ct.cancel();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stateless_delete_gets_405_with_allow() -> anyhow::Result<()> {
+    let ct = CancellationToken::new();
+    let (url, _) = serve(
+        StreamableHttpServerConfig::default().with_stateful_mode(false),
+        &ct,
+    )
+    .await?;
+
+    let response = reqwest::Client::new().delete(&url).send().await?;
+    assert_eq!(response.status(), reqwest::StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(response.headers()["allow"], "POST");
+
+    ct.cancel();
+    Ok(())
+}
@@ -192,7 +192,7 @@ async fn test_session_state_deleted_from_store_on_delete() -> anyhow::Result<()>
         .header("mcp-session-id", session_id.as_ref())
         .send()
         .await?;
-    assert_eq!(response.status(), 202);
+    assert_eq!(response.status(), 200);
 
     assert_eq!(
         store.len().await,
@@ -2,7 +2,11 @@
 //cargo test --test test_structured_output --features "client server macros"
 use rmcp::{
     Json, ServerHandler,
-    handler::server::{router::tool::ToolRouter, tool::IntoCallToolResult, wrapper::Parameters},
+    handler::server::{
+        router::tool::ToolRouter,
+        tool::IntoCallToolResult,
+        wrapper::{Parameters, ToolError},
+    },
     model::{CallToolResult, ContentBlock, ServerResult, Tool},
     tool, tool_handler, tool_router,
 };
@@ -93,6 +97,18 @@ impl TestServer {
             Err("User not found".to_string())
         }
     }
+
+    /// Tool that reports a missing user as a protocol error
+    #[tool(name = "find-user", description = "Find user info")]
+    pub async fn find_user(
+        &self,
+        params: Parameters<GetUserRequest>,
+    ) -> Result<Json<UserInfo>, rmcp::ErrorData> {
+        Err(rmcp::ErrorData::resource_not_found_for(format!(
+            "user://{}",
+            params.0.user_id
+        )))
+    }
 }
 
 #[tokio::test]
@@ -252,6 +268,110 @@ async fn test_structured_return_conversion() {
     assert_eq!(structured_value["product"], 12);
 }
 
+#[tokio::test]
+async fn test_error_data_result_propagates_as_protocol_error() {
+    let server = TestServer::new();
+
+    let result = server
+        .find_user(Parameters(GetUserRequest {
+            user_id: "456".to_string(),
+        }))
+        .await;
+    let error = IntoCallToolResult::into_call_tool_result(result)
+        .expect_err("ErrorData should not be flattened into a tool result");
+    assert_eq!(error.code, rmcp::model::ErrorCode::RESOURCE_NOT_FOUND);
+    assert_eq!(error.data, Some(json!({ "uri": "user://456" })));
+
+    // A non-`ErrorData` error type becomes an `is_error` tool result instead.
+    let result = server
+        .get_user(Parameters(GetUserRequest {
+            user_id: "456".to_string(),
+        }))
+        .await;
+    let call_result =
+        IntoCallToolResult::into_call_tool_result(result).expect("String errors are tool results");
+    assert_eq!(call_result.is_error, Some(true));
+}
+
+#[derive(Debug)]
+struct ConfigError(std::num::ParseIntError);
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid config")
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+fn parse_config(input: &str) -> Result<String, ToolError<ConfigError>> {
+    let value = input.parse::<i32>().map_err(ConfigError)?;
+    Ok(format!("port {value}"))
+}
+
+#[test]
+fn test_tool_error_becomes_tool_result_with_source_chain() {
+    let call_result = IntoCallToolResult::into_call_tool_result(parse_config("abc"))
+        .expect("ToolError should become a tool result");
+    assert_eq!(call_result.is_error, Some(true));
+    assert_eq!(
+        call_result.content.first().unwrap().as_text().unwrap().text,
+        "invalid config"
+    );
+    assert_eq!(
+        call_result.structured_content,
+        Some(json!({
+            "message": "invalid config",
+            "sources": ["invalid digit found in string"],
+        }))
+    );
+
+    let call_result = IntoCallToolResult::into_call_tool_result(parse_config("42")).unwrap();
+    assert_eq!(call_result.is_error, Some(false));
+}
+
+fn parse_port_anyhow(input: &str) -> Result<String, ToolError<anyhow::Error>> {
+    let value = anyhow::Context::context(input.parse::<i32>(), "invalid port")?;
+    Ok(format!("port {value}"))
+}
+
+fn parse_port_boxed(
+    input: &str,
+) -> Result<String, ToolError<Box<dyn std::error::Error + Send + Sync>>> {
+    let value: Result<i32, Box<dyn std::error::Error + Send + Sync>> =
+        input.parse::<i32>().map_err(|e| ConfigError(e).into());
+    Ok(format!("port {}", value?))
+}
+
+#[test]
+fn test_tool_error_accepts_anyhow_and_boxed_errors() {
+    let call_result = IntoCallToolResult::into_call_tool_result(parse_port_anyhow("abc"))
+        .expect("anyhow errors should become tool results");
+    assert_eq!(call_result.is_error, Some(true));
+    assert_eq!(
+        call_result.structured_content,
+        Some(json!({
+            "message": "invalid port",
+            "sources": ["invalid digit found in string"],
+        }))
+    );
+
+    let call_result = IntoCallToolResult::into_call_tool_result(parse_port_boxed("abc"))
+        .expect("boxed errors should become tool results");
+    assert_eq!(call_result.is_error, Some(true));
+    assert_eq!(
+        call_result.structured_content,
+        Some(json!({
+            "message": "invalid config",
+            "sources": ["invalid digit found in string"],
+        }))
+    );
+}
+
 #[tokio::test]
 async fn test_tool_serialization_with_output_schema() {
     let server = TestServer::new();
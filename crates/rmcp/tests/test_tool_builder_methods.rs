@@ -61,3 +61,83 @@ fn test_chained_builder_methods() {
     assert!(output_schema_str.contains("greeting"));
     assert!(output_schema_str.contains("is_adult"));
 }
+
+#[test]
+fn test_tool_annotation_hints() {
+    let annotations = rmcp::model::ToolAnnotations::with_title("Delete file")
+        .destructive(true)
+        .idempotent(true)
+        .open_world(false);
+    let tool = Tool::new("delete", "Delete a file", JsonObject::new()).annotate(annotations);
+
+    let json = serde_json::to_value(&tool).unwrap();
+    assert_eq!(
+        json["annotations"],
+        serde_json::json!({
+            "title": "Delete file",
+            "destructiveHint": true,
+            "idempotentHint": true,
+            "openWorldHint": false
+        })
+    );
+    let tool: Tool = serde_json::from_value(json).unwrap();
+    let annotations = tool.annotations.as_ref().unwrap();
+    assert_eq!(annotations.title.as_deref(), Some("Delete file"));
+    assert!(!annotations.is_read_only());
+    assert!(annotations.is_destructive());
+    assert!(annotations.is_idempotent());
+    assert!(!annotations.is_open_world());
+    assert!(tool.is_destructive());
+
+    let read_only = Tool::new("read", "Read a file", JsonObject::new())
+        .annotate(rmcp::model::ToolAnnotations::new().read_only(true));
+    assert!(read_only.is_read_only());
+    assert!(!read_only.is_destructive());
+
+    // Without annotations, hosts should assume the worst.
+    let unannotated = Tool::new("run", "Run a command", JsonObject::new());
+    assert!(!unannotated.is_read_only());
+    assert!(unannotated.is_destructive());
+}
+
+#[test]
+fn test_tool_builder() {
+    let tool = Tool::builder("greet")
+        .title("Greeter")
+        .description("Greet a person")
+        .input_schema_for::<InputData>()
+        .output_schema_for::<OutputData>()
+        .read_only()
+        .idempotent()
+        .open_world(false)
+        .build();
+
+    assert_eq!(tool.name, "greet");
+    assert_eq!(tool.title.as_deref(), Some("Greeter"));
+    assert_eq!(tool.description.as_deref(), Some("Greet a person"));
+    assert_eq!(
+        tool.input_schema.as_ref(),
+        Tool::new("greet", "", JsonObject::new())
+            .with_input_schema::<InputData>()
+            .input_schema
+            .as_ref()
+    );
+    assert!(tool.output_schema.is_some());
+    let annotations = tool.annotations.as_ref().unwrap();
+    assert!(annotations.is_read_only());
+    assert!(annotations.is_idempotent());
+    assert!(!annotations.is_open_world());
+    assert!(!tool.is_destructive());
+}
+
+#[test]
+fn test_tool_builder_defaults_to_empty_object_schema() {
+    let tool = Tool::builder("noop").build();
+
+    assert_eq!(tool.description, None);
+    assert_eq!(tool.annotations, None);
+    assert_eq!(
+        serde_json::Value::Object(tool.input_schema.as_ref().clone()),
+        serde_json::json!({ "type": "object", "properties": {} })
+    );
+}
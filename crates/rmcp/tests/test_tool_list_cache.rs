@@ -0,0 +1,108 @@
+#![cfg(not(feature = "local"))]
+use std::sync::Arc;
+
+use rmcp::{
+    ClientHandler, RoleClient, RoleServer, ServerHandler, ServiceExt,
+    handler::client::tool_list::{ToolListCache, ToolListDiff},
+    model::{ListToolsResult, PaginatedRequestParams, ServerCapabilities, ServerInfo, Tool},
+    service::{NotificationContext, RequestContext},
+};
+use tokio::sync::{Mutex, mpsc};
+
+#[derive(Clone)]
+struct MutableToolServer {
+    tools: Arc<Mutex<Vec<Tool>>>,
+}
+
+impl ServerHandler for MutableToolServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo::new(
+            ServerCapabilities::builder()
+                .enable_tools()
+                .enable_tool_list_changed()
+                .build(),
+        )
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, rmcp::ErrorData> {
+        Ok(ListToolsResult::with_all_items(
+            self.tools.lock().await.clone(),
+        ))
+    }
+}
+
+struct DiffingClient {
+    cache: ToolListCache,
+    diffs: mpsc::UnboundedSender<ToolListDiff>,
+}
+
+impl ClientHandler for DiffingClient {
+    fn tool_list_cache(&self) -> Option<&ToolListCache> {
+        Some(&self.cache)
+    }
+
+    async fn on_tools_changed(
+        &self,
+        diff: ToolListDiff,
+        _context: NotificationContext<RoleClient>,
+    ) {
+        let _ = self.diffs.send(diff);
+    }
+}
+
+fn tool(name: &'static str, description: &'static str) -> Tool {
+    Tool::new(name, description, Arc::new(Default::default()))
+}
+
+#[tokio::test]
+async fn test_tools_changed_reports_delta() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let tools = Arc::new(Mutex::new(vec![
+        tool("keep", "unchanged"),
+        tool("edit", "v1"),
+        tool("drop", "gone soon"),
+    ]));
+    let server = MutableToolServer {
+        tools: tools.clone(),
+    };
+    let (server_tx, server_rx) = tokio::sync::oneshot::channel();
+    let server_handle = tokio::spawn(async move {
+        let server = server.serve(server_transport).await?;
+        let _ = server_tx.send(server.peer().clone());
+        server.waiting().await?;
+        anyhow::Ok(())
+    });
+
+    let (diffs_tx, mut diffs) = mpsc::unbounded_channel();
+    let cache = ToolListCache::new();
+    let client = DiffingClient {
+        cache: cache.clone(),
+        diffs: diffs_tx,
+    }
+    .serve(client_transport)
+    .await?;
+    let seeded = cache.refresh(client.peer()).await?;
+    assert_eq!(seeded.added.len(), 3);
+
+    *tools.lock().await = vec![
+        tool("keep", "unchanged"),
+        tool("edit", "v2"),
+        tool("new", "fresh"),
+    ];
+    let server_peer = server_rx.await?;
+    server_peer.notify_tool_list_changed().await?;
+
+    let diff = diffs.recv().await.expect("tools changed");
+    assert_eq!(diff.added, [tool("new", "fresh")]);
+    assert_eq!(diff.removed, [tool("drop", "gone soon")]);
+    assert_eq!(diff.modified, [tool("edit", "v2")]);
+    assert_eq!(cache.tools().await.len(), 3);
+
+    client.cancel().await?;
+    server_handle.await??;
+    Ok(())
+}
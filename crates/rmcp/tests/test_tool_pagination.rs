@@ -0,0 +1,114 @@
+#![cfg(not(feature = "local"))]
+use futures::TryStreamExt;
+use rmcp::{
+    ServerHandler, ServiceExt,
+    handler::server::router::{Router, tool::ToolRouter},
+    model::PaginatedRequestParams,
+    tool, tool_handler, tool_router,
+};
+
+#[derive(Debug, Clone)]
+struct Catalog {
+    tool_router: ToolRouter<Self>,
+}
+
+#[tool_router]
+impl Catalog {
+    fn new() -> Self {
+        Self {
+            tool_router: Self::tool_router().with_page_size(2),
+        }
+    }
+
+    #[tool(description = "a")]
+    fn alpha(&self) -> String {
+        String::new()
+    }
+
+    #[tool(description = "b")]
+    fn bravo(&self) -> String {
+        String::new()
+    }
+
+    #[tool(description = "c")]
+    fn charlie(&self) -> String {
+        String::new()
+    }
+
+    #[tool(description = "d")]
+    fn delta(&self) -> String {
+        String::new()
+    }
+
+    #[tool(description = "e")]
+    fn echo(&self) -> String {
+        String::new()
+    }
+}
+
+#[tool_handler(router = self.tool_router)]
+impl ServerHandler for Catalog {}
+
+#[tokio::test]
+async fn test_tools_are_listed_in_pages() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    tokio::spawn(async move {
+        Catalog::new()
+            .serve(server_transport)
+            .await?
+            .waiting()
+            .await?;
+        anyhow::Ok(())
+    });
+    let client = ().serve(client_transport).await?;
+
+    let first = client.list_tools(None).await?;
+    let names: Vec<_> = first.tools.iter().map(|tool| tool.name.as_ref()).collect();
+    assert_eq!(names, ["alpha", "bravo"]);
+    assert_eq!(first.next_cursor.as_deref(), Some("bravo"));
+
+    let last = client
+        .list_tools(Some(
+            PaginatedRequestParams::default().with_cursor(Some("delta".to_string())),
+        ))
+        .await?;
+    let names: Vec<_> = last.tools.iter().map(|tool| tool.name.as_ref()).collect();
+    assert_eq!(names, ["echo"]);
+    assert_eq!(last.next_cursor, None);
+
+    let streamed: Vec<_> = client.list_all_tools_stream().try_collect().await?;
+    let names: Vec<_> = streamed.iter().map(|tool| tool.name.as_ref()).collect();
+    assert_eq!(names, ["alpha", "bravo", "charlie", "delta", "echo"]);
+    assert_eq!(client.list_all_tools().await?, streamed);
+
+    client.cancel().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_router_lists_tools_in_pages() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let mut router = Router::new(Catalog::new());
+    router.tool_router = Catalog::tool_router().with_page_size(2);
+    tokio::spawn(async move {
+        router.serve(server_transport).await?.waiting().await?;
+        anyhow::Ok(())
+    });
+    let client = ().serve(client_transport).await?;
+
+    let first = client.list_tools(None).await?;
+    let names: Vec<_> = first.tools.iter().map(|tool| tool.name.as_ref()).collect();
+    assert_eq!(names, ["alpha", "bravo"]);
+    assert_eq!(first.next_cursor.as_deref(), Some("bravo"));
+
+    let names: Vec<_> = client
+        .list_all_tools()
+        .await?
+        .into_iter()
+        .map(|tool| tool.name.to_string())
+        .collect();
+    assert_eq!(names, ["alpha", "bravo", "charlie", "delta", "echo"]);
+
+    client.cancel().await?;
+    Ok(())
+}
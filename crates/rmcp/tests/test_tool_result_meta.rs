@@ -38,3 +38,31 @@ fn serialize_tool_result_without_meta_omits_field() {
     // Ensure _meta is omitted
     assert!(v.get("_meta").is_none());
 }
+
+#[test]
+fn inspect_tool_result() {
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Sum {
+        sum: i32,
+    }
+
+    let result = CallToolResult::success(vec![
+        ContentBlock::text("first"),
+        ContentBlock::image("aGVsbG8=", "image/png"),
+        ContentBlock::text("second"),
+    ]);
+    assert!(result.is_success());
+    assert_eq!(result.text().as_deref(), Some("first\nsecond"));
+    assert!(result.structured_content_as::<Sum>().is_none());
+
+    let result = CallToolResult::structured(json!({ "sum": 3 }));
+    assert_eq!(
+        result.structured_content_as::<Sum>().unwrap().unwrap(),
+        Sum { sum: 3 }
+    );
+    assert!(result.structured_content_as::<String>().unwrap().is_err());
+
+    let result = CallToolResult::error(vec![]);
+    assert!(!result.is_success());
+    assert_eq!(result.text(), None);
+}
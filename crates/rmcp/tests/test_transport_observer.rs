@@ -0,0 +1,71 @@
+#![cfg(not(feature = "local"))]
+use std::sync::{Arc, Mutex};
+
+use rmcp::{
+    RoleClient, ServerHandler, ServiceExt,
+    model::{ClientRequest, ServerResult},
+    service::{RxJsonRpcMessage, TxJsonRpcMessage},
+    transport::{
+        async_rw::AsyncRwTransport,
+        observer::{ObservedMessage, ObservedTransport, TransportObserver},
+    },
+};
+
+struct EmptyServer;
+
+impl ServerHandler for EmptyServer {}
+
+#[derive(Default)]
+struct Recorder {
+    outbound: Mutex<Vec<ObservedMessage<TxJsonRpcMessage<RoleClient>>>>,
+    inbound: Mutex<Vec<ObservedMessage<RxJsonRpcMessage<RoleClient>>>>,
+}
+
+impl TransportObserver<RoleClient> for Recorder {
+    fn on_inbound(&self, message: ObservedMessage<RxJsonRpcMessage<RoleClient>>) {
+        self.inbound.lock().unwrap().push(message);
+    }
+
+    fn on_outbound(&self, message: ObservedMessage<TxJsonRpcMessage<RoleClient>>) {
+        self.outbound.lock().unwrap().push(message);
+    }
+}
+
+#[tokio::test]
+async fn test_observer_sees_both_directions() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let server_handle = tokio::spawn(async move {
+        EmptyServer.serve(server_transport).await?.waiting().await?;
+        anyhow::Ok(())
+    });
+
+    let recorder = Arc::new(Recorder::default());
+    let (read, write) = tokio::io::split(client_transport);
+    let transport =
+        ObservedTransport::new(AsyncRwTransport::new_client(read, write), recorder.clone());
+    let client = ().serve(transport).await?;
+    client
+        .send_request(ClientRequest::PingRequest(Default::default()))
+        .await?;
+    client.cancel().await?;
+    server_handle.await??;
+
+    let outbound = recorder.outbound.lock().unwrap();
+    let methods: Vec<_> = outbound
+        .iter()
+        .map(|m| serde_json::from_slice::<serde_json::Value>(&m.raw).unwrap()["method"].clone())
+        .collect();
+    assert_eq!(methods, ["initialize", "notifications/initialized", "ping"]);
+
+    let inbound = recorder.inbound.lock().unwrap();
+    assert_eq!(inbound.len(), 2);
+    assert!(inbound.iter().all(|m| {
+        serde_json::from_slice::<serde_json::Value>(&m.raw).unwrap()
+            == serde_json::to_value(&m.message).unwrap()
+    }));
+    assert!(matches!(
+        inbound[1].message.clone().into_response(),
+        Some((ServerResult::EmptyResult(_), _))
+    ));
+    Ok(())
+}
@@ -0,0 +1,45 @@
+#![cfg(all(unix, not(feature = "local")))]
+use std::os::unix::fs::PermissionsExt;
+
+use rmcp::{
+    ServerHandler, ServiceExt,
+    model::{ServerCapabilities, ServerInfo},
+    transport::{UnixSocketClientTransport, UnixSocketServerTransport},
+};
+
+#[derive(Clone)]
+struct EmptyServer;
+
+impl ServerHandler for EmptyServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo::new(ServerCapabilities::builder().enable_tools().build())
+    }
+}
+
+#[tokio::test]
+async fn test_unix_socket_round_trip() -> anyhow::Result<()> {
+    let path = std::env::temp_dir().join(format!("rmcp-test-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixSocketServerTransport::bind(&path)?.with_permissions(0o600)?;
+    assert_eq!(
+        std::fs::metadata(&path)?.permissions().mode() & 0o777,
+        0o600
+    );
+
+    let server_handle = tokio::spawn(async move {
+        let transport = listener.accept().await?;
+        EmptyServer.serve(transport).await?.waiting().await?;
+        // dropping the listener removes the socket file
+        drop(listener);
+        anyhow::Ok(())
+    });
+
+    let client = ().serve(UnixSocketClientTransport::connect(&path).await?).await?;
+    let tools = client.list_all_tools().await?;
+    assert!(tools.is_empty());
+    client.cancel().await?;
+    server_handle.await??;
+
+    assert!(!path.exists());
+    Ok(())
+}
@@ -0,0 +1,98 @@
+#![cfg(not(feature = "local"))]
+// cargo test --features "server client macros" --test test_validate_tool_args
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use rmcp::{
+    ServerHandler, ServiceExt,
+    handler::server::{router::tool::ToolRouter, wrapper::Parameters},
+    model::JsonObject,
+    service::ToolArgsError,
+    tool, tool_handler, tool_router,
+};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Deserialize, JsonSchema)]
+struct ResizeRequest {
+    width: u32,
+    height: u32,
+    #[expect(dead_code, reason = "only part of the schema")]
+    label: Option<String>,
+}
+
+#[derive(Clone)]
+struct ImageServer {
+    calls: Arc<AtomicUsize>,
+    tool_router: ToolRouter<Self>,
+}
+
+#[tool_router]
+impl ImageServer {
+    fn new(calls: Arc<AtomicUsize>) -> Self {
+        Self {
+            calls,
+            tool_router: Self::tool_router(),
+        }
+    }
+
+    #[tool(description = "Resize the image")]
+    async fn resize(&self, Parameters(request): Parameters<ResizeRequest>) -> String {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        format!("{}x{}", request.width, request.height)
+    }
+}
+
+#[tool_handler(router = self.tool_router)]
+impl ServerHandler for ImageServer {}
+
+fn object(value: serde_json::Value) -> JsonObject {
+    value.as_object().cloned().unwrap()
+}
+
+#[tokio::test]
+async fn test_validate_tool_args_without_calling_tool() -> anyhow::Result<()> {
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let calls = Arc::new(AtomicUsize::new(0));
+    let server = ImageServer::new(calls.clone());
+    let server_handle = tokio::spawn(async move {
+        server.serve(server_transport).await?.waiting().await?;
+        anyhow::Ok(())
+    });
+    let client = ().serve(client_transport).await?;
+
+    // Not listed yet, so the schema is fetched on demand.
+    client
+        .validate_tool_args(
+            "resize",
+            Some(&object(json!({ "width": 10, "height": 20 }))),
+        )
+        .await?;
+
+    let error = client
+        .validate_tool_args(
+            "resize",
+            Some(&object(json!({ "width": "wide", "label": 3 }))),
+        )
+        .await
+        .unwrap_err();
+    let ToolArgsError::Invalid(errors) = error else {
+        panic!("expected invalid arguments, got {error:?}");
+    };
+    let mut paths: Vec<_> = errors.iter().map(|error| error.path.as_str()).collect();
+    paths.sort();
+    assert_eq!(paths, ["", "/label", "/width"]);
+
+    assert!(matches!(
+        client.validate_tool_args("crop", None).await,
+        Err(ToolArgsError::UnknownTool(name)) if name == "crop"
+    ));
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+    client.cancel().await?;
+    server_handle.await??;
+    Ok(())
+}
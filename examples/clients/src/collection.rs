@@ -5,12 +5,7 @@
 use std::collections::HashMap;
 
 use anyhow::Result;
-use rmcp::{
-    model::CallToolRequestParams,
-    service::ServiceExt,
-    transport::{ConfigureCommandExt, TokioChildProcess},
-};
-use tokio::process::Command;
+use rmcp::{model::CallToolRequestParams, service::ServiceExt, transport::TokioChildProcess};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
@@ -26,14 +21,7 @@ async fn main() -> Result<()> {
 
     let mut clients_map = HashMap::new();
     for idx in 0..10 {
-        let client = ()
-            .into_dyn()
-            .serve(TokioChildProcess::new(Command::new("uvx").configure(
-                |cmd| {
-                    cmd.arg("mcp-client-git");
-                },
-            ))?)
-            .await?;
+        let client = ().into_dyn().serve(TokioChildProcess::uvx("mcp-client-git", &[])?).await?;
         clients_map.insert(idx, client);
     }
 
@@ -3,9 +3,8 @@ use rmcp::{
     ServiceExt,
     model::{CallToolRequestParams, GetPromptRequestParams, ReadResourceRequestParams},
     object,
-    transport::{ConfigureCommandExt, TokioChildProcess},
+    transport::TokioChildProcess,
 };
-use tokio::process::Command;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
@@ -20,11 +19,10 @@ async fn main() -> Result<()> {
         .init();
 
     let client = ()
-        .serve(TokioChildProcess::new(Command::new("npx").configure(
-            |cmd| {
-                cmd.arg("-y").arg("@modelcontextprotocol/server-everything");
-            },
-        ))?)
+        .serve(TokioChildProcess::npx(
+            "@modelcontextprotocol/server-everything",
+            &[],
+        )?)
         .await?;
 
     // Initialize
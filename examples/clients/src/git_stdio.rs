@@ -1,10 +1,6 @@
 use rmcp::{
-    RmcpError,
-    model::CallToolRequestParams,
-    service::ServiceExt,
-    transport::{ConfigureCommandExt, TokioChildProcess},
+    RmcpError, model::CallToolRequestParams, service::ServiceExt, transport::TokioChildProcess,
 };
-use tokio::process::Command;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[allow(clippy::result_large_err)]
@@ -20,10 +16,8 @@ async fn main() -> Result<(), RmcpError> {
         .init();
     let client = ()
         .serve(
-            TokioChildProcess::new(Command::new("uvx").configure(|cmd| {
-                cmd.arg("mcp-server-git");
-            }))
-            .map_err(RmcpError::transport_creation::<TokioChildProcess>)?,
+            TokioChildProcess::uvx("mcp-server-git", &[])
+                .map_err(RmcpError::transport_creation::<TokioChildProcess>)?,
         )
         .await?;
 
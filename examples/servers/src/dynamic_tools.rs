@@ -0,0 +1,136 @@
+//! Registers a tool after the server has started and calls it from an
+//! in-process client.
+//!
+//! Tools discovered at runtime (here: a single `shout` tool standing in for a
+//! plugin) are added with `ToolRouter::add_dynamic`, which also sends
+//! `notifications/tools/list_changed` to the connected client.
+use std::sync::Arc;
+
+use rmcp::{
+    ClientHandler, ErrorData as McpError, RoleClient, RoleServer, ServerHandler, ServiceExt,
+    handler::server::{router::tool::ToolRouter, tool::ToolCallContext},
+    model::{
+        CallToolRequestParams, CallToolResult, ContentBlock, ListToolsResult,
+        PaginatedRequestParams, ServerCapabilities, ServerInfo, Tool,
+    },
+    object,
+    service::{NotificationContext, RequestContext},
+};
+use tokio::sync::{Notify, RwLock};
+
+#[derive(Clone, Default)]
+struct PluginServer {
+    tool_router: Arc<RwLock<ToolRouter<Self>>>,
+}
+
+impl PluginServer {
+    /// Stand-in for scanning a plugin directory.
+    async fn load_plugins(&self) {
+        let tool = Tool::builder("shout")
+            .description("Upper-case the given text")
+            .input_schema(object!({
+                "type": "object",
+                "properties": { "text": { "type": "string" } },
+                "required": ["text"]
+            }))
+            .read_only()
+            .build();
+        self.tool_router.write().await.add_dynamic(tool, |context| {
+            let text = context
+                .arguments
+                .as_ref()
+                .and_then(|arguments| arguments.get("text"))
+                .and_then(|text| text.as_str())
+                .map(str::to_uppercase);
+            async move {
+                let text = text.ok_or_else(|| {
+                    McpError::invalid_params("missing string argument `text`", None)
+                })?;
+                Ok(CallToolResult::success(vec![ContentBlock::text(text)]))
+            }
+        });
+    }
+}
+
+impl ServerHandler for PluginServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo::new(
+            ServerCapabilities::builder()
+                .enable_tools()
+                .enable_tool_list_changed()
+                .build(),
+        )
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let router = self.tool_router.read().await;
+        router
+            .call(ToolCallContext::new(self, request, context))
+            .await
+    }
+
+    async fn list_tools(
+        &self,
+        request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        let cursor = request.and_then(|request| request.cursor);
+        Ok(self.tool_router.read().await.list_page(cursor.as_deref()))
+    }
+
+    async fn on_initialized(&self, context: NotificationContext<RoleServer>) {
+        self.tool_router
+            .write()
+            .await
+            .bind_peer_notifier(&context.peer);
+        self.load_plugins().await;
+    }
+}
+
+#[derive(Clone, Default)]
+struct Client {
+    tools_changed: Arc<Notify>,
+}
+
+impl ClientHandler for Client {
+    async fn on_tool_list_changed(&self, _context: NotificationContext<RoleClient>) {
+        self.tools_changed.notify_one();
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::from_default_env()
+                .add_directive(tracing::Level::DEBUG.into()),
+        )
+        .init();
+
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let server = tokio::spawn(PluginServer::default().serve(server_transport));
+
+    let client = Client::default();
+    let tools_changed = client.tools_changed.clone();
+    let client = client.serve(client_transport).await?;
+
+    tools_changed.notified().await;
+    let tools = client.list_all_tools().await?;
+    tracing::info!(
+        "tools after startup: {:?}",
+        tools.iter().map(|tool| &tool.name).collect::<Vec<_>>()
+    );
+
+    let result = client
+        .call_tool(CallToolRequestParams::new("shout").with_arguments(object!({ "text": "hello" })))
+        .await?;
+    tracing::info!("shout result: {result:?}");
+
+    client.cancel().await?;
+    server.abort();
+    Ok(())
+}